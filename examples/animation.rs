@@ -6,12 +6,13 @@
 //! clarify why that is.
 
 use cacao::color::Color;
+use cacao::geometry::Transform;
 use cacao::layout::{Layout, LayoutConstraint, LayoutConstraintAnimatorProxy};
 use cacao::view::{View, ViewAnimatorProxy};
 
 use cacao::appkit::menu::Menu;
 use cacao::appkit::window::{Window, WindowConfig, WindowDelegate};
-use cacao::appkit::{AnimationContext, App, AppDelegate};
+use cacao::appkit::{AnimationContext, App, AppDelegate, TimingFunction};
 use cacao::appkit::{Event, EventMask, EventMonitor};
 
 struct BasicApp {
@@ -40,7 +41,7 @@ impl AppDelegate for BasicApp {
 /// The basic mapping logic is this: each entry is a view's frame(s), and each frame is an array
 /// of:
 ///
-/// [top, left, width, height, alpha]
+/// [top, left, width, height, alpha, rotation (in degrees)]
 ///
 /// We then treat each frame index as follows:
 ///
@@ -48,27 +49,27 @@ impl AppDelegate for BasicApp {
 /// a: 1
 /// s: 2
 /// d: 3
-const ANIMATIONS: [[[f64; 5]; 4]; 3] = [
+const ANIMATIONS: [[[f64; 6]; 4]; 3] = [
     // Blue
     [
-        [44., 16., 100., 100., 1.],
-        [128., 84., 144., 124., 1.],
-        [32., 32., 44., 44., 0.7],
-        [328., 157., 200., 200., 0.7]
+        [44., 16., 100., 100., 1., 0.],
+        [128., 84., 144., 124., 1., -6.],
+        [32., 32., 44., 44., 0.7, 12.],
+        [328., 157., 200., 200., 0.7, 45.]
     ],
     // Red
     [
-        [44., 132., 100., 100., 1.],
-        [40., 47., 80., 64., 0.7],
-        [84., 220., 600., 109., 1.0],
-        [48., 600., 340., 44., 0.7]
+        [44., 132., 100., 100., 1., 0.],
+        [40., 47., 80., 64., 0.7, 8.],
+        [84., 220., 600., 109., 1.0, -4.],
+        [48., 600., 340., 44., 0.7, 90.]
     ],
     // Green
     [
-        [44., 248., 100., 100., 1.],
-        [420., 232., 420., 244., 0.7],
-        [310., 440., 150., 238., 0.7],
-        [32., 32., 44., 44., 1.]
+        [44., 248., 100., 100., 1., 0.],
+        [420., 232., 420., 244., 0.7, 15.],
+        [310., 440., 150., 238., 0.7, -10.],
+        [32., 32., 44., 44., 1., -45.]
     ]
 ];
 
@@ -110,7 +111,7 @@ impl WindowDelegate for AppWindow {
         let red_frame = apply_styles(&self.red, &self.content, Color::SystemRed, 1);
         let green_frame = apply_styles(&self.green, &self.content, Color::SystemGreen, 2);
 
-        let alpha_animators = [&self.blue, &self.red, &self.green]
+        let view_animators = [&self.blue, &self.red, &self.green]
             .iter()
             .map(|view| view.animator.clone())
             .collect::<Vec<ViewAnimatorProxy>>();
@@ -120,12 +121,10 @@ impl WindowDelegate for AppWindow {
             .map(|frame| {
                 LayoutConstraint::activate(frame);
 
-                vec![
-                    frame[0].animator.clone(),
-                    frame[1].animator.clone(),
-                    frame[2].animator.clone(),
-                    frame[3].animator.clone(),
-                ]
+                frame
+                    .iter()
+                    .map(|constraint| constraint.animator.clone().unwrap())
+                    .collect::<Vec<LayoutConstraintAnimatorProxy>>()
             })
             .collect::<Vec<Vec<LayoutConstraintAnimatorProxy>>>();
 
@@ -146,13 +145,17 @@ impl WindowDelegate for AppWindow {
                 return None;
             }
 
-            let alpha_animators = alpha_animators.clone();
+            let view_animators = view_animators.clone();
             let constraint_animators = constraint_animators.clone();
 
-            AnimationContext::run(move |_ctx| {
-                alpha_animators.iter().enumerate().for_each(move |(index, view)| {
+            AnimationContext::run(move |ctx| {
+                ctx.set_duration(0.5);
+                ctx.set_timing_function(TimingFunction::Spring { damping: 0.6 });
+
+                view_animators.iter().enumerate().for_each(move |(index, view)| {
                     let animation = ANIMATIONS[index][animation_index];
                     view.set_alpha(animation[4]);
+                    view.set_transform(Transform::rotate(animation[5].to_radians()));
                 });
 
                 constraint_animators.iter().enumerate().for_each(move |(index, frame)| {
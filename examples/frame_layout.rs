@@ -1,10 +1,10 @@
-//! This example showcases setting up a basic application and window, setting up some views to
-//! work with autolayout, and some basic ways to handle colors.
+//! This example showcases frame-based layout: some views that are repositioned by hand whenever
+//! the window is resized, and one that follows the window with an autoresizing mask.
 
 use cacao::color::Color;
 use cacao::geometry::Rect;
-use cacao::layout::Layout;
-use cacao::view::View;
+use cacao::layout::{AutoresizingMask, Layout};
+use cacao::view::{View, ViewDelegate};
 
 use cacao::appkit::menu::{Menu, MenuItem};
 use cacao::appkit::window::{Window, WindowConfig, WindowDelegate};
@@ -13,8 +13,8 @@ use cacao::appkit::{App, AppDelegate};
 const CORNER_RADIUS: f64 = 16.;
 const SPACING: f64 = 10.;
 const TOP: f64 = 40.;
-const WIDTH: f64 = 100.;
 const HEIGHT: f64 = 100.;
+const FOOTER_HEIGHT: f64 = 40.;
 
 struct BasicApp {
     window: Window<AppWindow>
@@ -45,7 +45,6 @@ impl AppDelegate for BasicApp {
         App::activate();
 
         self.window.show();
-        self.window.delegate.as_ref().unwrap().layout();
     }
 
     fn should_terminate_after_last_window_closed(&self) -> bool {
@@ -54,45 +53,51 @@ impl AppDelegate for BasicApp {
 }
 
 #[derive(Default)]
-struct AppWindow {
-    content: View,
+struct ContentView {
     blue: View,
     red: View,
-    green: View
+    green: View,
+    footer: View
 }
 
-impl AppWindow {
-    pub fn layout(&self) {
-        self.blue.set_background_color(Color::SystemBlue);
-        self.blue.set_frame(Rect {
-            top: TOP,
-            left: SPACING,
-            width: WIDTH,
-            height: HEIGHT
-        });
-        self.blue.layer.set_corner_radius(CORNER_RADIUS);
-        self.content.add_subview(&self.blue);
-
-        self.red.set_background_color(Color::SystemRed);
-        self.red.set_frame(Rect {
-            top: TOP,
-            left: WIDTH + (SPACING * 2.),
-            width: WIDTH,
-            height: HEIGHT
-        });
-        self.red.layer.set_corner_radius(CORNER_RADIUS);
-        self.content.add_subview(&self.red);
-
-        self.green.set_background_color(Color::SystemGreen);
-        self.green.set_frame(Rect {
-            top: TOP,
-            left: (WIDTH * 2.) + (SPACING * 3.),
-            width: WIDTH,
-            height: HEIGHT
-        });
-        self.green.layer.set_corner_radius(CORNER_RADIUS);
-        self.content.add_subview(&self.green);
+impl ViewDelegate for ContentView {
+    const NAME: &'static str = "FrameLayoutContentView";
+
+    fn did_load(&mut self, view: View) {
+        for (color, square) in [
+            (Color::SystemBlue, &self.blue),
+            (Color::SystemRed, &self.red),
+            (Color::SystemGreen, &self.green)
+        ] {
+            square.set_background_color(color);
+            square.layer.set_corner_radius(CORNER_RADIUS);
+            view.add_subview(square);
+        }
+
+        // The footer follows the bottom of the window, and stretches to its width, without any
+        // help from `layout_subviews()`. Masks carry over however much the superview changes in
+        // size, so the frame is relative to the content view's current (empty) size.
+        self.footer.set_background_color(Color::SystemGray);
+        self.footer.set_frame(Rect::new(-FOOTER_HEIGHT, 0., 0., FOOTER_HEIGHT));
+        self.footer
+            .set_autoresizing_mask(&[AutoresizingMask::FlexibleWidth, AutoresizingMask::FlexibleTopMargin]);
+        view.add_subview(&self.footer);
     }
+
+    /// Splits the width between the three squares, every time the window is resized.
+    fn layout_subviews(&self, bounds: Rect) {
+        let width = (bounds.width - SPACING * 4.) / 3.;
+        let mut left = SPACING;
+
+        for square in [&self.blue, &self.red, &self.green] {
+            square.set_frame(Rect::new(TOP, left, width, HEIGHT));
+            left += width + SPACING;
+        }
+    }
+}
+
+struct AppWindow {
+    content: View<ContentView>
 }
 
 impl WindowDelegate for AppWindow {
@@ -107,7 +112,9 @@ impl WindowDelegate for AppWindow {
 
 fn main() {
     App::new("com.test.window", BasicApp {
-        window: Window::with(WindowConfig::default(), AppWindow::default())
+        window: Window::with(WindowConfig::default(), AppWindow {
+            content: View::with(ContentView::default())
+        })
     })
     .run();
 }
@@ -44,7 +44,7 @@ impl TodosListView {
                 self.todos.insert(todo);
                 self.view.as_ref().unwrap().perform_batch_updates(|listview| {
                     // We know we always insert at the 0 index, so this is a simple calculation.
-                    // You'd need to diff yourself for anything more complicated.
+                    // For anything more complicated, `ListDataSource` can work out the changes.
                     listview.insert_rows(&[0], RowAnimation::SlideDown);
                 });
             },
@@ -1,7 +1,77 @@
 use block::ConcreteBlock;
-use objc::{class, msg_send, sel, sel_impl};
+use objc::runtime::Sel;
+use objc::{class, msg_send, sel, sel_impl, Message};
 
-use crate::foundation::id;
+use crate::foundation::{id, NSString, NO, YES};
+
+/// The pacing of an animation over its duration.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TimingFunction {
+    /// The system default: a subtle ease in and out.
+    Default,
+
+    /// A constant speed.
+    Linear,
+
+    /// Starts slowly, then speeds up.
+    EaseIn,
+
+    /// Starts quickly, then slows down.
+    EaseOut,
+
+    /// Starts slowly, speeds up, then slows down again.
+    EaseInOut,
+
+    /// Overshoots the target and settles back, like a spring. `damping` runs from `0.` (the most
+    /// overshoot) to `1.` (none at all, which is a plain ease-out).
+    ///
+    /// `NSAnimationContext` only supports cubic Bézier curves, so this overshoots once rather than
+    /// oscillating.
+    Spring {
+        /// How quickly the spring settles, from `0.` to `1.`.
+        damping: f64
+    },
+
+    /// A custom cubic Bézier curve from `(0, 0)` to `(1, 1)`, with the given control points
+    /// `(x1, y1, x2, y2)` - as in CSS's `cubic-bezier()`.
+    ControlPoints(f64, f64, f64, f64)
+}
+
+impl TimingFunction {
+    /// Returns a `CAMediaTimingFunction` for this timing function.
+    fn to_objc(self) -> id {
+        let name = match self {
+            TimingFunction::Default => "default",
+            TimingFunction::Linear => "linear",
+            TimingFunction::EaseIn => "easeIn",
+            TimingFunction::EaseOut => "easeOut",
+            TimingFunction::EaseInOut => "easeInEaseOut",
+
+            TimingFunction::Spring { damping } => {
+                let overshoot = 1. - damping.clamp(0., 1.);
+                return TimingFunction::ControlPoints(0.34, 1. + overshoot * 1.12, 0.64, 1.).to_objc();
+            },
+
+            TimingFunction::ControlPoints(x1, y1, x2, y2) => {
+                let points = (x1 as f32, y1 as f32, x2 as f32, y2 as f32);
+
+                // The selector is `functionWithControlPoints::::`, which `msg_send!` can't express.
+                // Sending it can only fail if message verification is turned on for `objc`, in
+                // which case this falls back to the default curve rather than panicking.
+                let selector = Sel::register("functionWithControlPoints::::");
+                let function = unsafe { class!(CAMediaTimingFunction).send_message(selector, points) };
+
+                match function {
+                    Ok(function) => return function,
+                    Err(_) => "default"
+                }
+            }
+        };
+
+        let name = NSString::new(name);
+        unsafe { msg_send![class!(CAMediaTimingFunction), functionWithName:&*name] }
+    }
+}
 
 /// A very, very basic wrapper around NSAnimationContext. 100% subject to change.
 #[derive(Debug)]
@@ -20,6 +90,25 @@ impl AnimationContext {
         }
     }
 
+    /// Sets the timing function, which controls the pacing of the animation.
+    pub fn set_timing_function(&mut self, timing_function: TimingFunction) {
+        unsafe {
+            let _: () = msg_send![self.0, setTimingFunction: timing_function.to_objc()];
+        }
+    }
+
+    /// Sets whether changes made directly to views (rather than through their `animator`
+    /// proxies) are animated, including layout passes. The `animator` proxies turn this on as
+    /// needed, so you usually don't need to.
+    pub fn set_allows_implicit_animation(&mut self, allows: bool) {
+        unsafe {
+            let _: () = msg_send![self.0, setAllowsImplicitAnimation:match allows {
+                true => YES,
+                false => NO
+            }];
+        }
+    }
+
     /// Pass it a block, and the changes in that block will be animated, provided they're
     /// properties that support animation.
     ///
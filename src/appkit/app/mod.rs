@@ -202,8 +202,11 @@ where
         });
     }
 
-    /// Dispatches a message by grabbing the `sharedApplication`, getting ahold of the delegate,
-    /// and passing back through there.
+    /// Dispatches a message to the app delegate's `on_background_message`.
+    ///
+    /// Note that this still runs on the main queue: the app delegate isn't required to be `Sync`,
+    /// so it can't safely be called from another thread. To handle messages on a background
+    /// queue, register a receiver with `notification_center::MessageRouter` instead.
     pub fn dispatch_background(message: M) {
         let queue = dispatch::Queue::main();
 
@@ -9,7 +9,7 @@ mod alert;
 pub use alert::Alert;
 
 mod animation;
-pub use animation::AnimationContext;
+pub use animation::{AnimationContext, TimingFunction};
 
 mod app;
 pub use app::*;
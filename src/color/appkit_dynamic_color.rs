@@ -19,6 +19,8 @@ use objc::declare::ClassDecl;
 use objc::runtime::{Class, Object, Sel, BOOL};
 use objc::{class, msg_send, sel, sel_impl};
 
+use super::palette::provided_style;
+use super::{Contrast, Theme};
 use crate::foundation::{id, nil, NSArray, NSInteger, NSString, NSUInteger, NO, YES};
 use crate::utils::os;
 
@@ -42,10 +44,22 @@ extern "C" {
 /// contrast checking on systems prior to 10.14: it's not that it couldn't be supported, but the
 /// ongoing question of how far back to support makes this not worth bothering with right now.
 ///
-/// On non-Apple systems, this returns the light aqua color at all times.
+/// If a `ThemeProvider` has been installed, its style wins over the system appearance. On
+/// non-Apple systems without one, this returns the light aqua color at all times.
 ///
 /// Pull requests to implement that check would be welcome.
 fn get_effective_color(this: &Object) -> id {
+    if let Some(style) = provided_style() {
+        unsafe {
+            return *this.get_ivar(match (style.theme, style.contrast) {
+                (Theme::Light, Contrast::Normal) => AQUA_LIGHT_COLOR_NORMAL_CONTRAST,
+                (Theme::Light, Contrast::High) => AQUA_LIGHT_COLOR_HIGH_CONTRAST,
+                (Theme::Dark, Contrast::Normal) => AQUA_DARK_COLOR_NORMAL_CONTRAST,
+                (Theme::Dark, Contrast::High) => AQUA_DARK_COLOR_HIGH_CONTRAST
+            });
+        }
+    }
+
     #[cfg(target_os = "macos")]
     if os::is_minimum_semversion(10, 14, 0) {
         unsafe {
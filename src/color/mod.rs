@@ -13,6 +13,7 @@
 /// fallbacks, specify the `color_fallbacks` target_os in your `Cargo.toml`.
 ///
 /// @TODO: bundle iOS/tvOS support.
+use std::str::FromStr;
 use std::sync::{Arc, RwLock};
 
 use core_graphics::base::CGFloat;
@@ -25,9 +26,25 @@ use objc_id::Id;
 use crate::foundation::id;
 use crate::utils::os;
 
+#[cfg(feature = "appkit")]
+use crate::foundation::nil;
+
+#[cfg(all(feature = "uikit", not(feature = "appkit")))]
+use crate::foundation::{to_bool, BOOL};
+
 #[cfg(feature = "appkit")]
 mod appkit_dynamic_color;
 
+mod parse;
+pub use parse::ColorParseError;
+
+mod value;
+pub use value::{ColorSpace, ContrastLevel, Hsba, Hsla, Rgba};
+
+mod palette;
+use palette::provided_color;
+pub use palette::{clear_theme_provider, set_theme_provider, Palette, PaletteEntry, StaticTheme, ThemeProvider};
+
 #[cfg(feature = "appkit")]
 use appkit_dynamic_color::{
     AQUA_DARK_COLOR_HIGH_CONTRAST, AQUA_DARK_COLOR_NORMAL_CONTRAST, AQUA_LIGHT_COLOR_HIGH_CONTRAST,
@@ -38,7 +55,7 @@ use appkit_dynamic_color::{
 /// In the event that a new variant is introduced in later versions of
 /// macOS or iOS, calls that use the dynamic color(s) from here will likely
 /// default to the `Light` theme.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Theme {
     /// The "default" theme on a platform. On macOS/Airyx, this is Aqua.
     /// On iOS and tvOS, this is whatever you call the system defined theme.
@@ -49,7 +66,7 @@ pub enum Theme {
 }
 
 /// Represents the contrast level for a rendering context.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Contrast {
     /// The default contrast level for the system.
     Normal,
@@ -61,7 +78,7 @@ pub enum Contrast {
 /// A `Style` is passed to you when doing dynamic color calculations. You can opt to
 /// provide different colors depending on the settings in here - notably, this is useful
 /// for supporting dark mode and high contrast accessibility contexts.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Style {
     /// Represents the current theme for where this color may render.
     pub theme: Theme,
@@ -246,6 +263,11 @@ impl Color {
         let g = green as CGFloat / 255.0;
         let b = blue as CGFloat / 255.0;
         let a = alpha as CGFloat / 255.0;
+        Color::rgba_components(r, g, b, a)
+    }
+
+    /// Creates and returns a color in the RGB space from components in the `0.0 - 1.0` range.
+    fn rgba_components(r: CGFloat, g: CGFloat, b: CGFloat, a: CGFloat) -> Self {
         #[cfg(feature = "appkit")]
         let ptr = unsafe { Id::from_ptr(msg_send![class!(NSColor), colorWithCalibratedRed:r green:g blue:b alpha:a]) };
         #[cfg(all(feature = "uikit", not(feature = "appkit")))]
@@ -309,22 +331,42 @@ impl Color {
         Color::white_alpha(level, 1.0)
     }
 
-    /// Given a hex code and alpha level, returns a `Color` in the RGB space.
+    /// Given a hex code and alpha level, returns a `Color` in the RGB space. The hex code can be
+    /// in any of the `rgb`, `rgba`, `rrggbb` or `rrggbbaa` forms, with or without a leading `#`.
+    ///
+    /// If the hex code carries its own alpha, it's multiplied with `alpha` - so `("#ff000080", 255)`
+    /// is half-transparent red, as is `("#ff0000", 128)`.
     ///
     /// This method is not an ideal one to use, but is offered as a convenience method for those
     /// coming from other environments where these are more common.
-    pub fn hexa(_hex: &str, _alpha: u8) -> Self {
-        Color::SystemRed
+    pub fn hexa(hex: &str, alpha: u8) -> Result<Self, ColorParseError> {
+        let hex = hex.trim();
+
+        if hex.is_empty() {
+            return Err(ColorParseError::Empty);
+        }
+
+        let [r, g, b, a] = parse::parse_hex(hex.strip_prefix('#').unwrap_or(hex))?;
+        Ok(Color::rgba_components(r, g, b, a * (alpha as CGFloat / 255.0)))
     }
 
     /// Given a hex code, returns a `Color` in the RGB space with alpha pre-set to `255`.
     ///
     /// This method is not an ideal one to use, but is offered as a convenience method for those
     /// coming from other environments where these are more common.
-    pub fn hex(hex: &str) -> Self {
+    pub fn hex(hex: &str) -> Result<Self, ColorParseError> {
         Color::hexa(hex, 255)
     }
 
+    /// Parses a CSS color string and returns a `Color` in the RGB space. This accepts hex codes
+    /// (`#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`), `rgb()`/`rgba()`, `hsl()`/`hsla()` and the named
+    /// CSS colors (e.g, `rebeccapurple`). This is also available via `FromStr`, so
+    /// `"#663399".parse::<Color>()` works as well.
+    pub fn css(input: &str) -> Result<Self, ColorParseError> {
+        let [r, g, b, a] = parse::parse(input)?;
+        Ok(Color::rgba_components(r, g, b, a))
+    }
+
     // @TODO: This is currently appkit-only but should be for uikit as well.
     /// Creates and returns a dynamic color, which stores a handler and enables returning specific
     /// colors at appearance time based on device traits (i.e, dark mode vs light mode, contrast
@@ -379,8 +421,8 @@ impl Color {
 
             (&mut *color).set_ivar(AQUA_DARK_COLOR_HIGH_CONTRAST, {
                 let color: id = handler(Style {
-                    theme: Theme::Light,
-                    contrast: Contrast::Normal
+                    theme: Theme::Dark,
+                    contrast: Contrast::High
                 })
                 .into();
 
@@ -391,6 +433,43 @@ impl Color {
         })))
     }
 
+    /// Resolves this color to its sRGB components, as they'd be drawn in the current appearance
+    /// context. This works for system and dynamic colors, but returns `None` for colors that
+    /// can't be represented as RGB (e.g, pattern colors).
+    pub fn resolve(&self) -> Option<Rgba> {
+        let mut red: CGFloat = 0.;
+        let mut green: CGFloat = 0.;
+        let mut blue: CGFloat = 0.;
+        let mut alpha: CGFloat = 0.;
+
+        unsafe {
+            let color: id = self.into();
+
+            #[cfg(feature = "appkit")]
+            {
+                let srgb: id = msg_send![class!(NSColorSpace), sRGBColorSpace];
+                let converted: id = msg_send![color, colorUsingColorSpace: srgb];
+
+                if converted == nil {
+                    return None;
+                }
+
+                let _: () = msg_send![converted, getRed:&mut red green:&mut green blue:&mut blue alpha:&mut alpha];
+            }
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            {
+                let result: BOOL = msg_send![color, getRed:&mut red green:&mut green blue:&mut blue alpha:&mut alpha];
+
+                if !to_bool(result) {
+                    return None;
+                }
+            }
+        }
+
+        Some(Rgba::new(red as f64, green as f64, blue as f64, alpha as f64))
+    }
+
     /// Returns a CGColor, which can be used in Core Graphics calls as well as other areas.
     ///
     /// Note that CGColor is _not_ a context-aware color, unlike our `NSColor` and `UIColor`
@@ -406,6 +485,35 @@ impl Color {
     }
 }
 
+impl From<Rgba> for Color {
+    /// Creates a color in the same color space as the `Rgba`. Linear sRGB is converted to
+    /// (gamma-encoded) sRGB first, as neither `NSColor` nor `UIColor` offer an initializer for it.
+    fn from(rgba: Rgba) -> Self {
+        Color::Custom(Arc::new(RwLock::new(unsafe { Id::from_ptr(rgba_to_objc(rgba)) })))
+    }
+}
+
+impl From<Hsla> for Color {
+    fn from(hsla: Hsla) -> Self {
+        Color::from(hsla.to_rgba())
+    }
+}
+
+impl From<Hsba> for Color {
+    fn from(hsba: Hsba) -> Self {
+        Color::from(hsba.to_rgba())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    /// Parses a CSS color string. See `Color::css` for the supported forms.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Color::css(input)
+    }
+}
+
 impl AsRef<Color> for Color {
     /// Provided to make passing `Color` types around less of a headache.
     #[inline]
@@ -460,6 +568,11 @@ macro_rules! system_color_with_fallback {
 /// The goal here is to make sure that this can't reasonably break on OS's, as `Color` is kind of
 /// an important piece. It's not on the framework to make your app look good, though.
 unsafe fn to_objc(obj: &Color) -> id {
+    // An installed `ThemeProvider` overrides the system's values for system colors.
+    if let Some(rgba) = provided_color(obj) {
+        return rgba_to_objc(rgba);
+    }
+
     #[cfg(feature = "appkit")]
     let color = class!(NSColor);
 
@@ -520,3 +633,44 @@ unsafe fn to_objc(obj: &Color) -> id {
         Color::MacOSUnderPageBackgroundColor => system_color_with_fallback!(color, underPageBackgroundColor, clearColor)
     }
 }
+
+/// Returns an (autoreleased) `NSColor` or `UIColor` for an `Rgba`, in the same color space.
+/// Linear sRGB is converted to (gamma-encoded) sRGB first, as neither class offers an
+/// initializer for it.
+unsafe fn rgba_to_objc(rgba: Rgba) -> id {
+    let rgba = match rgba.space {
+        ColorSpace::LinearSrgb => rgba.to_space(ColorSpace::Srgb),
+        _ => rgba
+    };
+
+    let r = rgba.red as CGFloat;
+    let g = rgba.green as CGFloat;
+    let b = rgba.blue as CGFloat;
+    let a = rgba.alpha as CGFloat;
+
+    match rgba.space {
+        ColorSpace::DisplayP3 => {
+            #[cfg(feature = "appkit")]
+            {
+                msg_send![class!(NSColor), colorWithDisplayP3Red:r green:g blue:b alpha:a]
+            }
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            {
+                msg_send![class!(UIColor), colorWithDisplayP3Red:r green:g blue:b alpha:a]
+            }
+        },
+
+        _ => {
+            #[cfg(feature = "appkit")]
+            {
+                msg_send![class!(NSColor), colorWithSRGBRed:r green:g blue:b alpha:a]
+            }
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            {
+                msg_send![class!(UIColor), colorWithRed:r green:g blue:b alpha:a]
+            }
+        }
+    }
+}
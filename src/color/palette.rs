@@ -0,0 +1,380 @@
+//! A `Palette` maps colors to concrete values for every `Style` (light/dark, normal/high
+//! contrast) without asking the system. This is useful in a few places:
+//!
+//! - Platforms without `NSAppearance` (e.g, GNUstep) have no way to tell us which appearance
+//!   is active, so dynamic colors would otherwise always render their light variant.
+//! - Tests can render against a fixed palette and style, rather than whatever the machine
+//!   running them happens to be set to.
+//! - Apps can override system colors with their own brand colors, and register extra named
+//!   colors alongside them.
+//!
+//! `Palette::system()` ships with values for every `Color::System*` variant (and the other
+//! semantic colors), taken from Apple's published Human Interface Guidelines. These are close to,
+//! but not guaranteed to be identical to, what a given OS release renders.
+//!
+//! Install a `ThemeProvider` with `set_theme_provider` to have `Color::System*` (and the other
+//! semantic colors) render with its palette's values, and dynamic colors (i.e, `Color::dynamic`
+//! and `Palette::color`) follow its style.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use lazy_static::lazy_static;
+
+use super::{Color, Contrast, Rgba, Style, Theme};
+
+lazy_static! {
+    static ref THEME_PROVIDER: RwLock<Option<Box<dyn ThemeProvider>>> = RwLock::new(None);
+}
+
+/// The concrete values for a single palette color, one for each `Style`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PaletteEntry {
+    /// The value used for the light theme at normal contrast.
+    pub light: Rgba,
+
+    /// The value used for the dark theme at normal contrast.
+    pub dark: Rgba,
+
+    /// The value used for the light theme at high contrast.
+    pub light_high_contrast: Rgba,
+
+    /// The value used for the dark theme at high contrast.
+    pub dark_high_contrast: Rgba
+}
+
+impl PaletteEntry {
+    /// Creates an entry that uses the same value for every style.
+    pub fn uniform(color: Rgba) -> Self {
+        PaletteEntry::new(color, color)
+    }
+
+    /// Creates an entry with light and dark values. The high contrast variants use the same
+    /// values; use `with_high_contrast` to set them separately.
+    pub fn new(light: Rgba, dark: Rgba) -> Self {
+        PaletteEntry {
+            light,
+            dark,
+            light_high_contrast: light,
+            dark_high_contrast: dark
+        }
+    }
+
+    /// Returns a copy of this entry with the high contrast variants replaced.
+    pub fn with_high_contrast(self, light_high_contrast: Rgba, dark_high_contrast: Rgba) -> Self {
+        PaletteEntry {
+            light_high_contrast,
+            dark_high_contrast,
+            ..self
+        }
+    }
+
+    /// Returns the value to use for the given style.
+    pub fn get(&self, style: Style) -> Rgba {
+        match (style.theme, style.contrast) {
+            (Theme::Light, Contrast::Normal) => self.light,
+            (Theme::Light, Contrast::High) => self.light_high_contrast,
+            (Theme::Dark, Contrast::Normal) => self.dark,
+            (Theme::Dark, Contrast::High) => self.dark_high_contrast
+        }
+    }
+}
+
+/// A set of named colors, each with a value per `Style`. System colors are stored under their
+/// platform names (e.g, `systemBlue`, `secondaryLabel`); see `Palette::system_name`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Palette {
+    entries: HashMap<String, PaletteEntry>
+}
+
+impl Palette {
+    /// Returns a palette with no entries.
+    pub fn empty() -> Self {
+        Palette::default()
+    }
+
+    /// Returns a palette populated with values for every system color.
+    pub fn system() -> Self {
+        let mut palette = Palette::empty();
+
+        for (name, [light, dark, light_high_contrast, dark_high_contrast]) in SYSTEM_COLORS {
+            let value = |hex: &str| hex.parse::<Rgba>().expect("Invalid system palette color");
+
+            palette.set(
+                *name,
+                PaletteEntry::new(value(light), value(dark))
+                    .with_high_contrast(value(light_high_contrast), value(dark_high_contrast))
+            );
+        }
+
+        palette
+    }
+
+    /// Returns the palette name for a system color, or `None` for `Color::Custom`.
+    pub fn system_name(color: &Color) -> Option<&'static str> {
+        Some(match color {
+            Color::Custom(_) => return None,
+            Color::SystemBlack => "black",
+            Color::SystemWhite => "white",
+            Color::SystemBrown => "systemBrown",
+            Color::SystemBlue => "systemBlue",
+            Color::SystemGreen => "systemGreen",
+            Color::SystemIndigo => "systemIndigo",
+            Color::SystemOrange => "systemOrange",
+            Color::SystemPink => "systemPink",
+            Color::SystemPurple => "systemPurple",
+            Color::SystemRed => "systemRed",
+            Color::SystemTeal => "systemTeal",
+            Color::SystemYellow => "systemYellow",
+            Color::SystemGray => "systemGray",
+            Color::SystemGray2 => "systemGray2",
+            Color::SystemGray3 => "systemGray3",
+            Color::SystemGray4 => "systemGray4",
+            Color::SystemGray5 => "systemGray5",
+            Color::SystemGray6 => "systemGray6",
+            Color::Clear => "clear",
+            Color::Label => "label",
+            Color::LabelSecondary => "secondaryLabel",
+            Color::LabelTertiary => "tertiaryLabel",
+            Color::LabelQuaternary => "quaternaryLabel",
+            Color::SystemFill => "systemFill",
+            Color::SystemFillSecondary => "secondarySystemFill",
+            Color::SystemFillTertiary => "tertiarySystemFill",
+            Color::SystemFillQuaternary => "quaternarySystemFill",
+            Color::PlaceholderText => "placeholderText",
+            Color::SystemBackground => "systemBackground",
+            Color::SystemBackgroundSecondary => "secondarySystemBackground",
+            Color::SystemBackgroundTertiary => "tertiarySystemBackground",
+            Color::Separator => "separator",
+
+            #[cfg(feature = "uikit")]
+            Color::OpaqueSeparator => "opaqueSeparator",
+
+            Color::Link => "link",
+            Color::DarkText => "darkText",
+            Color::LightText => "lightText",
+
+            #[cfg(feature = "appkit")]
+            Color::MacOSWindowBackgroundColor => "windowBackground",
+
+            #[cfg(feature = "appkit")]
+            Color::MacOSUnderPageBackgroundColor => "underPageBackground"
+        })
+    }
+
+    /// Adds (or replaces) a named entry. Use this both to register extra app colors and to
+    /// override system ones.
+    pub fn set<S: Into<String>>(&mut self, name: S, entry: PaletteEntry) {
+        self.entries.insert(name.into(), entry);
+    }
+
+    /// Overrides the entry for a system color. Returns `false` (and does nothing) if `color`
+    /// is `Color::Custom`.
+    pub fn set_system(&mut self, color: &Color, entry: PaletteEntry) -> bool {
+        match Palette::system_name(color) {
+            Some(name) => {
+                self.set(name, entry);
+                true
+            },
+
+            None => false
+        }
+    }
+
+    /// Removes a named entry, returning it if it existed.
+    pub fn remove(&mut self, name: &str) -> Option<PaletteEntry> {
+        self.entries.remove(name)
+    }
+
+    /// Returns the entry for a name, if one exists.
+    pub fn get(&self, name: &str) -> Option<&PaletteEntry> {
+        self.entries.get(name)
+    }
+
+    /// Returns an iterator over every name and entry in this palette, in no particular order.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &PaletteEntry)> {
+        self.entries.iter().map(|(name, entry)| (name.as_str(), entry))
+    }
+
+    /// Resolves a named entry to a concrete value for the given style.
+    pub fn resolve_named(&self, name: &str, style: Style) -> Option<Rgba> {
+        self.get(name).map(|entry| entry.get(style))
+    }
+
+    /// Resolves a `Color` to a concrete value for the given style. System colors are looked up
+    /// by their palette name; `Color::Custom` can't be inspected without the runtime, and
+    /// returns `None`.
+    pub fn resolve(&self, color: &Color, style: Style) -> Option<Rgba> {
+        Palette::system_name(color).and_then(|name| self.resolve_named(name, style))
+    }
+
+    /// Returns a dynamic `Color` for a named entry, which renders the appropriate value for
+    /// the current appearance (or the installed `ThemeProvider`, if there is one).
+    #[cfg(feature = "appkit")]
+    pub fn color(&self, name: &str) -> Option<Color> {
+        let entry = *self.get(name)?;
+        Some(Color::dynamic(move |style| Color::from(entry.get(style))))
+    }
+}
+
+/// Implement this to control which style (and palette) dynamic colors resolve against. Install
+/// one with `set_theme_provider`.
+pub trait ThemeProvider: Send + Sync {
+    /// The style that dynamic colors should render with.
+    fn style(&self) -> Style;
+
+    /// The palette to resolve colors against.
+    fn palette(&self) -> &Palette;
+
+    /// Resolves a color against this provider's palette and style.
+    fn resolve(&self, color: &Color) -> Option<Rgba> {
+        self.palette().resolve(color, self.style())
+    }
+}
+
+/// A `ThemeProvider` with a fixed style and palette. Handy for tests, and for platforms that
+/// can't report their appearance.
+#[derive(Clone, Debug)]
+pub struct StaticTheme {
+    /// The style that dynamic colors should render with.
+    pub style: Style,
+
+    /// The palette to resolve colors against.
+    pub palette: Palette
+}
+
+impl StaticTheme {
+    /// Returns a theme using the system palette with the given style.
+    pub fn new(style: Style) -> Self {
+        StaticTheme {
+            style,
+            palette: Palette::system()
+        }
+    }
+}
+
+impl ThemeProvider for StaticTheme {
+    fn style(&self) -> Style {
+        self.style
+    }
+
+    fn palette(&self) -> &Palette {
+        &self.palette
+    }
+}
+
+/// Installs a `ThemeProvider`. While one is installed, system colors render with the values it
+/// resolves them to (falling back to the system's for colors its palette doesn't have), and
+/// dynamic colors render using its style rather than asking the system for the current
+/// appearance.
+///
+/// Colors are resolved when they're handed to the system, so views that are already showing a
+/// system color pick up a new provider the next time they're given that color.
+pub fn set_theme_provider<T: ThemeProvider + 'static>(provider: T) {
+    let mut lock = THEME_PROVIDER.write().unwrap();
+    *lock = Some(Box::new(provider));
+}
+
+/// Removes any installed `ThemeProvider`, handing appearance back to the system.
+pub fn clear_theme_provider() {
+    let mut lock = THEME_PROVIDER.write().unwrap();
+    *lock = None;
+}
+
+/// Resolves a color against the installed `ThemeProvider`, if there is one.
+pub(crate) fn provided_color(color: &Color) -> Option<Rgba> {
+    let lock = THEME_PROVIDER.read().unwrap();
+    lock.as_ref().and_then(|provider| provider.resolve(color))
+}
+
+/// Returns the style of the installed `ThemeProvider`, if there is one.
+pub(crate) fn provided_style() -> Option<Style> {
+    let lock = THEME_PROVIDER.read().unwrap();
+    lock.as_ref().map(|provider| provider.style())
+}
+
+/// System color values, as `[light, dark, light high contrast, dark high contrast]`.
+static SYSTEM_COLORS: &[(&str, [&str; 4])] = &[
+    ("black", ["#000000", "#000000", "#000000", "#000000"]),
+    ("white", ["#ffffff", "#ffffff", "#ffffff", "#ffffff"]),
+    ("clear", ["#00000000", "#00000000", "#00000000", "#00000000"]),
+    ("systemRed", ["#ff3b30", "#ff453a", "#d70015", "#ff6961"]),
+    ("systemOrange", ["#ff9500", "#ff9f0a", "#c93400", "#ffb340"]),
+    ("systemYellow", ["#ffcc00", "#ffd60a", "#b25000", "#ffd426"]),
+    ("systemGreen", ["#34c759", "#30d158", "#248a3d", "#30db5b"]),
+    ("systemTeal", ["#30b0c7", "#40c8e0", "#008299", "#5de6ff"]),
+    ("systemBlue", ["#007aff", "#0a84ff", "#0040dd", "#409cff"]),
+    ("systemIndigo", ["#5856d6", "#5e5ce6", "#3634a3", "#7d7aff"]),
+    ("systemPurple", ["#af52de", "#bf5af2", "#8944ab", "#da8fff"]),
+    ("systemPink", ["#ff2d55", "#ff375f", "#d30f45", "#ff6482"]),
+    ("systemBrown", ["#a2845e", "#ac8e68", "#7f6545", "#b59469"]),
+    ("systemGray", ["#8e8e93", "#8e8e93", "#6c6c70", "#aeaeb2"]),
+    ("systemGray2", ["#aeaeb2", "#636366", "#8e8e93", "#7c7c80"]),
+    ("systemGray3", ["#c7c7cc", "#48484a", "#aeaeb2", "#545456"]),
+    ("systemGray4", ["#d1d1d6", "#3a3a3c", "#bcbcc0", "#444446"]),
+    ("systemGray5", ["#e5e5ea", "#2c2c2e", "#d8d8dc", "#363638"]),
+    ("systemGray6", ["#f2f2f7", "#1c1c1e", "#ebebf0", "#242426"]),
+    ("label", ["#000000", "#ffffff", "#000000", "#ffffff"]),
+    ("secondaryLabel", ["#3c3c4399", "#ebebf599", "#3c3c43ad", "#ebebf5b3"]),
+    ("tertiaryLabel", ["#3c3c434d", "#ebebf54d", "#3c3c435c", "#ebebf566"]),
+    ("quaternaryLabel", ["#3c3c432e", "#ebebf52e", "#3c3c4342", "#ebebf542"]),
+    ("systemFill", ["#78788033", "#7878805c", "#78788052", "#78788080"]),
+    ("secondarySystemFill", ["#78788029", "#78788052", "#78788042", "#78788070"]),
+    ("tertiarySystemFill", ["#7676801f", "#7676803d", "#76768033", "#7676805c"]),
+    ("quaternarySystemFill", ["#74748014", "#7676802e", "#74748029", "#76768042"]),
+    ("placeholderText", ["#3c3c434d", "#ebebf54d", "#3c3c435c", "#ebebf566"]),
+    ("systemBackground", ["#ffffff", "#000000", "#ffffff", "#000000"]),
+    ("secondarySystemBackground", ["#f2f2f7", "#1c1c1e", "#ebebf0", "#242426"]),
+    ("tertiarySystemBackground", ["#ffffff", "#2c2c2e", "#ffffff", "#363638"]),
+    ("separator", ["#3c3c434a", "#54545899", "#3c3c4380", "#545458b3"]),
+    ("opaqueSeparator", ["#c6c6c8", "#38383a", "#a0a0a4", "#4f4f52"]),
+    ("link", ["#007aff", "#0984ff", "#0040dd", "#409cff"]),
+    ("darkText", ["#000000", "#000000", "#000000", "#000000"]),
+    ("lightText", ["#ffffff99", "#ffffff99", "#ffffffb3", "#ffffffb3"]),
+    ("windowBackground", ["#ececec", "#323232", "#ececec", "#323232"]),
+    ("underPageBackground", ["#969696", "#282828", "#969696", "#282828"])
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{Palette, PaletteEntry, StaticTheme, ThemeProvider};
+    use crate::color::{Color, Contrast, ContrastLevel, Rgba, Style, Theme};
+
+    const DARK: Style = Style {
+        theme: Theme::Dark,
+        contrast: Contrast::Normal
+    };
+
+    const LIGHT_HIGH_CONTRAST: Style = Style {
+        theme: Theme::Light,
+        contrast: Contrast::High
+    };
+
+    #[test]
+    fn test_system_palette() {
+        let palette = Palette::system();
+
+        assert_eq!(palette.resolve(&Color::SystemBlue, DARK).unwrap().to_hex(), "#0a84ff");
+        assert_eq!(
+            palette.resolve(&Color::SystemRed, LIGHT_HIGH_CONTRAST).unwrap().to_hex(),
+            "#d70015"
+        );
+
+        let label = palette.resolve(&Color::Label, DARK).unwrap();
+        let background = palette.resolve(&Color::SystemBackground, DARK).unwrap();
+        assert!(label.meets_contrast(&background, ContrastLevel::AAA));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let brand = Rgba::from_u8(0x66, 0x33, 0x99, 255);
+        let mut theme = StaticTheme::new(DARK);
+
+        assert!(theme.palette.set_system(&Color::SystemBlue, PaletteEntry::uniform(brand)));
+        theme.palette.set("accent", PaletteEntry::new(brand, brand.lighten(0.2)));
+
+        assert_eq!(theme.resolve(&Color::SystemBlue), Some(brand));
+        assert_eq!(theme.palette.resolve_named("accent", DARK), Some(brand.lighten(0.2)));
+        assert_eq!(theme.palette.resolve_named("missing", DARK), None);
+    }
+}
@@ -0,0 +1,445 @@
+//! Parsing for CSS-style color strings. This is pure Rust and doesn't touch the Objective-C
+//! runtime, so it can be used (and tested) anywhere; `Color::hex`, `Color::hexa` and the
+//! `FromStr` implementation on `Color` are thin wrappers around it.
+//!
+//! The following forms are supported:
+//!
+//! - `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa` (the leading `#` is optional for `Color::hex`).
+//! - `rgb()` and `rgba()`, with either comma or space separated components, numbers in `0-255` or
+//!   percentages, and an optional alpha (`rgb(255 0 0 / 50%)`).
+//! - `hsl()` and `hsla()`, with the hue in degrees (or `deg`, `rad`, `grad` and `turn` units).
+//! - The named CSS colors (e.g, `rebeccapurple`), along with `transparent`.
+
+use std::error;
+use std::fmt;
+
+use super::value::hsl_to_rgb;
+
+/// Errors that can occur when parsing a color string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ColorParseError {
+    /// The input was empty (or only whitespace).
+    Empty,
+
+    /// A hex color had a character that isn't a hex digit.
+    InvalidHexDigit(char),
+
+    /// A hex color had a number of digits other than 3, 4, 6 or 8.
+    InvalidHexLength(usize),
+
+    /// A functional notation (e.g, `rgb()`) was malformed - usually a missing parenthesis.
+    MalformedFunction(String),
+
+    /// A functional notation that we don't know how to handle (e.g, `lab()`).
+    UnknownFunction(String),
+
+    /// A functional notation was given the wrong number of arguments.
+    WrongArgumentCount {
+        /// The function that was called.
+        function: String,
+
+        /// The number of arguments that were found.
+        found: usize
+    },
+
+    /// A component inside a functional notation couldn't be parsed as a number,
+    /// percentage or angle.
+    InvalidComponent(String),
+
+    /// The input didn't match any known named color.
+    UnknownName(String)
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColorParseError::Empty => write!(f, "cannot parse a color from an empty string"),
+            ColorParseError::InvalidHexDigit(c) => write!(f, "invalid hex digit '{}' in color", c),
+            ColorParseError::InvalidHexLength(len) => {
+                write!(f, "hex colors must have 3, 4, 6 or 8 digits (found {})", len)
+            },
+            ColorParseError::MalformedFunction(input) => write!(f, "malformed color function: {}", input),
+            ColorParseError::UnknownFunction(name) => write!(f, "unknown color function: {}()", name),
+            ColorParseError::WrongArgumentCount { function, found } => {
+                write!(f, "{}() expects 3 or 4 arguments (found {})", function, found)
+            },
+            ColorParseError::InvalidComponent(component) => write!(f, "invalid color component: {}", component),
+            ColorParseError::UnknownName(name) => write!(f, "unknown color name: {}", name)
+        }
+    }
+}
+
+impl error::Error for ColorParseError {}
+
+/// Parses any supported color string, returning `[red, green, blue, alpha]` components
+/// in the `0.0 - 1.0` range.
+pub(crate) fn parse(input: &str) -> Result<[f64; 4], ColorParseError> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Err(ColorParseError::Empty);
+    }
+
+    if let Some(hex) = input.strip_prefix('#') {
+        return parse_hex(hex);
+    }
+
+    if let Some(open) = input.find('(') {
+        return parse_function(input, open);
+    }
+
+    parse_named(input)
+}
+
+/// Parses a hex color string (without the leading `#`), returning `[red, green, blue, alpha]`
+/// components in the `0.0 - 1.0` range.
+pub(crate) fn parse_hex(hex: &str) -> Result<[f64; 4], ColorParseError> {
+    let mut digits = Vec::with_capacity(8);
+
+    for c in hex.chars() {
+        match c.to_digit(16) {
+            Some(digit) => digits.push(digit as u8),
+            None => return Err(ColorParseError::InvalidHexDigit(c))
+        }
+    }
+
+    let channels: Vec<u8> = match digits.len() {
+        // Shorthand forms repeat each digit, so `f` becomes `ff`.
+        3 | 4 => digits.iter().map(|d| d * 17).collect(),
+        6 | 8 => digits.chunks(2).map(|pair| pair[0] * 16 + pair[1]).collect(),
+        len => return Err(ColorParseError::InvalidHexLength(len))
+    };
+
+    let alpha = channels.get(3).copied().unwrap_or(255);
+
+    Ok([
+        channels[0] as f64 / 255.,
+        channels[1] as f64 / 255.,
+        channels[2] as f64 / 255.,
+        alpha as f64 / 255.
+    ])
+}
+
+/// Parses `rgb()`, `rgba()`, `hsl()` and `hsla()`.
+fn parse_function(input: &str, open: usize) -> Result<[f64; 4], ColorParseError> {
+    let name = input[..open].trim().to_ascii_lowercase();
+
+    let body = match input[open + 1..].strip_suffix(')') {
+        Some(body) => body,
+        None => return Err(ColorParseError::MalformedFunction(input.to_string()))
+    };
+
+    // Both the legacy (`rgb(1, 2, 3, 0.5)`) and modern (`rgb(1 2 3 / 50%)`) syntaxes are
+    // accepted; normalizing the separators lets us treat them the same.
+    let args: Vec<&str> = body
+        .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+        .filter(|arg| !arg.is_empty())
+        .collect();
+
+    if args.len() != 3 && args.len() != 4 {
+        return Err(ColorParseError::WrongArgumentCount {
+            function: name,
+            found: args.len()
+        });
+    }
+
+    let alpha = match args.get(3) {
+        Some(alpha) => parse_alpha(alpha)?,
+        None => 1.
+    };
+
+    match name.as_str() {
+        "rgb" | "rgba" => Ok([
+            parse_rgb_channel(args[0])?,
+            parse_rgb_channel(args[1])?,
+            parse_rgb_channel(args[2])?,
+            alpha
+        ]),
+
+        "hsl" | "hsla" => {
+            let hue = parse_hue(args[0])?;
+            let saturation = parse_percentage(args[1])?;
+            let lightness = parse_percentage(args[2])?;
+            let [r, g, b] = hsl_to_rgb(hue, saturation, lightness);
+            Ok([r, g, b, alpha])
+        },
+
+        _ => Err(ColorParseError::UnknownFunction(name))
+    }
+}
+
+/// Looks up a named CSS color.
+fn parse_named(input: &str) -> Result<[f64; 4], ColorParseError> {
+    let name = input.to_ascii_lowercase();
+
+    if name == "transparent" {
+        return Ok([0., 0., 0., 0.]);
+    }
+
+    match NAMED_COLORS.binary_search_by(|(key, _)| (*key).cmp(name.as_str())) {
+        Ok(index) => {
+            let value = NAMED_COLORS[index].1;
+
+            Ok([
+                ((value >> 16) & 0xff) as f64 / 255.,
+                ((value >> 8) & 0xff) as f64 / 255.,
+                (value & 0xff) as f64 / 255.,
+                1.
+            ])
+        },
+
+        Err(_) => Err(ColorParseError::UnknownName(input.to_string()))
+    }
+}
+
+/// Parses a plain number, erroring out with the original component if it's not one.
+fn parse_number(component: &str) -> Result<f64, ColorParseError> {
+    match component.parse::<f64>() {
+        Ok(value) if value.is_finite() => Ok(value),
+        _ => Err(ColorParseError::InvalidComponent(component.to_string()))
+    }
+}
+
+/// Parses a percentage (e.g, `50%`) into the `0.0 - 1.0` range.
+fn parse_percentage(component: &str) -> Result<f64, ColorParseError> {
+    match component.strip_suffix('%') {
+        Some(value) => Ok((parse_number(value)? / 100.).clamp(0., 1.)),
+        None => Err(ColorParseError::InvalidComponent(component.to_string()))
+    }
+}
+
+/// Parses an `rgb()` channel, which is either `0-255` or a percentage.
+fn parse_rgb_channel(component: &str) -> Result<f64, ColorParseError> {
+    if component.ends_with('%') {
+        return parse_percentage(component);
+    }
+
+    Ok((parse_number(component)? / 255.).clamp(0., 1.))
+}
+
+/// Parses an alpha value, which is either `0.0-1.0` or a percentage.
+fn parse_alpha(component: &str) -> Result<f64, ColorParseError> {
+    if component.ends_with('%') {
+        return parse_percentage(component);
+    }
+
+    Ok(parse_number(component)?.clamp(0., 1.))
+}
+
+/// Parses a hue into degrees, normalized to `0.0 - 360.0`. Unitless values are degrees.
+fn parse_hue(component: &str) -> Result<f64, ColorParseError> {
+    let lowercased = component.to_ascii_lowercase();
+
+    let degrees = if let Some(value) = lowercased.strip_suffix("deg") {
+        parse_number(value)?
+    } else if let Some(value) = lowercased.strip_suffix("grad") {
+        parse_number(value)? * 0.9
+    } else if let Some(value) = lowercased.strip_suffix("rad") {
+        parse_number(value)?.to_degrees()
+    } else if let Some(value) = lowercased.strip_suffix("turn") {
+        parse_number(value)? * 360.
+    } else {
+        parse_number(&lowercased)?
+    };
+
+    Ok(degrees.rem_euclid(360.))
+}
+
+/// The CSS named colors, sorted by name so we can binary search them.
+static NAMED_COLORS: &[(&str, u32)] = &[
+    ("aliceblue", 0xf0f8ff),
+    ("antiquewhite", 0xfaebd7),
+    ("aqua", 0x00ffff),
+    ("aquamarine", 0x7fffd4),
+    ("azure", 0xf0ffff),
+    ("beige", 0xf5f5dc),
+    ("bisque", 0xffe4c4),
+    ("black", 0x000000),
+    ("blanchedalmond", 0xffebcd),
+    ("blue", 0x0000ff),
+    ("blueviolet", 0x8a2be2),
+    ("brown", 0xa52a2a),
+    ("burlywood", 0xdeb887),
+    ("cadetblue", 0x5f9ea0),
+    ("chartreuse", 0x7fff00),
+    ("chocolate", 0xd2691e),
+    ("coral", 0xff7f50),
+    ("cornflowerblue", 0x6495ed),
+    ("cornsilk", 0xfff8dc),
+    ("crimson", 0xdc143c),
+    ("cyan", 0x00ffff),
+    ("darkblue", 0x00008b),
+    ("darkcyan", 0x008b8b),
+    ("darkgoldenrod", 0xb8860b),
+    ("darkgray", 0xa9a9a9),
+    ("darkgreen", 0x006400),
+    ("darkgrey", 0xa9a9a9),
+    ("darkkhaki", 0xbdb76b),
+    ("darkmagenta", 0x8b008b),
+    ("darkolivegreen", 0x556b2f),
+    ("darkorange", 0xff8c00),
+    ("darkorchid", 0x9932cc),
+    ("darkred", 0x8b0000),
+    ("darksalmon", 0xe9967a),
+    ("darkseagreen", 0x8fbc8f),
+    ("darkslateblue", 0x483d8b),
+    ("darkslategray", 0x2f4f4f),
+    ("darkslategrey", 0x2f4f4f),
+    ("darkturquoise", 0x00ced1),
+    ("darkviolet", 0x9400d3),
+    ("deeppink", 0xff1493),
+    ("deepskyblue", 0x00bfff),
+    ("dimgray", 0x696969),
+    ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1e90ff),
+    ("firebrick", 0xb22222),
+    ("floralwhite", 0xfffaf0),
+    ("forestgreen", 0x228b22),
+    ("fuchsia", 0xff00ff),
+    ("gainsboro", 0xdcdcdc),
+    ("ghostwhite", 0xf8f8ff),
+    ("gold", 0xffd700),
+    ("goldenrod", 0xdaa520),
+    ("gray", 0x808080),
+    ("green", 0x008000),
+    ("greenyellow", 0xadff2f),
+    ("grey", 0x808080),
+    ("honeydew", 0xf0fff0),
+    ("hotpink", 0xff69b4),
+    ("indianred", 0xcd5c5c),
+    ("indigo", 0x4b0082),
+    ("ivory", 0xfffff0),
+    ("khaki", 0xf0e68c),
+    ("lavender", 0xe6e6fa),
+    ("lavenderblush", 0xfff0f5),
+    ("lawngreen", 0x7cfc00),
+    ("lemonchiffon", 0xfffacd),
+    ("lightblue", 0xadd8e6),
+    ("lightcoral", 0xf08080),
+    ("lightcyan", 0xe0ffff),
+    ("lightgoldenrodyellow", 0xfafad2),
+    ("lightgray", 0xd3d3d3),
+    ("lightgreen", 0x90ee90),
+    ("lightgrey", 0xd3d3d3),
+    ("lightpink", 0xffb6c1),
+    ("lightsalmon", 0xffa07a),
+    ("lightseagreen", 0x20b2aa),
+    ("lightskyblue", 0x87cefa),
+    ("lightslategray", 0x778899),
+    ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xb0c4de),
+    ("lightyellow", 0xffffe0),
+    ("lime", 0x00ff00),
+    ("limegreen", 0x32cd32),
+    ("linen", 0xfaf0e6),
+    ("magenta", 0xff00ff),
+    ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66cdaa),
+    ("mediumblue", 0x0000cd),
+    ("mediumorchid", 0xba55d3),
+    ("mediumpurple", 0x9370db),
+    ("mediumseagreen", 0x3cb371),
+    ("mediumslateblue", 0x7b68ee),
+    ("mediumspringgreen", 0x00fa9a),
+    ("mediumturquoise", 0x48d1cc),
+    ("mediumvioletred", 0xc71585),
+    ("midnightblue", 0x191970),
+    ("mintcream", 0xf5fffa),
+    ("mistyrose", 0xffe4e1),
+    ("moccasin", 0xffe4b5),
+    ("navajowhite", 0xffdead),
+    ("navy", 0x000080),
+    ("oldlace", 0xfdf5e6),
+    ("olive", 0x808000),
+    ("olivedrab", 0x6b8e23),
+    ("orange", 0xffa500),
+    ("orangered", 0xff4500),
+    ("orchid", 0xda70d6),
+    ("palegoldenrod", 0xeee8aa),
+    ("palegreen", 0x98fb98),
+    ("paleturquoise", 0xafeeee),
+    ("palevioletred", 0xdb7093),
+    ("papayawhip", 0xffefd5),
+    ("peachpuff", 0xffdab9),
+    ("peru", 0xcd853f),
+    ("pink", 0xffc0cb),
+    ("plum", 0xdda0dd),
+    ("powderblue", 0xb0e0e6),
+    ("purple", 0x800080),
+    ("rebeccapurple", 0x663399),
+    ("red", 0xff0000),
+    ("rosybrown", 0xbc8f8f),
+    ("royalblue", 0x4169e1),
+    ("saddlebrown", 0x8b4513),
+    ("salmon", 0xfa8072),
+    ("sandybrown", 0xf4a460),
+    ("seagreen", 0x2e8b57),
+    ("seashell", 0xfff5ee),
+    ("sienna", 0xa0522d),
+    ("silver", 0xc0c0c0),
+    ("skyblue", 0x87ceeb),
+    ("slateblue", 0x6a5acd),
+    ("slategray", 0x708090),
+    ("slategrey", 0x708090),
+    ("snow", 0xfffafa),
+    ("springgreen", 0x00ff7f),
+    ("steelblue", 0x4682b4),
+    ("tan", 0xd2b48c),
+    ("teal", 0x008080),
+    ("thistle", 0xd8bfd8),
+    ("tomato", 0xff6347),
+    ("turquoise", 0x40e0d0),
+    ("violet", 0xee82ee),
+    ("wheat", 0xf5deb3),
+    ("white", 0xffffff),
+    ("whitesmoke", 0xf5f5f5),
+    ("yellow", 0xffff00),
+    ("yellowgreen", 0x9acd32)
+];
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, ColorParseError};
+
+    fn assert_rgba(input: &str, expected: [f64; 4]) {
+        let parsed = parse(input).unwrap();
+
+        for (actual, expected) in parsed.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 0.001, "{}: {:?} != {:?}", input, parsed, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_rgba("#f00", [1., 0., 0., 1.]);
+        assert_rgba("#f008", [1., 0., 0., 0.533]);
+        assert_rgba("#00ff00", [0., 1., 0., 1.]);
+        assert_rgba("#0000ff80", [0., 0., 1., 0.502]);
+        assert_eq!(parse("#12345"), Err(ColorParseError::InvalidHexLength(5)));
+        assert_eq!(parse("#zzz"), Err(ColorParseError::InvalidHexDigit('z')));
+    }
+
+    #[test]
+    fn test_parse_functions() {
+        assert_rgba("rgb(255, 0, 0)", [1., 0., 0., 1.]);
+        assert_rgba("rgba(0, 255, 0, 0.5)", [0., 1., 0., 0.5]);
+        assert_rgba("rgb(0 0 100% / 25%)", [0., 0., 1., 0.25]);
+        assert_rgba("hsl(120, 100%, 50%)", [0., 1., 0., 1.]);
+        assert_rgba("hsla(0.5turn 100% 50% / 0.5)", [0., 1., 1., 0.5]);
+        assert_eq!(parse("lab(1, 2, 3)"), Err(ColorParseError::UnknownFunction("lab".into())));
+        assert!(matches!(
+            parse("rgb(1, 2)"),
+            Err(ColorParseError::WrongArgumentCount { found: 2, .. })
+        ));
+        assert!(matches!(parse("rgb(1, 2, 3"), Err(ColorParseError::MalformedFunction(_))));
+    }
+
+    #[test]
+    fn test_parse_named() {
+        assert_rgba("rebeccapurple", [0.4, 0.2, 0.6, 1.]);
+        assert_rgba("AliceBlue", [0.941, 0.973, 1., 1.]);
+        assert_rgba("transparent", [0., 0., 0., 0.]);
+        assert_eq!(parse("notacolor"), Err(ColorParseError::UnknownName("notacolor".into())));
+        assert_eq!(parse("  "), Err(ColorParseError::Empty));
+    }
+}
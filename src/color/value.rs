@@ -0,0 +1,527 @@
+//! Plain Rust color values. Unlike `Color`, these don't touch the Objective-C runtime, so they can
+//! be inspected, converted and compared anywhere - including in unit tests that have no access to
+//! AppKit or UIKit.
+//!
+//! A `Color` can be built from any of these (`Color::from(Rgba::new(...))`), and resolved back to
+//! an `Rgba` with `Color::resolve`.
+//!
+//! All components are `f64` values in the `0.0 - 1.0` range, save for hues, which are in degrees.
+
+use std::str::FromStr;
+
+use super::parse;
+use super::ColorParseError;
+
+/// The color space that the components of an `Rgba` are expressed in.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorSpace {
+    /// Standard, gamma-encoded sRGB. This is what you get from hex codes and CSS colors.
+    #[default]
+    Srgb,
+
+    /// sRGB primaries with a linear transfer function. Useful for blending and lighting math.
+    LinearSrgb,
+
+    /// Display P3, the wide-gamut space used by most modern Apple displays. This shares the
+    /// sRGB transfer function but has wider primaries.
+    DisplayP3
+}
+
+/// The WCAG 2 contrast levels that a pair of colors can be checked against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ContrastLevel {
+    /// Level AA for normal text, requiring a ratio of at least 4.5:1.
+    AA,
+
+    /// Level AA for large text (18pt, or 14pt bold), requiring a ratio of at least 3:1.
+    AALargeText,
+
+    /// Level AAA for normal text, requiring a ratio of at least 7:1.
+    AAA,
+
+    /// Level AAA for large text (18pt, or 14pt bold), requiring a ratio of at least 4.5:1.
+    AAALargeText
+}
+
+impl ContrastLevel {
+    /// Returns the minimum contrast ratio required to meet this level.
+    pub fn minimum_ratio(&self) -> f64 {
+        match self {
+            ContrastLevel::AA => 4.5,
+            ContrastLevel::AALargeText => 3.,
+            ContrastLevel::AAA => 7.,
+            ContrastLevel::AAALargeText => 4.5
+        }
+    }
+}
+
+/// A color in an RGB color space, with an alpha channel.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Rgba {
+    /// The red component, from `0.0 - 1.0`.
+    pub red: f64,
+
+    /// The green component, from `0.0 - 1.0`.
+    pub green: f64,
+
+    /// The blue component, from `0.0 - 1.0`.
+    pub blue: f64,
+
+    /// The alpha component, from `0.0` (fully transparent) to `1.0` (fully opaque).
+    pub alpha: f64,
+
+    /// The color space the components are expressed in.
+    pub space: ColorSpace
+}
+
+impl Rgba {
+    /// Creates a new sRGB color from components in the `0.0 - 1.0` range.
+    pub fn new(red: f64, green: f64, blue: f64, alpha: f64) -> Self {
+        Rgba::with_space(red, green, blue, alpha, ColorSpace::Srgb)
+    }
+
+    /// Creates a new color from components in the `0.0 - 1.0` range, in the specified space.
+    pub fn with_space(red: f64, green: f64, blue: f64, alpha: f64, space: ColorSpace) -> Self {
+        Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+            space
+        }
+    }
+
+    /// Creates a new sRGB color from `0 - 255` components.
+    pub fn from_u8(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Rgba::new(
+            red as f64 / 255.,
+            green as f64 / 255.,
+            blue as f64 / 255.,
+            alpha as f64 / 255.
+        )
+    }
+
+    /// Returns the components as `[red, green, blue, alpha]` in the `0 - 255` range, clamping
+    /// anything that falls outside of the gamut.
+    pub fn to_u8(&self) -> [u8; 4] {
+        let channel = |value: f64| (value.clamp(0., 1.) * 255.).round() as u8;
+        [
+            channel(self.red),
+            channel(self.green),
+            channel(self.blue),
+            channel(self.alpha)
+        ]
+    }
+
+    /// Returns this color as an sRGB hex code - `#rrggbb` if it's opaque, and `#rrggbbaa` otherwise.
+    pub fn to_hex(&self) -> String {
+        let [r, g, b, a] = self.to_space(ColorSpace::Srgb).to_u8();
+
+        match a {
+            255 => format!("#{:02x}{:02x}{:02x}", r, g, b),
+            _ => format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+        }
+    }
+
+    /// Returns a copy of this color with the alpha replaced.
+    pub fn with_alpha(&self, alpha: f64) -> Self {
+        Rgba { alpha, ..*self }
+    }
+
+    /// Converts this color into the specified color space. Colors that fall outside of the
+    /// target gamut are not clamped, so round-tripping is lossless.
+    pub fn to_space(&self, space: ColorSpace) -> Self {
+        if self.space == space {
+            return *self;
+        }
+
+        let [r, g, b] = self.linear_srgb();
+
+        let [r, g, b] = match space {
+            ColorSpace::LinearSrgb => [r, g, b],
+            ColorSpace::Srgb => [encode(r), encode(g), encode(b)],
+            ColorSpace::DisplayP3 => {
+                let [r, g, b] = multiply(&LINEAR_SRGB_TO_LINEAR_P3, [r, g, b]);
+                [encode(r), encode(g), encode(b)]
+            }
+        };
+
+        Rgba::with_space(r, g, b, self.alpha, space)
+    }
+
+    /// Returns the `[red, green, blue]` components in linear sRGB.
+    fn linear_srgb(&self) -> [f64; 3] {
+        match self.space {
+            ColorSpace::LinearSrgb => [self.red, self.green, self.blue],
+            ColorSpace::Srgb => [decode(self.red), decode(self.green), decode(self.blue)],
+            ColorSpace::DisplayP3 => multiply(&LINEAR_P3_TO_LINEAR_SRGB, [
+                decode(self.red),
+                decode(self.green),
+                decode(self.blue)
+            ])
+        }
+    }
+
+    /// Converts this color to HSL. The result is based on the sRGB representation of this color.
+    pub fn to_hsla(&self) -> Hsla {
+        let srgb = self.to_space(ColorSpace::Srgb);
+        let (hue, max, min) = hue_max_min(srgb.red, srgb.green, srgb.blue);
+        let lightness = (max + min) / 2.;
+        let delta = max - min;
+
+        let saturation = match delta == 0. {
+            true => 0.,
+            false => delta / (1. - (2. * lightness - 1.).abs())
+        };
+
+        Hsla::new(hue, saturation, lightness, self.alpha)
+    }
+
+    /// Converts this color to HSB. The result is based on the sRGB representation of this color.
+    pub fn to_hsba(&self) -> Hsba {
+        let srgb = self.to_space(ColorSpace::Srgb);
+        let (hue, max, min) = hue_max_min(srgb.red, srgb.green, srgb.blue);
+
+        let saturation = match max == 0. {
+            true => 0.,
+            false => (max - min) / max
+        };
+
+        Hsba::new(hue, saturation, max, self.alpha)
+    }
+
+    /// Mixes this color with `other`, where an `amount` of `0.0` returns this color and `1.0`
+    /// returns `other`. Mixing happens in this color's space.
+    pub fn mix(&self, other: &Rgba, amount: f64) -> Self {
+        let other = other.to_space(self.space);
+        let amount = amount.clamp(0., 1.);
+        let lerp = |from: f64, to: f64| from + (to - from) * amount;
+
+        Rgba::with_space(
+            lerp(self.red, other.red),
+            lerp(self.green, other.green),
+            lerp(self.blue, other.blue),
+            lerp(self.alpha, other.alpha),
+            self.space
+        )
+    }
+
+    /// Composites this color over `background` (the "source over" operation), returning the
+    /// color you'd actually see. Compositing happens in this color's space.
+    pub fn over(&self, background: &Rgba) -> Self {
+        let background = background.to_space(self.space);
+        let alpha = self.alpha + background.alpha * (1. - self.alpha);
+
+        if alpha == 0. {
+            return Rgba::with_space(0., 0., 0., 0., self.space);
+        }
+
+        let composite = |fg: f64, bg: f64| (fg * self.alpha + bg * background.alpha * (1. - self.alpha)) / alpha;
+
+        Rgba::with_space(
+            composite(self.red, background.red),
+            composite(self.green, background.green),
+            composite(self.blue, background.blue),
+            alpha,
+            self.space
+        )
+    }
+
+    /// Returns a lighter version of this color, increasing the HSL lightness by `amount`.
+    pub fn lighten(&self, amount: f64) -> Self {
+        let mut hsla = self.to_hsla();
+        hsla.lightness = (hsla.lightness + amount).clamp(0., 1.);
+        hsla.to_rgba().to_space(self.space)
+    }
+
+    /// Returns a darker version of this color, decreasing the HSL lightness by `amount`.
+    pub fn darken(&self, amount: f64) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Returns the WCAG 2 relative luminance of this color, ignoring alpha.
+    pub fn relative_luminance(&self) -> f64 {
+        let [r, g, b] = self.linear_srgb();
+        0.2126 * r.clamp(0., 1.) + 0.7152 * g.clamp(0., 1.) + 0.0722 * b.clamp(0., 1.)
+    }
+
+    /// Returns the WCAG 2 contrast ratio (from `1.0` to `21.0`) of this color drawn on top of
+    /// `background`. If this color is translucent, it's composited over the background first.
+    pub fn contrast_ratio(&self, background: &Rgba) -> f64 {
+        let foreground = self.over(background).relative_luminance();
+        let background = background.relative_luminance();
+
+        let (lighter, darker) = match foreground > background {
+            true => (foreground, background),
+            false => (background, foreground)
+        };
+
+        (lighter + 0.05) / (darker + 0.05)
+    }
+
+    /// Returns whether this color drawn on top of `background` meets the given contrast level.
+    pub fn meets_contrast(&self, background: &Rgba, level: ContrastLevel) -> bool {
+        self.contrast_ratio(background) >= level.minimum_ratio()
+    }
+}
+
+impl FromStr for Rgba {
+    type Err = ColorParseError;
+
+    /// Parses a CSS color string into an sRGB color. See `Color::css` for the supported forms.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let [r, g, b, a] = parse::parse(input)?;
+        Ok(Rgba::new(r, g, b, a))
+    }
+}
+
+impl From<Hsla> for Rgba {
+    fn from(hsla: Hsla) -> Self {
+        hsla.to_rgba()
+    }
+}
+
+impl From<Hsba> for Rgba {
+    fn from(hsba: Hsba) -> Self {
+        hsba.to_rgba()
+    }
+}
+
+/// A color expressed as hue, saturation and lightness, with an alpha channel. This is always
+/// relative to sRGB.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Hsla {
+    /// The hue, in degrees (`0.0 - 360.0`).
+    pub hue: f64,
+
+    /// The saturation, from `0.0 - 1.0`.
+    pub saturation: f64,
+
+    /// The lightness, from `0.0 - 1.0`.
+    pub lightness: f64,
+
+    /// The alpha component, from `0.0` (fully transparent) to `1.0` (fully opaque).
+    pub alpha: f64
+}
+
+impl Hsla {
+    /// Creates a new HSL color.
+    pub fn new(hue: f64, saturation: f64, lightness: f64, alpha: f64) -> Self {
+        Hsla {
+            hue,
+            saturation,
+            lightness,
+            alpha
+        }
+    }
+
+    /// Converts this color to sRGB.
+    pub fn to_rgba(&self) -> Rgba {
+        let [r, g, b] = hsl_to_rgb(self.hue, self.saturation, self.lightness);
+        Rgba::new(r, g, b, self.alpha)
+    }
+}
+
+impl From<Rgba> for Hsla {
+    fn from(rgba: Rgba) -> Self {
+        rgba.to_hsla()
+    }
+}
+
+/// A color expressed as hue, saturation and brightness (also known as HSV), with an alpha
+/// channel. This is always relative to sRGB, and matches what `Color::hsba` expects.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Hsba {
+    /// The hue, in degrees (`0.0 - 360.0`).
+    pub hue: f64,
+
+    /// The saturation, from `0.0 - 1.0`.
+    pub saturation: f64,
+
+    /// The brightness, from `0.0 - 1.0`.
+    pub brightness: f64,
+
+    /// The alpha component, from `0.0` (fully transparent) to `1.0` (fully opaque).
+    pub alpha: f64
+}
+
+impl Hsba {
+    /// Creates a new HSB color.
+    pub fn new(hue: f64, saturation: f64, brightness: f64, alpha: f64) -> Self {
+        Hsba {
+            hue,
+            saturation,
+            brightness,
+            alpha
+        }
+    }
+
+    /// Converts this color to sRGB.
+    pub fn to_rgba(&self) -> Rgba {
+        let chroma = self.brightness * self.saturation;
+        let [r, g, b] = hue_to_rgb(self.hue, chroma);
+        let m = self.brightness - chroma;
+        Rgba::new(r + m, g + m, b + m, self.alpha)
+    }
+}
+
+impl From<Rgba> for Hsba {
+    fn from(rgba: Rgba) -> Self {
+        rgba.to_hsba()
+    }
+}
+
+/// Converts HSL (hue in degrees, saturation and lightness in `0.0 - 1.0`) to sRGB.
+pub(crate) fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [f64; 3] {
+    let chroma = (1. - (2. * lightness - 1.).abs()) * saturation;
+    let [r, g, b] = hue_to_rgb(hue, chroma);
+    let m = lightness - chroma / 2.;
+    [r + m, g + m, b + m]
+}
+
+/// Returns the `[red, green, blue]` components for a hue at a given chroma, before the
+/// lightness/brightness offset is applied.
+fn hue_to_rgb(hue: f64, chroma: f64) -> [f64; 3] {
+    let h = hue.rem_euclid(360.) / 60.;
+    let x = chroma * (1. - (h % 2. - 1.).abs());
+
+    match h as u8 {
+        0 => [chroma, x, 0.],
+        1 => [x, chroma, 0.],
+        2 => [0., chroma, x],
+        3 => [0., x, chroma],
+        4 => [x, 0., chroma],
+        _ => [chroma, 0., x]
+    }
+}
+
+/// Returns the hue (in degrees), max and min component for a set of sRGB components.
+fn hue_max_min(r: f64, g: f64, b: f64) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let hue = if delta == 0. {
+        0.
+    } else if max == r {
+        60. * ((g - b) / delta).rem_euclid(6.)
+    } else if max == g {
+        60. * ((b - r) / delta + 2.)
+    } else {
+        60. * ((r - g) / delta + 4.)
+    };
+
+    (hue, max, min)
+}
+
+/// Applies the sRGB transfer function (linear to gamma-encoded). Display P3 uses it as well.
+fn encode(value: f64) -> f64 {
+    let magnitude = value.abs();
+
+    let encoded = match magnitude <= 0.0031308 {
+        true => magnitude * 12.92,
+        false => 1.055 * magnitude.powf(1. / 2.4) - 0.055
+    };
+
+    encoded.copysign(value)
+}
+
+/// Removes the sRGB transfer function (gamma-encoded to linear). Display P3 uses it as well.
+fn decode(value: f64) -> f64 {
+    let magnitude = value.abs();
+
+    let decoded = match magnitude <= 0.04045 {
+        true => magnitude / 12.92,
+        false => ((magnitude + 0.055) / 1.055).powf(2.4)
+    };
+
+    decoded.copysign(value)
+}
+
+fn multiply(matrix: &[[f64; 3]; 3], [r, g, b]: [f64; 3]) -> [f64; 3] {
+    [
+        matrix[0][0] * r + matrix[0][1] * g + matrix[0][2] * b,
+        matrix[1][0] * r + matrix[1][1] * g + matrix[1][2] * b,
+        matrix[2][0] * r + matrix[2][1] * g + matrix[2][2] * b
+    ]
+}
+
+/// Converts linear sRGB to linear Display P3 (both D65).
+const LINEAR_SRGB_TO_LINEAR_P3: [[f64; 3]; 3] = [[0.8224621, 0.1775380, 0.0000000], [0.0331941, 0.9668058, 0.0000000], [
+    0.0170827, 0.0723974, 0.9105199
+]];
+
+/// Converts linear Display P3 to linear sRGB (both D65).
+const LINEAR_P3_TO_LINEAR_SRGB: [[f64; 3]; 3] = [[1.2249401, -0.2249404, 0.0000000], [-0.0420569, 1.0420571, 0.0000000], [
+    -0.0196376, -0.0786361, 1.0982735
+]];
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorSpace, ContrastLevel, Hsba, Hsla, Rgba};
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 0.001, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_hsl_hsb_round_trip() {
+        let color = Rgba::from_u8(102, 51, 153, 255);
+
+        let hsla = color.to_hsla();
+        assert_close(hsla.hue, 270.);
+        assert_close(hsla.saturation, 0.5);
+        assert_close(hsla.lightness, 0.4);
+        assert_eq!(hsla.to_rgba().to_u8(), [102, 51, 153, 255]);
+
+        let hsba = color.to_hsba();
+        assert_close(hsba.brightness, 0.6);
+        assert_eq!(Rgba::from(hsba).to_u8(), [102, 51, 153, 255]);
+        assert_eq!(Rgba::from(Hsba::new(0., 1., 1., 1.)).to_u8(), [255, 0, 0, 255]);
+        assert_eq!(Rgba::from(Hsla::new(240., 1., 0.5, 1.)).to_u8(), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_color_space_round_trip() {
+        let color = Rgba::new(0.25, 0.5, 0.75, 1.);
+
+        let linear = color.to_space(ColorSpace::LinearSrgb);
+        assert_close(linear.green, 0.214);
+
+        let p3 = color.to_space(ColorSpace::DisplayP3);
+        let back = p3.to_space(ColorSpace::Srgb);
+        assert_close(back.red, 0.25);
+        assert_close(back.green, 0.5);
+        assert_close(back.blue, 0.75);
+
+        // Pure sRGB red sits inside the P3 gamut.
+        let red = Rgba::new(1., 0., 0., 1.).to_space(ColorSpace::DisplayP3);
+        assert_close(red.red, 0.9175);
+        assert!(red.green > 0. && red.blue > 0.);
+    }
+
+    #[test]
+    fn test_blending() {
+        let black = Rgba::new(0., 0., 0., 1.);
+        let white = Rgba::new(1., 1., 1., 1.);
+
+        assert_eq!(black.mix(&white, 0.5).to_u8(), [128, 128, 128, 255]);
+        assert_eq!(white.with_alpha(0.5).over(&black).to_u8(), [128, 128, 128, 255]);
+        assert_eq!(Rgba::from_u8(255, 0, 0, 255).lighten(0.25).to_hex(), "#ff8080");
+        assert_eq!(Rgba::from_u8(255, 0, 0, 255).darken(0.25).to_hex(), "#800000");
+        assert_eq!("#ff000080".parse::<Rgba>().unwrap().to_hex(), "#ff000080");
+    }
+
+    #[test]
+    fn test_contrast() {
+        let black = Rgba::new(0., 0., 0., 1.);
+        let white = Rgba::new(1., 1., 1., 1.);
+        let gray = Rgba::from_u8(118, 118, 118, 255);
+
+        assert_close(black.contrast_ratio(&white), 21.);
+        assert_close(white.contrast_ratio(&black), 21.);
+        assert!(gray.meets_contrast(&white, ContrastLevel::AA));
+        assert!(!gray.meets_contrast(&white, ContrastLevel::AAA));
+        assert!(!white.with_alpha(0.1).meets_contrast(&white, ContrastLevel::AALargeText));
+    }
+}
@@ -0,0 +1,196 @@
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::DefaultsBackend;
+use crate::defaults::{json, PlistFormat, Value};
+
+/// The on-disk format used by a `FileBackend`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FileFormat {
+    /// Pretty-printed JSON. `Value::Data` and `Value::Date` are stored as `{ "$data": "..." }`
+    /// and `{ "$date": ... }` objects respectively.
+    Json,
+
+    /// An XML property list, as written by `PlistFormat::Xml`.
+    XmlPlist,
+
+    /// A binary property list, as written by `PlistFormat::Binary`.
+    BinaryPlist
+}
+
+impl FileFormat {
+    fn encode(&self, value: &Value) -> io::Result<Vec<u8>> {
+        match self {
+            FileFormat::Json => json::to_string(value)
+                .map(String::into_bytes)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            FileFormat::XmlPlist => Ok(value.to_plist(PlistFormat::Xml)),
+            FileFormat::BinaryPlist => Ok(value.to_plist(PlistFormat::Binary))
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> io::Result<Value> {
+        match self {
+            FileFormat::Json => {
+                let text = std::str::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                json::from_str(text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            },
+
+            FileFormat::XmlPlist | FileFormat::BinaryPlist => {
+                Value::from_plist(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            },
+        }
+    }
+}
+
+/// A `DefaultsBackend` that persists values to a single file. This is intended for platforms that
+/// don't have `NSUserDefaults` (or where you'd rather manage the file yourself).
+///
+/// Writes go to disk immediately, via a temporary file that's renamed into place so a crash can't
+/// leave a half-written file behind. If a write fails, the change is still held in memory and
+/// `synchronize()` will retry it (returning `false` if it fails again). Registered defaults are
+/// never written to disk.
+///
+/// ```rust,no_run
+/// use cacao::defaults::{FileBackend, FileFormat, UserDefaults, Value};
+///
+/// let backend = FileBackend::open("settings.json", FileFormat::Json).unwrap();
+/// let mut defaults = UserDefaults::with_backend(backend);
+/// defaults.insert("volume", Value::Float(0.8));
+/// ```
+#[derive(Debug)]
+pub struct FileBackend {
+    path: PathBuf,
+    format: FileFormat,
+    registered: HashMap<String, Value>,
+    values: HashMap<String, Value>,
+    dirty: Cell<bool>
+}
+
+impl FileBackend {
+    /// Opens (or prepares to create) the defaults file at `path`. A missing file is treated as
+    /// empty; an unreadable or malformed one is an error.
+    pub fn open<P: AsRef<Path>>(path: P, format: FileFormat) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let values = match fs::read(&path) {
+            Ok(bytes) => match format.decode(&bytes)? {
+                Value::Dictionary(values) => values,
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "defaults file must contain a dictionary at the top level"
+                    ))
+                },
+            },
+
+            Err(error) if error.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(error) => return Err(error)
+        };
+
+        Ok(FileBackend {
+            path,
+            format,
+            registered: HashMap::new(),
+            values,
+            dirty: Cell::new(false)
+        })
+    }
+
+    /// Returns the path this backend reads from and writes to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the current values to disk.
+    pub fn save(&self) -> io::Result<()> {
+        let bytes = self.format.encode(&Value::Dictionary(self.values.clone()))?;
+
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+
+        fs::write(&temporary, bytes)?;
+        fs::rename(&temporary, &self.path)?;
+
+        self.dirty.set(false);
+        Ok(())
+    }
+
+    /// Attempts to write to disk after a change, leaving the backend marked dirty on failure.
+    fn persist(&self) {
+        self.dirty.set(true);
+        let _ = self.save();
+    }
+}
+
+impl DefaultsBackend for FileBackend {
+    fn register(&mut self, values: HashMap<String, Value>) {
+        self.registered.extend(values);
+    }
+
+    fn insert(&mut self, key: &str, value: Value) {
+        self.values.insert(key.to_string(), value);
+        self.persist();
+    }
+
+    fn remove(&mut self, key: &str) {
+        if self.values.remove(key).is_some() {
+            self.persist();
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<Value> {
+        self.values.get(key).or_else(|| self.registered.get(key)).cloned()
+    }
+
+    fn persistent_values(&self) -> HashMap<String, Value> {
+        self.values.clone()
+    }
+
+    fn synchronize(&self) -> bool {
+        !self.dirty.get() || self.save().is_ok()
+    }
+}
+
+impl Drop for FileBackend {
+    /// Makes a last-ditch attempt to flush any writes that previously failed.
+    fn drop(&mut self) {
+        if self.dirty.get() {
+            let _ = self.save();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{FileBackend, FileFormat};
+    use crate::defaults::backend::DefaultsBackend;
+    use crate::defaults::Value;
+
+    #[test]
+    fn test_file_backend_persists() {
+        let path = std::env::temp_dir().join(format!("cacao-defaults-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        {
+            let mut backend = FileBackend::open(&path, FileFormat::Json).unwrap();
+            backend.register(vec![("registered".to_string(), Value::Bool(true))].into_iter().collect());
+            backend.insert("volume", Value::Float(0.5));
+            backend.insert("name", Value::string("cacao"));
+            backend.remove("name");
+            assert!(backend.synchronize());
+        }
+
+        let backend = FileBackend::open(&path, FileFormat::Json).unwrap();
+        assert_eq!(backend.get("volume"), Some(Value::Float(0.5)));
+        assert_eq!(backend.get("name"), None);
+        assert_eq!(backend.get("registered"), None);
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use super::DefaultsBackend;
+use crate::defaults::Value;
+
+/// A `DefaultsBackend` that keeps everything in memory. Nothing is persisted, which makes this
+/// ideal for unit tests.
+///
+/// ```rust
+/// use cacao::defaults::{MemoryBackend, UserDefaults, Value};
+///
+/// let mut backend = MemoryBackend::new();
+/// backend.force("managed", Value::Bool(true));
+///
+/// let mut defaults = UserDefaults::with_backend(backend);
+/// defaults.insert("managed", Value::Bool(false));
+///
+/// assert_eq!(defaults.get("managed"), Some(Value::Bool(true)));
+/// assert!(defaults.is_forced_for_key("managed"));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryBackend {
+    registered: HashMap<String, Value>,
+    values: HashMap<String, Value>,
+    forced: HashMap<String, Value>
+}
+
+impl MemoryBackend {
+    /// Returns a new, empty `MemoryBackend`.
+    pub fn new() -> Self {
+        MemoryBackend::default()
+    }
+
+    /// Returns a `MemoryBackend` pre-populated with the given values, as if they'd been inserted.
+    pub fn with_values<K: Into<String>>(values: HashMap<K, Value>) -> Self {
+        MemoryBackend {
+            values: values.into_iter().map(|(key, value)| (key.into(), value)).collect(),
+            ..MemoryBackend::default()
+        }
+    }
+
+    /// Forces a value for the given key, emulating a key managed by an administrator. Forced
+    /// values take precedence over inserted ones.
+    pub fn force<K: Into<String>>(&mut self, key: K, value: Value) {
+        self.forced.insert(key.into(), value);
+    }
+
+    /// Returns the inserted (non-registered, non-forced) values.
+    pub fn values(&self) -> &HashMap<String, Value> {
+        &self.values
+    }
+}
+
+impl DefaultsBackend for MemoryBackend {
+    fn register(&mut self, values: HashMap<String, Value>) {
+        self.registered.extend(values);
+    }
+
+    fn insert(&mut self, key: &str, value: Value) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    fn remove(&mut self, key: &str) {
+        self.values.remove(key);
+    }
+
+    fn get(&self, key: &str) -> Option<Value> {
+        self.forced
+            .get(key)
+            .or_else(|| self.values.get(key))
+            .or_else(|| self.registered.get(key))
+            .cloned()
+    }
+
+    fn persistent_values(&self) -> HashMap<String, Value> {
+        self.values.clone()
+    }
+
+    fn is_forced_for_key(&self, key: &str) -> bool {
+        self.forced.contains_key(key)
+    }
+}
@@ -0,0 +1,75 @@
+//! Storage backends for `UserDefaults`. By default, `UserDefaults` is backed by `NSUserDefaults`,
+//! but anything implementing `DefaultsBackend` can be swapped in via `UserDefaults::with_backend`.
+//!
+//! - `NSUserDefaultsBackend` wraps `NSUserDefaults`, and is what `UserDefaults::standard()` and
+//!   `UserDefaults::suite()` use.
+//! - `MemoryBackend` keeps everything in memory, which is handy for unit testing code that reads
+//!   and writes preferences without touching the real defaults database.
+//! - `FileBackend` persists values to a file on disk, for platforms without `NSUserDefaults`.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::Value;
+
+mod file;
+pub use file::{FileBackend, FileFormat};
+
+mod memory;
+pub use memory::MemoryBackend;
+
+mod system;
+pub use system::NSUserDefaultsBackend;
+
+/// A callback handed to `DefaultsBackend::watch`, to be called with the new value for the watched
+/// key whenever it changes.
+pub type ChangeHandler = Arc<dyn Fn(Option<Value>) + Send + Sync + 'static>;
+
+/// The storage surface that `UserDefaults` forwards to. The semantics mirror `NSUserDefaults`:
+/// registered values are fallbacks that are never persisted, and anything inserted takes
+/// precedence over them.
+pub trait DefaultsBackend: fmt::Debug {
+    /// Registers fallback values, used for any key that hasn't had a value inserted.
+    fn register(&mut self, values: HashMap<String, Value>);
+
+    /// Stores a value for the given key.
+    fn insert(&mut self, key: &str, value: Value);
+
+    /// Removes the stored value for the given key. Registered values are unaffected.
+    fn remove(&mut self, key: &str);
+
+    /// Returns the value for the given key, falling back to registered values.
+    fn get(&self, key: &str) -> Option<Value>;
+
+    /// Returns every value that has been inserted (and not removed). Registered values aren't
+    /// included, as they're never persisted. This is what `UserDefaults::export_plist` writes
+    /// out; backends that can't list their values can leave this as the default, which returns
+    /// an empty map.
+    fn persistent_values(&self) -> HashMap<String, Value> {
+        HashMap::new()
+    }
+
+    /// Returns whether the value for the given key is managed by an administrator. Backends
+    /// without a notion of managed keys can leave this as the default, which returns `false`.
+    fn is_forced_for_key(&self, _key: &str) -> bool {
+        false
+    }
+
+    /// Flushes any pending writes, returning whether it succeeded. Backends that write
+    /// synchronously can leave this as the default, which returns `true`.
+    fn synchronize(&self) -> bool {
+        true
+    }
+
+    /// Starts watching `key` for changes that don't go through this backend's own methods - e.g,
+    /// another process writing to the same suite - calling `changed` with the new value. Watching
+    /// stops when the returned value is dropped.
+    ///
+    /// `UserDefaults` already reports changes made through itself, so backends that can only be
+    /// changed that way can leave this as the default, which returns `None`.
+    fn watch(&self, _key: &str, _changed: ChangeHandler) -> Option<Box<dyn Any>> {
+        None
+    }
+}
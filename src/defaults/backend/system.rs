@@ -0,0 +1,213 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::ptr;
+
+use objc::runtime::{Class, Object, Sel};
+use objc::{class, msg_send, sel, sel_impl};
+use objc_id::{Id, ShareId};
+
+use super::{ChangeHandler, DefaultsBackend};
+use crate::defaults::Value;
+use crate::foundation::{id, load_or_register_class, to_bool, NSMutableDictionary, NSString, NSUInteger, BOOL};
+use crate::utils::load;
+
+static DEFAULTS_OBSERVER_PTR: &str = "rstDefaultsObserverPtr";
+
+/// `NSKeyValueObservingOptionNew`.
+const OBSERVING_OPTION_NEW: NSUInteger = 1;
+
+/// A `DefaultsBackend` backed by `NSUserDefaults`. This is what `UserDefaults::standard()` and
+/// `UserDefaults::suite()` use under the hood.
+#[derive(Debug)]
+pub struct NSUserDefaultsBackend {
+    /// The underlying `NSUserDefaults` instance.
+    pub objc: Id<Object>,
+
+    suite: Option<String>
+}
+
+impl NSUserDefaultsBackend {
+    /// Returns a backend wrapping `standardUserDefaults`.
+    pub fn standard() -> Self {
+        NSUserDefaultsBackend {
+            objc: unsafe { Id::from_ptr(msg_send![class!(NSUserDefaults), standardUserDefaults]) },
+            suite: None
+        }
+    }
+
+    /// Returns a backend wrapping the `NSUserDefaults` for the given suite name.
+    pub fn suite(named: &str) -> Self {
+        let name = NSString::new(named);
+
+        NSUserDefaultsBackend {
+            objc: unsafe {
+                let alloc: id = msg_send![class!(NSUserDefaults), alloc];
+                Id::from_ptr(msg_send![alloc, initWithSuiteName:&*name])
+            },
+
+            suite: Some(named.to_string())
+        }
+    }
+
+    /// Returns the name of the persistent domain this instance writes to: the suite name, or for
+    /// `standard()`, the bundle identifier (falling back to the process name, as `NSUserDefaults`
+    /// does for unbundled executables).
+    fn domain(&self) -> String {
+        if let Some(suite) = &self.suite {
+            return suite.clone();
+        }
+
+        unsafe {
+            let bundle: id = msg_send![class!(NSBundle), mainBundle];
+            let identifier: id = msg_send![bundle, bundleIdentifier];
+
+            match identifier.is_null() {
+                false => NSString::retain(identifier).to_string(),
+
+                true => {
+                    let info: id = msg_send![class!(NSProcessInfo), processInfo];
+                    NSString::retain(msg_send![info, processName]).to_string()
+                }
+            }
+        }
+    }
+}
+
+impl DefaultsBackend for NSUserDefaultsBackend {
+    fn register(&mut self, values: HashMap<String, Value>) {
+        let dictionary = NSMutableDictionary::from(values);
+
+        unsafe {
+            let _: () = msg_send![&*self.objc, registerDefaults:&*dictionary];
+        }
+    }
+
+    fn insert(&mut self, key: &str, value: Value) {
+        let key = NSString::new(key);
+        let value: id = value.into();
+
+        unsafe {
+            let _: () = msg_send![&*self.objc, setObject:value forKey:key];
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        let key = NSString::new(key);
+
+        unsafe {
+            let _: () = msg_send![&*self.objc, removeObjectForKey:&*key];
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<Value> {
+        let key = NSString::new(key);
+        let result: id = unsafe { msg_send![&*self.objc, objectForKey:&*key] };
+
+        Value::from_objc(result)
+    }
+
+    fn persistent_values(&self) -> HashMap<String, Value> {
+        let domain = NSString::new(&self.domain());
+        let result: id = unsafe { msg_send![&*self.objc, persistentDomainForName:&*domain] };
+
+        match Value::from_objc(result) {
+            Some(Value::Dictionary(values)) => values,
+            _ => HashMap::new()
+        }
+    }
+
+    fn is_forced_for_key(&self, key: &str) -> bool {
+        let result: BOOL = unsafe {
+            let key = NSString::new(key);
+            msg_send![&*self.objc, objectIsForcedForKey:&*key]
+        };
+
+        to_bool(result)
+    }
+
+    fn synchronize(&self) -> bool {
+        let result: BOOL = unsafe { msg_send![&*self.objc, synchronize] };
+        to_bool(result)
+    }
+
+    /// Watches the key via key-value observing, which `NSUserDefaults` supports for changes made
+    /// by any instance - including ones in other processes that share the same suite.
+    ///
+    /// Note that KVO treats `.` as a key path separator, so keys containing dots can't be watched
+    /// this way; changes made through `UserDefaults` itself are still reported for them.
+    fn watch(&self, key: &str, changed: ChangeHandler) -> Option<Box<dyn Any>> {
+        if key.contains('.') {
+            return None;
+        }
+
+        Some(Box::new(KeyObserver::new(&self.objc, key, changed)))
+    }
+}
+
+/// Owns a KVO registration on an `NSUserDefaults` instance, removing it on drop.
+#[derive(Debug)]
+struct KeyObserver {
+    defaults: ShareId<Object>,
+    observer: Id<Object>,
+    key: String,
+    handler: *mut ChangeHandler
+}
+
+impl KeyObserver {
+    fn new(defaults: &Object, key: &str, changed: ChangeHandler) -> Self {
+        let handler = Box::into_raw(Box::new(changed));
+        let key_path = NSString::new(key);
+
+        unsafe {
+            let defaults = ShareId::from_ptr(defaults as *const Object as id);
+
+            let observer: id = msg_send![register_observer_class(), new];
+            (&mut *observer).set_ivar(DEFAULTS_OBSERVER_PTR, handler as usize);
+
+            let _: () = msg_send![&*defaults, addObserver:observer
+                forKeyPath:&*key_path
+                options:OBSERVING_OPTION_NEW
+                context:ptr::null_mut::<c_void>()];
+
+            KeyObserver {
+                defaults,
+                observer: Id::from_retained_ptr(observer),
+                key: key.to_string(),
+                handler
+            }
+        }
+    }
+}
+
+impl Drop for KeyObserver {
+    fn drop(&mut self) {
+        let key_path = NSString::new(&self.key);
+
+        unsafe {
+            let _: () = msg_send![&*self.defaults, removeObserver:&*self.observer forKeyPath:&*key_path];
+            drop(Box::from_raw(self.handler));
+        }
+    }
+}
+
+/// Called by KVO when the observed key changes. Rather than trusting the change dictionary (which
+/// reports `NSNull` on removal, even if there's a registered default), this re-reads the key.
+extern "C" fn observe_value(this: &Object, _: Sel, key_path: id, object: id, _change: id, _context: *mut c_void) {
+    let handler = load::<ChangeHandler>(this, DEFAULTS_OBSERVER_PTR);
+    let value: id = unsafe { msg_send![object, objectForKey: key_path] };
+
+    handler(Value::from_objc(value));
+}
+
+/// Injects an `NSObject` subclass that forwards KVO notifications to a Rust closure.
+fn register_observer_class() -> *const Class {
+    load_or_register_class("NSObject", "RSTUserDefaultsObserver", |decl| unsafe {
+        decl.add_ivar::<usize>(DEFAULTS_OBSERVER_PTR);
+
+        decl.add_method(
+            sel!(observeValueForKeyPath:ofObject:change:context:),
+            observe_value as extern "C" fn(&Object, _, _, _, _, _)
+        );
+    })
+}
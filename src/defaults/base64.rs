@@ -0,0 +1,74 @@
+//! A small, dependency-free base64 (standard alphabet, padded) implementation. This is used for
+//! storing `Value::Data` in text-based formats.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as a padded base64 string.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = chunk.get(1).copied().unwrap_or(0) as u32;
+        let b2 = chunk.get(2).copied().unwrap_or(0) as u32;
+        let triple = (b0 << 16) | (b1 << 8) | b2;
+
+        output.push(ALPHABET[(triple >> 18) as usize & 63] as char);
+        output.push(ALPHABET[(triple >> 12) as usize & 63] as char);
+
+        output.push(match chunk.len() > 1 {
+            true => ALPHABET[(triple >> 6) as usize & 63] as char,
+            false => '='
+        });
+
+        output.push(match chunk.len() > 2 {
+            true => ALPHABET[triple as usize & 63] as char,
+            false => '='
+        });
+    }
+
+    output
+}
+
+/// Decodes a base64 string, ignoring any whitespace (which XML property lists are fond of).
+/// Returns `None` if the input isn't valid base64.
+pub(crate) fn decode(input: &str) -> Option<Vec<u8>> {
+    let mut output = Vec::with_capacity(input.len() / 4 * 3);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    let mut padding = 0;
+
+    for c in input.bytes().filter(|c| !c.is_ascii_whitespace()) {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => {
+                padding += 1;
+                continue;
+            },
+            _ => return None
+        };
+
+        // Data after padding is malformed.
+        if padding > 0 {
+            return None;
+        }
+
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    match padding <= 2 {
+        true => Some(output),
+        false => None
+    }
+}
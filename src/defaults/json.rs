@@ -0,0 +1,467 @@
+//! A small JSON reader/writer for `Value` trees, used by the file-backed defaults store. This is
+//! intentionally not a general purpose JSON library: it only needs to round-trip what `Value`
+//! can hold.
+//!
+//! JSON has no native representation for bytes or dates, so these are written as single-entry
+//! objects: `{ "$data": "<base64>" }` and `{ "$date": <seconds since the Unix epoch> }`. So that
+//! dictionary keys can't be mistaken for these, keys starting with `$` are escaped by doubling
+//! the `$`. Floats are always written with a decimal point or exponent so they read back as
+//! `Value::Float`; NaN and the infinities have no JSON representation, so writing them fails.
+//! `null` isn't representable as a `Value`, so it's skipped when reading arrays and objects.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::base64;
+use super::value::{interval_from_system_time, system_time_from_interval};
+use super::Value;
+
+const DATA_KEY: &str = "$data";
+const DATE_KEY: &str = "$date";
+
+/// Serializes a `Value` into pretty-printed JSON. Dictionary keys are sorted, so the output is
+/// stable across runs. Fails if the value holds a float that isn't finite.
+pub(crate) fn to_string(value: &Value) -> Result<String, String> {
+    let mut output = String::new();
+    write_value(&mut output, value, 0)?;
+    output.push('\n');
+    Ok(output)
+}
+
+/// Escapes a dictionary key, so that keys starting with `$` can't collide with `$data` and
+/// `$date`.
+fn escape_key(key: &str) -> String {
+    match key.starts_with('$') {
+        true => format!("${}", key),
+        false => key.to_string()
+    }
+}
+
+/// Reverses `escape_key`.
+fn unescape_key(key: String) -> String {
+    match key.starts_with("$$") {
+        true => key[1..].to_string(),
+        false => key
+    }
+}
+
+/// Parses JSON into a `Value`, returning a message describing the problem (and where it
+/// happened) if the input is malformed.
+pub(crate) fn from_str(input: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        input: input.as_bytes(),
+        position: 0
+    };
+
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+
+    match (value, parser.position == parser.input.len()) {
+        (Some(value), true) => Ok(value),
+        (None, true) => Err("top-level value cannot be null".into()),
+        (_, false) => Err(parser.error("trailing characters"))
+    }
+}
+
+fn write_indent(output: &mut String, depth: usize) {
+    for _ in 0..depth {
+        output.push_str("  ");
+    }
+}
+
+fn write_string(output: &mut String, value: &str) {
+    output.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(output, "\\u{:04x}", c as u32);
+            },
+            c => output.push(c)
+        }
+    }
+
+    output.push('"');
+}
+
+fn write_float(output: &mut String, value: f64) -> Result<(), String> {
+    if !value.is_finite() {
+        return Err(format!("{} cannot be written as JSON", value));
+    }
+
+    // `Debug` always includes a decimal point or exponent, which keeps floats distinct
+    // from integers when read back in.
+    let _ = write!(output, "{:?}", value);
+    Ok(())
+}
+
+fn write_value(output: &mut String, value: &Value, depth: usize) -> Result<(), String> {
+    match value {
+        Value::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+        Value::String(value) => write_string(output, value),
+        Value::Float(value) => write_float(output, *value)?,
+        Value::Integer(value) => {
+            let _ = write!(output, "{}", value);
+        },
+
+        Value::Data(data) => {
+            output.push_str("{ ");
+            write_string(output, DATA_KEY);
+            output.push_str(": ");
+            write_string(output, &base64::encode(data));
+            output.push_str(" }");
+        },
+
+        Value::Date(date) => {
+            output.push_str("{ ");
+            write_string(output, DATE_KEY);
+            output.push_str(": ");
+            write_float(output, interval_from_system_time(*date))?;
+            output.push_str(" }");
+        },
+
+        Value::Array(values) => {
+            if values.is_empty() {
+                output.push_str("[]");
+                return Ok(());
+            }
+
+            output.push_str("[\n");
+
+            for (index, value) in values.iter().enumerate() {
+                write_indent(output, depth + 1);
+                write_value(output, value, depth + 1)?;
+                output.push_str(if index + 1 < values.len() { ",\n" } else { "\n" });
+            }
+
+            write_indent(output, depth);
+            output.push(']');
+        },
+
+        Value::Dictionary(map) => {
+            if map.is_empty() {
+                output.push_str("{}");
+                return Ok(());
+            }
+
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+
+            output.push_str("{\n");
+
+            for (index, key) in keys.iter().enumerate() {
+                write_indent(output, depth + 1);
+                write_string(output, &escape_key(key));
+                output.push_str(": ");
+                write_value(output, &map[*key], depth + 1)?;
+                output.push_str(if index + 1 < keys.len() { ",\n" } else { "\n" });
+            }
+
+            write_indent(output, depth);
+            output.push('}');
+        }
+    }
+
+    Ok(())
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    position: usize
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> String {
+        format!("{} at byte {}", message, self.position)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\n' | b'\r' | b'\t') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        self.skip_whitespace();
+
+        match self.peek() == Some(byte) {
+            true => {
+                self.position += 1;
+                Ok(())
+            },
+
+            false => Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    fn consume_literal(&mut self, literal: &str) -> Result<(), String> {
+        match self.input[self.position..].starts_with(literal.as_bytes()) {
+            true => {
+                self.position += literal.len();
+                Ok(())
+            },
+
+            false => Err(self.error("invalid literal"))
+        }
+    }
+
+    /// Parses the next value. Returns `Ok(None)` for `null`.
+    fn parse_value(&mut self) -> Result<Option<Value>, String> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'{') => self.parse_object().map(Some),
+            Some(b'[') => self.parse_array().map(Some),
+            Some(b'"') => self.parse_string().map(|s| Some(Value::String(s))),
+            Some(b't') => self.consume_literal("true").map(|_| Some(Value::Bool(true))),
+            Some(b'f') => self.consume_literal("false").map(|_| Some(Value::Bool(false))),
+            Some(b'n') => self.consume_literal("null").map(|_| None),
+            Some(b'-' | b'0'..=b'9') => self.parse_number().map(Some),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input"))
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<Value, String> {
+        let start = self.position;
+        let mut is_float = false;
+
+        while let Some(c) = self.peek() {
+            match c {
+                b'0'..=b'9' | b'-' | b'+' => {},
+                b'.' | b'e' | b'E' => is_float = true,
+                _ => break
+            }
+
+            self.position += 1;
+        }
+
+        let text = std::str::from_utf8(&self.input[start..self.position]).unwrap();
+
+        if !is_float {
+            if let Ok(value) = text.parse::<i64>() {
+                return Ok(Value::Integer(value));
+            }
+        }
+
+        text.parse::<f64>()
+            .map(Value::Float)
+            .map_err(|_| self.error("invalid number"))
+    }
+
+    fn parse_hex_escape(&mut self) -> Result<u32, String> {
+        let digits = self
+            .input
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+
+        self.position += 4;
+        Ok(digits)
+    }
+
+    fn parse_string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+
+        loop {
+            let c = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+            self.position += 1;
+
+            match c {
+                b'"' => break,
+
+                b'\\' => {
+                    let escape = self.peek().ok_or_else(|| self.error("unterminated string"))?;
+                    self.position += 1;
+
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+
+                        b'u' => {
+                            let mut code = self.parse_hex_escape()?;
+
+                            // Characters outside the BMP are encoded as a surrogate pair.
+                            if (0xd800..0xdc00).contains(&code) {
+                                self.consume_literal("\\u")?;
+                                let low = self.parse_hex_escape()?;
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff);
+                            }
+
+                            std::char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))?
+                        },
+
+                        _ => return Err(self.error("invalid escape"))
+                    };
+
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                },
+
+                c => bytes.push(c)
+            }
+        }
+
+        String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8 in string"))
+    }
+
+    fn parse_array(&mut self) -> Result<Value, String> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(Value::Array(values));
+        }
+
+        loop {
+            if let Some(value) = self.parse_value()? {
+                values.push(value);
+            }
+
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(Value::Array(values));
+                },
+                _ => return Err(self.error("expected ',' or ']'"))
+            }
+        }
+    }
+
+    /// Parses an object, unwrapping the `$data` and `$date` forms and unescaping keys.
+    fn parse_object(&mut self) -> Result<Value, String> {
+        self.expect(b'{')?;
+        let mut map = HashMap::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(Value::Dictionary(map));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.parse_string()?;
+            self.expect(b':')?;
+
+            if let Some(value) = self.parse_value()? {
+                map.insert(key, value);
+            }
+
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    break;
+                },
+                _ => return Err(self.error("expected ',' or '}'"))
+            }
+        }
+
+        if map.len() == 1 {
+            match map.iter().next() {
+                Some((key, Value::String(encoded))) if key == DATA_KEY => {
+                    let data = base64::decode(encoded).ok_or_else(|| self.error("invalid base64 data"))?;
+                    return Ok(Value::Data(data));
+                },
+
+                Some((key, Value::Float(interval))) if key == DATE_KEY => {
+                    let date = system_time_from_interval(*interval).ok_or_else(|| self.error("date out of range"))?;
+                    return Ok(Value::Date(date));
+                },
+
+                Some((key, Value::Integer(interval))) if key == DATE_KEY => {
+                    let date = system_time_from_interval(*interval as f64).ok_or_else(|| self.error("date out of range"))?;
+                    return Ok(Value::Date(date));
+                },
+
+                _ => {}
+            }
+        }
+
+        Ok(Value::Dictionary(
+            map.into_iter().map(|(key, value)| (unescape_key(key), value)).collect()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{from_str, to_string};
+    use crate::defaults::Value;
+
+    #[test]
+    fn test_round_trip() {
+        let mut map = HashMap::new();
+        map.insert("bool".to_string(), Value::Bool(true));
+        map.insert("int".to_string(), Value::Integer(-42));
+        map.insert("float".to_string(), Value::Float(1.));
+        map.insert("string".to_string(), Value::string("quote \" and \u{1F600}"));
+        map.insert("data".to_string(), Value::Data(vec![0, 1, 2, 254, 255]));
+        map.insert("date".to_string(), Value::Date(UNIX_EPOCH + Duration::from_millis(1_500)));
+        map.insert(
+            "array".to_string(),
+            Value::Array(vec![Value::Integer(1), Value::Array(vec![])])
+        );
+        map.insert("empty".to_string(), Value::Dictionary(HashMap::new()));
+
+        // Keys that look like the `$data` and `$date` forms are escaped.
+        map.insert("$date".to_string(), Value::Integer(1));
+        map.insert("$$data".to_string(), Value::Dictionary(HashMap::new()));
+
+        let value = Value::Dictionary(map);
+        let json = to_string(&value).unwrap();
+        assert_eq!(from_str(&json), Ok(value));
+
+        let mut map = HashMap::new();
+        map.insert("$data".to_string(), Value::string("AAEC"));
+        assert_eq!(
+            from_str(&to_string(&Value::Dictionary(map.clone())).unwrap()),
+            Ok(Value::Dictionary(map))
+        );
+
+        assert!(to_string(&Value::Array(vec![Value::Float(f64::NAN)])).is_err());
+        assert!(to_string(&Value::Float(f64::INFINITY)).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let value = from_str(r#"{ "a": [1, null, 2.5e1], "b": "é\ud83d\ude00", "c": null }"#).unwrap();
+        let map = value.as_dictionary().unwrap();
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], Value::Array(vec![Value::Integer(1), Value::Float(25.)]));
+        assert_eq!(map["b"], Value::string("\u{e9}\u{1F600}"));
+        assert!(from_str("{ \"a\": 1 ").is_err());
+        assert!(from_str("[1] 2").is_err());
+        assert!(from_str("null").is_err());
+        assert!(from_str(r#"{ "$date": 1e300 }"#).is_err());
+    }
+}
@@ -4,8 +4,19 @@
 //! this case, `Value` handles wrapping types for insertion/retrieval, shepherding between
 //! the Objective-C runtime and your Rust code.
 //!
-//! It currently supports a number of primitive types, as well as a generic `Data` type for custom
-//! usage. Note that the `Data` type is stored internally as an `NSData` instance.
+//! It currently supports a number of primitive types, dates, arrays and dictionaries, as well as a
+//! generic `Data` type for custom usage. Note that the `Data` type is stored internally as an
+//! `NSData` instance.
+//!
+//! With the `serde` feature enabled, `UserDefaults::get_typed` and `UserDefaults::set_typed` can
+//! store any `Serialize`/`Deserialize` type, mapping structs and maps onto dictionaries and
+//! sequences onto arrays.
+//!
+//! Defaults can also be moved in and out of property lists (XML or binary) - see `export_plist`,
+//! `import_plist` and `register_bundled_plist`.
+//!
+//! `UserDefaults::observe` lets you react to a key changing - whether that happens through this
+//! instance, another one, or (for `NSUserDefaults`) another process sharing the same suite.
 //!
 //! Do not use this for storing sensitive data - you want the Keychain for that.
 //!
@@ -33,22 +44,51 @@
 //! ```
 
 use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
 
-use objc::runtime::Object;
 use objc::{class, msg_send, sel, sel_impl};
-use objc_id::Id;
 
-use crate::foundation::{id, nil, to_bool, NSData, NSMutableDictionary, NSNumber, NSString, BOOL, NO, YES};
+use crate::foundation::{id, NSString};
+
+mod backend;
+pub use backend::{ChangeHandler, DefaultsBackend, FileBackend, FileFormat, MemoryBackend, NSUserDefaultsBackend};
+
+mod base64;
+mod json;
+
+mod plist;
+pub use plist::{PlistError, PlistFormat};
+
+mod observer;
+pub use observer::DefaultsObservation;
+use observer::Observers;
 
 mod value;
 pub use value::Value;
 
+#[cfg(feature = "serde")]
+mod typed;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use typed::{from_value, to_value, ValueError};
+
 /// Wraps and provides methods for interacting with `NSUserDefaults`, which can be used for storing
 /// pieces of information (preferences, or _defaults_) to persist across application launches.
 ///
+/// The storage itself is handled by a `DefaultsBackend`. `standard()` and `suite()` use
+/// `NSUserDefaults`, but you can supply your own (or one of the bundled `MemoryBackend` and
+/// `FileBackend` types) via `with_backend()` - e.g, to unit test preference handling.
+///
 /// This should not be used for sensitive data - use the Keychain for that.
 #[derive(Debug)]
-pub struct UserDefaults(pub Id<Object>);
+pub struct UserDefaults {
+    /// The backend that values are read from and written to.
+    pub backend: Box<dyn DefaultsBackend>,
+
+    observers: Observers
+}
 
 impl Default for UserDefaults {
     /// Equivalent to calling `UserDefaults::standard()`.
@@ -71,7 +111,7 @@ impl UserDefaults {
     /// let _ = defaults.get("test");
     /// ```
     pub fn standard() -> Self {
-        UserDefaults(unsafe { Id::from_ptr(msg_send![class!(NSUserDefaults), standardUserDefaults]) })
+        UserDefaults::with_backend(NSUserDefaultsBackend::standard())
     }
 
     /// Returns a user defaults instance for the given suite name. You typically use this to share
@@ -86,12 +126,29 @@ impl UserDefaults {
     /// let _ = defaults.get("test");
     /// ```
     pub fn suite(named: &str) -> Self {
-        let name = NSString::new(named);
+        UserDefaults::with_backend(NSUserDefaultsBackend::suite(named))
+    }
+
+    /// Returns a user defaults instance that stores everything in memory. Nothing is persisted,
+    /// which makes this useful for unit tests.
+    ///
+    /// ```rust
+    /// use cacao::defaults::{UserDefaults, Value};
+    ///
+    /// let mut defaults = UserDefaults::in_memory();
+    /// defaults.insert("test", Value::Bool(true));
+    /// assert_eq!(defaults.get("test"), Some(Value::Bool(true)));
+    /// ```
+    pub fn in_memory() -> Self {
+        UserDefaults::with_backend(MemoryBackend::new())
+    }
 
-        UserDefaults(unsafe {
-            let alloc: id = msg_send![class!(NSUserDefaults), alloc];
-            Id::from_ptr(msg_send![alloc, initWithSuiteName:&*name])
-        })
+    /// Returns a user defaults instance backed by the given `DefaultsBackend`.
+    pub fn with_backend<B: DefaultsBackend + 'static>(backend: B) -> Self {
+        UserDefaults {
+            backend: Box::new(backend),
+            observers: Observers::default()
+        }
     }
 
     /// You can use this to register defaults at the beginning of your program. Note that these are
@@ -112,15 +169,21 @@ impl UserDefaults {
     /// });
     /// ```
     pub fn register<K: AsRef<str>>(&mut self, values: HashMap<K, Value>) {
-        let dictionary = NSMutableDictionary::from(values);
+        let values: HashMap<String, Value> = values
+            .into_iter()
+            .map(|(key, value)| (key.as_ref().to_string(), value))
+            .collect();
 
-        unsafe {
-            let _: () = msg_send![&*self.0, registerDefaults:&*dictionary];
+        let keys: Vec<String> = values.keys().cloned().collect();
+        self.backend.register(values);
+
+        for key in keys {
+            self.notify(&key);
         }
     }
 
-    /// Inserts a value for the specified key. This synchronously updates the backing
-    /// `NSUserDefaults` store, and asynchronously persists to the disk.
+    /// Inserts a value for the specified key. For `NSUserDefaults`, this synchronously updates
+    /// the backing store, and asynchronously persists to the disk.
     ///
     /// ```rust
     /// use cacao::defaults::{UserDefaults, Value};
@@ -129,12 +192,8 @@ impl UserDefaults {
     /// defaults.insert("test", Value::Bool(true));
     /// ```
     pub fn insert<K: AsRef<str>>(&mut self, key: K, value: Value) {
-        let key = NSString::new(key.as_ref());
-        let value: id = value.into();
-
-        unsafe {
-            let _: () = msg_send![&*self.0, setObject:value forKey:key];
-        }
+        self.backend.insert(key.as_ref(), value);
+        self.notify(key.as_ref());
     }
 
     /// Remove the default associated with the key. If the key doesn't exist, this is a noop.
@@ -146,11 +205,8 @@ impl UserDefaults {
     /// defaults.remove("test");
     /// ```
     pub fn remove<K: AsRef<str>>(&mut self, key: K) {
-        let key = NSString::new(key.as_ref());
-
-        unsafe {
-            let _: () = msg_send![&*self.0, removeObjectForKey:&*key];
-        }
+        self.backend.remove(key.as_ref());
+        self.notify(key.as_ref());
     }
 
     /// Returns a `Value` for the given key, from which you can further extract the data you
@@ -172,52 +228,148 @@ impl UserDefaults {
     /// assert_eq!(value, "value");
     /// ```
     pub fn get<K: AsRef<str>>(&self, key: K) -> Option<Value> {
-        let key = NSString::new(key.as_ref());
+        self.backend.get(key.as_ref())
+    }
+
+    /// Registers the contents of a property list (XML or binary) as defaults, as `register()`
+    /// does. The plist must hold a dictionary at the top level.
+    pub fn register_plist(&mut self, bytes: &[u8]) -> Result<(), PlistError> {
+        self.register(plist::dictionary_from_plist(bytes)?);
+        Ok(())
+    }
+
+    /// Registers defaults from a property list shipped in the main bundle's resources - e.g,
+    /// passing `"Defaults"` loads `Defaults.plist`.
+    ///
+    /// ```rust,no_run
+    /// use cacao::defaults::UserDefaults;
+    ///
+    /// let mut defaults = UserDefaults::standard();
+    /// defaults.register_bundled_plist("Defaults").expect("Defaults.plist is missing or malformed");
+    /// ```
+    pub fn register_bundled_plist(&mut self, name: &str) -> Result<(), PlistError> {
+        let path = unsafe {
+            let bundle: id = msg_send![class!(NSBundle), mainBundle];
+            let resource = NSString::new(name);
+            let extension = NSString::new("plist");
+            let path: id = msg_send![bundle, pathForResource:&*resource ofType:&*extension];
+
+            match path.is_null() {
+                true => return Err(PlistError::ResourceNotFound(name.to_string())),
+                false => NSString::retain(path).to_string()
+            }
+        };
+
+        self.register_plist(&fs::read(path)?)
+    }
 
-        let result: id = unsafe { msg_send![&*self.0, objectForKey:&*key] };
+    /// Exports every value that has been set (registered defaults aren't included) as a property
+    /// list dictionary, e.g to attach to a support ticket.
+    ///
+    /// ```rust,no_run
+    /// use cacao::defaults::{PlistFormat, UserDefaults};
+    ///
+    /// let defaults = UserDefaults::standard();
+    /// std::fs::write("preferences.plist", defaults.export_plist(PlistFormat::Xml)).unwrap();
+    /// ```
+    pub fn export_plist(&self, format: PlistFormat) -> Vec<u8> {
+        Value::Dictionary(self.backend.persistent_values()).to_plist(format)
+    }
 
-        if result == nil {
-            return None;
+    /// Inserts every entry from a property list (XML or binary) dictionary, overwriting any
+    /// existing values for those keys. Nothing is inserted if the plist can't be read.
+    pub fn import_plist(&mut self, bytes: &[u8]) -> Result<(), PlistError> {
+        for (key, value) in plist::dictionary_from_plist(bytes)? {
+            self.insert(key, value);
         }
 
-        if NSData::is(result) {
-            let data = NSData::retain(result);
-            return Some(Value::Data(data.into_vec()));
+        Ok(())
+    }
+
+    /// Calls `handler` with the new value for `key` (or `None`, if it was removed) whenever it
+    /// changes. This covers writes through this instance and, for `NSUserDefaults`, any other
+    /// writer - including other processes using the same `suite()`. For other backends, see
+    /// `DefaultsBackend::watch`.
+    ///
+    /// The handler stays registered until the returned `DefaultsObservation` is dropped. Changes
+    /// from other processes can arrive on any thread, so hop to the main thread before touching
+    /// UI.
+    ///
+    /// ```rust,no_run
+    /// use cacao::defaults::{UserDefaults, Value};
+    ///
+    /// let mut defaults = UserDefaults::standard();
+    ///
+    /// let observation = defaults.observe("theme", |value| {
+    ///     println!("Theme is now {:?}", value);
+    /// });
+    ///
+    /// defaults.insert("theme", Value::string("dark"));
+    /// drop(observation);
+    /// ```
+    pub fn observe<K: AsRef<str>, F: Fn(Option<Value>) + Send + Sync + 'static>(
+        &self,
+        key: K,
+        handler: F
+    ) -> DefaultsObservation {
+        let key = key.as_ref();
+        let id = self.observers.add(key, self.get(key), Arc::new(handler));
+
+        let observers = self.observers.clone();
+        let watch = self
+            .backend
+            .watch(key, Arc::new(move |value| observers.notify_one(id, value)));
+
+        DefaultsObservation::new(id, key, self.observers.clone(), watch)
+    }
+
+    /// Reports the current value for `key` to any observers. Observers only fire if it differs
+    /// from what they last saw, so this is cheap to call after every write.
+    fn notify(&self, key: &str) {
+        if self.observers.is_observing(key) {
+            self.observers.notify(key, self.get(key));
         }
+    }
 
-        if NSString::is(result) {
-            let s = NSString::retain(result).to_string();
-            return Some(Value::String(s));
+    /// Deserializes the value stored for the given key into `T`. Returns `Ok(None)` if there's no
+    /// value for the key, and an error if the stored value doesn't match the shape of `T`.
+    ///
+    /// ```rust,no_run
+    /// use serde::{Deserialize, Serialize};
+    /// use cacao::defaults::UserDefaults;
+    ///
+    /// #[derive(Serialize, Deserialize)]
+    /// struct WindowState {
+    ///     width: f64,
+    ///     height: f64
+    /// }
+    ///
+    /// let mut defaults = UserDefaults::standard();
+    /// defaults.set_typed("window", &WindowState { width: 800., height: 600. }).unwrap();
+    ///
+    /// let state: WindowState = defaults.get_typed("window").unwrap().unwrap();
+    /// assert_eq!(state.width, 800.);
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn get_typed<K: AsRef<str>, T: serde::de::DeserializeOwned>(&self, key: K) -> Result<Option<T>, ValueError> {
+        match self.get(key) {
+            Some(value) => from_value(value).map(Some),
+            None => Ok(None)
         }
+    }
 
-        // This works, but might not be the best approach. We basically need to inspect the
-        // `NSNumber` returned and see what the wrapped encoding type is. `q` and `d` represent
-        // `NSInteger` (platform specific) and `double` (f64) respectively, but conceivably we
-        // might need others.
-        //
-        // BOOL returns as "c", which... something makes me feel weird there, but testing it seems
-        // reliable.
-        //
-        // For context: https://nshipster.com/type-encodings/
-        if NSNumber::is(result) {
-            let number = NSNumber::wrap(result);
-
-            return match number.objc_type() {
-                "c" => Some(Value::Bool(number.as_bool())),
-                "d" => Some(Value::Float(number.as_f64())),
-                "q" => Some(Value::Integer(number.as_i64())),
-
-                _x => {
-                    // Debugging code that should be removed at some point.
-                    #[cfg(debug_assertions)]
-                    println!("Unexpected code type found: {}", _x);
-
-                    None
-                }
-            };
+    /// Serializes `value` and stores it for the given key. If `value` serializes to `None`, the
+    /// key is removed instead, as `NSUserDefaults` can't store `nil`.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn set_typed<K: AsRef<str>, T: serde::Serialize + ?Sized>(&mut self, key: K, value: &T) -> Result<(), ValueError> {
+        match to_value(value)? {
+            Some(value) => self.insert(key, value),
+            None => self.remove(key)
         }
 
-        None
+        Ok(())
     }
 
     /// Returns a boolean value if the object stored for the specified key is managed by an
@@ -236,18 +388,15 @@ impl UserDefaults {
     /// assert_eq!(value, false);
     /// ```
     pub fn is_forced_for_key<K: AsRef<str>>(&self, key: K) -> bool {
-        let result: BOOL = unsafe {
-            let key = NSString::new(key.as_ref());
-            msg_send![&*self.0, objectIsForcedForKey:&*key]
-        };
-
-        to_bool(result)
+        self.backend.is_forced_for_key(key.as_ref())
     }
 
-    /// Blocks for any asynchronous updates to the defaults database and returns.
+    /// Blocks for any asynchronous updates to the defaults database and returns whether they
+    /// were saved successfully.
     ///
-    /// This method is legacy, likely unnecessary and shouldn't be used unless you know exactly why
-    /// you need it... and even then, you should double check it.
+    /// For `NSUserDefaults`, this method is legacy, likely unnecessary and shouldn't be used unless
+    /// you know exactly why you need it... and even then, you should double check it. For a
+    /// `FileBackend`, this retries any writes that previously failed.
     /// ```rust
     /// use cacao::defaults::{UserDefaults, Value};
     ///
@@ -255,9 +404,7 @@ impl UserDefaults {
     /// defaults.insert("test", Value::string("value"));
     /// defaults.synchronize();
     /// ```
-    pub fn synchronize(&self) {
-        unsafe {
-            let _: () = msg_send![&*self.0, synchronize];
-        }
+    pub fn synchronize(&self) -> bool {
+        self.backend.synchronize()
     }
 }
@@ -0,0 +1,172 @@
+//! Bookkeeping for `UserDefaults::observe`. Handlers are stored in a shared registry alongside the
+//! last value they saw for their key, so that a change reported by both `UserDefaults` itself and
+//! the backend (e.g, KVO on `NSUserDefaults`) only fires each handler once.
+
+use std::any::Any;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use super::backend::ChangeHandler;
+use super::Value;
+
+struct Entry {
+    id: usize,
+    key: String,
+    last: Option<Value>,
+    handler: ChangeHandler
+}
+
+impl fmt::Debug for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entry")
+            .field("id", &self.id)
+            .field("key", &self.key)
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    next_id: usize,
+    entries: Vec<Entry>
+}
+
+/// A shared, cloneable set of observation handlers, keyed by defaults key.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Observers(Arc<Mutex<Registry>>);
+
+impl Observers {
+    /// Registers a handler for `key`, returning the id used to remove it later. `current` is the
+    /// value for the key right now, which won't be reported as a change.
+    pub(crate) fn add(&self, key: &str, current: Option<Value>, handler: ChangeHandler) -> usize {
+        let mut registry = self.0.lock().unwrap();
+        let id = registry.next_id;
+        registry.next_id += 1;
+
+        registry.entries.push(Entry {
+            id,
+            key: key.to_string(),
+            last: current,
+            handler
+        });
+
+        id
+    }
+
+    /// Removes the handler with the given id.
+    pub(crate) fn remove(&self, id: usize) {
+        self.0.lock().unwrap().entries.retain(|entry| entry.id != id);
+    }
+
+    /// Returns whether anything is observing `key`.
+    pub(crate) fn is_observing(&self, key: &str) -> bool {
+        self.0.lock().unwrap().entries.iter().any(|entry| entry.key == key)
+    }
+
+    /// Reports the value for `key` to every handler observing it, in the order they were added.
+    pub(crate) fn notify(&self, key: &str, value: Option<Value>) {
+        self.dispatch(|entry| entry.key == key, value);
+    }
+
+    /// Reports the value for the observed key to the handler with the given id only.
+    pub(crate) fn notify_one(&self, id: usize, value: Option<Value>) {
+        self.dispatch(|entry| entry.id == id, value);
+    }
+
+    /// Calls the matching handlers whose last seen value differs from `value`. Handlers are called
+    /// after the lock is released, so they're free to read, write, or observe defaults themselves.
+    fn dispatch<P: Fn(&Entry) -> bool>(&self, matches: P, value: Option<Value>) {
+        let handlers: Vec<ChangeHandler> = {
+            let mut registry = self.0.lock().unwrap();
+
+            registry
+                .entries
+                .iter_mut()
+                .filter(|entry| matches(entry) && entry.last != value)
+                .map(|entry| {
+                    entry.last = value.clone();
+                    entry.handler.clone()
+                })
+                .collect()
+        };
+
+        for handler in handlers {
+            handler(value.clone());
+        }
+    }
+}
+
+/// Returned from `UserDefaults::observe`. The handler stays registered for as long as this is
+/// alive, and is removed when it's dropped - so hold on to it (e.g, in your window controller).
+pub struct DefaultsObservation {
+    id: usize,
+    key: String,
+    observers: Observers,
+    _watch: Option<Box<dyn Any>>
+}
+
+impl DefaultsObservation {
+    pub(crate) fn new(id: usize, key: &str, observers: Observers, watch: Option<Box<dyn Any>>) -> Self {
+        DefaultsObservation {
+            id,
+            key: key.to_string(),
+            observers,
+            _watch: watch
+        }
+    }
+
+    /// Returns the key being observed.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl fmt::Debug for DefaultsObservation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DefaultsObservation")
+            .field("id", &self.id)
+            .field("key", &self.key)
+            .finish()
+    }
+}
+
+impl Drop for DefaultsObservation {
+    /// Unregisters the handler. Any backend watch is torn down right after, as the fields drop.
+    fn drop(&mut self) {
+        self.observers.remove(self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{DefaultsObservation, Observers};
+    use crate::defaults::Value;
+
+    #[test]
+    fn test_observers_fire_once_per_change() {
+        let observers = Observers::default();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let id = observers.add("volume", None, {
+            let seen = seen.clone();
+            Arc::new(move |value| seen.lock().unwrap().push(value))
+        });
+
+        let observation = DefaultsObservation::new(id, "volume", observers.clone(), None);
+
+        // The same change reported twice (e.g, once locally and once via the backend) only fires once.
+        observers.notify("volume", Some(Value::Float(0.5)));
+        observers.notify_one(id, Some(Value::Float(0.5)));
+        observers.notify("other", Some(Value::Bool(true)));
+        observers.notify("volume", None);
+
+        drop(observation);
+        assert!(!observers.is_observing("volume"));
+        observers.notify("volume", Some(Value::Float(1.)));
+
+        assert_eq!(*seen.lock().unwrap(), vec![Some(Value::Float(0.5)), None]);
+    }
+}
@@ -0,0 +1,417 @@
+//! The binary (`bplist00`) property list format. A binary plist is a flat table of objects that
+//! refer to each other by index, followed by a table of each object's byte offset and a fixed-size
+//! trailer describing how wide those indices and offsets are.
+//!
+//! The writer doesn't deduplicate objects (Foundation does, for strings and numbers), which only
+//! costs a little space.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use super::{cf_interval_from_system_time, system_time_from_cf_interval};
+use crate::defaults::Value;
+
+pub(crate) const MAGIC: &[u8] = b"bplist00";
+
+const TRAILER_LENGTH: usize = 32;
+
+/// An object in the flattened object table, with children referred to by index.
+enum Object<'a> {
+    Value(&'a Value),
+    Key(&'a str),
+    Array(Vec<usize>),
+    Dictionary(Vec<usize>, Vec<usize>)
+}
+
+/// Appends `value` (and everything it contains) to the object table, returning its index.
+fn flatten<'a>(value: &'a Value, objects: &mut Vec<Object<'a>>) -> usize {
+    let index = objects.len();
+
+    match value {
+        Value::Array(values) => {
+            objects.push(Object::Array(Vec::new()));
+            let children = values.iter().map(|value| flatten(value, objects)).collect();
+            objects[index] = Object::Array(children);
+        },
+
+        Value::Dictionary(values) => {
+            objects.push(Object::Dictionary(Vec::new(), Vec::new()));
+
+            let mut entries: Vec<(&String, &Value)> = values.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+
+            let keys = entries
+                .iter()
+                .map(|(key, _)| {
+                    objects.push(Object::Key(key));
+                    objects.len() - 1
+                })
+                .collect();
+
+            let children = entries.iter().map(|(_, value)| flatten(value, objects)).collect();
+            objects[index] = Object::Dictionary(keys, children);
+        },
+
+        value => objects.push(Object::Value(value))
+    }
+
+    index
+}
+
+/// Returns the number of bytes needed to store `value` as an unsigned integer.
+fn byte_width(value: u64) -> u8 {
+    match value {
+        0..=0xFF => 1,
+        0x100..=0xFFFF => 2,
+        0x1_0000..=0xFFFF_FFFF => 4,
+        _ => 8
+    }
+}
+
+fn write_sized(output: &mut Vec<u8>, value: u64, width: u8) {
+    output.extend_from_slice(&value.to_be_bytes()[8 - width as usize..]);
+}
+
+/// Writes an object marker, spilling the length into a following integer if it doesn't fit in the
+/// marker's low nibble.
+fn write_marker(output: &mut Vec<u8>, kind: u8, length: usize) {
+    match length < 0xF {
+        true => output.push(kind | length as u8),
+
+        false => {
+            output.push(kind | 0xF);
+            write_integer(output, length as i64);
+        }
+    }
+}
+
+/// Writes an integer object. Foundation treats 1, 2 and 4 byte integers as unsigned and 8 byte
+/// integers as signed, so negative numbers always take the full 8 bytes.
+fn write_integer(output: &mut Vec<u8>, value: i64) {
+    let width = match value < 0 {
+        true => 8,
+        false => byte_width(value as u64)
+    };
+
+    output.push(0x10 | width.trailing_zeros() as u8);
+    write_sized(output, value as u64, width);
+}
+
+fn write_string(output: &mut Vec<u8>, value: &str) {
+    match value.is_ascii() {
+        true => {
+            write_marker(output, 0x50, value.len());
+            output.extend_from_slice(value.as_bytes());
+        },
+
+        false => {
+            let units: Vec<u16> = value.encode_utf16().collect();
+            write_marker(output, 0x60, units.len());
+
+            for unit in units {
+                output.extend_from_slice(&unit.to_be_bytes());
+            }
+        }
+    }
+}
+
+/// Serializes a `Value` into a binary plist.
+pub(crate) fn to_vec(value: &Value) -> Vec<u8> {
+    let mut objects = Vec::new();
+    flatten(value, &mut objects);
+
+    let reference_width = byte_width(objects.len() as u64);
+    let mut output = MAGIC.to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+
+    for object in &objects {
+        offsets.push(output.len() as u64);
+
+        match object {
+            Object::Key(key) => write_string(&mut output, key),
+
+            Object::Array(children) => {
+                write_marker(&mut output, 0xA0, children.len());
+
+                for child in children {
+                    write_sized(&mut output, *child as u64, reference_width);
+                }
+            },
+
+            Object::Dictionary(keys, children) => {
+                write_marker(&mut output, 0xD0, keys.len());
+
+                for index in keys.iter().chain(children) {
+                    write_sized(&mut output, *index as u64, reference_width);
+                }
+            },
+
+            Object::Value(value) => match value {
+                Value::Bool(false) => output.push(0x08),
+                Value::Bool(true) => output.push(0x09),
+                Value::Integer(value) => write_integer(&mut output, *value),
+
+                Value::Float(value) => {
+                    output.push(0x23);
+                    output.extend_from_slice(&value.to_be_bytes());
+                },
+
+                Value::Date(time) => {
+                    output.push(0x33);
+                    output.extend_from_slice(&cf_interval_from_system_time(*time).to_be_bytes());
+                },
+
+                Value::Data(bytes) => {
+                    write_marker(&mut output, 0x40, bytes.len());
+                    output.extend_from_slice(bytes);
+                },
+
+                Value::String(value) => write_string(&mut output, value),
+
+                // Flattened into `Object::Array` and `Object::Dictionary` above.
+                Value::Array(_) | Value::Dictionary(_) => unreachable!()
+            }
+        }
+    }
+
+    let offset_table = output.len() as u64;
+    let offset_width = byte_width(offset_table);
+
+    for offset in offsets {
+        write_sized(&mut output, offset, offset_width);
+    }
+
+    output.extend_from_slice(&[0; 6]);
+    output.push(offset_width);
+    output.push(reference_width);
+    output.extend_from_slice(&(objects.len() as u64).to_be_bytes());
+    output.extend_from_slice(&0u64.to_be_bytes());
+    output.extend_from_slice(&offset_table.to_be_bytes());
+    output
+}
+
+/// Parses a binary plist into a `Value`.
+pub(crate) fn from_slice(bytes: &[u8]) -> Result<Value, String> {
+    if bytes.len() < MAGIC.len() + TRAILER_LENGTH {
+        return Err("file is too short".into());
+    }
+
+    let trailer = &bytes[bytes.len() - TRAILER_LENGTH..];
+    let offset_width = trailer[6] as usize;
+    let reference_width = trailer[7] as usize;
+    let object_count = read_sized(&trailer[8..16]);
+    let top_object = read_sized(&trailer[16..24]);
+    let offset_table = read_sized(&trailer[24..32]);
+
+    let valid_width = |width: usize| (1..=8).contains(&width);
+    let table_end = (object_count as u128) * (offset_width as u128) + offset_table as u128;
+
+    if !valid_width(offset_width) || !valid_width(reference_width) {
+        return Err("invalid offset or reference size".into());
+    }
+
+    if top_object >= object_count || table_end > (bytes.len() - TRAILER_LENGTH) as u128 {
+        return Err("object table is out of bounds".into());
+    }
+
+    let reader = Reader {
+        bytes,
+        offset_table: offset_table as usize,
+        offset_width,
+        reference_width,
+        object_count: object_count as usize
+    };
+
+    reader.read_object(top_object as usize, &mut Vec::new())
+}
+
+fn read_sized(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |value, byte| (value << 8) | *byte as u64)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    offset_table: usize,
+    offset_width: usize,
+    reference_width: usize,
+    object_count: usize
+}
+
+impl<'a> Reader<'a> {
+    fn slice(&self, start: usize, length: usize) -> Result<&'a [u8], String> {
+        match start.checked_add(length) {
+            Some(end) if end <= self.offset_table => Ok(&self.bytes[start..end]),
+            _ => Err(format!("object at offset {} is out of bounds", start))
+        }
+    }
+
+    /// Reads an integer object at `position`, returning it along with the position just past it.
+    fn read_integer(&self, position: usize) -> Result<(i64, usize), String> {
+        let marker = self.slice(position, 1)?[0];
+
+        if marker & 0xF0 != 0x10 {
+            return Err(format!("expected an integer at offset {}", position));
+        }
+
+        let width = 1usize << (marker & 0x0F);
+        let bytes = self.slice(position + 1, width)?;
+
+        let value = match width {
+            1 | 2 | 4 | 8 => read_sized(bytes) as i64,
+
+            // 16 byte integers only exist to hold values above `i64::MAX`.
+            16 => match bytes[..8].iter().all(|byte| *byte == 0) {
+                true if bytes[8] < 0x80 => read_sized(&bytes[8..]) as i64,
+                _ => return Err(format!("integer at offset {} is out of range", position))
+            },
+
+            _ => return Err(format!("invalid integer width at offset {}", position))
+        };
+
+        Ok((value, position + 1 + width))
+    }
+
+    /// Reads the length encoded in an object marker, returning it along with the position of the
+    /// object's contents.
+    fn read_length(&self, position: usize, marker: u8) -> Result<(usize, usize), String> {
+        match marker & 0x0F {
+            0x0F => {
+                let (length, next) = self.read_integer(position + 1)?;
+
+                match usize::try_from(length) {
+                    Ok(length) => Ok((length, next)),
+                    Err(_) => Err(format!("invalid length at offset {}", position))
+                }
+            },
+
+            length => Ok((length as usize, position + 1))
+        }
+    }
+
+    fn read_references(&self, position: usize, count: usize) -> Result<Vec<usize>, String> {
+        let length = count
+            .checked_mul(self.reference_width)
+            .ok_or_else(|| format!("invalid length at offset {}", position))?;
+
+        let bytes = self.slice(position, length)?;
+
+        Ok(bytes
+            .chunks(self.reference_width)
+            .map(|chunk| read_sized(chunk) as usize)
+            .collect())
+    }
+
+    /// Reads the object at `index`. `ancestors` holds the containers currently being read, so a
+    /// (malicious) reference cycle is reported rather than recursing forever.
+    fn read_object(&self, index: usize, ancestors: &mut Vec<usize>) -> Result<Value, String> {
+        if index >= self.object_count {
+            return Err(format!("reference to object {} is out of bounds", index));
+        }
+
+        let entry = self.offset_table + index * self.offset_width;
+        let position = read_sized(&self.bytes[entry..entry + self.offset_width]) as usize;
+        let marker = self.slice(position, 1)?[0];
+
+        match marker & 0xF0 {
+            0x00 => match marker {
+                0x08 => Ok(Value::Bool(false)),
+                0x09 => Ok(Value::Bool(true)),
+                _ => Err(format!("unsupported object type {:#04x} at offset {}", marker, position))
+            },
+
+            0x10 => Ok(Value::Integer(self.read_integer(position)?.0)),
+
+            0x20 => match marker {
+                0x22 => {
+                    let bytes = self.slice(position + 1, 4)?;
+                    Ok(Value::Float(
+                        f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64
+                    ))
+                },
+
+                0x23 => {
+                    let mut bytes = [0; 8];
+                    bytes.copy_from_slice(self.slice(position + 1, 8)?);
+                    Ok(Value::Float(f64::from_be_bytes(bytes)))
+                },
+
+                _ => Err(format!("invalid real width at offset {}", position))
+            },
+
+            0x30 if marker == 0x33 => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(self.slice(position + 1, 8)?);
+                match system_time_from_cf_interval(f64::from_be_bytes(bytes)) {
+                    Some(time) => Ok(Value::Date(time)),
+                    None => Err(format!("invalid date at offset {}", position))
+                }
+            },
+
+            0x40 => {
+                let (length, start) = self.read_length(position, marker)?;
+                Ok(Value::Data(self.slice(start, length)?.to_vec()))
+            },
+
+            0x50 => {
+                let (length, start) = self.read_length(position, marker)?;
+
+                match std::str::from_utf8(self.slice(start, length)?) {
+                    Ok(value) => Ok(Value::string(value)),
+                    Err(_) => Err(format!("invalid ASCII string at offset {}", position))
+                }
+            },
+
+            0x60 => {
+                let (length, start) = self.read_length(position, marker)?;
+                let bytes = self.slice(start, length.saturating_mul(2))?;
+                let units: Vec<u16> = bytes.chunks(2).map(|unit| u16::from_be_bytes([unit[0], unit[1]])).collect();
+
+                match String::from_utf16(&units) {
+                    Ok(value) => Ok(Value::String(value)),
+                    Err(_) => Err(format!("invalid UTF-16 string at offset {}", position))
+                }
+            },
+
+            0xA0 | 0xD0 => {
+                if ancestors.contains(&index) {
+                    return Err(format!("object {} contains itself", index));
+                }
+
+                let (count, start) = self.read_length(position, marker)?;
+                ancestors.push(index);
+
+                let value = match marker & 0xF0 {
+                    0xA0 => Value::Array(
+                        self.read_references(start, count)?
+                            .into_iter()
+                            .map(|child| self.read_object(child, ancestors))
+                            .collect::<Result<_, _>>()?
+                    ),
+
+                    _ => {
+                        let references = self.read_references(start, count.saturating_mul(2))?;
+                        let (keys, children) = references.split_at(count);
+                        let mut values = HashMap::with_capacity(count);
+
+                        for (key, child) in keys.iter().zip(children) {
+                            let key = match self.read_object(*key, ancestors)? {
+                                Value::String(key) => key,
+                                _ => return Err(format!("dictionary at offset {} has a non-string key", position))
+                            };
+
+                            values.insert(key, self.read_object(*child, ancestors)?);
+                        }
+
+                        Value::Dictionary(values)
+                    }
+                };
+
+                ancestors.pop();
+                Ok(value)
+            },
+
+            0x80 => Err(format!("UID objects (at offset {}) are not supported", position)),
+            _ => Err(format!("unsupported object type {:#04x} at offset {}", marker, position))
+        }
+    }
+}
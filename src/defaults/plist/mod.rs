@@ -0,0 +1,230 @@
+//! A pure-Rust property list reader/writer for `Value` trees, supporting both the XML and binary
+//! (`bplist00`) formats. This is what powers `UserDefaults::export_plist`, `import_plist` and
+//! `register_plist`, and the plist variants of `FileFormat`.
+//!
+//! Every `Value` maps directly onto a plist type. Going the other way, the only plist type that
+//! has no `Value` counterpart is the binary-only `UID` (used by `NSKeyedArchiver`), which is
+//! rejected when reading.
+//!
+//! ```rust
+//! use cacao::defaults::{PlistFormat, Value};
+//!
+//! let value = Value::Array(vec![Value::Bool(true), Value::string("hello")]);
+//!
+//! for format in &[PlistFormat::Xml, PlistFormat::Binary] {
+//!     let bytes = value.to_plist(*format);
+//!     assert_eq!(Value::from_plist(&bytes).unwrap(), value);
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::io;
+use std::time::SystemTime;
+
+use super::value::{interval_from_system_time, system_time_from_interval};
+use super::Value;
+
+mod binary;
+mod xml;
+
+/// The number of seconds between the Unix epoch and the Core Foundation epoch (2001-01-01), which
+/// binary plists measure dates from.
+const CF_EPOCH_OFFSET: f64 = 978_307_200.;
+
+/// The two property list encodings.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PlistFormat {
+    /// The human-readable XML format. This is what you'd typically ship in a bundle or attach to
+    /// a support ticket.
+    Xml,
+
+    /// The compact `bplist00` binary format, which is what `NSUserDefaults` writes to disk.
+    Binary
+}
+
+/// Errors that can occur when reading a property list.
+#[derive(Debug)]
+pub enum PlistError {
+    /// The input was neither a binary plist nor valid XML plist data.
+    InvalidXml(String),
+
+    /// The input had a binary plist header, but the contents were malformed.
+    InvalidBinary(String),
+
+    /// The plist was valid, but a dictionary was expected at the top level and something else
+    /// was found.
+    NotADictionary,
+
+    /// No resource with the given name could be found in the main bundle.
+    ResourceNotFound(String),
+
+    /// Reading the plist from disk failed.
+    Io(io::Error)
+}
+
+impl fmt::Display for PlistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlistError::InvalidXml(message) => write!(f, "Invalid XML property list: {}", message),
+            PlistError::InvalidBinary(message) => write!(f, "Invalid binary property list: {}", message),
+            PlistError::NotADictionary => write!(f, "Expected a dictionary at the top level of the property list"),
+            PlistError::ResourceNotFound(name) => write!(f, "Could not find {}.plist in the main bundle", name),
+            PlistError::Io(error) => write!(f, "Could not read property list: {}", error)
+        }
+    }
+}
+
+impl error::Error for PlistError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            PlistError::Io(error) => Some(error),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for PlistError {
+    fn from(error: io::Error) -> Self {
+        PlistError::Io(error)
+    }
+}
+
+impl Value {
+    /// Encodes this value as a property list in the given format. Dictionary keys are written in
+    /// sorted order, so the output is stable across runs.
+    pub fn to_plist(&self, format: PlistFormat) -> Vec<u8> {
+        match format {
+            PlistFormat::Xml => xml::to_string(self).into_bytes(),
+            PlistFormat::Binary => binary::to_vec(self)
+        }
+    }
+
+    /// Decodes a property list, detecting whether it's in the XML or binary format.
+    pub fn from_plist(bytes: &[u8]) -> Result<Value, PlistError> {
+        match bytes.starts_with(binary::MAGIC) {
+            true => binary::from_slice(bytes).map_err(PlistError::InvalidBinary),
+
+            false => {
+                let text = std::str::from_utf8(bytes).map_err(|e| PlistError::InvalidXml(e.to_string()))?;
+                xml::from_str(text).map_err(PlistError::InvalidXml)
+            }
+        }
+    }
+}
+
+/// Decodes a property list that must hold a dictionary at the top level, as defaults files do.
+pub(crate) fn dictionary_from_plist(bytes: &[u8]) -> Result<HashMap<String, Value>, PlistError> {
+    match Value::from_plist(bytes)? {
+        Value::Dictionary(values) => Ok(values),
+        _ => Err(PlistError::NotADictionary)
+    }
+}
+
+/// Converts a `SystemTime` to seconds since the Core Foundation epoch.
+fn cf_interval_from_system_time(time: SystemTime) -> f64 {
+    interval_from_system_time(time) - CF_EPOCH_OFFSET
+}
+
+/// Converts seconds since the Core Foundation epoch to a `SystemTime`, if it can be represented.
+fn system_time_from_cf_interval(interval: f64) -> Option<SystemTime> {
+    system_time_from_interval(interval + CF_EPOCH_OFFSET)
+}
+
+/// Returns the number of days since the Unix epoch for the given (proleptic Gregorian) date, or
+/// `None` if it doesn't fit in an `i64`. `month` and `day` are expected to be in range.
+fn days_from_civil(year: i64, month: i64, day: i64) -> Option<i64> {
+    let year = if month <= 2 { year.checked_sub(1)? } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era.checked_mul(146_097)?.checked_add(day_of_era - 719_468)
+}
+
+/// Returns the (year, month, day) for the given number of days since the Unix epoch.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+
+    (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{civil_from_days, days_from_civil, PlistError, PlistFormat};
+    use crate::defaults::Value;
+
+    #[test]
+    fn test_round_trip() {
+        let value = Value::Dictionary({
+            let mut map = HashMap::new();
+            map.insert("bool".to_string(), Value::Bool(false));
+            map.insert("int".to_string(), Value::Integer(-42));
+            map.insert("big".to_string(), Value::Integer(i64::MAX));
+            map.insert("float".to_string(), Value::Float(0.25));
+            map.insert("string".to_string(), Value::string("<caf\u{e9} & \u{1f980}>"));
+            map.insert("data".to_string(), Value::Data(vec![0, 1, 2, 254, 255]));
+            map.insert(
+                "date".to_string(),
+                Value::Date(UNIX_EPOCH + Duration::from_secs(1_600_000_000))
+            );
+            map.insert(
+                "array".to_string(),
+                Value::Array((0..20).map(|i| Value::Integer(i * 1000)).collect())
+            );
+            map.insert("empty".to_string(), Value::Dictionary(HashMap::new()));
+            map
+        });
+
+        for format in &[PlistFormat::Xml, PlistFormat::Binary] {
+            let bytes = value.to_plist(*format);
+            assert_eq!(Value::from_plist(&bytes).unwrap(), value, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_civil_dates() {
+        assert_eq!(days_from_civil(1970, 1, 1), Some(0));
+        assert_eq!(days_from_civil(2001, 1, 1), Some(11_323));
+        assert_eq!(days_from_civil(i64::MAX, 12, 31), None);
+        assert_eq!(civil_from_days(11_323), (2001, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29).unwrap()), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            Value::from_plist(b"bplist00\x08"),
+            Err(PlistError::InvalidBinary(_))
+        ));
+        assert!(matches!(Value::from_plist(b"<plist><dict>"), Err(PlistError::InvalidXml(_))));
+
+        // Dates that can't be represented are errors, not panics.
+        assert!(matches!(
+            Value::from_plist(b"<plist><date>99999999999999999-01-01T00:00:00Z</date></plist>"),
+            Err(PlistError::InvalidXml(_))
+        ));
+
+        let mut bytes = Value::Date(UNIX_EPOCH).to_plist(PlistFormat::Binary);
+        let marker = bytes.iter().position(|byte| *byte == 0x33).unwrap();
+        bytes[marker + 1..marker + 9].copy_from_slice(&f64::NAN.to_be_bytes());
+        assert!(matches!(Value::from_plist(&bytes), Err(PlistError::InvalidBinary(_))));
+        assert!(matches!(
+            super::dictionary_from_plist(&Value::Bool(true).to_plist(PlistFormat::Xml)),
+            Err(PlistError::NotADictionary)
+        ));
+    }
+}
@@ -0,0 +1,495 @@
+//! The XML property list format. The reader is a small hand-rolled parser that understands just
+//! enough XML for plists: the prolog, `DOCTYPE`, comments, `CDATA` sections and the standard
+//! entities are handled, and anything else is an error.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::{civil_from_days, days_from_civil};
+use crate::defaults::base64;
+use crate::defaults::value::{interval_from_system_time, system_time_from_interval};
+use crate::defaults::Value;
+
+const HEADER: &str = concat!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+    "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+    "<plist version=\"1.0\">\n"
+);
+
+/// Serializes a `Value` into an XML plist, indented with tabs the same way Foundation does it.
+pub(crate) fn to_string(value: &Value) -> String {
+    let mut output = String::from(HEADER);
+    write_value(&mut output, value, 0);
+    output.push_str("</plist>\n");
+    output
+}
+
+/// Parses an XML plist into a `Value`. A bare value without the enclosing `<plist>` element is
+/// also accepted.
+pub(crate) fn from_str(input: &str) -> Result<Value, String> {
+    let mut parser = Parser { input, position: 0 };
+    parser.skip_misc()?;
+
+    let value = match parser.input[parser.position..].starts_with("<plist") {
+        true => {
+            let (_, self_closing) = parser.parse_open_tag()?;
+
+            if self_closing {
+                return Err("the plist element is empty".into());
+            }
+
+            parser.skip_misc()?;
+            let value = parser.parse_value()?;
+            parser.skip_misc()?;
+            parser.parse_close_tag("plist")?;
+            value
+        },
+
+        false => parser.parse_value()?
+    };
+
+    parser.skip_misc()?;
+
+    match parser.position == parser.input.len() {
+        true => Ok(value),
+        false => Err(parser.error("trailing characters"))
+    }
+}
+
+fn write_indent(output: &mut String, depth: usize) {
+    for _ in 0..depth {
+        output.push('\t');
+    }
+}
+
+fn write_escaped(output: &mut String, value: &str) {
+    for c in value.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            c => output.push(c)
+        }
+    }
+}
+
+fn write_element(output: &mut String, tag: &str, contents: &str, depth: usize) {
+    write_indent(output, depth);
+    let _ = write!(output, "<{}>", tag);
+    write_escaped(output, contents);
+    let _ = writeln!(output, "</{}>", tag);
+}
+
+/// Formats a date as ISO 8601 in UTC. Plist dates only carry whole seconds.
+fn format_date(seconds: i64) -> String {
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time = seconds.rem_euclid(86_400);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+fn write_value(output: &mut String, value: &Value, depth: usize) {
+    match value {
+        Value::Bool(true) => {
+            write_indent(output, depth);
+            output.push_str("<true/>\n");
+        },
+
+        Value::Bool(false) => {
+            write_indent(output, depth);
+            output.push_str("<false/>\n");
+        },
+
+        Value::String(value) => write_element(output, "string", value, depth),
+        Value::Integer(value) => write_element(output, "integer", &value.to_string(), depth),
+
+        Value::Float(value) => {
+            let formatted = match value {
+                v if v.is_nan() => "nan".to_string(),
+                v if v.is_infinite() && *v > 0. => "+infinity".to_string(),
+                v if v.is_infinite() => "-infinity".to_string(),
+                v => format!("{:?}", v)
+            };
+
+            write_element(output, "real", &formatted, depth);
+        },
+
+        Value::Data(bytes) => write_element(output, "data", &base64::encode(bytes), depth),
+
+        Value::Date(time) => {
+            let seconds = interval_from_system_time(*time).floor() as i64;
+            write_element(output, "date", &format_date(seconds), depth);
+        },
+
+        Value::Array(values) => {
+            write_indent(output, depth);
+
+            if values.is_empty() {
+                output.push_str("<array/>\n");
+                return;
+            }
+
+            output.push_str("<array>\n");
+
+            for value in values {
+                write_value(output, value, depth + 1);
+            }
+
+            write_indent(output, depth);
+            output.push_str("</array>\n");
+        },
+
+        Value::Dictionary(values) => {
+            write_indent(output, depth);
+
+            if values.is_empty() {
+                output.push_str("<dict/>\n");
+                return;
+            }
+
+            output.push_str("<dict>\n");
+
+            let mut keys: Vec<&String> = values.keys().collect();
+            keys.sort();
+
+            for key in keys {
+                write_element(output, "key", key, depth + 1);
+                write_value(output, &values[key], depth + 1);
+            }
+
+            write_indent(output, depth);
+            output.push_str("</dict>\n");
+        }
+    }
+}
+
+/// Parses an ISO 8601 date (`YYYY-MM-DDTHH:MM:SSZ`, optionally with fractional seconds) into
+/// seconds since the Unix epoch.
+fn parse_date(input: &str) -> Option<f64> {
+    let input = input.strip_suffix('Z')?;
+    let (date, time) = input.split_once('T')?;
+
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    let mut time = time.splitn(3, ':');
+    let hour = time.next()?.parse::<i64>().ok()?;
+    let minute = time.next()?.parse::<i64>().ok()?;
+    let second = time.next()?.parse::<f64>().ok()?;
+
+    let in_range = (1..=12).contains(&month)
+        && (1..=31).contains(&day)
+        && (0..24).contains(&hour)
+        && (0..60).contains(&minute)
+        && (0. ..61.).contains(&second);
+
+    if !in_range {
+        return None;
+    }
+
+    let seconds = days_from_civil(year, month, day)?
+        .checked_mul(86_400)?
+        .checked_add(hour * 3600 + minute * 60)?;
+
+    Some(seconds as f64 + second)
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &str) -> String {
+        let line = self.input[..self.position].matches('\n').count() + 1;
+        format!("{} on line {}", message, line)
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    /// Skips past `terminator`, erroring if the input ends first.
+    fn skip_past(&mut self, terminator: &str) -> Result<(), String> {
+        match self.rest().find(terminator) {
+            Some(index) => {
+                self.position += index + terminator.len();
+                Ok(())
+            },
+
+            None => Err(self.error(&format!("expected `{}`", terminator)))
+        }
+    }
+
+    /// Skips whitespace, processing instructions, comments and the `DOCTYPE`.
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.position = self.input.len() - trimmed.len();
+
+            if trimmed.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if trimmed.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if trimmed.starts_with("<!DOCTYPE") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Parses an opening tag, returning its name and whether it was self-closing (`<true/>`).
+    /// Attributes are ignored.
+    fn parse_open_tag(&mut self) -> Result<(&'a str, bool), String> {
+        if !self.rest().starts_with('<') || self.rest().starts_with("</") {
+            return Err(self.error("expected an element"));
+        }
+
+        let end = match self.rest().find('>') {
+            Some(end) => end,
+            None => return Err(self.error("unterminated element"))
+        };
+
+        let tag = &self.rest()[1..end];
+        self.position += end + 1;
+
+        let (tag, self_closing) = match tag.strip_suffix('/') {
+            Some(tag) => (tag, true),
+            None => (tag, false)
+        };
+
+        let name = tag.split_whitespace().next().unwrap_or("");
+        Ok((name, self_closing))
+    }
+
+    fn parse_close_tag(&mut self, name: &str) -> Result<(), String> {
+        let expected = format!("</{}", name);
+
+        if !self.rest().starts_with(&expected) {
+            return Err(self.error(&format!("expected `{}>`", expected)));
+        }
+
+        self.position += expected.len();
+        let trimmed = self.rest().trim_start();
+        self.position = self.input.len() - trimmed.len();
+
+        match trimmed.starts_with('>') {
+            true => {
+                self.position += 1;
+                Ok(())
+            },
+
+            false => Err(self.error(&format!("expected `{}>`", expected)))
+        }
+    }
+
+    /// Reads the text content of the element `name` up to and including its closing tag,
+    /// decoding entities and `CDATA` sections.
+    fn parse_text(&mut self, name: &str) -> Result<String, String> {
+        let mut text = String::new();
+
+        loop {
+            let rest = self.rest();
+
+            if rest.starts_with("<![CDATA[") {
+                let end = match rest.find("]]>") {
+                    Some(end) => end,
+                    None => return Err(self.error("unterminated CDATA section"))
+                };
+
+                text.push_str(&rest[9..end]);
+                self.position += end + 3;
+            } else if rest.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if rest.starts_with('<') {
+                self.parse_close_tag(name)?;
+                return Ok(text);
+            } else if rest.starts_with('&') {
+                let end = match rest.find(';') {
+                    Some(end) => end,
+                    None => return Err(self.error("unterminated entity"))
+                };
+
+                let decoded = match &rest[1..end] {
+                    "amp" => Some('&'),
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    entity => match entity.strip_prefix("#x").or_else(|| entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok().and_then(char::from_u32),
+                        None => entity.strip_prefix('#').and_then(|d| d.parse().ok()).and_then(char::from_u32)
+                    }
+                };
+
+                match decoded {
+                    Some(c) => text.push(c),
+                    None => return Err(self.error(&format!("unknown entity `{}`", &rest[..=end])))
+                }
+
+                self.position += end + 1;
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("unterminated `<{}>` element", name)));
+            } else {
+                let end = rest.find(&['<', '&'][..]).unwrap_or(rest.len());
+                text.push_str(&rest[..end]);
+                self.position += end;
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        let (name, self_closing) = self.parse_open_tag()?;
+
+        let text = match (name, self_closing) {
+            ("true", true) => return Ok(Value::Bool(true)),
+            ("false", true) => return Ok(Value::Bool(false)),
+            ("array", true) => return Ok(Value::Array(Vec::new())),
+            ("dict", true) => return Ok(Value::Dictionary(HashMap::new())),
+            ("string", true) | ("data", true) => String::new(),
+            ("array", false) => return self.parse_array(),
+            ("dict", false) => return self.parse_dictionary(),
+            ("true", false) | ("false", false) => {
+                self.skip_misc()?;
+                self.parse_close_tag(name)?;
+                return Ok(Value::Bool(name == "true"));
+            },
+            (_, true) => return Err(self.error(&format!("unexpected empty `<{}/>` element", name))),
+            (_, false) => self.parse_text(name)?
+        };
+
+        match name {
+            "string" => Ok(Value::String(text)),
+
+            "integer" => match text.trim().parse::<i64>() {
+                Ok(value) => Ok(Value::Integer(value)),
+                Err(_) => Err(self.error(&format!("invalid integer `{}`", text.trim())))
+            },
+
+            "real" => {
+                let value = match text.trim() {
+                    "nan" => Some(f64::NAN),
+                    "+infinity" | "infinity" | "inf" => Some(f64::INFINITY),
+                    "-infinity" | "-inf" => Some(f64::NEG_INFINITY),
+                    text => text.parse().ok()
+                };
+
+                match value {
+                    Some(value) => Ok(Value::Float(value)),
+                    None => Err(self.error(&format!("invalid real `{}`", text.trim())))
+                }
+            },
+
+            "data" => match base64::decode(&text) {
+                Some(bytes) => Ok(Value::Data(bytes)),
+                None => Err(self.error("invalid base64 data"))
+            },
+
+            "date" => match parse_date(text.trim()).and_then(system_time_from_interval) {
+                Some(time) => Ok(Value::Date(time)),
+                None => Err(self.error(&format!("invalid date `{}`", text.trim())))
+            },
+
+            _ => Err(self.error(&format!("unknown element `<{}>`", name)))
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Value, String> {
+        let mut values = Vec::new();
+
+        loop {
+            self.skip_misc()?;
+
+            if self.rest().starts_with("</") {
+                self.parse_close_tag("array")?;
+                return Ok(Value::Array(values));
+            }
+
+            values.push(self.parse_value()?);
+        }
+    }
+
+    fn parse_dictionary(&mut self) -> Result<Value, String> {
+        let mut values = HashMap::new();
+
+        loop {
+            self.skip_misc()?;
+
+            if self.rest().starts_with("</") {
+                self.parse_close_tag("dict")?;
+                return Ok(Value::Dictionary(values));
+            }
+
+            let key = match self.parse_open_tag()? {
+                ("key", false) => self.parse_text("key")?,
+                ("key", true) => String::new(),
+                _ => return Err(self.error("expected `<key>`"))
+            };
+
+            self.skip_misc()?;
+            let value = self.parse_value()?;
+            values.insert(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::from_str;
+    use crate::defaults::Value;
+
+    #[test]
+    fn test_parse() {
+        let input = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<!-- shipped defaults -->
+<dict>
+    <key>name</key>
+    <string>Tom &amp; Jerry &#x263A;</string>
+    <key>notes</key>
+    <string><![CDATA[<raw>]]></string>
+    <key>launched</key>
+    <date>2001-01-01T00:00:00Z</date>
+    <key>items</key>
+    <array>
+        <integer>-3</integer>
+        <real>1.5</real>
+        <true/>
+        <data>
+            AAEC
+        </data>
+    </array>
+</dict>
+</plist>"#;
+
+        let value = from_str(input).unwrap();
+        let values = value.as_dictionary().unwrap();
+
+        assert_eq!(values["name"], Value::string("Tom & Jerry \u{263a}"));
+        assert_eq!(values["notes"], Value::string("<raw>"));
+        assert_eq!(
+            values["items"],
+            Value::Array(vec![
+                Value::Integer(-3),
+                Value::Float(1.5),
+                Value::Bool(true),
+                Value::Data(vec![0, 1, 2])
+            ])
+        );
+
+        match &values["launched"] {
+            Value::Date(time) => assert_eq!(crate::defaults::value::interval_from_system_time(*time), 978_307_200.),
+            _ => panic!("expected a date")
+        }
+    }
+}
@@ -0,0 +1,692 @@
+//! Serde support for `Value`, which powers `UserDefaults::get_typed` and
+//! `UserDefaults::set_typed`. This is roughly modeled after `serde_json::to_value` and
+//! `serde_json::from_value`, and maps Rust types onto what `NSUserDefaults` can store:
+//!
+//! - Structs and maps become `Value::Dictionary` (map keys must be strings, chars or integers).
+//! - Sequences, tuples and sets become `Value::Array`.
+//! - Unit enum variants become `Value::String`, and other variants become a single-entry
+//!   `Value::Dictionary` keyed by the variant name (the same as `serde_json`'s default).
+//! - `None` fields are omitted from the dictionary entirely, as `NSUserDefaults` can't store
+//!   `nil`. A top-level `None` passed to `set_typed` removes the key.
+//!
+//! `Value::Date` deserializes as a `{ secs_since_epoch, nanos_since_epoch }` map, which is what
+//! `SystemTime` expects - so `SystemTime` fields can be read from an `NSDate`. Serde has no date
+//! type, though, so `SystemTime` fields are *written* as that same map: a dictionary, rather than
+//! an `NSDate`. Both read back as a `SystemTime`.
+
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::time::UNIX_EPOCH;
+
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::ser::{self, Serialize};
+
+use super::Value;
+
+/// Errors that can occur when converting between Rust types and `Value`s.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValueError {
+    /// A `None` was found somewhere it can't be omitted (e.g, inside of a sequence).
+    UnsupportedNone,
+
+    /// A unit value (`()`) was found; these have no `NSUserDefaults` representation.
+    UnsupportedUnit,
+
+    /// A map key wasn't a string, char or integer.
+    KeyMustBeAString,
+
+    /// An unsigned integer was too large to be stored as an `i64`.
+    IntegerOutOfRange(u64),
+
+    /// A catch-all for errors raised by `Serialize` or `Deserialize` implementations.
+    Custom(String)
+}
+
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValueError::UnsupportedNone => write!(f, "None can only be stored as a struct field or map value"),
+            ValueError::UnsupportedUnit => write!(f, "unit values cannot be stored in UserDefaults"),
+            ValueError::KeyMustBeAString => write!(f, "map keys must be strings"),
+            ValueError::IntegerOutOfRange(value) => write!(f, "{} is too large to be stored as an i64", value),
+            ValueError::Custom(message) => write!(f, "{}", message)
+        }
+    }
+}
+
+impl error::Error for ValueError {}
+
+impl ser::Error for ValueError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        ValueError::Custom(message.to_string())
+    }
+}
+
+impl de::Error for ValueError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        ValueError::Custom(message.to_string())
+    }
+}
+
+/// Converts any `Serialize` type into a `Value`. Returns `Ok(None)` if `value` serializes to
+/// `None`.
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Option<Value>, ValueError> {
+    value.serialize(Serializer)
+}
+
+/// Converts a `Value` into any `DeserializeOwned` type.
+pub fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, ValueError> {
+    T::deserialize(value)
+}
+
+/// Serializes Rust types into `Value`s. The output is an `Option` so that `None` can be omitted
+/// from dictionaries rather than erroring out.
+#[derive(Debug)]
+struct Serializer;
+
+/// Unwraps the output of a nested serialization, where `None` isn't allowed.
+fn required(value: Option<Value>) -> Result<Value, ValueError> {
+    value.ok_or(ValueError::UnsupportedNone)
+}
+
+/// Wraps a value in a single-entry dictionary keyed by an enum variant name.
+fn variant(name: &str, value: Value) -> Value {
+    let mut map = HashMap::new();
+    map.insert(name.to_string(), value);
+    Value::Dictionary(map)
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Option<Value>;
+    type Error = ValueError;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeArray;
+    type SerializeMap = SerializeDictionary;
+    type SerializeStruct = SerializeDictionary;
+    type SerializeStructVariant = SerializeDictionary;
+
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Value::Bool(value)))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Value::Integer(value)))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_i64(value as i64)
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Self::Ok, Self::Error> {
+        match value <= i64::MAX as u64 {
+            true => self.serialize_i64(value as i64),
+            false => Err(ValueError::IntegerOutOfRange(value))
+        }
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
+        self.serialize_f64(value as f64)
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Value::Float(value)))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Value::String(value.to_string())))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Value::String(value.to_string())))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Value::Data(value.to_vec())))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(ValueError::UnsupportedUnit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Some(Value::Dictionary(HashMap::new())))
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        name: &'static str,
+        value: &T
+    ) -> Result<Self::Ok, Self::Error> {
+        let value = required(value.serialize(Serializer)?)?;
+        Ok(Some(variant(name, value)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SerializeArray {
+            variant: None,
+            values: Vec::with_capacity(len.unwrap_or(0))
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(SerializeArray {
+            variant: Some(variant),
+            values: Vec::with_capacity(len)
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(SerializeDictionary {
+            variant: None,
+            map: HashMap::new(),
+            next_key: None
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(SerializeDictionary {
+            variant: Some(variant),
+            map: HashMap::new(),
+            next_key: None
+        })
+    }
+}
+
+/// Collects sequences, tuples and tuple variants into a `Value::Array`.
+#[derive(Debug)]
+struct SerializeArray {
+    variant: Option<&'static str>,
+    values: Vec<Value>
+}
+
+impl SerializeArray {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), ValueError> {
+        self.values.push(required(value.serialize(Serializer)?)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Option<Value>, ValueError> {
+        let array = Value::Array(self.values);
+
+        Ok(Some(match self.variant {
+            Some(name) => variant(name, array),
+            None => array
+        }))
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = Option<Value>;
+    type Error = ValueError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = Option<Value>;
+    type Error = ValueError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = Option<Value>;
+    type Error = ValueError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray {
+    type Ok = Option<Value>;
+    type Error = ValueError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+/// Collects maps, structs and struct variants into a `Value::Dictionary`.
+#[derive(Debug)]
+struct SerializeDictionary {
+    variant: Option<&'static str>,
+    map: HashMap<String, Value>,
+    next_key: Option<String>
+}
+
+impl SerializeDictionary {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), ValueError> {
+        // `None` values are simply left out, which `Option` fields handle on the way back in.
+        if let Some(value) = value.serialize(Serializer)? {
+            self.map.insert(key, value);
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Option<Value>, ValueError> {
+        let dictionary = Value::Dictionary(self.map);
+
+        Ok(Some(match self.variant {
+            Some(name) => variant(name, dictionary),
+            None => dictionary
+        }))
+    }
+}
+
+impl ser::SerializeMap for SerializeDictionary {
+    type Ok = Option<Value>;
+    type Error = ValueError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.next_key = Some(match key.serialize(Serializer)? {
+            Some(Value::String(key)) => key,
+            Some(Value::Integer(key)) => key.to_string(),
+            _ => return Err(ValueError::KeyMustBeAString)
+        });
+
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| ValueError::Custom("serialize_value called before serialize_key".into()))?;
+
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeDictionary {
+    type Ok = Option<Value>;
+    type Error = ValueError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeDictionary {
+    type Ok = Option<Value>;
+    type Error = ValueError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.finish()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, ValueError> for Value {
+    type Deserializer = Value;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Value {
+    type Error = ValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::Bool(value) => visitor.visit_bool(value),
+            Value::String(value) => visitor.visit_string(value),
+            Value::Float(value) => visitor.visit_f64(value),
+            Value::Integer(value) => visitor.visit_i64(value),
+            Value::Data(value) => visitor.visit_byte_buf(value),
+
+            Value::Date(date) => {
+                let (secs, nanos) = match date.duration_since(UNIX_EPOCH) {
+                    Ok(duration) => (duration.as_secs() as i64, duration.subsec_nanos() as i64),
+                    Err(_) => return Err(ValueError::Custom("dates before 1970 are not supported".into()))
+                };
+
+                let mut map = HashMap::new();
+                map.insert("secs_since_epoch".to_string(), Value::Integer(secs));
+                map.insert("nanos_since_epoch".to_string(), Value::Integer(nanos));
+                Value::Dictionary(map).deserialize_any(visitor)
+            },
+
+            Value::Array(values) => {
+                let mut deserializer = SeqDeserializer::new(values.into_iter());
+                let value = visitor.visit_seq(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(value)
+            },
+
+            Value::Dictionary(map) => {
+                let mut deserializer = MapDeserializer::new(map.into_iter().map(|(key, value)| (Key(key), value)));
+                let value = visitor.visit_map(&mut deserializer)?;
+                deserializer.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        // A `Value` always holds something; missing keys are handled by `Option` fields directly.
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::String(variant) => visitor.visit_enum(EnumDeserializer { variant, value: None }),
+
+            Value::Dictionary(map) if map.len() == 1 => {
+                let (variant, value) = map.into_iter().next().unwrap();
+                visitor.visit_enum(EnumDeserializer {
+                    variant,
+                    value: Some(value)
+                })
+            },
+
+            _ => Err(de::Error::custom("expected a string or single-entry dictionary for an enum"))
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// A dictionary key. Keys are always stored as strings, so integer keys (which are serialized in
+/// decimal) are parsed back when the visitor asks for an integer.
+#[derive(Debug)]
+struct Key(String);
+
+impl<'de> IntoDeserializer<'de, ValueError> for Key {
+    type Deserializer = Key;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_integer_key {
+    ($($method:ident => $visit:ident),*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(de::Error::invalid_value(de::Unexpected::Str(&self.0), &visitor))
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Key {
+    type Error = ValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0)
+    }
+
+    deserialize_integer_key! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(IntoDeserializer::<ValueError>::into_deserializer(self.0))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf unit unit_struct seq tuple tuple_struct map
+        struct identifier ignored_any
+    }
+}
+
+/// Walks an enum variant, which is either a bare name (unit variants) or a name and a value.
+#[derive(Debug)]
+struct EnumDeserializer {
+    variant: String,
+    value: Option<Value>
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = ValueError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(IntoDeserializer::<ValueError>::into_deserializer(self.variant))?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+/// Walks the content of an enum variant, if there is any.
+#[derive(Debug)]
+struct VariantDeserializer(Option<Value>);
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = ValueError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.0 {
+            None => Ok(()),
+            Some(_) => Err(de::Error::custom("expected a unit variant"))
+        }
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+        match self.0 {
+            Some(value) => seed.deserialize(value),
+            None => Err(de::Error::custom("expected a newtype variant"))
+        }
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Some(value @ Value::Array(_)) => de::Deserializer::deserialize_any(value, visitor),
+            _ => Err(de::Error::custom("expected a tuple variant"))
+        }
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Some(value @ Value::Dictionary(_)) => de::Deserializer::deserialize_any(value, visitor),
+            _ => Err(de::Error::custom("expected a struct variant"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use serde::{Deserialize, Serialize};
+
+    use super::{from_value, to_value, ValueError};
+    use crate::defaults::Value;
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    enum Layout {
+        Grid,
+        List { dense: bool },
+        Columns(u8, u8)
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        name: String,
+        volume: f64,
+        launches: u32,
+        tags: Vec<String>,
+        nickname: Option<String>,
+        layout: Layout,
+        windows: HashMap<String, (i32, i32)>,
+        scores: HashMap<u32, i64>,
+        last_opened: SystemTime
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let settings = Settings {
+            name: "Cacao".into(),
+            volume: 0.5,
+            launches: 3,
+            tags: vec!["a".into(), "b".into()],
+            nickname: None,
+            layout: Layout::List { dense: true },
+            windows: vec![("main".to_string(), (10, 20))].into_iter().collect(),
+            scores: vec![(7, -1), (42, 100)].into_iter().collect(),
+            last_opened: UNIX_EPOCH + Duration::from_secs(1_600_000_000)
+        };
+
+        let value = to_value(&settings).unwrap().unwrap();
+        let map = value.as_dictionary().unwrap();
+        assert!(!map.contains_key("nickname"));
+        assert_eq!(map.get("launches"), Some(&Value::Integer(3)));
+
+        // Dates come back out of `NSUserDefaults` as `NSDate`, which should still deserialize.
+        let mut map = map.clone();
+        map.insert("last_opened".into(), Value::Date(settings.last_opened));
+        assert_eq!(from_value::<Settings>(Value::Dictionary(map)).unwrap(), settings);
+
+        for layout in [Layout::Grid, Layout::Columns(2, 3)].iter() {
+            let value = to_value(layout).unwrap().unwrap();
+            assert_eq!(&from_value::<Layout>(value).unwrap(), layout);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(to_value(&None::<u8>), Ok(None));
+        assert_eq!(to_value(&vec![Some(1), None]), Err(ValueError::UnsupportedNone));
+        assert_eq!(to_value(&u64::MAX), Err(ValueError::IntegerOutOfRange(u64::MAX)));
+        assert!(from_value::<u8>(Value::Integer(300)).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::foundation::{id, NSData, NSMutableDictionary, NSNumber, NSString};
+use objc::{class, msg_send, sel, sel_impl};
+
+use crate::foundation::{id, nil, to_bool, NSArray, NSData, NSMutableDictionary, NSNumber, NSString, BOOL};
 
 /// Represents a Value that can be stored or queried with `UserDefaults`.
 ///
@@ -23,7 +26,16 @@ pub enum Value {
 
     /// Represents Data (bytes). You can use this to store arbitrary things that aren't supported
     /// above. You're responsible for moving things back and forth to the necessary types.
-    Data(Vec<u8>)
+    Data(Vec<u8>),
+
+    /// Represents a Date, stored as an `NSDate`.
+    Date(SystemTime),
+
+    /// Represents an Array of `Value`s, stored as an `NSArray`.
+    Array(Vec<Value>),
+
+    /// Represents a Dictionary of `Value`s keyed by `String`, stored as an `NSDictionary`.
+    Dictionary(HashMap<String, Value>)
 }
 
 impl Value {
@@ -128,6 +140,146 @@ impl Value {
             _ => None
         }
     }
+
+    /// Returns `true` if the value is a date. Returns `false` otherwise.
+    pub fn is_date(&self) -> bool {
+        matches!(self, Value::Date(_))
+    }
+
+    /// If this is a date, returns it (`SystemTime`). Returns `None` otherwise.
+    pub fn as_date(&self) -> Option<SystemTime> {
+        match self {
+            Value::Date(date) => Some(*date),
+            _ => None
+        }
+    }
+
+    /// Returns `true` if the value is an array. Returns `false` otherwise.
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
+    }
+
+    /// If this is an array, returns it (`&[Value]`). Returns `None` otherwise.
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None
+        }
+    }
+
+    /// Returns `true` if the value is a dictionary. Returns `false` otherwise.
+    pub fn is_dictionary(&self) -> bool {
+        matches!(self, Value::Dictionary(_))
+    }
+
+    /// If this is a dictionary, returns it (`&HashMap<String, Value>`). Returns `None` otherwise.
+    pub fn as_dictionary(&self) -> Option<&HashMap<String, Value>> {
+        match self {
+            Value::Dictionary(map) => Some(map),
+            _ => None
+        }
+    }
+
+    /// Attempts to convert an Objective-C object vended by `NSUserDefaults` (or anything holding
+    /// property list types) into a `Value`. Returns `None` for `nil` and for types that can't be
+    /// represented.
+    pub(crate) fn from_objc(obj: id) -> Option<Value> {
+        if obj == nil {
+            return None;
+        }
+
+        if NSData::is(obj) {
+            let data = NSData::retain(obj);
+            return Some(Value::Data(data.into_vec()));
+        }
+
+        if NSString::is(obj) {
+            let s = NSString::retain(obj).to_string();
+            return Some(Value::String(s));
+        }
+
+        // This works, but might not be the best approach. We basically need to inspect the
+        // `NSNumber` returned and see what the wrapped encoding type is. `q` and `d` represent
+        // `NSInteger` (platform specific) and `double` (f64) respectively, but conceivably we
+        // might need others.
+        //
+        // BOOL returns as "c", which... something makes me feel weird there, but testing it seems
+        // reliable.
+        //
+        // For context: https://nshipster.com/type-encodings/
+        if NSNumber::is(obj) {
+            let number = NSNumber::retain(obj);
+
+            return match number.objc_type() {
+                "c" => Some(Value::Bool(number.as_bool())),
+                "d" => Some(Value::Float(number.as_f64())),
+                "q" => Some(Value::Integer(number.as_i64())),
+
+                _x => {
+                    // Debugging code that should be removed at some point.
+                    #[cfg(debug_assertions)]
+                    println!("Unexpected code type found: {}", _x);
+
+                    None
+                }
+            };
+        }
+
+        if is_kind_of(obj, class!(NSDate)) {
+            let interval: f64 = unsafe { msg_send![obj, timeIntervalSince1970] };
+            return system_time_from_interval(interval).map(Value::Date);
+        }
+
+        if is_kind_of(obj, class!(NSArray)) {
+            let array = NSArray::retain(obj);
+            return Some(Value::Array(array.map(Value::from_objc).into_iter().flatten().collect()));
+        }
+
+        if is_kind_of(obj, class!(NSDictionary)) {
+            let keys = NSArray::retain(unsafe { msg_send![obj, allKeys] });
+
+            let map = keys
+                .map(|key| {
+                    if !NSString::is(key) {
+                        return None;
+                    }
+
+                    let value: id = unsafe { msg_send![obj, objectForKey: key] };
+                    let key = NSString::retain(key).to_string();
+                    Value::from_objc(value).map(|value| (key, value))
+                })
+                .into_iter()
+                .flatten()
+                .collect();
+
+            return Some(Value::Dictionary(map));
+        }
+
+        None
+    }
+}
+
+/// A helper method for determining if a given `NSObject` is of a specific class (or subclass).
+fn is_kind_of(obj: id, class: &objc::runtime::Class) -> bool {
+    let result: BOOL = unsafe { msg_send![obj, isKindOfClass: class] };
+    to_bool(result)
+}
+
+/// Converts a `SystemTime` to seconds since the Unix epoch, which is what `NSDate` expects.
+pub(crate) fn interval_from_system_time(time: SystemTime) -> f64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs_f64(),
+        Err(error) => -error.duration().as_secs_f64()
+    }
+}
+
+/// Converts seconds since the Unix epoch (as vended by `NSDate`) to a `SystemTime`. Returns
+/// `None` for intervals that aren't finite, or that `SystemTime` can't represent.
+pub(crate) fn system_time_from_interval(interval: f64) -> Option<SystemTime> {
+    match interval >= 0. {
+        true => UNIX_EPOCH.checked_add(Duration::try_from_secs_f64(interval).ok()?),
+        false => UNIX_EPOCH.checked_sub(Duration::try_from_secs_f64(-interval).ok()?)
+    }
 }
 
 impl From<Value> for id {
@@ -140,7 +292,19 @@ impl From<Value> for id {
             Value::String(s) => NSString::new(&s).into(),
             Value::Float(f) => NSNumber::float(f).into(),
             Value::Integer(i) => NSNumber::integer(i).into(),
-            Value::Data(data) => NSData::new(data).into()
+            Value::Data(data) => NSData::new(data).into(),
+
+            Value::Date(date) => unsafe {
+                let interval = interval_from_system_time(date);
+                msg_send![class!(NSDate), dateWithTimeIntervalSince1970: interval]
+            },
+
+            Value::Array(values) => {
+                let objects: Vec<id> = values.into_iter().map(|value| value.into()).collect();
+                NSArray::from(objects).into()
+            },
+
+            Value::Dictionary(map) => NSMutableDictionary::from(map).into_inner()
         }
     }
 }
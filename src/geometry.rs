@@ -1,10 +1,141 @@
-//! Wrapper methods for various geometry types (rects, sizes, ec).
+//! Wrapper methods for various geometry types (points, sizes, rects, insets and transforms), along
+//! with the operations you'd otherwise reach for `CGRect*`/`NSRect*` functions for.
+//!
+//! ## Coordinates
+//! All of these types use the coordinate system that Cacao views use: the origin is at the
+//! top-left, and y increases downwards. On iOS this is what `UIView` does anyway; on macOS, Cacao
+//! views are flipped (`isFlipped` returns `YES`) so that they match. This is why `Rect` talks about
+//! its `top` and `bottom`, rather than its min and max y.
+//!
+//! The one exception is window and screen frames on macOS (e.g, `WindowConfig::initial_dimensions`,
+//! or `Window::set_frame`), which AppKit always measures from the bottom-left of the screen - there,
+//! `Rect::top` is really the distance from the bottom of the screen, and `Rect::bottom()` is above
+//! it. The operations here don't care which way y goes, so they work in either system; only the
+//! naming assumes a flipped one.
+//!
+//! All values are in points, as `f64` (32-bit is mostly dead on Cocoa, so... this is "okay"). Use
+//! `Rect::integral()` with a screen or window's backing scale factor to line a rect up with pixels.
 
-use core_graphics::geometry::{CGPoint, CGRect, CGSize};
+use core_graphics::base::CGFloat;
+use core_graphics::geometry::{CGAffineTransform, CGPoint, CGRect, CGSize};
+
+use objc::{Encode, Encoding};
+
+/// A point - e.g, a location in a view, or the origin of a rect. Converts to and from `CGPoint`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Point {
+    /// Distance from the left, in points.
+    pub x: f64,
+
+    /// Distance from the top, in points.
+    pub y: f64
+}
+
+impl Point {
+    /// Returns a new `Point` initialized with the values specified.
+    pub fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+
+    /// Returns a point at the origin.
+    pub fn zero() -> Self {
+        Point { x: 0., y: 0. }
+    }
+
+    /// Returns this point moved by `dx` and `dy`.
+    pub fn offset(&self, dx: f64, dy: f64) -> Self {
+        Point::new(self.x + dx, self.y + dy)
+    }
+
+    /// Returns the distance between this point and `other`.
+    pub fn distance_to(&self, other: Point) -> f64 {
+        (other.x - self.x).hypot(other.y - self.y)
+    }
+}
+
+impl From<Point> for CGPoint {
+    fn from(point: Point) -> CGPoint {
+        CGPoint::new(point.x as CGFloat, point.y as CGFloat)
+    }
+}
+
+impl From<CGPoint> for Point {
+    fn from(point: CGPoint) -> Point {
+        Point::new(point.x, point.y)
+    }
+}
+
+/// A size - width and height. Converts to and from `CGSize`.
+///
+/// This is also laid out (and encoded) like `CGSize`, so delegate methods can take and return it
+/// directly - `core_graphics::CGSize` doesn't implement `Encode`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Size {
+    /// Width, in points.
+    pub width: f64,
+
+    /// Height, in points.
+    pub height: f64
+}
+
+impl Size {
+    /// Returns a new `Size` initialized with the values specified.
+    pub fn new(width: f64, height: f64) -> Self {
+        Size { width, height }
+    }
+
+    /// Returns a zero'd out size.
+    pub fn zero() -> Self {
+        Size { width: 0., height: 0. }
+    }
+
+    /// Whether this size has no area - i.e, either dimension is zero or negative.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0. || self.height <= 0.
+    }
+}
+
+impl From<Size> for CGSize {
+    fn from(size: Size) -> CGSize {
+        CGSize::new(size.width as CGFloat, size.height as CGFloat)
+    }
+}
+
+impl From<CGSize> for Size {
+    fn from(size: CGSize) -> Size {
+        Size::new(size.width, size.height)
+    }
+}
+
+unsafe impl Encode for Size {
+    /// Adds support for CGSize Objective-C encoding.
+    fn encode() -> Encoding {
+        let encoding = format!("{{CGSize={}{}}}", f64::encode().as_str(), f64::encode().as_str());
+
+        unsafe { Encoding::from_str(&encoding) }
+    }
+}
+
+/// The edges of a rect, for `Rect::divide()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RectEdge {
+    /// The top edge.
+    Top,
+
+    /// The left edge.
+    Left,
+
+    /// The bottom edge.
+    Bottom,
+
+    /// The right edge.
+    Right
+}
 
 /// A struct that represents a box - top, left, width and height. You might use this for, say,
 /// setting the initial frame of a view.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct Rect {
     /// Distance from the top, in points.
     pub top: f64,
@@ -39,6 +170,170 @@ impl Rect {
             height: 0.0
         }
     }
+
+    /// Returns a new `Rect` with the given origin (top-left corner) and size.
+    pub fn from_origin_and_size(origin: Point, size: Size) -> Self {
+        Rect::new(origin.y, origin.x, size.width, size.height)
+    }
+
+    /// The top-left corner of this rect.
+    pub fn origin(&self) -> Point {
+        Point::new(self.left, self.top)
+    }
+
+    /// The size of this rect.
+    pub fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    /// Distance from the left to the right edge of this rect.
+    pub fn right(&self) -> f64 {
+        self.left + self.width
+    }
+
+    /// Distance from the top to the bottom edge of this rect.
+    pub fn bottom(&self) -> f64 {
+        self.top + self.height
+    }
+
+    /// The center point of this rect.
+    pub fn center(&self) -> Point {
+        Point::new(self.left + self.width / 2., self.top + self.height / 2.)
+    }
+
+    /// Whether this rect has no area - i.e, its width or height is zero or negative.
+    pub fn is_empty(&self) -> bool {
+        self.size().is_empty()
+    }
+
+    /// Whether `point` lies within this rect. Like `CGRectContainsPoint`, the top and left edges
+    /// count as inside, and the bottom and right ones don't - so a point is never in two adjacent
+    /// rects at once.
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.left && point.x < self.right() && point.y >= self.top && point.y < self.bottom()
+    }
+
+    /// Whether `other` lies entirely within this rect. An empty rect contains nothing.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        !self.is_empty()
+            && !other.is_empty()
+            && other.left >= self.left
+            && other.top >= self.top
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// Whether this rect and `other` overlap.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Returns the area that this rect and `other` have in common, or `None` if they don't
+    /// overlap (rects that only share an edge don't).
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let left = self.left.max(other.left);
+        let top = self.top.max(other.top);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        match right > left && bottom > top {
+            true => Some(Rect::new(top, left, right - left, bottom - top)),
+            false => None
+        }
+    }
+
+    /// Returns the smallest rect that contains both this rect and `other`. Empty rects are
+    /// ignored, as with `CGRectUnion`.
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        }
+
+        if other.is_empty() {
+            return *self;
+        }
+
+        let left = self.left.min(other.left);
+        let top = self.top.min(other.top);
+        let right = self.right().max(other.right());
+        let bottom = self.bottom().max(other.bottom());
+
+        Rect::new(top, left, right - left, bottom - top)
+    }
+
+    /// Returns this rect shrunk by `insets` - or grown, for negative insets.
+    pub fn inset(&self, insets: EdgeInsets) -> Rect {
+        Rect::new(
+            self.top + insets.top,
+            self.left + insets.left,
+            self.width - insets.left - insets.right,
+            self.height - insets.top - insets.bottom
+        )
+    }
+
+    /// Returns this rect moved by `dx` and `dy`.
+    pub fn offset(&self, dx: f64, dy: f64) -> Rect {
+        Rect::new(self.top + dy, self.left + dx, self.width, self.height)
+    }
+
+    /// Splits this rect in two along a line `amount` points in from `edge`, returning the slice
+    /// next to `edge` and the remainder, like `CGRectDivide`. `amount` is clamped to the rect, so
+    /// one of the two can be empty.
+    pub fn divide(&self, amount: f64, edge: RectEdge) -> (Rect, Rect) {
+        let (top, left, width, height) = (self.top, self.left, self.width, self.height);
+
+        match edge {
+            RectEdge::Top => {
+                let amount = amount.max(0.).min(height);
+                (
+                    Rect::new(top, left, width, amount),
+                    Rect::new(top + amount, left, width, height - amount)
+                )
+            },
+
+            RectEdge::Left => {
+                let amount = amount.max(0.).min(width);
+                (
+                    Rect::new(top, left, amount, height),
+                    Rect::new(top, left + amount, width - amount, height)
+                )
+            },
+
+            RectEdge::Bottom => {
+                let amount = amount.max(0.).min(height);
+                (
+                    Rect::new(top + height - amount, left, width, amount),
+                    Rect::new(top, left, width, height - amount)
+                )
+            },
+
+            RectEdge::Right => {
+                let amount = amount.max(0.).min(width);
+                (
+                    Rect::new(top, left + width - amount, amount, height),
+                    Rect::new(top, left, width - amount, height)
+                )
+            }
+        }
+    }
+
+    /// Returns the smallest rect that contains this one and whose edges all fall on pixel
+    /// boundaries, for a display with the given scale factor (e.g, `2.` for Retina). With a scale
+    /// of `1.`, this is `CGRectIntegral`.
+    ///
+    /// Drawing and positioning along pixel boundaries avoids blurry, anti-aliased edges.
+    pub fn integral(&self, scale: f64) -> Rect {
+        if self.is_empty() || scale <= 0. {
+            return *self;
+        }
+
+        let left = (self.left * scale).floor() / scale;
+        let top = (self.top * scale).floor() / scale;
+        let right = (self.right() * scale).ceil() / scale;
+        let bottom = (self.bottom() * scale).ceil() / scale;
+
+        Rect::new(top, left, right - left, bottom - top)
+    }
 }
 
 impl From<Rect> for CGRect {
@@ -57,3 +352,303 @@ impl From<CGRect> for Rect {
         }
     }
 }
+
+/// Insets from each edge of a rect, in points - e.g, for padding content inside a container.
+///
+/// This is laid out like `NSEdgeInsets` and `UIEdgeInsets`, so it can be handed to the system
+/// as-is.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct EdgeInsets {
+    /// Inset from the top edge, in points.
+    pub top: f64,
+
+    /// Inset from the left edge, in points.
+    pub left: f64,
+
+    /// Inset from the bottom edge, in points.
+    pub bottom: f64,
+
+    /// Inset from the right edge, in points.
+    pub right: f64
+}
+
+impl EdgeInsets {
+    /// Returns a new `EdgeInsets` initialized with the values specified.
+    pub fn new(top: f64, left: f64, bottom: f64, right: f64) -> Self {
+        EdgeInsets {
+            top,
+            left,
+            bottom,
+            right
+        }
+    }
+
+    /// Returns insets of the same amount on every edge.
+    pub fn uniform(inset: f64) -> Self {
+        EdgeInsets::new(inset, inset, inset, inset)
+    }
+
+    /// Returns insets of `vertical` on the top and bottom edges, and `horizontal` on the left and
+    /// right ones.
+    pub fn symmetric(vertical: f64, horizontal: f64) -> Self {
+        EdgeInsets::new(vertical, horizontal, vertical, horizontal)
+    }
+
+    /// The total of the left and right insets.
+    pub fn horizontal(&self) -> f64 {
+        self.left + self.right
+    }
+
+    /// The total of the top and bottom insets.
+    pub fn vertical(&self) -> f64 {
+        self.top + self.bottom
+    }
+}
+
+/// An affine transform - some combination of translation, scaling and rotation - for points,
+/// sizes and rects, or for a view's layer.
+///
+/// A point `(x, y)` maps to `(a * x + c * y + tx, b * x + d * y + ty)`. Converts to and from
+/// `CGAffineTransform`.
+///
+/// Transforms are built up in the order they're applied:
+///
+/// ```rust
+/// use cacao::geometry::{Point, Transform};
+///
+/// // Scale by 2, then move 10 points right.
+/// let transform = Transform::scale(2., 2.).translated(10., 0.);
+/// assert_eq!(transform.apply_to_point(Point::new(1., 1.)), Point::new(12., 2.));
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    /// How far x moves in x.
+    pub a: f64,
+
+    /// How far x moves in y.
+    pub b: f64,
+
+    /// How far y moves in x.
+    pub c: f64,
+
+    /// How far y moves in y.
+    pub d: f64,
+
+    /// Translation in x.
+    pub tx: f64,
+
+    /// Translation in y.
+    pub ty: f64
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+impl Transform {
+    /// Returns the transform that leaves everything as-is.
+    pub fn identity() -> Self {
+        Transform {
+            a: 1.,
+            b: 0.,
+            c: 0.,
+            d: 1.,
+            tx: 0.,
+            ty: 0.
+        }
+    }
+
+    /// Returns a transform that moves everything by `tx` and `ty`.
+    pub fn translate(tx: f64, ty: f64) -> Self {
+        Transform {
+            tx,
+            ty,
+            ..Transform::identity()
+        }
+    }
+
+    /// Returns a transform that scales everything by `sx` and `sy`, around the origin.
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Transform {
+            a: sx,
+            d: sy,
+            ..Transform::identity()
+        }
+    }
+
+    /// Returns a transform that rotates everything by `radians` around the origin. With y
+    /// increasing downwards (see the module docs), positive angles rotate clockwise.
+    pub fn rotate(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+
+        Transform {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            tx: 0.,
+            ty: 0.
+        }
+    }
+
+    /// Whether this is the identity transform.
+    pub fn is_identity(&self) -> bool {
+        *self == Transform::identity()
+    }
+
+    /// Returns a transform that applies this one, then `other`.
+    pub fn then(&self, other: &Transform) -> Transform {
+        Transform {
+            a: self.a * other.a + self.b * other.c,
+            b: self.a * other.b + self.b * other.d,
+            c: self.c * other.a + self.d * other.c,
+            d: self.c * other.b + self.d * other.d,
+            tx: self.tx * other.a + self.ty * other.c + other.tx,
+            ty: self.tx * other.b + self.ty * other.d + other.ty
+        }
+    }
+
+    /// Returns a transform that applies this one, then moves everything by `tx` and `ty`.
+    pub fn translated(&self, tx: f64, ty: f64) -> Transform {
+        self.then(&Transform::translate(tx, ty))
+    }
+
+    /// Returns a transform that applies this one, then scales everything by `sx` and `sy`.
+    pub fn scaled(&self, sx: f64, sy: f64) -> Transform {
+        self.then(&Transform::scale(sx, sy))
+    }
+
+    /// Returns a transform that applies this one, then rotates everything by `radians`.
+    pub fn rotated(&self, radians: f64) -> Transform {
+        self.then(&Transform::rotate(radians))
+    }
+
+    /// Returns the transform that undoes this one, or `None` if it can't be undone (e.g, it
+    /// scales by zero).
+    pub fn inverted(&self) -> Option<Transform> {
+        let determinant = self.a * self.d - self.b * self.c;
+
+        if determinant == 0. {
+            return None;
+        }
+
+        Some(Transform {
+            a: self.d / determinant,
+            b: -self.b / determinant,
+            c: -self.c / determinant,
+            d: self.a / determinant,
+            tx: (self.c * self.ty - self.d * self.tx) / determinant,
+            ty: (self.b * self.tx - self.a * self.ty) / determinant
+        })
+    }
+
+    /// Applies this transform to a point.
+    pub fn apply_to_point(&self, point: Point) -> Point {
+        Point::new(
+            self.a * point.x + self.c * point.y + self.tx,
+            self.b * point.x + self.d * point.y + self.ty
+        )
+    }
+
+    /// Applies this transform to a size. Translation doesn't affect sizes, and the result can
+    /// have negative dimensions (e.g, after a rotation).
+    pub fn apply_to_size(&self, size: Size) -> Size {
+        Size::new(
+            self.a * size.width + self.c * size.height,
+            self.b * size.width + self.d * size.height
+        )
+    }
+
+    /// Applies this transform to a rect, returning the smallest rect that contains the result -
+    /// which, after a rotation, is larger than the rect itself.
+    pub fn apply_to_rect(&self, rect: &Rect) -> Rect {
+        let corners = [
+            self.apply_to_point(Point::new(rect.left, rect.top)),
+            self.apply_to_point(Point::new(rect.right(), rect.top)),
+            self.apply_to_point(Point::new(rect.left, rect.bottom())),
+            self.apply_to_point(Point::new(rect.right(), rect.bottom()))
+        ];
+
+        let left = corners.iter().map(|point| point.x).fold(f64::INFINITY, f64::min);
+        let top = corners.iter().map(|point| point.y).fold(f64::INFINITY, f64::min);
+        let right = corners.iter().map(|point| point.x).fold(f64::NEG_INFINITY, f64::max);
+        let bottom = corners.iter().map(|point| point.y).fold(f64::NEG_INFINITY, f64::max);
+
+        Rect::new(top, left, right - left, bottom - top)
+    }
+}
+
+impl From<Transform> for CGAffineTransform {
+    fn from(t: Transform) -> CGAffineTransform {
+        CGAffineTransform::new(
+            t.a as CGFloat,
+            t.b as CGFloat,
+            t.c as CGFloat,
+            t.d as CGFloat,
+            t.tx as CGFloat,
+            t.ty as CGFloat
+        )
+    }
+}
+
+impl From<CGAffineTransform> for Transform {
+    fn from(t: CGAffineTransform) -> Transform {
+        Transform {
+            a: t.a,
+            b: t.b,
+            c: t.c,
+            d: t.d,
+            tx: t.tx,
+            ty: t.ty
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect_operations() {
+        let rect = Rect::new(10., 20., 100., 50.);
+
+        assert_eq!(rect.center(), Point::new(70., 35.));
+        assert!(rect.contains(Point::new(20., 10.)));
+        assert!(!rect.contains(Point::new(120., 10.)));
+        assert!(rect.contains_rect(&Rect::new(20., 30., 10., 10.)));
+
+        let other = Rect::new(40., 100., 50., 50.);
+        assert_eq!(rect.intersection(&other), Some(Rect::new(40., 100., 20., 20.)));
+        assert_eq!(rect.union(&other), Rect::new(10., 20., 130., 80.));
+        assert_eq!(rect.intersection(&rect.offset(100., 0.)), None);
+
+        assert_eq!(rect.inset(EdgeInsets::symmetric(5., 10.)), Rect::new(15., 30., 80., 40.));
+        assert_eq!(
+            rect.divide(30., RectEdge::Right),
+            (Rect::new(10., 90., 30., 50.), Rect::new(10., 20., 70., 50.))
+        );
+
+        assert_eq!(Rect::new(0.3, 0.8, 10., 10.).integral(1.), Rect::new(0., 0., 11., 11.));
+        assert_eq!(Rect::new(0.3, 0.8, 10., 10.).integral(2.), Rect::new(0., 0.5, 10.5, 10.5));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let transform = Transform::rotate(std::f64::consts::FRAC_PI_2).translated(10., 0.);
+        let point = transform.apply_to_point(Point::new(1., 0.));
+        assert!((point.x - 10.).abs() < 1e-9 && (point.y - 1.).abs() < 1e-9);
+
+        let inverse = transform.inverted().unwrap();
+        let point = inverse.apply_to_point(transform.apply_to_point(Point::new(3., 4.)));
+        assert!((point.x - 3.).abs() < 1e-9 && (point.y - 4.).abs() < 1e-9);
+
+        assert_eq!(Transform::scale(0., 1.).inverted(), None);
+        assert_eq!(
+            Transform::scale(2., 3.).apply_to_rect(&Rect::new(1., 1., 2., 2.)),
+            Rect::new(3., 2., 4., 6.)
+        );
+    }
+}
@@ -13,10 +13,12 @@
 //! ```
 
 use core_graphics::base::CGFloat;
+use core_graphics::geometry::CGAffineTransform;
 
 use objc::{class, msg_send, sel, sel_impl};
 
 use crate::foundation::id;
+use crate::geometry::Transform;
 use crate::utils::properties::ObjcProperty;
 
 /// Represents a `CALayer`.
@@ -61,4 +63,20 @@ impl Layer {
             let _: () = msg_send![obj, setCornerRadius: radius as CGFloat];
         });
     }
+
+    /// Sets the affine transform applied to this layer's contents - e.g, to rotate or scale a
+    /// view without changing its frame.
+    pub fn set_transform(&self, transform: Transform) {
+        let transform: CGAffineTransform = transform.into();
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setAffineTransform: transform];
+        });
+    }
+
+    /// Returns the affine transform applied to this layer's contents.
+    pub fn transform(&self) -> Transform {
+        let transform: CGAffineTransform = self.objc.get(|obj| unsafe { msg_send![obj, affineTransform] });
+        transform.into()
+    }
 }
@@ -1,19 +1,51 @@
 use core_graphics::base::CGFloat;
 
-use objc::runtime::{Class, Object};
-use objc::{msg_send, sel, sel_impl};
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
 use objc_id::ShareId;
 
-use crate::foundation::id;
+use crate::foundation::{id, NO, YES};
 
 /// A wrapper for an animation proxy object in Cocoa that supports basic animations.
+///
+/// Offsets animate on their own. Priority and activation changes don't - the system animates the
+/// frames they lead to - so follow them with `ViewAnimatorProxy::layout_if_needed()` on a view
+/// that contains everything affected, inside the same `AnimationContext::run()`:
+///
+/// ```rust,no_run
+/// use cacao::appkit::{AnimationContext, TimingFunction};
+/// use cacao::layout::{Layout, LayoutConstraint, LayoutConstraintAnimatorProxy};
+/// use cacao::view::View;
+///
+/// let content = View::new();
+/// let sidebar = View::new();
+/// content.add_subview(&sidebar);
+///
+/// let collapsed = sidebar.width.constraint_equal_to_constant(0.);
+/// let expanded = sidebar.width.constraint_equal_to_constant(240.);
+/// LayoutConstraint::activate(&[collapsed.clone()]);
+///
+/// let content = content.animator.clone();
+/// let (collapsed, expanded) = (collapsed.animator.clone().unwrap(), expanded.animator.clone().unwrap());
+///
+/// AnimationContext::run(move |ctx| {
+///     ctx.set_timing_function(TimingFunction::EaseInOut);
+///     LayoutConstraintAnimatorProxy::swap(&[collapsed.clone()], &[expanded.clone()]);
+///     content.layout_if_needed();
+/// });
+/// ```
+///
+/// The first field is the constraint's `animator` proxy.
 #[derive(Clone, Debug)]
-pub struct LayoutConstraintAnimatorProxy(pub ShareId<Object>);
+pub struct LayoutConstraintAnimatorProxy(pub ShareId<Object>, ShareId<Object>);
 
 impl LayoutConstraintAnimatorProxy {
     /// Wraps and returns a proxy for animation of layout constraint values.
     pub fn new(proxy: id) -> Self {
-        Self(unsafe { ShareId::from_ptr(msg_send![proxy, animator]) })
+        let animator = unsafe { ShareId::from_ptr(msg_send![proxy, animator]) };
+        let constraint = unsafe { ShareId::from_ptr(proxy) };
+
+        Self(animator, constraint)
     }
 
     /// Sets the constant (usually referred to as `offset` in Cacao) value for the constraint being animated.
@@ -22,4 +54,46 @@ impl LayoutConstraintAnimatorProxy {
             let _: () = msg_send![&*self.0, setConstant: value];
         }
     }
+
+    /// Sets the priority for the constraint being animated, from 1 to 1000 (required).
+    ///
+    /// Note that the system doesn't allow switching an active constraint between required and
+    /// optional.
+    pub fn set_priority(&self, priority: f64) {
+        unsafe {
+            let p = priority as f32;
+            let _: () = msg_send![&*self.1, setPriority: p];
+        }
+    }
+
+    /// Sets whether the constraint being animated is active.
+    pub fn set_active(&self, active: bool) {
+        unsafe {
+            let _: () = msg_send![&*self.1, setActive:match active {
+                true => YES,
+                false => NO
+            }];
+        }
+    }
+
+    /// Deactivates one set of constraints and activates another, in that order - so the two sets
+    /// never conflict, even briefly.
+    pub fn swap(deactivate: &[LayoutConstraintAnimatorProxy], activate: &[LayoutConstraintAnimatorProxy]) {
+        Self::set_all_active(deactivate, false);
+        Self::set_all_active(activate, true);
+    }
+
+    /// Batch (de)activates the system constraints behind a set of proxies.
+    fn set_all_active(proxies: &[LayoutConstraintAnimatorProxy], active: bool) {
+        let ids: Vec<&Object> = proxies.iter().map(|proxy| &*proxy.1).collect();
+
+        unsafe {
+            let constraints: id = msg_send![class!(NSArray), arrayWithObjects:ids.as_ptr() count:ids.len()];
+
+            let _: () = match active {
+                true => msg_send![class!(NSLayoutConstraint), activateConstraints: constraints],
+                false => msg_send![class!(NSLayoutConstraint), deactivateConstraints: constraints]
+            };
+        }
+    }
 }
@@ -1,7 +1,7 @@
 use crate::foundation::{NSInteger, NSUInteger};
 
 /// Represents whether a layout is vertical or horizontal.
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum LayoutConstraintOrientation {
     /// Horizontal orientation.
     Horizontal,
@@ -25,8 +25,18 @@ impl From<NSInteger> for LayoutConstraintOrientation {
     }
 }
 
+impl From<LayoutConstraintOrientation> for NSInteger {
+    fn from(orientation: LayoutConstraintOrientation) -> Self {
+        match orientation {
+            LayoutConstraintOrientation::Horizontal => 0,
+            LayoutConstraintOrientation::Vertical => 1,
+            LayoutConstraintOrientation::Unknown(i) => i
+        }
+    }
+}
+
 /// Represents a relation between layout constraints. Used mostly internally.
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum LayoutRelation {
     /// Relation is less than or equal to another specified relation.
     LessThanOrEqual,
@@ -59,7 +69,7 @@ impl From<NSInteger> for LayoutRelation {
 /// Note that this only covers attributes that are shared across platforms. In general, this is enough
 /// to build apps that work everywhere - but if you need to specify something else, you can handle
 /// it yourself with the `Unknown` variant.
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum LayoutAttribute {
     /// The left side of the object’s alignment rectangle.
     Left,
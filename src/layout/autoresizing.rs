@@ -0,0 +1,53 @@
+//! Autoresizing masks, for frame-based layout. These tell a view how to adjust its frame when its
+//! superview is resized, without needing any constraints.
+
+use crate::foundation::NSUInteger;
+
+/// Which parts of a view's frame can change when its superview is resized. Anything not marked
+/// flexible stays fixed - so a view with no mask at all keeps its frame, relative to the top-left
+/// of its superview.
+///
+/// These are relative to the superview's coordinate system, which for Cacao views is flipped
+/// (see the `geometry` module) - `FlexibleTopMargin` is the distance from the top of the
+/// superview on both AppKit and UIKit.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AutoresizingMask {
+    /// The distance from the left edge of the superview can change.
+    FlexibleLeftMargin,
+
+    /// The width can change.
+    FlexibleWidth,
+
+    /// The distance from the right edge of the superview can change.
+    FlexibleRightMargin,
+
+    /// The distance from the top edge of the superview can change.
+    FlexibleTopMargin,
+
+    /// The height can change.
+    FlexibleHeight,
+
+    /// The distance from the bottom edge of the superview can change.
+    FlexibleBottomMargin
+}
+
+impl From<&AutoresizingMask> for NSUInteger {
+    fn from(mask: &AutoresizingMask) -> Self {
+        // `NSAutoresizingMaskOptions` and `UIViewAutoresizing` share values; the AppKit names
+        // refer to min/max y, which are top/bottom in a flipped superview.
+        match mask {
+            AutoresizingMask::FlexibleLeftMargin => 1 << 0,
+            AutoresizingMask::FlexibleWidth => 1 << 1,
+            AutoresizingMask::FlexibleRightMargin => 1 << 2,
+            AutoresizingMask::FlexibleTopMargin => 1 << 3,
+            AutoresizingMask::FlexibleHeight => 1 << 4,
+            AutoresizingMask::FlexibleBottomMargin => 1 << 5
+        }
+    }
+}
+
+impl From<AutoresizingMask> for NSUInteger {
+    fn from(mask: AutoresizingMask) -> Self {
+        (&mask).into()
+    }
+}
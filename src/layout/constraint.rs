@@ -10,17 +10,26 @@ use objc_id::ShareId;
 
 use crate::foundation::{id, NO, YES};
 
+use super::engine::EngineConstraint;
+use super::LayoutError;
+
 #[cfg(all(feature = "appkit", target_os = "macos"))]
 use super::LayoutConstraintAnimatorProxy;
 
 /// A wrapper for `NSLayoutConstraint`. This both acts as a central path through which to activate
 /// constraints, as well as a wrapper for layout constraints that are not axis bound (e.g, width or
 /// height).
+///
+/// Constraints created from the anchors of a `LayoutNode` are backed by a `LayoutEngine` rather
+/// than the system, but are otherwise used the same way.
 #[derive(Clone, Debug)]
 pub struct LayoutConstraint {
-    /// A shared pointer to the underlying view. Provided your view isn't dropped, this will always
-    /// be valid.
-    pub constraint: ShareId<Object>,
+    /// A shared pointer to the underlying constraint. Provided your view isn't dropped, this will
+    /// always be valid.
+    ///
+    /// Constraints that belong to a `LayoutEngine` have no system constraint, so this is `None`
+    /// for those; use the methods on this type instead.
+    pub constraint: Option<ShareId<Object>>,
 
     /// The offset used in computing this constraint.
     pub offset: f64,
@@ -31,65 +40,154 @@ pub struct LayoutConstraint {
     /// The priority used in computing this constraint.
     pub priority: f64,
 
-    /// An animator proxy that can be used inside animation contexts.
+    /// An animator proxy that can be used inside animation contexts, or `None` for constraints
+    /// that belong to a `LayoutEngine` (which have nothing for the system to animate).
     /// This is currently only supported on macOS with the `appkit` feature.
     #[cfg(all(feature = "appkit", target_os = "macos"))]
-    pub animator: LayoutConstraintAnimatorProxy
+    pub animator: Option<LayoutConstraintAnimatorProxy>,
+
+    /// The `LayoutEngine` constraint this wraps, if any.
+    pub(crate) engine: Option<EngineConstraint>
 }
 
 impl LayoutConstraint {
-    /// An internal method for wrapping existing constraints.
+    /// An internal method for wrapping existing constraints. The offset, multiplier and priority
+    /// are read from the constraint.
     pub(crate) fn new(object: id) -> Self {
+        let (offset, multiplier, priority) = unsafe {
+            let offset: CGFloat = msg_send![object, constant];
+            let multiplier: CGFloat = msg_send![object, multiplier];
+            let priority: f32 = msg_send![object, priority];
+
+            (offset as f64, multiplier as f64, priority as f64)
+        };
+
         LayoutConstraint {
             #[cfg(all(feature = "appkit", target_os = "macos"))]
-            animator: LayoutConstraintAnimatorProxy::new(object),
+            animator: Some(LayoutConstraintAnimatorProxy::new(object)),
 
-            constraint: unsafe { ShareId::from_ptr(object) },
-            offset: 0.0,
-            multiplier: 0.0,
-            priority: 0.0
+            constraint: Some(unsafe { ShareId::from_ptr(object) }),
+            offset,
+            multiplier,
+            priority,
+            engine: None
         }
     }
 
-    /// Sets the offset for this constraint.
-    pub fn offset<F: Into<f64>>(self, offset: F) -> Self {
-        let offset: f64 = offset.into();
-        unsafe {
-            let o = offset as CGFloat;
-            let _: () = msg_send![&*self.constraint, setConstant: o];
-        }
-
+    /// An internal method for wrapping constraints created by a `LayoutEngine`. These start out
+    /// required, as system constraints do.
+    pub(crate) fn with_engine(constraint: EngineConstraint, multiplier: f64, offset: f64) -> Self {
         LayoutConstraint {
             #[cfg(all(feature = "appkit", target_os = "macos"))]
-            animator: self.animator,
+            animator: None,
 
-            constraint: self.constraint,
-            offset: offset,
-            multiplier: self.multiplier,
-            priority: self.priority
+            constraint: None,
+            offset,
+            multiplier,
+            priority: 1000.0,
+            engine: Some(constraint)
         }
     }
 
+    /// Sets the offset for this constraint.
+    pub fn offset<F: Into<f64>>(self, offset: F) -> Self {
+        let offset: f64 = offset.into();
+        self.set_offset(offset);
+
+        LayoutConstraint { offset, ..self }
+    }
+
     /// Sets the offset of a borrowed constraint.
     pub fn set_offset<F: Into<f64>>(&self, offset: F) {
+        // Unsatisfiable constraints are left out of the layout, and reported by `LayoutEngine::check()`.
+        let _ = self.try_set_offset(offset);
+    }
+
+    /// Sets the offset of a borrowed constraint. For `LayoutEngine` constraints, this returns an
+    /// `UnsatisfiableConstraint` error if the new offset conflicts with the other active
+    /// constraints (which, as with `set_offset()`, leaves it out of the layout). System
+    /// constraints are solved later, so always return `Ok(())`; see `check_layout()` for those.
+    pub fn try_set_offset<F: Into<f64>>(&self, offset: F) -> Result<(), LayoutError> {
         let offset: f64 = offset.into();
 
-        unsafe {
-            let o = offset as CGFloat;
-            let _: () = msg_send![&*self.constraint, setConstant: o];
+        if let Some(constraint) = &self.engine {
+            return constraint.set_offset(offset);
+        }
+
+        if let Some(constraint) = &self.constraint {
+            unsafe {
+                let o = offset as CGFloat;
+                let _: () = msg_send![&**constraint, setConstant: o];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the priority for this constraint, from 1 to 1000 (required, the default). Accepts a
+    /// `LayoutPriority` or a plain number.
+    ///
+    /// Note that the system doesn't allow switching an active constraint between required and
+    /// optional, so set this before activating.
+    pub fn priority<F: Into<f64>>(self, priority: F) -> Self {
+        let priority: f64 = priority.into();
+        self.set_priority(priority);
+
+        LayoutConstraint { priority, ..self }
+    }
+
+    /// Sets the priority of a borrowed constraint, from 1 to 1000. Accepts a `LayoutPriority` or
+    /// a plain number.
+    ///
+    /// As with `priority()`, an active constraint can't be switched between required and
+    /// optional - but it can move freely between optional priorities.
+    pub fn set_priority<F: Into<f64>>(&self, priority: F) {
+        let _ = self.try_set_priority(priority);
+    }
+
+    /// Sets the priority of a borrowed constraint, returning an error if it's a `LayoutEngine`
+    /// constraint that can't be satisfied at its new priority. See `try_set_offset()`.
+    pub fn try_set_priority<F: Into<f64>>(&self, priority: F) -> Result<(), LayoutError> {
+        let priority: f64 = priority.into();
+
+        if let Some(constraint) = &self.engine {
+            return constraint.set_priority(priority);
         }
+
+        if let Some(constraint) = &self.constraint {
+            unsafe {
+                let p = priority as f32;
+                let _: () = msg_send![&**constraint, setPriority: p];
+            }
+        }
+
+        Ok(())
     }
 
     /// Set whether this constraint is active or not. If you're doing this across a batch of
     /// constraints, it's often more performant to batch-deactivate with
     /// `LayoutConstraint::deactivate()`.
     pub fn set_active(&self, active: bool) {
-        unsafe {
-            let _: () = msg_send![&*self.constraint, setActive:match active {
-                true => YES,
-                false => NO
-            }];
+        let _ = self.try_set_active(active);
+    }
+
+    /// Set whether this constraint is active or not, returning an error if it's a `LayoutEngine`
+    /// constraint that conflicts with the other active constraints. See `try_set_offset()`.
+    pub fn try_set_active(&self, active: bool) -> Result<(), LayoutError> {
+        if let Some(constraint) = &self.engine {
+            return constraint.set_active(active);
         }
+
+        if let Some(constraint) = &self.constraint {
+            unsafe {
+                let _: () = msg_send![&**constraint, setActive:match active {
+                    true => YES,
+                    false => NO
+                }];
+            }
+        }
+
+        Ok(())
     }
 
     /// Call this with your batch of constraints to activate them.
@@ -101,18 +199,34 @@ impl LayoutConstraint {
     //
     // I regret nothing, lol. If you have a better solution I'm all ears.
     pub fn activate(constraints: &[LayoutConstraint]) {
-        let ids: Vec<&Object> = constraints.into_iter().map(|constraint| &*constraint.constraint).collect();
-        unsafe {
-            let constraints: id = msg_send![class!(NSArray), arrayWithObjects:ids.as_ptr() count:ids.len()];
-            let _: () = msg_send![class!(NSLayoutConstraint), activateConstraints: constraints];
-        }
+        Self::set_all_active(constraints, true);
     }
 
     pub fn deactivate(constraints: &[LayoutConstraint]) {
-        let ids: Vec<&Object> = constraints.into_iter().map(|constraint| &*constraint.constraint).collect();
-        unsafe {
-            let constraints: id = msg_send![class!(NSArray), arrayWithObjects:ids.as_ptr() count:ids.len()];
-            let _: () = msg_send![class!(NSLayoutConstraint), deactivateConstraints: constraints];
+        Self::set_all_active(constraints, false);
+    }
+
+    /// Batch (de)activates system constraints, and (de)activates engine constraints one by one.
+    fn set_all_active(constraints: &[LayoutConstraint], active: bool) {
+        let ids: Vec<&Object> = constraints
+            .iter()
+            .filter_map(|constraint| constraint.constraint.as_deref())
+            .collect();
+
+        if !ids.is_empty() {
+            unsafe {
+                let constraints: id = msg_send![class!(NSArray), arrayWithObjects:ids.as_ptr() count:ids.len()];
+
+                let _: () = match active {
+                    true => msg_send![class!(NSLayoutConstraint), activateConstraints: constraints],
+                    false => msg_send![class!(NSLayoutConstraint), deactivateConstraints: constraints]
+                };
+            }
+        }
+
+        for constraint in constraints.iter().filter_map(|constraint| constraint.engine.as_ref()) {
+            // As with `set_active()`, conflicts are left out, and reported by `LayoutEngine::check()`.
+            let _ = constraint.set_active(active);
         }
     }
 }
@@ -0,0 +1,226 @@
+//! A debug-mode checker for the constraints the system is solving. The system only ever logs
+//! broken and ambiguous layouts to the console; this collects them as `LayoutError`s instead, so
+//! they can be asserted on, or surfaced however your app sees fit.
+
+use core_graphics::base::CGFloat;
+use core_graphics::geometry::CGRect;
+
+use objc::runtime::Object;
+use objc::{msg_send, sel, sel_impl};
+
+use crate::foundation::{id, nil, to_bool, NSArray, NSInteger, BOOL};
+use crate::layout::error::describe;
+use crate::layout::{Layout, LayoutAttribute, LayoutError, LayoutRelation};
+
+/// How far (in points) a constraint can be off before it counts as broken. Frames are rounded to
+/// pixels, so the two sides of a satisfied constraint can each be up to half a point off.
+const TOLERANCE: f64 = 1.;
+
+/// Lays out `view`, then checks it and its subviews for:
+///
+/// - Required constraints that the system had to break, because they conflict with others
+///   (`LayoutError::UnsatisfiableConstraint`).
+/// - Views whose constraints don't determine their frame (`LayoutError::AmbiguousLayout`).
+///
+/// Call this once the view is in a window. This walks the entire view hierarchy, so it's only
+/// meant for debug builds; in release builds it does nothing, and always returns `Ok(())`.
+///
+/// The system doesn't expose which constraints it broke, so this infers them: a required, active
+/// constraint counts as broken if the laid out frames are more than a point off satisfying it.
+/// That has a few blind spots:
+///
+/// - Constraints on attributes that can't be measured from a frame (e.g, baselines) are never
+///   reported.
+/// - A constraint the system broke, but that the frames happen to satisfy anyway, isn't reported.
+/// - If several required constraints conflict, every one the frames don't satisfy is reported -
+///   not only the one the system chose to break.
+///
+/// ```rust,no_run
+/// use cacao::layout::check_layout;
+/// use cacao::view::View;
+///
+/// let content = View::new();
+///
+/// if let Err(errors) = check_layout(&content) {
+///     for error in errors {
+///         eprintln!("{}", error);
+///     }
+/// }
+/// ```
+pub fn check_layout<V: Layout>(view: &V) -> Result<(), Vec<LayoutError>> {
+    if !cfg!(debug_assertions) {
+        return Ok(());
+    }
+
+    let view = view.get_from_backing_obj(|obj| obj as *const Object as id);
+
+    unsafe {
+        #[cfg(feature = "appkit")]
+        let _: () = msg_send![view, layoutSubtreeIfNeeded];
+
+        #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+        let _: () = msg_send![view, layoutIfNeeded];
+    }
+
+    let mut errors = Vec::new();
+    check_view(view, &mut errors);
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors)
+    }
+}
+
+/// Checks a view and, recursively, its subviews. Constraints are installed on the closest common
+/// ancestor of the items they relate, so this sees every active constraint in the hierarchy.
+fn check_view(view: id, errors: &mut Vec<LayoutError>) {
+    let (ambiguous, constraints, subviews) = unsafe {
+        let ambiguous: BOOL = msg_send![view, hasAmbiguousLayout];
+        let constraints = NSArray::retain(msg_send![view, constraints]);
+        let subviews = NSArray::retain(msg_send![view, subviews]);
+
+        (to_bool(ambiguous), constraints, subviews)
+    };
+
+    if ambiguous {
+        errors.push(LayoutError::AmbiguousLayout {
+            view: describe(unsafe { &*view })
+        });
+    }
+
+    for constraint in constraints.map(|constraint| constraint) {
+        if is_broken(constraint) {
+            errors.push(LayoutError::UnsatisfiableConstraint {
+                constraint: describe(unsafe { &*constraint })
+            });
+        }
+    }
+
+    for subview in subviews.map(|subview| subview) {
+        check_view(subview, errors);
+    }
+}
+
+/// Whether a required, active constraint doesn't hold for the current frames. The system only
+/// ever breaks required constraints when they can't be satisfied, so this is how those show up.
+fn is_broken(constraint: id) -> bool {
+    let (active, priority, relation, multiplier, constant) = unsafe {
+        let active: BOOL = msg_send![constraint, isActive];
+        let priority: f32 = msg_send![constraint, priority];
+        let relation: NSInteger = msg_send![constraint, relation];
+        let multiplier: CGFloat = msg_send![constraint, multiplier];
+        let constant: CGFloat = msg_send![constraint, constant];
+
+        (to_bool(active), priority, relation, multiplier, constant)
+    };
+
+    if !active || priority < 1000. {
+        return false;
+    }
+
+    let (first, second) = unsafe {
+        let first_item: id = msg_send![constraint, firstItem];
+        let first_attribute: NSInteger = msg_send![constraint, firstAttribute];
+        let second_item: id = msg_send![constraint, secondItem];
+        let second_attribute: NSInteger = msg_send![constraint, secondAttribute];
+
+        let second = match second_item == nil {
+            true => Some(0.),
+            false => value(second_item, second_attribute.into())
+        };
+
+        (value(first_item, first_attribute.into()), second)
+    };
+
+    // Baselines (and anything else we can't measure from a frame) are skipped.
+    let (first, second) = match (first, second) {
+        (Some(first), Some(second)) => (first, second),
+        _ => return false
+    };
+
+    let target = multiplier * second + constant;
+
+    match LayoutRelation::from(relation) {
+        LayoutRelation::Equal => (first - target).abs() > TOLERANCE,
+        LayoutRelation::LessThanOrEqual => first > target + TOLERANCE,
+        LayoutRelation::GreaterThanOrEqual => first < target - TOLERANCE,
+        LayoutRelation::Unknown(_) => false
+    }
+}
+
+/// Returns the value of an attribute for a view or layout guide, in window coordinates - with y
+/// increasing downwards and, for right-to-left layouts, leading and trailing increasing
+/// leftwards, as the constraint solver sees them.
+fn value(item: id, attribute: LayoutAttribute) -> Option<f64> {
+    let (rect, owning_view) = unsafe {
+        let is_guide: BOOL = msg_send![item, respondsToSelector: sel!(owningView)];
+
+        match to_bool(is_guide) {
+            true => {
+                let owning_view: id = msg_send![item, owningView];
+
+                #[cfg(feature = "appkit")]
+                let frame: CGRect = msg_send![item, frame];
+
+                #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+                let frame: CGRect = msg_send![item, layoutFrame];
+
+                let rect: CGRect = msg_send![owning_view, convertRect:frame toView:nil];
+                (rect, owning_view)
+            },
+
+            false => {
+                let frame: CGRect = msg_send![item, frame];
+                let alignment: CGRect = msg_send![item, alignmentRectForFrame: frame];
+                let superview: id = msg_send![item, superview];
+
+                let rect: CGRect = match superview == nil {
+                    true => alignment,
+                    false => msg_send![superview, convertRect:alignment toView:nil]
+                };
+
+                (rect, item)
+            }
+        }
+    };
+
+    let (x, width) = (rect.origin.x, rect.size.width);
+    let (y, height) = (rect.origin.y, rect.size.height);
+
+    // AppKit's window coordinates have y increasing upwards.
+    #[cfg(feature = "appkit")]
+    let top = -(y + height);
+
+    #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+    let top = y;
+
+    let (leading, trailing) = match is_right_to_left(owning_view) {
+        true => (-(x + width), -x),
+        false => (x, x + width)
+    };
+
+    match attribute {
+        LayoutAttribute::Left => Some(x),
+        LayoutAttribute::Right => Some(x + width),
+        LayoutAttribute::Leading => Some(leading),
+        LayoutAttribute::Trailing => Some(trailing),
+        LayoutAttribute::CenterX => Some(x + width / 2.),
+        LayoutAttribute::Top => Some(top),
+        LayoutAttribute::Bottom => Some(top + height),
+        LayoutAttribute::CenterY => Some(top + height / 2.),
+        LayoutAttribute::Width => Some(width),
+        LayoutAttribute::Height => Some(height),
+        _ => None
+    }
+}
+
+/// Whether a view lays out right-to-left.
+fn is_right_to_left(view: id) -> bool {
+    #[cfg(feature = "appkit")]
+    let direction: NSInteger = unsafe { msg_send![view, userInterfaceLayoutDirection] };
+
+    #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+    let direction: NSInteger = unsafe { msg_send![view, effectiveUserInterfaceLayoutDirection] };
+
+    direction == 1
+}
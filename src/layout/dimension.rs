@@ -6,6 +6,9 @@ use objc_id::ShareId;
 
 use crate::foundation::{id, nil, NSInteger};
 use crate::layout::constraint::LayoutConstraint;
+use crate::layout::engine::EngineAnchor;
+use crate::layout::error::{describe, uninitialized};
+use crate::layout::{AnchorPosition, LayoutError};
 
 use super::attributes::{LayoutAttribute, LayoutRelation};
 
@@ -25,7 +28,10 @@ pub enum LayoutAnchorDimension {
     Width(ShareId<Object>),
 
     /// Represents a Height anchor.
-    Height(ShareId<Object>)
+    Height(ShareId<Object>),
+
+    /// Represents an anchor on a `LayoutNode`, solved by a `LayoutEngine` rather than the system.
+    Engine(EngineAnchor)
 }
 
 impl Default for LayoutAnchorDimension {
@@ -46,87 +52,316 @@ impl LayoutAnchorDimension {
         Self::Height(unsafe { ShareId::from_ptr(msg_send![view, heightAnchor]) })
     }
 
-    /// Return a constraint equal to a constant value.
-    pub fn constraint_equal_to_constant(&self, constant: f64) -> LayoutConstraint {
-        if let Self::Width(obj) | Self::Height(obj) = self {
-            return LayoutConstraint::new(unsafe {
-                let value = constant as CGFloat;
-                msg_send![*obj, constraintEqualToConstant: value]
-            });
+    /// Describes this anchor and the view it belongs to, for errors. Returns `None` if it's
+    /// uninitialized.
+    fn description(&self) -> Option<String> {
+        match self {
+            Self::Uninitialized => None,
+            Self::Width(obj) | Self::Height(obj) => Some(describe(obj)),
+            Self::Engine(anchor) => Some(anchor.description())
         }
-
-        panic!("Attempted to create a constant constraint with an uninitialized anchor.");
     }
 
-    /// Return a constraint greater than or equal to a constant value.
-    pub fn constraint_greater_than_or_equal_to_constant(&self, constant: f64) -> LayoutConstraint {
-        if let Self::Width(obj) | Self::Height(obj) = self {
-            return LayoutConstraint::new(unsafe {
-                let value = constant as CGFloat;
-                msg_send![*obj, constraintGreaterThanOrEqualToConstant: value]
-            });
+    /// Boilerplate for handling constraints against a constant value.
+    fn constant_constraint_with<F>(
+        &self,
+        relation: LayoutRelation,
+        constant: f64,
+        handler: F
+    ) -> Result<LayoutConstraint, LayoutError>
+    where
+        F: Fn(&ShareId<Object>, CGFloat) -> id
+    {
+        match self {
+            Self::Engine(anchor) => anchor.constraint(relation, None, 1., constant),
+            Self::Width(obj) | Self::Height(obj) => Ok(LayoutConstraint::new(handler(obj, constant as CGFloat))),
+            Self::Uninitialized => Err(LayoutError::UninitializedAnchor {
+                axis: "dimension",
+                position: AnchorPosition::From,
+                other: None
+            })
         }
+    }
 
-        panic!("Attempted to create a constraint (>=) with an uninitialized anchor.");
+    /// Return a constraint equal to a constant value.
+    ///
+    /// Panics if this anchor is uninitialized; see `try_constraint_equal_to_constant()`.
+    pub fn constraint_equal_to_constant(&self, constant: f64) -> LayoutConstraint {
+        self.try_constraint_equal_to_constant(constant)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Return a constraint greater than or equal to a constant value.
+    ///
+    /// Panics if this anchor is uninitialized; see
+    /// `try_constraint_greater_than_or_equal_to_constant()`.
+    pub fn constraint_greater_than_or_equal_to_constant(&self, constant: f64) -> LayoutConstraint {
+        self.try_constraint_greater_than_or_equal_to_constant(constant)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Return a constraint less than or equal to a constant value.
+    ///
+    /// Panics if this anchor is uninitialized; see
+    /// `try_constraint_less_than_or_equal_to_constant()`.
     pub fn constraint_less_than_or_equal_to_constant(&self, constant: f64) -> LayoutConstraint {
-        if let Self::Width(obj) | Self::Height(obj) = self {
-            return LayoutConstraint::new(unsafe {
-                let value = constant as CGFloat;
-                msg_send![*obj, constraintLessThanOrEqualToConstant: value]
-            });
-        }
+        self.try_constraint_less_than_or_equal_to_constant(constant)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Return a constraint equal to a constant value, or an error if this anchor is
+    /// uninitialized.
+    pub fn try_constraint_equal_to_constant(&self, constant: f64) -> Result<LayoutConstraint, LayoutError> {
+        self.constant_constraint_with(LayoutRelation::Equal, constant, |obj, value| unsafe {
+            msg_send![*obj, constraintEqualToConstant: value]
+        })
+    }
 
-        panic!("Attempted to create a constraint (<=) with an uninitialized anchor.");
+    /// Return a constraint greater than or equal to a constant value, or an error if this anchor
+    /// is uninitialized.
+    pub fn try_constraint_greater_than_or_equal_to_constant(&self, constant: f64) -> Result<LayoutConstraint, LayoutError> {
+        self.constant_constraint_with(LayoutRelation::GreaterThanOrEqual, constant, |obj, value| unsafe {
+            msg_send![*obj, constraintGreaterThanOrEqualToConstant: value]
+        })
+    }
+
+    /// Return a constraint less than or equal to a constant value, or an error if this anchor is
+    /// uninitialized.
+    pub fn try_constraint_less_than_or_equal_to_constant(&self, constant: f64) -> Result<LayoutConstraint, LayoutError> {
+        self.constant_constraint_with(LayoutRelation::LessThanOrEqual, constant, |obj, value| unsafe {
+            msg_send![*obj, constraintLessThanOrEqualToConstant: value]
+        })
     }
 
-    /// Boilerplate for handling constraint construction and panic'ing with some more helpful
-    /// messages. The goal here is to make AutoLayout slightly easier to debug when things go
-    /// wrong.
-    fn constraint_with<F>(&self, anchor_to: &LayoutAnchorDimension, handler: F) -> LayoutConstraint
+    /// Boilerplate for handling constraint construction and returning some more helpful errors.
+    /// The goal here is to make AutoLayout slightly easier to debug when things go wrong.
+    fn constraint_with<F>(
+        &self,
+        anchor_to: &LayoutAnchorDimension,
+        relation: LayoutRelation,
+        handler: F
+    ) -> Result<LayoutConstraint, LayoutError>
     where
         F: Fn(&ShareId<Object>, &ShareId<Object>) -> id
     {
         match (self, anchor_to) {
+            (Self::Engine(from), Self::Engine(to)) => from.constraint(relation, Some(to), 1., 0.),
+
             (Self::Width(from), Self::Width(to))
             | (Self::Width(from), Self::Height(to))
             | (Self::Height(from), Self::Width(to))
-            | (Self::Height(from), Self::Height(to)) => LayoutConstraint::new(handler(from, to)),
-
-            (Self::Uninitialized, Self::Uninitialized) => {
-                panic!("Attempted to create constraints with an uninitialized \"from\" and \"to\" dimension anchor.");
-            },
+            | (Self::Height(from), Self::Height(to)) => Ok(LayoutConstraint::new(handler(from, to))),
 
-            (Self::Uninitialized, _) => {
-                panic!("Attempted to create constraints with an uninitialized \"from\" dimension anchor.");
+            (Self::Uninitialized, _) | (_, Self::Uninitialized) => {
+                Err(uninitialized("dimension", self.description(), anchor_to.description()))
             },
 
-            (_, Self::Uninitialized) => {
-                panic!("Attempted to create constraints with an uninitialized \"to\" dimension anchor.");
-            }
+            (Self::Engine(_), _) | (_, Self::Engine(_)) => Err(LayoutError::MismatchedEngines {
+                from: self.description().unwrap_or_default(),
+                to: anchor_to.description().unwrap_or_default()
+            })
         }
     }
 
     /// Return a constraint equal to another dimension anchor.
+    ///
+    /// Panics if either anchor is uninitialized; see `try_constraint_equal_to()`.
     pub fn constraint_equal_to(&self, anchor_to: &LayoutAnchorDimension) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
-            msg_send![*from, constraintEqualToAnchor:&**to]
-        })
+        self.try_constraint_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Return a constraint greater than or equal to another dimension anchor.
+    ///
+    /// Panics if either anchor is uninitialized; see `try_constraint_greater_than_or_equal_to()`.
     pub fn constraint_greater_than_or_equal_to(&self, anchor_to: &LayoutAnchorDimension) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
-            msg_send![*from, constraintGreaterThanOrEqualToAnchor:&**to]
-        })
+        self.try_constraint_greater_than_or_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Return a constraint less than or equal to another dimension anchor.
+    ///
+    /// Panics if either anchor is uninitialized; see `try_constraint_less_than_or_equal_to()`.
     pub fn constraint_less_than_or_equal_to(&self, anchor_to: &LayoutAnchorDimension) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
+        self.try_constraint_less_than_or_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Return a constraint equal to another dimension anchor, or an error if either anchor is
+    /// uninitialized.
+    pub fn try_constraint_equal_to(&self, anchor_to: &LayoutAnchorDimension) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::Equal, |from, to| unsafe {
+            msg_send![*from, constraintEqualToAnchor:&**to]
+        })
+    }
+
+    /// Return a constraint greater than or equal to another dimension anchor, or an error if
+    /// either anchor is uninitialized.
+    pub fn try_constraint_greater_than_or_equal_to(
+        &self,
+        anchor_to: &LayoutAnchorDimension
+    ) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::GreaterThanOrEqual, |from, to| unsafe {
+            msg_send![*from, constraintGreaterThanOrEqualToAnchor:&**to]
+        })
+    }
+
+    /// Return a constraint less than or equal to another dimension anchor, or an error if either
+    /// anchor is uninitialized.
+    pub fn try_constraint_less_than_or_equal_to(
+        &self,
+        anchor_to: &LayoutAnchorDimension
+    ) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::LessThanOrEqual, |from, to| unsafe {
             msg_send![*from, constraintLessThanOrEqualToAnchor:&**to]
         })
     }
+
+    /// Boilerplate for constraints against a multiple of another dimension anchor.
+    fn constraint_with_multiplier<F>(
+        &self,
+        anchor_to: &LayoutAnchorDimension,
+        relation: LayoutRelation,
+        multiplier: f64,
+        handler: F
+    ) -> Result<LayoutConstraint, LayoutError>
+    where
+        F: Fn(&ShareId<Object>, &ShareId<Object>, CGFloat) -> id
+    {
+        if let (Self::Engine(from), Self::Engine(to)) = (self, anchor_to) {
+            return from.constraint(relation, Some(to), multiplier, 0.);
+        }
+
+        let constraint = self.constraint_with(anchor_to, relation, |from, to| handler(from, to, multiplier as CGFloat))?;
+        Ok(LayoutConstraint {
+            multiplier,
+            ..constraint
+        })
+    }
+
+    /// Return a constraint equal to a multiple of another dimension anchor - e.g, for aspect
+    /// ratios.
+    ///
+    /// Panics if either anchor is uninitialized; see `try_constraint_equal_to_with_multiplier()`.
+    pub fn constraint_equal_to_with_multiplier(&self, anchor_to: &LayoutAnchorDimension, multiplier: f64) -> LayoutConstraint {
+        self.try_constraint_equal_to_with_multiplier(anchor_to, multiplier)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Return a constraint greater than or equal to a multiple of another dimension anchor.
+    ///
+    /// Panics if either anchor is uninitialized; see
+    /// `try_constraint_greater_than_or_equal_to_with_multiplier()`.
+    pub fn constraint_greater_than_or_equal_to_with_multiplier(
+        &self,
+        anchor_to: &LayoutAnchorDimension,
+        multiplier: f64
+    ) -> LayoutConstraint {
+        self.try_constraint_greater_than_or_equal_to_with_multiplier(anchor_to, multiplier)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Return a constraint less than or equal to a multiple of another dimension anchor.
+    ///
+    /// Panics if either anchor is uninitialized; see
+    /// `try_constraint_less_than_or_equal_to_with_multiplier()`.
+    pub fn constraint_less_than_or_equal_to_with_multiplier(
+        &self,
+        anchor_to: &LayoutAnchorDimension,
+        multiplier: f64
+    ) -> LayoutConstraint {
+        self.try_constraint_less_than_or_equal_to_with_multiplier(anchor_to, multiplier)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Return a constraint equal to a multiple of another dimension anchor, or an error if
+    /// either anchor is uninitialized.
+    pub fn try_constraint_equal_to_with_multiplier(
+        &self,
+        anchor_to: &LayoutAnchorDimension,
+        multiplier: f64
+    ) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with_multiplier(anchor_to, LayoutRelation::Equal, multiplier, |from, to, m| unsafe {
+            msg_send![*from, constraintEqualToAnchor:&**to multiplier:m]
+        })
+    }
+
+    /// Return a constraint greater than or equal to a multiple of another dimension anchor, or
+    /// an error if either anchor is uninitialized.
+    pub fn try_constraint_greater_than_or_equal_to_with_multiplier(
+        &self,
+        anchor_to: &LayoutAnchorDimension,
+        multiplier: f64
+    ) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with_multiplier(
+            anchor_to,
+            LayoutRelation::GreaterThanOrEqual,
+            multiplier,
+            |from, to, m| unsafe { msg_send![*from, constraintGreaterThanOrEqualToAnchor:&**to multiplier:m] }
+        )
+    }
+
+    /// Return a constraint less than or equal to a multiple of another dimension anchor, or an
+    /// error if either anchor is uninitialized.
+    pub fn try_constraint_less_than_or_equal_to_with_multiplier(
+        &self,
+        anchor_to: &LayoutAnchorDimension,
+        multiplier: f64
+    ) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with_multiplier(anchor_to, LayoutRelation::LessThanOrEqual, multiplier, |from, to, m| unsafe {
+            msg_send![*from, constraintLessThanOrEqualToAnchor:&**to multiplier:m]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutAnchorDimension;
+    use crate::layout::{AnchorPosition, LayoutEngine, LayoutError};
+
+    #[test]
+    fn test_uninitialized_anchors() {
+        let engine = LayoutEngine::new();
+        let node = engine.add_node(&engine.root());
+        let uninitialized = LayoutAnchorDimension::default();
+
+        let error = |position, other: Option<&str>| LayoutError::UninitializedAnchor {
+            axis: "dimension",
+            position,
+            other: other.map(String::from)
+        };
+
+        assert_eq!(
+            uninitialized.try_constraint_equal_to_constant(100.).unwrap_err(),
+            error(AnchorPosition::From, None)
+        );
+
+        assert_eq!(
+            uninitialized.try_constraint_less_than_or_equal_to_constant(100.).unwrap_err(),
+            error(AnchorPosition::From, None)
+        );
+
+        assert_eq!(
+            uninitialized
+                .try_constraint_equal_to(&LayoutAnchorDimension::Uninitialized)
+                .unwrap_err(),
+            error(AnchorPosition::Both, None)
+        );
+
+        assert_eq!(
+            uninitialized
+                .try_constraint_greater_than_or_equal_to_with_multiplier(&node.width, 2.)
+                .unwrap_err(),
+            error(AnchorPosition::From, Some("LayoutNode 1's Width anchor"))
+        );
+
+        assert_eq!(
+            node.height
+                .try_constraint_equal_to_with_multiplier(&uninitialized, 0.5)
+                .unwrap_err(),
+            error(AnchorPosition::To, Some("LayoutNode 1's Height anchor"))
+        );
+
+        assert!(node.width.try_constraint_equal_to_constant(100.).is_ok());
+    }
 }
@@ -0,0 +1,362 @@
+//! Support for the `constraints!` macro, which builds sets of constraints from a compact,
+//! equation-like syntax.
+//!
+//! Anchors are type checked against each other at compile time: relating an X anchor to a Y or
+//! dimension anchor (or a position to a constant) won't build, rather than panicking at runtime.
+
+use crate::geometry::EdgeInsets;
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY, LayoutConstraint, LayoutRelation};
+
+/// Picks between three constraint constructors, based on the relation.
+fn by_relation<F, G, H>(relation: LayoutRelation, equal: F, greater: G, less: H) -> LayoutConstraint
+where
+    F: FnOnce() -> LayoutConstraint,
+    G: FnOnce() -> LayoutConstraint,
+    H: FnOnce() -> LayoutConstraint
+{
+    match relation {
+        LayoutRelation::Equal => equal(),
+        LayoutRelation::GreaterThanOrEqual => greater(),
+        LayoutRelation::LessThanOrEqual => less(),
+        LayoutRelation::Unknown(relation) => panic!("Attempted to create a constraint with an unknown relation ({}).", relation)
+    }
+}
+
+/// Implemented for everything an anchor of type `A` can be constrained to: anchors of the same
+/// type and, for dimension anchors, constants.
+pub trait ConstraintTarget<A> {
+    /// Returns a constraint of `anchor <relation> self + constant`.
+    fn constraint_from(&self, anchor: &A, relation: LayoutRelation, constant: f64) -> LayoutConstraint;
+}
+
+impl ConstraintTarget<LayoutAnchorX> for LayoutAnchorX {
+    fn constraint_from(&self, anchor: &LayoutAnchorX, relation: LayoutRelation, constant: f64) -> LayoutConstraint {
+        by_relation(
+            relation,
+            || anchor.constraint_equal_to(self),
+            || anchor.constraint_greater_than_or_equal_to(self),
+            || anchor.constraint_less_than_or_equal_to(self)
+        )
+        .offset(constant)
+    }
+}
+
+impl ConstraintTarget<LayoutAnchorY> for LayoutAnchorY {
+    fn constraint_from(&self, anchor: &LayoutAnchorY, relation: LayoutRelation, constant: f64) -> LayoutConstraint {
+        by_relation(
+            relation,
+            || anchor.constraint_equal_to(self),
+            || anchor.constraint_greater_than_or_equal_to(self),
+            || anchor.constraint_less_than_or_equal_to(self)
+        )
+        .offset(constant)
+    }
+}
+
+impl ConstraintTarget<LayoutAnchorDimension> for LayoutAnchorDimension {
+    fn constraint_from(&self, anchor: &LayoutAnchorDimension, relation: LayoutRelation, constant: f64) -> LayoutConstraint {
+        by_relation(
+            relation,
+            || anchor.constraint_equal_to(self),
+            || anchor.constraint_greater_than_or_equal_to(self),
+            || anchor.constraint_less_than_or_equal_to(self)
+        )
+        .offset(constant)
+    }
+}
+
+impl ConstraintTarget<LayoutAnchorDimension> for f64 {
+    fn constraint_from(&self, anchor: &LayoutAnchorDimension, relation: LayoutRelation, constant: f64) -> LayoutConstraint {
+        let value = self + constant;
+
+        by_relation(
+            relation,
+            || anchor.constraint_equal_to_constant(value),
+            || anchor.constraint_greater_than_or_equal_to_constant(value),
+            || anchor.constraint_less_than_or_equal_to_constant(value)
+        )
+    }
+}
+
+impl ConstraintTarget<LayoutAnchorDimension> for i32 {
+    fn constraint_from(&self, anchor: &LayoutAnchorDimension, relation: LayoutRelation, constant: f64) -> LayoutConstraint {
+        f64::from(*self).constraint_from(anchor, relation, constant)
+    }
+}
+
+/// Insets for `pin_edges` in the `constraints!` macro, as `(top, leading, bottom, trailing)`.
+/// Insets are measured inwards, so positive values always shrink the pinned view.
+pub trait ConstraintInsets {
+    /// Returns the insets, as `(top, leading, bottom, trailing)`.
+    fn insets(&self) -> (f64, f64, f64, f64);
+}
+
+impl ConstraintInsets for f64 {
+    fn insets(&self) -> (f64, f64, f64, f64) {
+        (*self, *self, *self, *self)
+    }
+}
+
+impl ConstraintInsets for i32 {
+    fn insets(&self) -> (f64, f64, f64, f64) {
+        f64::from(*self).insets()
+    }
+}
+
+impl ConstraintInsets for EdgeInsets {
+    /// Left and right insets are applied to the leading and trailing edges, as the rest of
+    /// `pin_edges` is.
+    fn insets(&self) -> (f64, f64, f64, f64) {
+        (self.top, self.left, self.bottom, self.right)
+    }
+}
+
+/// Used by `constraints!`: `anchor <relation> to + constant`.
+#[doc(hidden)]
+pub fn relate<A, T>(anchor: &A, relation: LayoutRelation, to: &T, constant: f64) -> LayoutConstraint
+where
+    T: ConstraintTarget<A> + ?Sized
+{
+    to.constraint_from(anchor, relation, constant)
+}
+
+/// Used by `constraints!`: `anchor <relation> to * multiplier + constant`. Only dimension anchors
+/// support multipliers.
+#[doc(hidden)]
+pub fn relate_scaled(
+    anchor: &LayoutAnchorDimension,
+    relation: LayoutRelation,
+    to: &LayoutAnchorDimension,
+    multiplier: f64,
+    constant: f64
+) -> LayoutConstraint {
+    by_relation(
+        relation,
+        || anchor.constraint_equal_to_with_multiplier(to, multiplier),
+        || anchor.constraint_greater_than_or_equal_to_with_multiplier(to, multiplier),
+        || anchor.constraint_less_than_or_equal_to_with_multiplier(to, multiplier)
+    )
+    .offset(constant)
+}
+
+/// Builds a `Vec<LayoutConstraint>` from a compact, equation-like syntax. Pass the result to
+/// `LayoutConstraint::activate()`.
+///
+/// Each entry is one of:
+///
+/// - `a.anchor == b.anchor`, optionally followed by `+ constant` or `- constant`. `<=` and `>=`
+///   work as well.
+/// - `a.width == constant` for dimension anchors (`width` and `height`).
+/// - `a.width == 2. * b.height` or `a.width == b.height * 2.` for dimension anchors, again with
+///   an optional `+`/`- constant`. Handy for aspect ratios.
+/// - `pin_edges(a, to: b)` or `pin_edges(a, to: b, insets)`, which pins the top, leading,
+///   trailing and bottom anchors of `a` to those of `b`, inset by `insets` (anything that
+///   implements `ConstraintInsets`, e.g a number or `EdgeInsets`).
+/// - `center(a, in: b)`, which centers `a` in `b` on both axes.
+///
+/// Any relation can be followed by `@ priority` to make it optional.
+///
+/// Constants and multipliers need to be a single token - a literal, a variable, or an expression
+/// wrapped in parentheses.
+///
+/// Anchors (and the views passed to `pin_edges` and `center`) need to be a path of field names,
+/// like `view.top` or `self.content.safe_layout_guide.top`. Anything else - a method call such
+/// as `scroll_view.layout_margins_guide().top`, or an index - is rejected, so bind it to a
+/// variable first:
+///
+/// ```rust,no_run
+/// use cacao::constraints;
+/// use cacao::layout::LayoutConstraint;
+/// use cacao::scrollview::ScrollView;
+/// use cacao::view::View;
+///
+/// let scroll_view = ScrollView::new();
+/// let label = View::new();
+/// let margins = scroll_view.layout_margins_guide();
+///
+/// LayoutConstraint::activate(&constraints![
+///     label.top == margins.top,
+///     label.leading == margins.leading
+/// ]);
+/// ```
+///
+/// ```rust,no_run
+/// use cacao::constraints;
+/// use cacao::layout::LayoutConstraint;
+/// use cacao::view::View;
+///
+/// let content = View::new();
+/// let sidebar = View::new();
+/// let preview = View::new();
+/// let spacing = 16.;
+///
+/// LayoutConstraint::activate(&constraints![
+///     sidebar.top == content.top,
+///     sidebar.leading == content.leading,
+///     sidebar.bottom == content.bottom,
+///     sidebar.width >= 200.,
+///     sidebar.width == 300. @ 250,
+///
+///     preview.leading == sidebar.trailing + spacing,
+///     preview.trailing <= content.trailing - (spacing * 2.),
+///     preview.height == preview.width * 0.75,
+///     preview.center_y == content.center_y
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! constraints {
+    (@items $list:ident;) => {};
+
+    (@items $list:ident; pin_edges($($a:ident).+, to: $($b:ident).+) $(, $($rest:tt)*)?) => {
+        $crate::constraints!(@items $list; pin_edges($($a).+, to: $($b).+, 0.) $(, $($rest)*)?);
+    };
+
+    (@items $list:ident; pin_edges($($a:ident).+, to: $($b:ident).+, $insets:expr) $(, $($rest:tt)*)?) => {
+        let (top, leading, bottom, trailing) = $crate::layout::ConstraintInsets::insets(&$insets);
+        $list.push($($a).+.top.constraint_equal_to(&$($b).+.top).offset(top));
+        $list.push($($a).+.leading.constraint_equal_to(&$($b).+.leading).offset(leading));
+        $list.push($($a).+.trailing.constraint_equal_to(&$($b).+.trailing).offset(-trailing));
+        $list.push($($a).+.bottom.constraint_equal_to(&$($b).+.bottom).offset(-bottom));
+        $crate::constraints!(@items $list; $($($rest)*)?);
+    };
+
+    (@items $list:ident; center($($a:ident).+, in: $($b:ident).+) $(, $($rest:tt)*)?) => {
+        $list.push($($a).+.center_x.constraint_equal_to(&$($b).+.center_x));
+        $list.push($($a).+.center_y.constraint_equal_to(&$($b).+.center_y));
+        $crate::constraints!(@items $list; $($($rest)*)?);
+    };
+
+    (@items $list:ident; $($rest:tt)+) => {
+        $crate::constraints!(@lhs $list [] $($rest)+);
+    };
+
+    // Collects the left hand side, up to the relation.
+    (@lhs $list:ident [$($lhs:tt)*] == $($rest:tt)+) => {
+        $crate::constraints!(@rhs $list [$($lhs)*] Equal [] $($rest)+);
+    };
+
+    (@lhs $list:ident [$($lhs:tt)*] >= $($rest:tt)+) => {
+        $crate::constraints!(@rhs $list [$($lhs)*] GreaterThanOrEqual [] $($rest)+);
+    };
+
+    (@lhs $list:ident [$($lhs:tt)*] <= $($rest:tt)+) => {
+        $crate::constraints!(@rhs $list [$($lhs)*] LessThanOrEqual [] $($rest)+);
+    };
+
+    (@lhs $list:ident [$($lhs:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::constraints!(@lhs $list [$($lhs)* $next] $($rest)*);
+    };
+
+    (@lhs $list:ident [$($lhs:tt)*]) => {
+        compile_error!(concat!("Expected `==`, `<=` or `>=` in constraint: ", stringify!($($lhs)*)));
+    };
+
+    // Collects the right hand side, up to the priority or the next entry.
+    (@rhs $list:ident [$($lhs:tt)*] $relation:ident [$($rhs:tt)*] @ $priority:tt $(, $($rest:tt)*)?) => {
+        $list.push($crate::constraints!(@constraint [$($lhs)*] $relation $($rhs)*).priority($priority));
+        $crate::constraints!(@items $list; $($($rest)*)?);
+    };
+
+    (@rhs $list:ident [$($lhs:tt)*] $relation:ident [$($rhs:tt)*] , $($rest:tt)*) => {
+        $list.push($crate::constraints!(@constraint [$($lhs)*] $relation $($rhs)*));
+        $crate::constraints!(@items $list; $($rest)*);
+    };
+
+    (@rhs $list:ident [$($lhs:tt)*] $relation:ident [$($rhs:tt)*]) => {
+        $list.push($crate::constraints!(@constraint [$($lhs)*] $relation $($rhs)*));
+    };
+
+    (@rhs $list:ident [$($lhs:tt)*] $relation:ident [$($rhs:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::constraints!(@rhs $list [$($lhs)*] $relation [$($rhs)* $next] $($rest)*);
+    };
+
+    // Builds a single constraint from both sides.
+    (@constraint [$($a:ident).+] $relation:ident $m:tt * $($b:ident).+ $(+ $c:tt)?) => {
+        $crate::constraints!(@scaled [$($a).+] $relation [$($b).+] $m, 0. $(+ f64::from($c))?)
+    };
+
+    (@constraint [$($a:ident).+] $relation:ident $m:tt * $($b:ident).+ - $c:tt) => {
+        $crate::constraints!(@scaled [$($a).+] $relation [$($b).+] $m, -f64::from($c))
+    };
+
+    (@constraint [$($a:ident).+] $relation:ident $($b:ident).+ * $m:tt $(+ $c:tt)?) => {
+        $crate::constraints!(@scaled [$($a).+] $relation [$($b).+] $m, 0. $(+ f64::from($c))?)
+    };
+
+    (@constraint [$($a:ident).+] $relation:ident $($b:ident).+ * $m:tt - $c:tt) => {
+        $crate::constraints!(@scaled [$($a).+] $relation [$($b).+] $m, -f64::from($c))
+    };
+
+    (@constraint [$($a:ident).+] $relation:ident $($b:ident).+ $(+ $c:tt)?) => {
+        $crate::layout::relate(&$($a).+, $crate::layout::LayoutRelation::$relation, &$($b).+, 0. $(+ f64::from($c))?)
+    };
+
+    (@constraint [$($a:ident).+] $relation:ident $($b:ident).+ - $c:tt) => {
+        $crate::layout::relate(&$($a).+, $crate::layout::LayoutRelation::$relation, &$($b).+, -f64::from($c))
+    };
+
+    (@constraint [$($a:ident).+] $relation:ident $c:tt) => {
+        $crate::layout::relate(&$($a).+, $crate::layout::LayoutRelation::$relation, &$c, 0.)
+    };
+
+    (@constraint [$($lhs:tt)*] $relation:ident $($rhs:tt)*) => {
+        compile_error!(concat!("Unsupported constraint: ", stringify!($($lhs)* $relation $($rhs)*)))
+    };
+
+    (@scaled [$($a:ident).+] $relation:ident [$($b:ident).+] $m:tt, $constant:expr) => {
+        $crate::layout::relate_scaled(
+            &$($a).+,
+            $crate::layout::LayoutRelation::$relation,
+            &$($b).+,
+            f64::from($m),
+            $constant
+        )
+    };
+
+    ($($entries:tt)*) => {{
+        let mut constraints: Vec<$crate::layout::LayoutConstraint> = Vec::new();
+        $crate::constraints!(@items constraints; $($entries)*);
+        constraints
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::{LayoutConstraint, LayoutEngine};
+
+    #[test]
+    fn test_constraints_macro() {
+        let engine = LayoutEngine::new();
+        engine.set_size(400., 300.);
+
+        let root = engine.root();
+        let content = engine.add_node(&root);
+        let thumbnail = engine.add_node(&content);
+        let badge = engine.add_node(&content);
+        let spacing = 8.;
+
+        let constraints = constraints![
+            pin_edges(content, to: root, 20),
+            thumbnail.top == content.top + spacing,
+            thumbnail.leading == content.leading,
+            thumbnail.width == 100.,
+            thumbnail.height == 0.5 * thumbnail.width + (spacing * 2.),
+            thumbnail.width >= 150 @ 500,
+            center(badge, in: content),
+            badge.width == content.width * 0.25 - 10,
+            badge.height <= badge.width,
+        ];
+
+        assert_eq!(constraints.len(), 13);
+        LayoutConstraint::activate(&constraints);
+
+        let frame = content.frame();
+        assert_eq!((frame.top, frame.left, frame.width, frame.height), (20., 20., 360., 260.));
+
+        let frame = thumbnail.frame();
+        assert_eq!((frame.top, frame.left, frame.width, frame.height), (8., 0., 100., 66.));
+
+        let frame = badge.frame();
+        assert_eq!((frame.left, frame.width), (140., 80.));
+    }
+}
@@ -0,0 +1,723 @@
+//! A pure-Rust layout engine, for evaluating constraints without AppKit or UIKit.
+//!
+//! `LayoutEngine` manages a tree of `LayoutNode`s, each of which exposes the same anchors that
+//! views do (`top`, `leading`, `width`, and so on). Constraints between those anchors are built
+//! with the usual `constraint_*` methods and activated with `LayoutConstraint::activate()` or
+//! `set_active()` - but instead of being handed to the system, they're solved here, using the
+//! Cassowary algorithm that AppKit and UIKit are built on.
+//!
+//! This makes layouts unit-testable, usable on platforms where native autolayout support is weak
+//! (e.g, GNUstep), and debuggable outside of Xcode. Once solved, apply the frames to your views
+//! with `Layout::set_frame()`.
+//!
+//! A few notes on semantics:
+//!
+//! - Frames use a top-left origin, with y increasing downwards, and are relative to the parent
+//!   node (matching `Rect` and flipped views).
+//! - Leading and trailing anchors are resolved left-to-right.
+//! - Priorities range from 1 to 1000, where 1000 is required, like `NSLayoutPriority`. Optional
+//!   constraints are weighted exponentially by priority, which approximates (but isn't quite) the
+//!   strict ordering the system solver uses.
+//! - As with the system solver, activating a required constraint that conflicts with the active
+//!   ones leaves it out of the layout, rather than failing. `LayoutEngine::check()` reports these,
+//!   as do the `try_*` methods on `LayoutConstraint`, and they're retried whenever another
+//!   constraint is deactivated or changed.
+//! - Active constraints stay in the engine until they're deactivated, even if every
+//!   `LayoutConstraint` for them has been dropped (as with views and their constraints).
+//!   Inactive ones are removed once their last `LayoutConstraint` is dropped.
+//!
+//! ```rust
+//! use cacao::layout::{LayoutConstraint, LayoutEngine};
+//!
+//! let engine = LayoutEngine::new();
+//! engine.set_size(800., 600.);
+//!
+//! let root = engine.root();
+//! let sidebar = engine.add_node(&root);
+//!
+//! LayoutConstraint::activate(&[
+//!     sidebar.top.constraint_equal_to(&root.top),
+//!     sidebar.leading.constraint_equal_to(&root.leading),
+//!     sidebar.bottom.constraint_equal_to(&root.bottom),
+//!     sidebar.width.constraint_equal_to_constant(200.)
+//! ]);
+//!
+//! let frame = sidebar.frame();
+//! assert_eq!((frame.left, frame.width, frame.height), (0., 200., 600.));
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::geometry::Rect;
+use crate::layout::constraint::LayoutConstraint;
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY, LayoutAttribute, LayoutError, LayoutRelation};
+
+pub(crate) mod solver;
+use solver::{ConstraintId, Expression, Relation, Solver, SolverError, Variable, REQUIRED};
+
+/// How strongly the root node holds on to the size set with `LayoutEngine::set_size()`: stronger
+/// than any optional constraint, but still below required.
+const ROOT_SIZE_STRENGTH: f64 = 1.0e8;
+
+/// Maps an `NSLayoutPriority`-style value (1 - 1000) to a solver strength.
+fn strength(priority: f64) -> f64 {
+    match priority >= 1000. {
+        true => REQUIRED,
+        false => 10f64.powf(priority.max(0.) * 6. / 1000.)
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    parent: Option<usize>,
+    children: Vec<usize>,
+    left: Variable,
+    top: Variable,
+    width: Variable,
+    height: Variable
+}
+
+/// The definition of a constraint, kept around so it can be re-added to the solver when its
+/// offset or priority changes.
+#[derive(Debug)]
+struct Spec {
+    first: (usize, LayoutAttribute),
+    relation: LayoutRelation,
+    second: Option<(usize, LayoutAttribute)>,
+    multiplier: f64,
+    offset: f64,
+    priority: f64,
+    active: bool,
+
+    /// The solver's handle for this constraint, if it's active and satisfiable.
+    solved: Option<ConstraintId>
+}
+
+impl Spec {
+    /// Describes this constraint in equation form, for errors.
+    fn description(&self) -> String {
+        let relation = match self.relation {
+            LayoutRelation::LessThanOrEqual => "<=",
+            LayoutRelation::GreaterThanOrEqual => ">=",
+            _ => "=="
+        };
+
+        let mut description = format!("LayoutNode {}'s {:?} {}", self.first.0, self.first.1, relation);
+
+        if let Some((node, attribute)) = self.second {
+            description += &format!(" {} * LayoutNode {}'s {:?} +", self.multiplier, node, attribute);
+        }
+
+        format!("{} {} (priority {})", description, self.offset, self.priority)
+    }
+}
+
+#[derive(Debug)]
+struct Engine {
+    solver: Solver,
+    nodes: Vec<Node>,
+
+    /// Constraint definitions, indexed by id. Slots are emptied when a constraint is removed, and
+    /// reused (via `free`) for the next one that's added.
+    constraints: Vec<Option<Spec>>,
+    free: Vec<usize>
+}
+
+impl Engine {
+    fn new() -> Self {
+        let mut engine = Engine {
+            solver: Solver::new(),
+            nodes: Vec::new(),
+            constraints: Vec::new(),
+            free: Vec::new()
+        };
+
+        let root = engine.add_node(None);
+        let (left, top, width, height) = {
+            let node = &engine.nodes[root];
+            (node.left, node.top, node.width, node.height)
+        };
+
+        // These are all valid on an empty solver, so there's nothing to handle here.
+        for variable in [left, top].iter() {
+            let _ = engine
+                .solver
+                .add_constraint(&Expression::default().term(*variable, 1.), Relation::Equal, REQUIRED);
+        }
+
+        for variable in [width, height].iter() {
+            let _ = engine.solver.add_edit_variable(*variable, ROOT_SIZE_STRENGTH);
+        }
+
+        engine
+    }
+
+    fn add_node(&mut self, parent: Option<usize>) -> usize {
+        let id = self.nodes.len();
+
+        self.nodes.push(Node {
+            parent,
+            children: Vec::new(),
+            left: self.solver.new_variable(),
+            top: self.solver.new_variable(),
+            width: self.solver.new_variable(),
+            height: self.solver.new_variable()
+        });
+
+        if let Some(parent) = parent {
+            self.nodes[parent].children.push(id);
+        }
+
+        id
+    }
+
+    /// Returns the expression for an attribute of a node, in root coordinates.
+    fn expression(&self, node: usize, attribute: LayoutAttribute) -> Expression {
+        let node = &self.nodes[node];
+        let expression = Expression::default();
+
+        match attribute {
+            LayoutAttribute::Left | LayoutAttribute::Leading => expression.term(node.left, 1.),
+            LayoutAttribute::Right | LayoutAttribute::Trailing => expression.term(node.left, 1.).term(node.width, 1.),
+            LayoutAttribute::CenterX => expression.term(node.left, 1.).term(node.width, 0.5),
+            LayoutAttribute::Top => expression.term(node.top, 1.),
+            LayoutAttribute::Bottom => expression.term(node.top, 1.).term(node.height, 1.),
+            LayoutAttribute::CenterY => expression.term(node.top, 1.).term(node.height, 0.5),
+            LayoutAttribute::Width => expression.term(node.width, 1.),
+            LayoutAttribute::Height => expression.term(node.height, 1.),
+            attribute => panic!("LayoutEngine doesn't support {:?} constraints.", attribute)
+        }
+    }
+
+    fn add_constraint(&mut self, spec: Spec) -> usize {
+        match self.free.pop() {
+            Some(id) => {
+                self.constraints[id] = Some(spec);
+                id
+            },
+
+            None => {
+                self.constraints.push(Some(spec));
+                self.constraints.len() - 1
+            }
+        }
+    }
+
+    /// Removes an inactive constraint, freeing its slot. Active constraints are kept, since
+    /// they're still part of the layout.
+    fn remove_constraint(&mut self, id: usize) {
+        if let Some(false) = self.constraints[id].as_ref().map(|spec| spec.active) {
+            self.constraints[id] = None;
+            self.free.push(id);
+        }
+    }
+
+    fn spec(&self, id: usize) -> &Spec {
+        self.constraints[id]
+            .as_ref()
+            .expect("LayoutEngine constraints are only removed once nothing refers to them")
+    }
+
+    fn spec_mut(&mut self, id: usize) -> &mut Spec {
+        self.constraints[id]
+            .as_mut()
+            .expect("LayoutEngine constraints are only removed once nothing refers to them")
+    }
+
+    /// Adds a constraint to the solver, if it's active and not already there.
+    fn solve(&mut self, id: usize) -> Result<(), SolverError> {
+        let spec = match &self.constraints[id] {
+            Some(spec) if spec.active && spec.solved.is_none() => spec,
+            _ => return Ok(())
+        };
+
+        // first <relation> multiplier * second + offset, rearranged to compare with zero.
+        let mut expression = self.expression(spec.first.0, spec.first.1);
+        expression.constant -= spec.offset;
+
+        if let Some((node, attribute)) = spec.second {
+            expression = expression.add(&self.expression(node, attribute), -spec.multiplier);
+        }
+
+        let relation = match spec.relation {
+            LayoutRelation::LessThanOrEqual => Relation::LessThanOrEqual,
+            LayoutRelation::GreaterThanOrEqual => Relation::GreaterThanOrEqual,
+            _ => Relation::Equal
+        };
+
+        let solved = self.solver.add_constraint(&expression, relation, strength(spec.priority))?;
+        self.spec_mut(id).solved = Some(solved);
+        Ok(())
+    }
+
+    /// Removes a constraint from the solver, returning whether it was there.
+    fn unsolve(&mut self, id: usize) -> bool {
+        match self.spec_mut(id).solved.take() {
+            Some(solved) => {
+                // This can only fail for unknown constraints, and we only track ones the solver knows.
+                let _ = self.solver.remove_constraint(solved);
+                true
+            },
+
+            None => false
+        }
+    }
+
+    /// Tries to add every active constraint that was left out of the layout - which is worth
+    /// doing whenever a constraint they might have conflicted with is removed or changed.
+    fn retry_unsolved(&mut self) {
+        for id in 0..self.constraints.len() {
+            // Anything that still conflicts stays out, and is reported by `check()`.
+            let _ = self.solve(id);
+        }
+    }
+
+    fn set_active(&mut self, id: usize, active: bool) -> Result<(), SolverError> {
+        self.spec_mut(id).active = active;
+
+        match active {
+            true => self.solve(id),
+            false => {
+                if self.unsolve(id) {
+                    self.retry_unsolved();
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Applies `update` to a constraint's definition, then re-solves it. The updated constraint
+    /// goes back in first, so it takes precedence over any left out before.
+    fn update<F: FnOnce(&mut Spec)>(&mut self, id: usize, update: F) -> Result<(), SolverError> {
+        let removed = self.unsolve(id);
+        update(self.spec_mut(id));
+        let result = self.solve(id);
+
+        if removed {
+            self.retry_unsolved();
+        }
+
+        result
+    }
+
+    fn frame(&self, node: usize) -> Rect {
+        let n = &self.nodes[node];
+        let (mut top, mut left) = (self.solver.value(n.top), self.solver.value(n.left));
+
+        if let Some(parent) = n.parent {
+            top -= self.solver.value(self.nodes[parent].top);
+            left -= self.solver.value(self.nodes[parent].left);
+        }
+
+        Rect::new(top, left, self.solver.value(n.width), self.solver.value(n.height))
+    }
+}
+
+/// A pure-Rust constraint solver, for laying out a tree of `LayoutNode`s. See the module
+/// documentation for details.
+///
+/// This is cheap to clone; clones refer to the same engine.
+#[derive(Clone)]
+pub struct LayoutEngine(Arc<Mutex<Engine>>);
+
+impl Default for LayoutEngine {
+    fn default() -> Self {
+        LayoutEngine::new()
+    }
+}
+
+impl LayoutEngine {
+    /// Returns a new engine, with a zero-sized root node.
+    pub fn new() -> Self {
+        LayoutEngine(Arc::new(Mutex::new(Engine::new())))
+    }
+
+    /// Returns the root node. Its origin is always zero, and its size is set by `set_size()`.
+    pub fn root(&self) -> LayoutNode {
+        LayoutNode::new(self, 0)
+    }
+
+    /// Sets the size of the root node - e.g, to the size of the window you're laying out.
+    pub fn set_size(&self, width: f64, height: f64) {
+        let mut engine = self.0.lock().unwrap();
+        let (width_variable, height_variable) = (engine.nodes[0].width, engine.nodes[0].height);
+
+        // The root's size variables are always being edited, so this can't fail.
+        let _ = engine.solver.suggest_value(width_variable, width);
+        let _ = engine.solver.suggest_value(height_variable, height);
+    }
+
+    /// Adds a new node as a child of `parent`. Until constraints are activated against it, its
+    /// frame is zero.
+    pub fn add_node(&self, parent: &LayoutNode) -> LayoutNode {
+        let id = self.0.lock().unwrap().add_node(Some(parent.id));
+        LayoutNode::new(self, id)
+    }
+
+    /// Returns an `UnsatisfiableConstraint` error for each active constraint that conflicts with
+    /// the others, and so was left out of the layout.
+    pub fn check(&self) -> Result<(), Vec<LayoutError>> {
+        let errors: Vec<LayoutError> = self
+            .0
+            .lock()
+            .unwrap()
+            .constraints
+            .iter()
+            .flatten()
+            .filter(|spec| spec.active && spec.solved.is_none())
+            .map(|spec| LayoutError::UnsatisfiableConstraint {
+                constraint: spec.description()
+            })
+            .collect();
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors)
+        }
+    }
+}
+
+impl fmt::Debug for LayoutEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let engine = self.0.lock().unwrap();
+
+        f.debug_struct("LayoutEngine")
+            .field("nodes", &engine.nodes.len())
+            .field("constraints", &engine.constraints.iter().flatten().count())
+            .finish()
+    }
+}
+
+/// A node in a `LayoutEngine`'s tree - the engine's equivalent of a view. Constrain it via its
+/// anchors, exactly as you would a view, and read the result back with `frame()`.
+#[derive(Clone)]
+pub struct LayoutNode {
+    engine: LayoutEngine,
+    id: usize,
+
+    /// A pointer to the top layout anchor.
+    pub top: LayoutAnchorY,
+
+    /// A pointer to the leading layout anchor.
+    pub leading: LayoutAnchorX,
+
+    /// A pointer to the left layout anchor.
+    pub left: LayoutAnchorX,
+
+    /// A pointer to the trailing layout anchor.
+    pub trailing: LayoutAnchorX,
+
+    /// A pointer to the right layout anchor.
+    pub right: LayoutAnchorX,
+
+    /// A pointer to the bottom layout anchor.
+    pub bottom: LayoutAnchorY,
+
+    /// A pointer to the width layout anchor.
+    pub width: LayoutAnchorDimension,
+
+    /// A pointer to the height layout anchor.
+    pub height: LayoutAnchorDimension,
+
+    /// A pointer to the center X layout anchor.
+    pub center_x: LayoutAnchorX,
+
+    /// A pointer to the center Y layout anchor.
+    pub center_y: LayoutAnchorY
+}
+
+impl LayoutNode {
+    fn new(engine: &LayoutEngine, id: usize) -> Self {
+        let anchor = |attribute| EngineAnchor {
+            engine: engine.clone(),
+            node: id,
+            attribute
+        };
+
+        LayoutNode {
+            engine: engine.clone(),
+            id,
+            top: LayoutAnchorY::Engine(anchor(LayoutAttribute::Top)),
+            leading: LayoutAnchorX::Engine(anchor(LayoutAttribute::Leading)),
+            left: LayoutAnchorX::Engine(anchor(LayoutAttribute::Left)),
+            trailing: LayoutAnchorX::Engine(anchor(LayoutAttribute::Trailing)),
+            right: LayoutAnchorX::Engine(anchor(LayoutAttribute::Right)),
+            bottom: LayoutAnchorY::Engine(anchor(LayoutAttribute::Bottom)),
+            width: LayoutAnchorDimension::Engine(anchor(LayoutAttribute::Width)),
+            height: LayoutAnchorDimension::Engine(anchor(LayoutAttribute::Height)),
+            center_x: LayoutAnchorX::Engine(anchor(LayoutAttribute::CenterX)),
+            center_y: LayoutAnchorY::Engine(anchor(LayoutAttribute::CenterY))
+        }
+    }
+
+    /// Returns the solved frame for this node, relative to its parent.
+    pub fn frame(&self) -> Rect {
+        self.engine.0.lock().unwrap().frame(self.id)
+    }
+
+    /// Returns this node's parent, or `None` for the root node.
+    pub fn parent(&self) -> Option<LayoutNode> {
+        let parent = self.engine.0.lock().unwrap().nodes[self.id].parent;
+        parent.map(|parent| LayoutNode::new(&self.engine, parent))
+    }
+
+    /// Returns this node's children, in the order they were added.
+    pub fn children(&self) -> Vec<LayoutNode> {
+        let children = self.engine.0.lock().unwrap().nodes[self.id].children.clone();
+        children
+            .into_iter()
+            .map(|child| LayoutNode::new(&self.engine, child))
+            .collect()
+    }
+}
+
+impl fmt::Debug for LayoutNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayoutNode")
+            .field("id", &self.id)
+            .field("frame", &self.frame())
+            .finish()
+    }
+}
+
+/// An anchor on a `LayoutNode`. These are created for you as the fields of a node; you shouldn't
+/// need to create or inspect them yourself.
+#[derive(Clone)]
+pub struct EngineAnchor {
+    engine: LayoutEngine,
+    node: usize,
+    attribute: LayoutAttribute
+}
+
+impl EngineAnchor {
+    /// The attribute of the node this anchor refers to.
+    pub(crate) fn attribute(&self) -> LayoutAttribute {
+        self.attribute
+    }
+
+    /// Describes this anchor, for errors.
+    pub(crate) fn description(&self) -> String {
+        format!("LayoutNode {}'s {:?} anchor", self.node, self.attribute)
+    }
+
+    /// Creates an (inactive) constraint of `self <relation> to * multiplier + constant`, or of
+    /// `self <relation> constant` if there's no anchor to relate to.
+    pub(crate) fn constraint(
+        &self,
+        relation: LayoutRelation,
+        to: Option<&EngineAnchor>,
+        multiplier: f64,
+        constant: f64
+    ) -> Result<LayoutConstraint, LayoutError> {
+        if let Some(to) = to {
+            if !Arc::ptr_eq(&self.engine.0, &to.engine.0) {
+                return Err(LayoutError::MismatchedEngines {
+                    from: self.description(),
+                    to: to.description()
+                });
+            }
+        }
+
+        let id = self.engine.0.lock().unwrap().add_constraint(Spec {
+            first: (self.node, self.attribute),
+            relation,
+            second: to.map(|to| (to.node, to.attribute)),
+            multiplier,
+            offset: constant,
+            priority: 1000.,
+            active: false,
+            solved: None
+        });
+
+        Ok(LayoutConstraint::with_engine(
+            EngineConstraint(Arc::new(Handle {
+                engine: self.engine.clone(),
+                id
+            })),
+            multiplier,
+            constant
+        ))
+    }
+}
+
+impl fmt::Debug for EngineAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EngineAnchor")
+            .field("node", &self.node)
+            .field("attribute", &self.attribute)
+            .finish()
+    }
+}
+
+/// A handle to a constraint that lives in a `LayoutEngine`. Clones share the same constraint,
+/// which is removed from the engine once the last one is dropped (unless it's active).
+#[derive(Clone)]
+pub(crate) struct EngineConstraint(Arc<Handle>);
+
+struct Handle {
+    engine: LayoutEngine,
+    id: usize
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        // The engine is never locked while user code runs, but don't panic in drop if a panic
+        // elsewhere poisoned it.
+        if let Ok(mut engine) = self.engine.0.lock() {
+            engine.remove_constraint(self.id);
+        }
+    }
+}
+
+impl EngineConstraint {
+    /// Runs `handler` against the engine. If the solver rejects the constraint, it's been left
+    /// out of the layout, and this returns the same error `LayoutEngine::check()` would.
+    fn with_engine<F: FnOnce(&mut Engine, usize) -> Result<(), SolverError>>(&self, handler: F) -> Result<(), LayoutError> {
+        let mut engine = self.0.engine.0.lock().unwrap();
+        let id = self.0.id;
+
+        handler(&mut engine, id).map_err(|_| LayoutError::UnsatisfiableConstraint {
+            constraint: engine.spec(id).description()
+        })
+    }
+
+    pub fn set_offset(&self, offset: f64) -> Result<(), LayoutError> {
+        self.with_engine(|engine, id| engine.update(id, |spec| spec.offset = offset))
+    }
+
+    pub fn set_priority(&self, priority: f64) -> Result<(), LayoutError> {
+        self.with_engine(|engine, id| engine.update(id, |spec| spec.priority = priority))
+    }
+
+    pub fn set_active(&self, active: bool) -> Result<(), LayoutError> {
+        self.with_engine(|engine, id| engine.set_active(id, active))
+    }
+}
+
+impl fmt::Debug for EngineConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let engine = self.0.engine.0.lock().unwrap();
+        let spec = engine.spec(self.0.id);
+
+        f.debug_struct("EngineConstraint")
+            .field("first", &spec.first)
+            .field("relation", &spec.relation)
+            .field("second", &spec.second)
+            .field("offset", &spec.offset)
+            .field("priority", &spec.priority)
+            .field("active", &spec.active)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LayoutEngine, LayoutNode};
+    use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY, LayoutConstraint};
+
+    #[test]
+    fn test_engine_types_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<LayoutEngine>();
+        assert_send_sync::<LayoutNode>();
+        assert_send_sync::<LayoutConstraint>();
+        assert_send_sync::<LayoutAnchorX>();
+        assert_send_sync::<LayoutAnchorY>();
+        assert_send_sync::<LayoutAnchorDimension>();
+    }
+
+    #[test]
+    fn test_frames_priorities_and_updates() {
+        let engine = LayoutEngine::new();
+        engine.set_size(400., 300.);
+
+        let root = engine.root();
+        let content = engine.add_node(&root);
+        let button = engine.add_node(&content);
+
+        let inset = content.top.constraint_equal_to(&root.top).offset(20.);
+        let preferred_width = button.width.constraint_equal_to_constant(500.).priority(500.);
+
+        LayoutConstraint::activate(&[
+            inset.clone(),
+            content.leading.constraint_equal_to(&root.leading).offset(20.),
+            content.trailing.constraint_equal_to(&root.trailing).offset(-20.),
+            content.bottom.constraint_equal_to(&root.bottom).offset(-20.),
+            button.center_x.constraint_equal_to(&content.center_x),
+            button.bottom.constraint_equal_to(&content.bottom).offset(-10.),
+            button.height.constraint_equal_to_constant(30.),
+            button.width.constraint_less_than_or_equal_to(&content.width),
+            preferred_width.clone()
+        ]);
+
+        let frame = content.frame();
+        assert_eq!((frame.top, frame.left, frame.width, frame.height), (20., 20., 360., 260.));
+
+        // The optional width gives way to the required one, and frames are parent-relative.
+        let frame = button.frame();
+        assert_eq!((frame.top, frame.left, frame.width, frame.height), (220., 0., 360., 30.));
+
+        preferred_width.set_offset(100.);
+        inset.set_offset(40.);
+        engine.set_size(600., 300.);
+
+        let frame = button.frame();
+        assert_eq!((frame.top, frame.left, frame.width, frame.height), (200., 230., 100., 30.));
+        assert_eq!(content.frame().height, 240.);
+
+        // Conflicting required constraints are left out, rather than breaking the layout.
+        let conflict = button.height.constraint_equal_to_constant(50.);
+        conflict.set_active(true);
+        assert_eq!(button.frame().height, 30.);
+
+        let errors = engine.check().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("LayoutNode 2's Height == 50"));
+
+        conflict.set_active(false);
+        assert!(engine.check().is_ok());
+
+        LayoutConstraint::deactivate(&[inset]);
+        assert_eq!(content.children().len(), 1);
+    }
+
+    #[test]
+    fn test_conflicts_are_retried_and_removed() {
+        let engine = LayoutEngine::new();
+        let root = engine.root();
+        let node = engine.add_node(&root);
+
+        let narrow = node.width.constraint_equal_to_constant(100.);
+        let wide = node.width.constraint_equal_to_constant(200.);
+        narrow.set_active(true);
+
+        let error = wide.try_set_active(true).unwrap_err();
+        assert!(error.to_string().contains("LayoutNode 1's Width == 200"));
+        assert_eq!(node.frame().width, 100.);
+
+        // Removing the conflict lets the rejected constraint back in.
+        narrow.set_active(false);
+        assert_eq!(node.frame().width, 200.);
+        assert!(engine.check().is_ok());
+
+        // As does changing it so that it no longer conflicts.
+        narrow.set_active(true);
+        assert!(engine.check().is_err());
+        assert!(narrow.try_set_offset(200.).is_ok());
+        assert!(engine.check().is_ok());
+
+        // Inactive constraints are removed once they're dropped; active ones are kept.
+        LayoutConstraint::deactivate(&[narrow.clone()]);
+        drop(narrow);
+        assert_eq!(format!("{:?}", engine), "LayoutEngine { nodes: 2, constraints: 1 }");
+
+        drop(wide);
+        assert_eq!(node.frame().width, 200.);
+
+        node.height.constraint_equal_to_constant(50.);
+        assert_eq!(format!("{:?}", engine), "LayoutEngine { nodes: 2, constraints: 1 }");
+    }
+}
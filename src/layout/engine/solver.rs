@@ -0,0 +1,745 @@
+//! An incremental simplex solver for linear constraint systems, implementing the Cassowary
+//! algorithm (Badros, Borning & Stuckey, 2001) - the same approach AppKit and UIKit use under the
+//! hood. This follows the structure of the Kiwi implementation fairly closely, so if you're
+//! debugging something in here, the Kiwi sources make for a good reference.
+//!
+//! Constraints are of the form `expression <relation> 0`, with a strength: required constraints
+//! must hold, while optional ones are satisfied as well as possible, weighted by strength.
+
+use std::collections::{BTreeMap, HashMap};
+use std::error;
+use std::fmt;
+
+/// The strength of a constraint that must be satisfied.
+pub(crate) const REQUIRED: f64 = 1_001_001_000.0;
+
+const EPSILON: f64 = 1.0e-8;
+
+fn near_zero(value: f64) -> bool {
+    value.abs() < EPSILON
+}
+
+/// A variable the solver computes a value for.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub(crate) struct Variable(usize);
+
+/// A handle to a constraint that's been added to a `Solver`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ConstraintId(usize);
+
+/// How an expression relates to zero.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Relation {
+    LessThanOrEqual,
+    Equal,
+    GreaterThanOrEqual
+}
+
+/// A linear expression: a sum of weighted variables, plus a constant.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Expression {
+    pub terms: Vec<(Variable, f64)>,
+    pub constant: f64
+}
+
+impl Expression {
+    /// Returns an expression holding just `constant`.
+    pub fn constant(constant: f64) -> Self {
+        Expression {
+            terms: Vec::new(),
+            constant
+        }
+    }
+
+    /// Adds `coefficient * variable` to this expression.
+    pub fn term(mut self, variable: Variable, coefficient: f64) -> Self {
+        self.terms.push((variable, coefficient));
+        self
+    }
+
+    /// Adds all of `other`, scaled by `coefficient`, to this expression.
+    pub fn add(mut self, other: &Expression, coefficient: f64) -> Self {
+        self.terms
+            .extend(other.terms.iter().map(|(variable, value)| (*variable, value * coefficient)));
+        self.constant += other.constant * coefficient;
+        self
+    }
+}
+
+/// Errors that can occur when modifying a `Solver`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum SolverError {
+    /// A required constraint conflicts with the required constraints already added.
+    UnsatisfiableConstraint,
+
+    /// The constraint handle isn't known to this solver.
+    UnknownConstraint,
+
+    /// The variable is already being edited.
+    DuplicateEditVariable,
+
+    /// The variable isn't being edited.
+    UnknownEditVariable,
+
+    /// Edit variables can't be required.
+    BadRequiredStrength,
+
+    /// The solver ended up in a state it shouldn't be able to reach.
+    Internal(&'static str)
+}
+
+impl fmt::Display for SolverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolverError::UnsatisfiableConstraint => write!(f, "The constraint can't be satisfied"),
+            SolverError::UnknownConstraint => write!(f, "Unknown constraint"),
+            SolverError::DuplicateEditVariable => write!(f, "The variable is already being edited"),
+            SolverError::UnknownEditVariable => write!(f, "The variable isn't being edited"),
+            SolverError::BadRequiredStrength => write!(f, "Edit variables can't be required"),
+            SolverError::Internal(message) => write!(f, "Internal solver error: {}", message)
+        }
+    }
+}
+
+impl error::Error for SolverError {}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SymbolKind {
+    Invalid,
+    External,
+    Slack,
+    Error,
+    Dummy
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Symbol(usize, SymbolKind);
+
+impl Symbol {
+    fn invalid() -> Self {
+        Symbol(0, SymbolKind::Invalid)
+    }
+
+    fn kind(&self) -> SymbolKind {
+        self.1
+    }
+
+    fn is_pivotable(&self) -> bool {
+        matches!(self.1, SymbolKind::Slack | SymbolKind::Error)
+    }
+}
+
+/// A row of the tableau. Cells are kept in a `BTreeMap` so that pivoting is deterministic, which
+/// in turn keeps the layout of under-constrained systems stable between runs.
+#[derive(Clone, Debug, Default)]
+struct Row {
+    constant: f64,
+    cells: BTreeMap<Symbol, f64>
+}
+
+impl Row {
+    fn new(constant: f64) -> Self {
+        Row {
+            constant,
+            cells: BTreeMap::new()
+        }
+    }
+
+    fn add(&mut self, value: f64) -> f64 {
+        self.constant += value;
+        self.constant
+    }
+
+    fn insert_symbol(&mut self, symbol: Symbol, coefficient: f64) {
+        let value = self.cells.entry(symbol).or_insert(0.0);
+        *value += coefficient;
+
+        if near_zero(*value) {
+            self.cells.remove(&symbol);
+        }
+    }
+
+    fn insert_row(&mut self, other: &Row, coefficient: f64) {
+        self.constant += other.constant * coefficient;
+
+        for (symbol, value) in &other.cells {
+            self.insert_symbol(*symbol, value * coefficient);
+        }
+    }
+
+    fn remove(&mut self, symbol: Symbol) {
+        self.cells.remove(&symbol);
+    }
+
+    fn reverse_sign(&mut self) {
+        self.constant = -self.constant;
+
+        for value in self.cells.values_mut() {
+            *value = -*value;
+        }
+    }
+
+    /// Solves the row for `symbol`, which must be present in it.
+    fn solve_for(&mut self, symbol: Symbol) {
+        let coefficient = -1.0 / self.cells.remove(&symbol).unwrap_or(1.0);
+        self.constant *= coefficient;
+
+        for value in self.cells.values_mut() {
+            *value *= coefficient;
+        }
+    }
+
+    /// Solves the row `lhs = row` for `rhs`.
+    fn solve_for_symbols(&mut self, lhs: Symbol, rhs: Symbol) {
+        self.insert_symbol(lhs, -1.0);
+        self.solve_for(rhs);
+    }
+
+    fn coefficient_for(&self, symbol: Symbol) -> f64 {
+        self.cells.get(&symbol).copied().unwrap_or(0.0)
+    }
+
+    /// Replaces `symbol` in this row with `row`. Returns whether anything changed.
+    fn substitute(&mut self, symbol: Symbol, row: &Row) -> bool {
+        match self.cells.remove(&symbol) {
+            Some(coefficient) => {
+                self.insert_row(row, coefficient);
+                true
+            },
+
+            None => false
+        }
+    }
+}
+
+/// The symbols that were introduced for a constraint, which are needed to remove it again.
+#[derive(Copy, Clone, Debug)]
+struct Tag {
+    marker: Symbol,
+    other: Symbol
+}
+
+#[derive(Clone, Debug)]
+struct ConstraintInfo {
+    tag: Tag,
+    strength: f64
+}
+
+#[derive(Clone, Debug)]
+struct EditInfo {
+    tag: Tag,
+    constraint: ConstraintId,
+    constant: f64
+}
+
+/// Which row `optimize()` should work against.
+#[derive(Copy, Clone, Debug)]
+enum Objective {
+    Main,
+    Artificial
+}
+
+/// The solver itself. Add constraints, then read variable values back with `value()`.
+#[derive(Debug, Default)]
+pub(crate) struct Solver {
+    next_variable: usize,
+    next_symbol: usize,
+    next_constraint: usize,
+    constraints: HashMap<ConstraintId, ConstraintInfo>,
+    rows: BTreeMap<Symbol, Row>,
+    variables: HashMap<Variable, Symbol>,
+    edits: HashMap<Variable, EditInfo>,
+    infeasible: Vec<Symbol>,
+    objective: Row,
+    artificial: Option<Row>
+}
+
+impl Solver {
+    /// Returns a new, empty solver.
+    pub fn new() -> Self {
+        Solver::default()
+    }
+
+    /// Creates a new variable.
+    pub fn new_variable(&mut self) -> Variable {
+        self.next_variable += 1;
+        Variable(self.next_variable)
+    }
+
+    /// Returns the current value of `variable`. Variables that aren't part of any constraint are
+    /// always zero.
+    pub fn value(&self, variable: Variable) -> f64 {
+        let value = self
+            .variables
+            .get(&variable)
+            .and_then(|symbol| self.rows.get(symbol))
+            .map(|row| row.constant)
+            .unwrap_or(0.0);
+
+        // Avoid handing back -0.0, which is confusing to look at when debugging.
+        match near_zero(value) {
+            true => 0.0,
+            false => value
+        }
+    }
+
+    fn new_symbol(&mut self, kind: SymbolKind) -> Symbol {
+        self.next_symbol += 1;
+        Symbol(self.next_symbol, kind)
+    }
+
+    /// Adds the constraint `expression <relation> 0`. Strengths are clamped to `REQUIRED`; a
+    /// required constraint that conflicts with the existing required constraints is rejected with
+    /// `SolverError::UnsatisfiableConstraint`, and leaves the solver as it was.
+    pub fn add_constraint(
+        &mut self,
+        expression: &Expression,
+        relation: Relation,
+        strength: f64
+    ) -> Result<ConstraintId, SolverError> {
+        let strength = strength.clamp(0.0, REQUIRED);
+        let (mut row, tag) = self.create_row(expression, relation, strength);
+        let mut subject = choose_subject(&row, &tag);
+
+        if subject.kind() == SymbolKind::Invalid && row.cells.keys().all(|symbol| symbol.kind() == SymbolKind::Dummy) {
+            if !near_zero(row.constant) {
+                return Err(SolverError::UnsatisfiableConstraint);
+            }
+
+            subject = tag.marker;
+        }
+
+        if subject.kind() == SymbolKind::Invalid {
+            // Solving with an artificial variable pivots the tableau around, so keep a copy to
+            // roll back to if the row turns out to be unsatisfiable.
+            let (rows, objective) = (self.rows.clone(), self.objective.clone());
+
+            if !self.add_with_artificial_variable(&row)? {
+                self.rows = rows;
+                self.objective = objective;
+                self.infeasible.clear();
+                return Err(SolverError::UnsatisfiableConstraint);
+            }
+        } else {
+            row.solve_for(subject);
+            self.substitute(subject, &row);
+            self.rows.insert(subject, row);
+        }
+
+        self.next_constraint += 1;
+        let id = ConstraintId(self.next_constraint);
+        self.constraints.insert(id, ConstraintInfo { tag, strength });
+        self.optimize(Objective::Main)?;
+
+        Ok(id)
+    }
+
+    /// Removes a previously added constraint.
+    pub fn remove_constraint(&mut self, id: ConstraintId) -> Result<(), SolverError> {
+        let info = self.constraints.remove(&id).ok_or(SolverError::UnknownConstraint)?;
+        let tag = info.tag;
+
+        // Remove the error weights from the objective before pivoting the marker out.
+        for symbol in [tag.marker, tag.other].iter() {
+            if symbol.kind() == SymbolKind::Error {
+                match self.rows.get(symbol) {
+                    Some(row) => {
+                        let row = row.clone();
+                        self.objective.insert_row(&row, -info.strength);
+                    },
+
+                    None => self.objective.insert_symbol(*symbol, -info.strength)
+                }
+            }
+        }
+
+        if self.rows.remove(&tag.marker).is_none() {
+            let leaving = self
+                .marker_leaving_symbol(tag.marker)
+                .ok_or(SolverError::Internal("failed to find a leaving row"))?;
+
+            let mut row = self.rows.remove(&leaving).unwrap_or_default();
+            row.solve_for_symbols(leaving, tag.marker);
+            self.substitute(tag.marker, &row);
+        }
+
+        self.optimize(Objective::Main)
+    }
+
+    /// Marks `variable` as editable, so that its value can be suggested with `suggest_value()`.
+    pub fn add_edit_variable(&mut self, variable: Variable, strength: f64) -> Result<(), SolverError> {
+        if self.edits.contains_key(&variable) {
+            return Err(SolverError::DuplicateEditVariable);
+        }
+
+        let strength = strength.clamp(0.0, REQUIRED);
+
+        if strength >= REQUIRED {
+            return Err(SolverError::BadRequiredStrength);
+        }
+
+        let constraint = self.add_constraint(&Expression::default().term(variable, 1.0), Relation::Equal, strength)?;
+        let tag = self.constraints[&constraint].tag;
+
+        self.edits.insert(variable, EditInfo {
+            tag,
+            constraint,
+            constant: 0.0
+        });
+
+        Ok(())
+    }
+
+    /// Stops editing `variable`.
+    pub fn remove_edit_variable(&mut self, variable: Variable) -> Result<(), SolverError> {
+        let edit = self.edits.remove(&variable).ok_or(SolverError::UnknownEditVariable)?;
+        self.remove_constraint(edit.constraint)
+    }
+
+    /// Suggests a value for an edit variable. The solver moves it as close to `value` as the
+    /// constraints allow.
+    pub fn suggest_value(&mut self, variable: Variable, value: f64) -> Result<(), SolverError> {
+        let (tag, delta) = {
+            let edit = self.edits.get_mut(&variable).ok_or(SolverError::UnknownEditVariable)?;
+            let delta = value - edit.constant;
+            edit.constant = value;
+            (edit.tag, delta)
+        };
+
+        if let Some(row) = self.rows.get_mut(&tag.marker) {
+            if row.add(-delta) < 0.0 {
+                self.infeasible.push(tag.marker);
+            }
+
+            return self.dual_optimize();
+        }
+
+        if let Some(row) = self.rows.get_mut(&tag.other) {
+            if row.add(delta) < 0.0 {
+                self.infeasible.push(tag.other);
+            }
+
+            return self.dual_optimize();
+        }
+
+        for (symbol, row) in self.rows.iter_mut() {
+            let coefficient = row.coefficient_for(tag.marker);
+
+            if coefficient != 0.0 && row.add(delta * coefficient) < 0.0 && symbol.kind() != SymbolKind::External {
+                self.infeasible.push(*symbol);
+            }
+        }
+
+        self.dual_optimize()
+    }
+
+    /// Builds a tableau row for a constraint, substituting any basic variables.
+    fn create_row(&mut self, expression: &Expression, relation: Relation, strength: f64) -> (Row, Tag) {
+        let mut row = Row::new(expression.constant);
+
+        for (variable, coefficient) in &expression.terms {
+            if near_zero(*coefficient) {
+                continue;
+            }
+
+            let symbol = match self.variables.get(variable) {
+                Some(symbol) => *symbol,
+                None => {
+                    let symbol = self.new_symbol(SymbolKind::External);
+                    self.variables.insert(*variable, symbol);
+                    symbol
+                }
+            };
+
+            match self.rows.get(&symbol) {
+                Some(other) => row.insert_row(other, *coefficient),
+                None => row.insert_symbol(symbol, *coefficient)
+            }
+        }
+
+        let mut tag = Tag {
+            marker: Symbol::invalid(),
+            other: Symbol::invalid()
+        };
+
+        match relation {
+            Relation::LessThanOrEqual | Relation::GreaterThanOrEqual => {
+                let coefficient = match relation {
+                    Relation::LessThanOrEqual => 1.0,
+                    _ => -1.0
+                };
+
+                let slack = self.new_symbol(SymbolKind::Slack);
+                tag.marker = slack;
+                row.insert_symbol(slack, coefficient);
+
+                if strength < REQUIRED {
+                    let error = self.new_symbol(SymbolKind::Error);
+                    tag.other = error;
+                    row.insert_symbol(error, -coefficient);
+                    self.objective.insert_symbol(error, strength);
+                }
+            },
+
+            Relation::Equal => match strength < REQUIRED {
+                true => {
+                    let plus = self.new_symbol(SymbolKind::Error);
+                    let minus = self.new_symbol(SymbolKind::Error);
+                    tag.marker = plus;
+                    tag.other = minus;
+                    row.insert_symbol(plus, -1.0);
+                    row.insert_symbol(minus, 1.0);
+                    self.objective.insert_symbol(plus, strength);
+                    self.objective.insert_symbol(minus, strength);
+                },
+
+                false => {
+                    let dummy = self.new_symbol(SymbolKind::Dummy);
+                    tag.marker = dummy;
+                    row.insert_symbol(dummy, 1.0);
+                }
+            }
+        }
+
+        if row.constant < 0.0 {
+            row.reverse_sign();
+        }
+
+        (row, tag)
+    }
+
+    /// Adds `row` using an artificial variable, for rows with no obvious subject. Returns whether
+    /// the row could be satisfied.
+    fn add_with_artificial_variable(&mut self, row: &Row) -> Result<bool, SolverError> {
+        let artificial = self.new_symbol(SymbolKind::Slack);
+        self.rows.insert(artificial, row.clone());
+        self.artificial = Some(row.clone());
+
+        self.optimize(Objective::Artificial)?;
+        let success = self.artificial.take().map(|row| near_zero(row.constant)).unwrap_or(false);
+
+        if let Some(mut row) = self.rows.remove(&artificial) {
+            if row.cells.is_empty() {
+                return Ok(success);
+            }
+
+            let entering = match row.cells.keys().find(|symbol| symbol.is_pivotable()) {
+                Some(symbol) => *symbol,
+                None => return Ok(false)
+            };
+
+            row.solve_for_symbols(artificial, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+
+        for row in self.rows.values_mut() {
+            row.remove(artificial);
+        }
+
+        self.objective.remove(artificial);
+        Ok(success)
+    }
+
+    /// Substitutes `symbol` with `row` across the tableau and objective(s).
+    fn substitute(&mut self, symbol: Symbol, row: &Row) {
+        for (basic, other) in self.rows.iter_mut() {
+            other.substitute(symbol, row);
+
+            if basic.kind() != SymbolKind::External && other.constant < 0.0 {
+                self.infeasible.push(*basic);
+            }
+        }
+
+        self.objective.substitute(symbol, row);
+
+        if let Some(artificial) = self.artificial.as_mut() {
+            artificial.substitute(symbol, row);
+        }
+    }
+
+    /// Runs the primal simplex method against the given objective.
+    fn optimize(&mut self, objective: Objective) -> Result<(), SolverError> {
+        loop {
+            let entering = {
+                let row = match objective {
+                    Objective::Main => &self.objective,
+                    Objective::Artificial => match &self.artificial {
+                        Some(row) => row,
+                        None => return Ok(())
+                    }
+                };
+
+                row.cells
+                    .iter()
+                    .find(|(symbol, value)| symbol.kind() != SymbolKind::Dummy && **value < 0.0)
+                    .map(|(symbol, _)| *symbol)
+            };
+
+            let entering = match entering {
+                Some(symbol) => symbol,
+                None => return Ok(())
+            };
+
+            let mut ratio = f64::MAX;
+            let mut leaving = None;
+
+            for (symbol, row) in &self.rows {
+                if symbol.kind() != SymbolKind::External {
+                    let coefficient = row.coefficient_for(entering);
+
+                    if coefficient < 0.0 {
+                        let candidate = -row.constant / coefficient;
+
+                        if candidate < ratio {
+                            ratio = candidate;
+                            leaving = Some(*symbol);
+                        }
+                    }
+                }
+            }
+
+            let leaving = leaving.ok_or(SolverError::Internal("the objective is unbounded"))?;
+            let mut row = self.rows.remove(&leaving).unwrap_or_default();
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+    }
+
+    /// Runs the dual simplex method, restoring feasibility after edits.
+    fn dual_optimize(&mut self) -> Result<(), SolverError> {
+        while let Some(leaving) = self.infeasible.pop() {
+            let entering = match self.rows.get(&leaving) {
+                Some(row) if row.constant < 0.0 => {
+                    let mut ratio = f64::MAX;
+                    let mut entering = None;
+
+                    for (symbol, coefficient) in &row.cells {
+                        if *coefficient > 0.0 && symbol.kind() != SymbolKind::Dummy {
+                            let candidate = self.objective.coefficient_for(*symbol) / coefficient;
+
+                            if candidate < ratio {
+                                ratio = candidate;
+                                entering = Some(*symbol);
+                            }
+                        }
+                    }
+
+                    entering.ok_or(SolverError::Internal("dual optimize failed"))?
+                },
+
+                _ => continue
+            };
+
+            let mut row = self.rows.remove(&leaving).unwrap_or_default();
+            row.solve_for_symbols(leaving, entering);
+            self.substitute(entering, &row);
+            self.rows.insert(entering, row);
+        }
+
+        Ok(())
+    }
+
+    /// Finds the row to pivot a constraint's marker out of, when removing it.
+    fn marker_leaving_symbol(&self, marker: Symbol) -> Option<Symbol> {
+        let mut first = (f64::MAX, None);
+        let mut second = (f64::MAX, None);
+        let mut third = None;
+
+        for (symbol, row) in &self.rows {
+            let coefficient = row.coefficient_for(marker);
+
+            if coefficient == 0.0 {
+                continue;
+            }
+
+            if symbol.kind() == SymbolKind::External {
+                third = Some(*symbol);
+            } else if coefficient < 0.0 {
+                let ratio = -row.constant / coefficient;
+
+                if ratio < first.0 {
+                    first = (ratio, Some(*symbol));
+                }
+            } else {
+                let ratio = row.constant / coefficient;
+
+                if ratio < second.0 {
+                    second = (ratio, Some(*symbol));
+                }
+            }
+        }
+
+        first.1.or(second.1).or(third)
+    }
+}
+
+/// Picks the symbol to solve a new row for: an external variable if there is one, otherwise a
+/// new slack or error variable with a negative coefficient.
+fn choose_subject(row: &Row, tag: &Tag) -> Symbol {
+    if let Some(symbol) = row.cells.keys().find(|symbol| symbol.kind() == SymbolKind::External) {
+        return *symbol;
+    }
+
+    for symbol in [tag.marker, tag.other].iter() {
+        if symbol.is_pivotable() && row.coefficient_for(*symbol) < 0.0 {
+            return *symbol;
+        }
+    }
+
+    Symbol::invalid()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Expression, Relation, Solver, SolverError, REQUIRED};
+
+    #[test]
+    fn test_required_optional_and_edits() {
+        let mut solver = Solver::new();
+        let left = solver.new_variable();
+        let width = solver.new_variable();
+
+        // left >= 10, left + width <= 100, width == 200 (optional).
+        solver
+            .add_constraint(
+                &Expression::constant(-10.0).term(left, 1.0),
+                Relation::GreaterThanOrEqual,
+                REQUIRED
+            )
+            .unwrap();
+        solver
+            .add_constraint(
+                &Expression::constant(-100.0).term(left, 1.0).term(width, 1.0),
+                Relation::LessThanOrEqual,
+                REQUIRED
+            )
+            .unwrap();
+        let optional = solver
+            .add_constraint(&Expression::constant(-200.0).term(width, 1.0), Relation::Equal, 1.0)
+            .unwrap();
+
+        assert_eq!(solver.value(left), 10.0);
+        assert_eq!(solver.value(width), 90.0);
+
+        // A conflicting required constraint is rejected, and doesn't disturb anything.
+        assert_eq!(
+            solver.add_constraint(
+                &Expression::constant(-110.0).term(left, 1.0).term(width, 1.0),
+                Relation::GreaterThanOrEqual,
+                REQUIRED
+            ),
+            Err(SolverError::UnsatisfiableConstraint)
+        );
+        assert_eq!(solver.value(width), 90.0);
+
+        solver.remove_constraint(optional).unwrap();
+        solver.add_edit_variable(width, 1000.0).unwrap();
+        solver.suggest_value(width, 40.0).unwrap();
+        assert_eq!(solver.value(width), 40.0);
+
+        solver.suggest_value(width, 500.0).unwrap();
+        assert_eq!(solver.value(left) + solver.value(width), 100.0);
+    }
+}
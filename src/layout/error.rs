@@ -0,0 +1,221 @@
+//! Errors that can come up when building or checking constraints.
+
+use std::error;
+use std::fmt;
+
+use objc::runtime::Object;
+use objc::{msg_send, sel, sel_impl};
+
+use crate::foundation::NSString;
+
+/// Which side of a constraint an anchor was on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AnchorPosition {
+    /// The anchor being constrained (`self`, in `constraint_*` methods).
+    From,
+
+    /// The anchor it was being constrained to.
+    To,
+
+    /// Both anchors.
+    Both
+}
+
+/// Errors that can occur when creating constraints, or that `check_layout()` and
+/// `LayoutEngine::check()` can find in a set of active constraints.
+///
+/// Anchors and views are described the way the system describes them (e.g,
+/// `NSView:0x7f8b...leading`), so they can be matched up against the view debugger and the
+/// system's own logging.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutError {
+    /// An anchor was uninitialized - usually because it belongs to a view that wasn't created
+    /// through Cacao, or hasn't been created yet.
+    UninitializedAnchor {
+        /// The kind of anchor: `"x"`, `"y"` or `"dimension"`.
+        axis: &'static str,
+
+        /// Which of the anchors was uninitialized.
+        position: AnchorPosition,
+
+        /// A description of the other anchor, and the view it belongs to, if it was initialized.
+        /// An uninitialized anchor has no view, so this is the best lead there is.
+        other: Option<String>
+    },
+
+    /// A direction-dependent anchor (leading/trailing) was attached to a fixed one (left/right),
+    /// which would behave differently for left-to-right and right-to-left languages.
+    MismatchedDirection {
+        /// A description of the anchor being constrained.
+        from: String,
+
+        /// A description of the anchor it was being constrained to.
+        to: String
+    },
+
+    /// An anchor from a `LayoutEngine` was attached to a system anchor, or to an anchor from a
+    /// different `LayoutEngine`.
+    MismatchedEngines {
+        /// A description of the anchor being constrained.
+        from: String,
+
+        /// A description of the anchor it was being constrained to.
+        to: String
+    },
+
+    /// A required constraint conflicts with the other active constraints, and was left out of
+    /// the layout.
+    UnsatisfiableConstraint {
+        /// A description of the constraint.
+        constraint: String
+    },
+
+    /// A view's constraints don't fully determine its frame, so the system is picking one of
+    /// several possible layouts.
+    AmbiguousLayout {
+        /// A description of the view.
+        view: String
+    }
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::UninitializedAnchor { axis, position, other } => {
+                let which = match position {
+                    AnchorPosition::From => "an uninitialized \"from\"",
+                    AnchorPosition::To => "an uninitialized \"to\"",
+                    AnchorPosition::Both => "uninitialized \"from\" and \"to\""
+                };
+
+                write!(f, "Attempted to create constraints with {} {} anchor", which, axis)?;
+
+                match other {
+                    Some(other) => write!(f, " (the other anchor is {}).", other),
+                    None => write!(f, ".")
+                }
+            },
+
+            LayoutError::MismatchedDirection { from, to } => write!(
+                f,
+                "Attempted to attach {} to {}. Mixing leading/trailing and left/right anchors results in undefined \
+                 behavior for LTR and RTL system settings, and Cacao blocks this. Use either left/right or leading/trailing.",
+                from, to
+            ),
+
+            LayoutError::MismatchedEngines { from, to } => write!(
+                f,
+                "Attempted to create a constraint between {} and {}, which belong to different layout engines.",
+                from, to
+            ),
+
+            LayoutError::UnsatisfiableConstraint { constraint } => {
+                write!(
+                    f,
+                    "Unable to satisfy constraint, so it was left out of the layout: {}",
+                    constraint
+                )
+            },
+
+            LayoutError::AmbiguousLayout { view } => {
+                write!(
+                    f,
+                    "The constraints for {} don't determine its frame, so its layout is ambiguous.",
+                    view
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for LayoutError {}
+
+/// Returns the system's description of an object - for anchors, this includes the view they
+/// belong to (e.g, `<NSLayoutXAxisAnchor:0x... "NSView:0x....leading">`).
+pub(crate) fn describe(object: &Object) -> String {
+    NSString::retain(unsafe { msg_send![object, debugDescription] }).to_string()
+}
+
+/// Builds an `UninitializedAnchor` error from the descriptions of both anchors, where `None`
+/// marks an uninitialized one.
+pub(crate) fn uninitialized(axis: &'static str, from: Option<String>, to: Option<String>) -> LayoutError {
+    let (position, other) = match (from, to) {
+        (None, None) => (AnchorPosition::Both, None),
+        (None, to) => (AnchorPosition::From, to),
+        (from, _) => (AnchorPosition::To, from)
+    };
+
+    LayoutError::UninitializedAnchor { axis, position, other }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{uninitialized, AnchorPosition, LayoutError};
+
+    #[test]
+    fn test_uninitialized_positions() {
+        let error = |position, other: Option<&str>| LayoutError::UninitializedAnchor {
+            axis: "y",
+            position,
+            other: other.map(String::from)
+        };
+
+        assert_eq!(uninitialized("y", None, None), error(AnchorPosition::Both, None));
+        assert_eq!(
+            uninitialized("y", None, Some("top".into())),
+            error(AnchorPosition::From, Some("top"))
+        );
+        assert_eq!(
+            uninitialized("y", Some("bottom".into()), None),
+            error(AnchorPosition::To, Some("bottom"))
+        );
+    }
+
+    #[test]
+    fn test_error_messages() {
+        let messages = [
+            (
+                uninitialized("x", None, None),
+                "Attempted to create constraints with uninitialized \"from\" and \"to\" x anchor."
+            ),
+            (
+                uninitialized("dimension", Some("NSView:0x1.width".into()), None),
+                "Attempted to create constraints with an uninitialized \"to\" dimension anchor (the other anchor is \
+                 NSView:0x1.width)."
+            ),
+            (
+                LayoutError::MismatchedDirection {
+                    from: "NSView:0x1.leading".into(),
+                    to: "NSView:0x2.left".into()
+                },
+                "Attempted to attach NSView:0x1.leading to NSView:0x2.left. Mixing leading/trailing and left/right anchors \
+                 results in undefined behavior for LTR and RTL system settings, and Cacao blocks this. Use either \
+                 left/right or leading/trailing."
+            ),
+            (
+                LayoutError::MismatchedEngines {
+                    from: "LayoutNode 1's Top anchor".into(),
+                    to: "NSView:0x1.top".into()
+                },
+                "Attempted to create a constraint between LayoutNode 1's Top anchor and NSView:0x1.top, which belong to \
+                 different layout engines."
+            ),
+            (
+                LayoutError::UnsatisfiableConstraint {
+                    constraint: "NSView:0x1.width == 100".into()
+                },
+                "Unable to satisfy constraint, so it was left out of the layout: NSView:0x1.width == 100"
+            ),
+            (
+                LayoutError::AmbiguousLayout {
+                    view: "NSView:0x1".into()
+                },
+                "The constraints for NSView:0x1 don't determine its frame, so its layout is ambiguous."
+            )
+        ];
+
+        for (error, message) in messages.iter() {
+            assert_eq!(error.to_string(), *message);
+        }
+    }
+}
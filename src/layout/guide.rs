@@ -0,0 +1,300 @@
+//! Layout guides: invisible rectangles that take part in autolayout like views do, without
+//! being drawn, receiving events, or cluttering the view hierarchy. They're handy for spacing
+//! (constrain views to the edges of a guide between them), and for equal-width columns (constrain
+//! several guides to the same width).
+
+use core_graphics::geometry::CGRect;
+
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
+
+use crate::foundation::{id, nil, NSString};
+use crate::geometry::Rect;
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY};
+use crate::utils::properties::ObjcProperty;
+
+#[cfg(feature = "appkit")]
+use crate::foundation::NSArray;
+
+#[cfg(feature = "appkit")]
+use crate::layout::{Layout, LayoutConstraint};
+
+#[cfg(feature = "appkit")]
+use crate::objc_access::ObjcAccess;
+
+#[cfg(feature = "appkit")]
+use crate::utils::os;
+
+/// The widest the readable content guide gets, in points. This is the width UIKit uses at the
+/// default text size, which AppKit (lacking a readable content guide) emulates.
+#[cfg(feature = "appkit")]
+const READABLE_WIDTH: f64 = 672.;
+
+/// Identifies the guides that Cacao adds to views itself, so they can be found again rather than
+/// added twice.
+#[cfg(feature = "appkit")]
+const LAYOUT_MARGINS_IDENTIFIER: &str = "cacaoLayoutMarginsGuide";
+
+#[cfg(feature = "appkit")]
+const READABLE_CONTENT_IDENTIFIER: &str = "cacaoReadableContentGuide";
+
+/// Wraps `NSLayoutGuide` and `UILayoutGuide` across platforms.
+///
+/// A guide has anchors like a view, and is constrained the same way, once it's been added to a
+/// view with `Layout::add_layout_guide()`:
+///
+/// ```rust,no_run
+/// use cacao::layout::{Layout, LayoutConstraint, LayoutGuide};
+/// use cacao::view::View;
+///
+/// let content = View::new();
+/// let (left, right) = (View::new(), View::new());
+/// content.add_subview(&left);
+/// content.add_subview(&right);
+///
+/// // Keep the two views apart by a quarter of the container's width.
+/// let spacer = LayoutGuide::new();
+/// content.add_layout_guide(&spacer);
+///
+/// LayoutConstraint::activate(&[
+///     spacer.leading.constraint_equal_to(&left.trailing),
+///     spacer.trailing.constraint_equal_to(&right.leading),
+///     spacer.width.constraint_equal_to_with_multiplier(&content.width, 0.25)
+/// ]);
+/// ```
+///
+/// ## Layout margins and readable content
+/// Views also come with two guides of their own, which don't need adding:
+///
+/// - `layout_margins_guide()` covers the view's layout margins - the system's standard spacing
+///   inside its edges. On macOS 10.15 and under, where views have no layout margins, it's pinned
+///   to the view's edges.
+/// - `readable_content_guide()` covers the area that text reads comfortably in: the layout
+///   margins, narrowed down (and centered) when the view is wide. AppKit has no readable content
+///   guide, so Cacao adds one to the view (once) the first time it's asked for.
+#[derive(Clone, Debug)]
+pub struct LayoutGuide {
+    /// A pointer to the Objective-C runtime layout guide.
+    pub objc: ObjcProperty,
+
+    /// A pointer to the Objective-C runtime top layout constraint.
+    pub top: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime leading layout constraint.
+    pub leading: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime left layout constraint.
+    pub left: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime trailing layout constraint.
+    pub trailing: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime right layout constraint.
+    pub right: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime bottom layout constraint.
+    pub bottom: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime width layout constraint.
+    pub width: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime height layout constraint.
+    pub height: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime center X layout constraint.
+    pub center_x: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime center Y layout constraint.
+    pub center_y: LayoutAnchorY
+}
+
+impl Default for LayoutGuide {
+    fn default() -> Self {
+        LayoutGuide::new()
+    }
+}
+
+impl LayoutGuide {
+    /// Returns a new layout guide. It needs to be added to a view (with
+    /// `Layout::add_layout_guide()`) before it's constrained.
+    pub fn new() -> Self {
+        #[cfg(feature = "appkit")]
+        let guide: id = unsafe { msg_send![class!(NSLayoutGuide), new] };
+
+        #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+        let guide: id = unsafe { msg_send![class!(UILayoutGuide), new] };
+
+        Self::init(ObjcProperty::from_retained(guide))
+    }
+
+    /// Wraps an existing layout guide.
+    pub(crate) fn wrap(guide: id) -> Self {
+        Self::init(ObjcProperty::retain(guide))
+    }
+
+    /// Pulls the anchors out of a guide.
+    fn init(objc: ObjcProperty) -> Self {
+        let guide = objc.get(|obj| obj as *const Object as id);
+
+        LayoutGuide {
+            top: LayoutAnchorY::top(guide),
+            left: LayoutAnchorX::left(guide),
+            leading: LayoutAnchorX::leading(guide),
+            right: LayoutAnchorX::right(guide),
+            trailing: LayoutAnchorX::trailing(guide),
+            bottom: LayoutAnchorY::bottom(guide),
+            width: LayoutAnchorDimension::width(guide),
+            height: LayoutAnchorDimension::height(guide),
+            center_x: LayoutAnchorX::center(guide),
+            center_y: LayoutAnchorY::center(guide),
+            objc
+        }
+    }
+
+    /// Sets an identifier for this guide, which shows up in the system's logging of unsatisfiable
+    /// and ambiguous layouts.
+    pub fn set_identifier(&self, identifier: &str) {
+        let identifier = NSString::new(identifier);
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setIdentifier:&*identifier];
+        });
+    }
+
+    /// Returns the frame of this guide, as of the last layout pass, in the coordinates of the view
+    /// it was added to.
+    pub fn frame(&self) -> Rect {
+        #[cfg(feature = "appkit")]
+        let frame: CGRect = self.objc.get(|obj| unsafe { msg_send![obj, frame] });
+
+        #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+        let frame: CGRect = self.objc.get(|obj| unsafe { msg_send![obj, layoutFrame] });
+
+        frame.into()
+    }
+
+    /// Removes this guide from the view it was added to, deactivating any constraints that
+    /// involve it.
+    pub fn remove_from_owning_view(&self) {
+        self.objc.with_mut(|guide| unsafe {
+            let view: id = msg_send![guide, owningView];
+
+            if view != nil {
+                let _: () = msg_send![view, removeLayoutGuide: guide];
+            }
+        });
+    }
+
+    /// Returns the guide for a view's layout margins. On macOS 10.15 and under, where views have
+    /// no layout margins, this is a guide pinned to the view's edges.
+    pub(crate) fn layout_margins(view: id) -> Self {
+        #[cfg(feature = "appkit")]
+        if !os::is_minimum_version(11) {
+            return LayoutGuide::find_or_add(view, LAYOUT_MARGINS_IDENTIFIER, |guide, view| {
+                vec![
+                    guide.top.constraint_equal_to(&view.top),
+                    guide.leading.constraint_equal_to(&view.leading),
+                    guide.trailing.constraint_equal_to(&view.trailing),
+                    guide.bottom.constraint_equal_to(&view.bottom),
+                ]
+            });
+        }
+
+        LayoutGuide::wrap(unsafe { msg_send![view, layoutMarginsGuide] })
+    }
+
+    /// Returns the guide for the area of a view that text reads comfortably in: the layout
+    /// margins, narrowed down (and centered) on wide views.
+    ///
+    /// AppKit has no readable content guide, so this is a guide that Cacao adds to the view and
+    /// constrains to the layout margins - capped at the width UIKit uses for its default text
+    /// size.
+    #[cfg(feature = "appkit")]
+    pub(crate) fn readable_content(view: id) -> Self {
+        let margins = LayoutGuide::layout_margins(view);
+
+        LayoutGuide::find_or_add(view, READABLE_CONTENT_IDENTIFIER, move |guide, _| {
+            vec![
+                guide.top.constraint_equal_to(&margins.top),
+                guide.bottom.constraint_equal_to(&margins.bottom),
+                guide.center_x.constraint_equal_to(&margins.center_x),
+                guide.width.constraint_less_than_or_equal_to(&margins.width),
+                guide.width.constraint_less_than_or_equal_to_constant(READABLE_WIDTH),
+                guide.width.constraint_equal_to(&margins.width).priority(999),
+            ]
+        })
+    }
+
+    /// Returns the guide for the area of a view that text reads comfortably in: the layout
+    /// margins, narrowed down (and centered) on wide views.
+    #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+    pub(crate) fn readable_content(view: id) -> Self {
+        LayoutGuide::wrap(unsafe { msg_send![view, readableContentGuide] })
+    }
+
+    /// Returns the guide with `identifier` that Cacao previously added to `view` - or, the first
+    /// time around, adds one and activates the constraints that `constraints` returns for it.
+    #[cfg(feature = "appkit")]
+    fn find_or_add<F>(view: id, identifier: &str, constraints: F) -> Self
+    where
+        F: Fn(&LayoutGuide, &GuideOwner) -> Vec<LayoutConstraint>
+    {
+        let guides = NSArray::retain(unsafe { msg_send![view, layoutGuides] });
+
+        let existing = guides.map(|guide| guide).into_iter().find(|guide| {
+            let name: id = unsafe { msg_send![*guide, identifier] };
+            name != nil && NSString::retain(name).to_str() == identifier
+        });
+
+        if let Some(guide) = existing {
+            return LayoutGuide::wrap(guide);
+        }
+
+        let guide = LayoutGuide::new();
+        guide.set_identifier(identifier);
+
+        let owner = GuideOwner::wrap(view);
+        owner.add_layout_guide(&guide);
+        LayoutConstraint::activate(&constraints(&guide, &owner));
+
+        guide
+    }
+}
+
+/// The view that a guide is being added to, with its anchors.
+#[cfg(feature = "appkit")]
+#[derive(Debug)]
+struct GuideOwner {
+    objc: ObjcProperty,
+    top: LayoutAnchorY,
+    leading: LayoutAnchorX,
+    trailing: LayoutAnchorX,
+    bottom: LayoutAnchorY
+}
+
+#[cfg(feature = "appkit")]
+impl GuideOwner {
+    fn wrap(view: id) -> Self {
+        GuideOwner {
+            objc: ObjcProperty::retain(view),
+            top: LayoutAnchorY::top(view),
+            leading: LayoutAnchorX::leading(view),
+            trailing: LayoutAnchorX::trailing(view),
+            bottom: LayoutAnchorY::bottom(view)
+        }
+    }
+}
+
+#[cfg(feature = "appkit")]
+impl ObjcAccess for GuideOwner {
+    fn with_backing_obj_mut<F: Fn(id)>(&self, handler: F) {
+        self.objc.with_mut(handler);
+    }
+
+    fn get_from_backing_obj<F: Fn(&Object) -> R, R>(&self, handler: F) -> R {
+        self.objc.get(handler)
+    }
+}
+
+#[cfg(feature = "appkit")]
+impl Layout for GuideOwner {}
@@ -4,12 +4,16 @@ use objc_id::ShareId;
 
 use crate::foundation::id;
 use crate::layout::constraint::LayoutConstraint;
+use crate::layout::engine::EngineAnchor;
+use crate::layout::error::{describe, uninitialized};
+use crate::layout::{LayoutAttribute, LayoutError, LayoutRelation};
 
 /// A wrapper for `NSLayoutAnchorX`, used to handle values for how a given view should
 /// layout along the x-axis.
 ///
 /// Of note: mismatches of incorrect left/leading and right/trailing anchors are detected at
-/// runtime, and will panic - this is by design, as your UI needs to work. Be careful!
+/// runtime, and will panic - this is by design, as your UI needs to work. Be careful! The
+/// `try_constraint_*` methods return a `LayoutError` instead.
 #[derive(Clone, Debug)]
 pub enum LayoutAnchorX {
     /// Represents an uninitialized anchor (e.g, for a view that's not created yet).
@@ -28,7 +32,10 @@ pub enum LayoutAnchorX {
     Right(ShareId<Object>),
 
     /// Represents a center anchor on the X axis.
-    Center(ShareId<Object>)
+    Center(ShareId<Object>),
+
+    /// Represents an anchor on a `LayoutNode`, solved by a `LayoutEngine` rather than the system.
+    Engine(EngineAnchor)
 }
 
 impl Default for LayoutAnchorX {
@@ -64,117 +71,191 @@ impl LayoutAnchorX {
         Self::Center(unsafe { ShareId::from_ptr(msg_send![view, centerXAnchor]) })
     }
 
-    /// Boilerplate for handling constraint construction and panic'ing with some more helpful
-    /// messages. The goal here is to make AutoLayout slightly easier to debug when things go
-    /// wrong.
-    fn constraint_with<F>(&self, anchor_to: &LayoutAnchorX, handler: F) -> LayoutConstraint
+    /// Describes this anchor and the view it belongs to, for errors. Returns `None` if it's
+    /// uninitialized.
+    fn description(&self) -> Option<String> {
+        match self {
+            Self::Uninitialized => None,
+            Self::Leading(obj) | Self::Left(obj) | Self::Trailing(obj) | Self::Right(obj) | Self::Center(obj) => {
+                Some(describe(obj))
+            },
+            Self::Engine(anchor) => Some(anchor.description())
+        }
+    }
+
+    /// Boilerplate for handling constraint construction and returning some more helpful errors.
+    /// The goal here is to make AutoLayout slightly easier to debug when things go wrong.
+    fn constraint_with<F>(
+        &self,
+        anchor_to: &LayoutAnchorX,
+        relation: LayoutRelation,
+        handler: F
+    ) -> Result<LayoutConstraint, LayoutError>
     where
         F: Fn(&ShareId<Object>, &ShareId<Object>) -> id
     {
         match (self, anchor_to) {
+            (Self::Engine(from), Self::Engine(to)) => match (direction(from.attribute()), direction(to.attribute())) {
+                (Some(from_direction), Some(to_direction)) if from_direction != to_direction => {
+                    Err(LayoutError::MismatchedDirection {
+                        from: from.description(),
+                        to: to.description()
+                    })
+                },
+
+                _ => from.constraint(relation, Some(to), 1., 0.)
+            },
+
             // The anchors that can connect to each other. These blocks could be condensed, but are
             // kept separate for readability reasons.
             (Self::Leading(from), Self::Leading(to))
             | (Self::Leading(from), Self::Trailing(to))
-            | (Self::Leading(from), Self::Center(to)) => LayoutConstraint::new(handler(from, to)),
+            | (Self::Leading(from), Self::Center(to)) => Ok(LayoutConstraint::new(handler(from, to))),
 
             (Self::Trailing(from), Self::Trailing(to))
             | (Self::Trailing(from), Self::Leading(to))
-            | (Self::Trailing(from), Self::Center(to)) => LayoutConstraint::new(handler(from, to)),
+            | (Self::Trailing(from), Self::Center(to)) => Ok(LayoutConstraint::new(handler(from, to))),
 
             (Self::Left(from), Self::Left(to)) | (Self::Left(from), Self::Right(to)) | (Self::Left(from), Self::Center(to)) => {
-                LayoutConstraint::new(handler(from, to))
+                Ok(LayoutConstraint::new(handler(from, to)))
             },
 
             (Self::Right(from), Self::Right(to))
             | (Self::Right(from), Self::Left(to))
-            | (Self::Right(from), Self::Center(to)) => LayoutConstraint::new(handler(from, to)),
+            | (Self::Right(from), Self::Center(to)) => Ok(LayoutConstraint::new(handler(from, to))),
 
             (Self::Center(from), Self::Center(to))
             | (Self::Center(from), Self::Leading(to))
             | (Self::Center(from), Self::Trailing(to))
             | (Self::Center(from), Self::Left(to))
-            | (Self::Center(from), Self::Right(to)) => LayoutConstraint::new(handler(from, to)),
+            | (Self::Center(from), Self::Right(to)) => Ok(LayoutConstraint::new(handler(from, to))),
 
             // These anchors explicitly cannot be attached to each other, as it results in
             // undefined/unexpected layout behavior when a system has differing ltr/rtl setups.
-            (Self::Leading(_), Self::Left(_)) | (Self::Left(_), Self::Leading(_)) => {
-                panic!(
-                    r#"
-                    Attempted to attach a "leading" constraint to a "left" constraint. This will
-                    result in undefined behavior for LTR and RTL system settings, and Cacao blocks this.
-
-                    Use either left/right or leading/trailing.
-                "#
-                );
-            },
-
-            (Self::Leading(_), Self::Right(_)) | (Self::Right(_), Self::Leading(_)) => {
-                panic!(
-                    r#"
-                    Attempted to attach a "leading" constraint to a "right" constraint. This will
-                    result in undefined behavior for LTR and RTL system settings, and Cacao blocks this.
-
-                    Use either left/right or leading/trailing.
-                "#
-                );
-            },
-
-            (Self::Trailing(_), Self::Left(_)) | (Self::Left(_), Self::Trailing(_)) => {
-                panic!(
-                    r#"
-                    Attempted to attach a "trailing" constraint to a "left" constraint. This will
-                    result in undefined behavior for LTR and RTL system settings, and Cacao blocks this.
-
-                    Use either left/right or leading/trailing.
-                "#
-                );
-            },
-
-            (Self::Trailing(_), Self::Right(_)) | (Self::Right(_), Self::Trailing(_)) => {
-                panic!(
-                    r#"
-                    Attempted to attach a "trailing" constraint to a "right" constraint. This will
-                    result in undefined behavior for LTR and RTL system settings, and Cacao blocks this.
-
-                    Use either left/right or leading/trailing.
-                "#
-                );
-            },
+            (Self::Leading(from), Self::Left(to))
+            | (Self::Left(from), Self::Leading(to))
+            | (Self::Leading(from), Self::Right(to))
+            | (Self::Right(from), Self::Leading(to))
+            | (Self::Trailing(from), Self::Left(to))
+            | (Self::Left(from), Self::Trailing(to))
+            | (Self::Trailing(from), Self::Right(to))
+            | (Self::Right(from), Self::Trailing(to)) => Err(LayoutError::MismatchedDirection {
+                from: describe(from),
+                to: describe(to)
+            }),
 
             // If anything is attempted with an uninitialized anchor, then block it.
-            (Self::Uninitialized, Self::Uninitialized) => {
-                panic!("Attempted to create constraints with an uninitialized \"from\" and \"to\" X anchor.");
+            (Self::Uninitialized, _) | (_, Self::Uninitialized) => {
+                Err(uninitialized("x", self.description(), anchor_to.description()))
             },
 
-            (Self::Uninitialized, _) => {
-                panic!("Attempted to create constraints with an uninitialized \"from\" X anchor.");
-            },
-
-            (_, Self::Uninitialized) => {
-                panic!("Attempted to create constraints with an uninitialized \"to\" X anchor.");
-            }
+            (Self::Engine(_), _) | (_, Self::Engine(_)) => Err(LayoutError::MismatchedEngines {
+                from: self.description().unwrap_or_default(),
+                to: anchor_to.description().unwrap_or_default()
+            })
         }
     }
 
     /// Return a constraint equal to another horizontal anchor.
+    ///
+    /// Panics if either anchor is uninitialized, or if the anchors can't be attached to each
+    /// other; see `try_constraint_equal_to()`.
     pub fn constraint_equal_to(&self, anchor_to: &LayoutAnchorX) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
-            msg_send![*from, constraintEqualToAnchor:&**to]
-        })
+        self.try_constraint_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Return a constraint greater than or equal to another horizontal anchor.
+    ///
+    /// Panics if either anchor is uninitialized, or if the anchors can't be attached to each
+    /// other; see `try_constraint_greater_than_or_equal_to()`.
     pub fn constraint_greater_than_or_equal_to(&self, anchor_to: &LayoutAnchorX) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
-            msg_send![*from, constraintGreaterThanOrEqualToAnchor:&**to]
-        })
+        self.try_constraint_greater_than_or_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Return a constraint less than or equal to another horizontal anchor.
+    ///
+    /// Panics if either anchor is uninitialized, or if the anchors can't be attached to each
+    /// other; see `try_constraint_less_than_or_equal_to()`.
     pub fn constraint_less_than_or_equal_to(&self, anchor_to: &LayoutAnchorX) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
+        self.try_constraint_less_than_or_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Return a constraint equal to another horizontal anchor, or an error if either anchor is
+    /// uninitialized or the anchors can't be attached to each other.
+    pub fn try_constraint_equal_to(&self, anchor_to: &LayoutAnchorX) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::Equal, |from, to| unsafe {
+            msg_send![*from, constraintEqualToAnchor:&**to]
+        })
+    }
+
+    /// Return a constraint greater than or equal to another horizontal anchor, or an error if
+    /// either anchor is uninitialized or the anchors can't be attached to each other.
+    pub fn try_constraint_greater_than_or_equal_to(&self, anchor_to: &LayoutAnchorX) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::GreaterThanOrEqual, |from, to| unsafe {
+            msg_send![*from, constraintGreaterThanOrEqualToAnchor:&**to]
+        })
+    }
+
+    /// Return a constraint less than or equal to another horizontal anchor, or an error if
+    /// either anchor is uninitialized or the anchors can't be attached to each other.
+    pub fn try_constraint_less_than_or_equal_to(&self, anchor_to: &LayoutAnchorX) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::LessThanOrEqual, |from, to| unsafe {
             msg_send![*from, constraintLessThanOrEqualToAnchor:&**to]
         })
     }
 }
+
+/// Whether an attribute is direction-dependent (leading/trailing) or not (left/right). Center
+/// anchors can attach to either, so they have no direction.
+fn direction(attribute: LayoutAttribute) -> Option<bool> {
+    match attribute {
+        LayoutAttribute::Leading | LayoutAttribute::Trailing => Some(true),
+        LayoutAttribute::Left | LayoutAttribute::Right => Some(false),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutAnchorX;
+    use crate::layout::{AnchorPosition, LayoutEngine, LayoutError};
+
+    #[test]
+    fn test_uninitialized_anchors() {
+        let engine = LayoutEngine::new();
+        let node = engine.add_node(&engine.root());
+        let uninitialized = LayoutAnchorX::default();
+
+        let error = |position, other: Option<&str>| LayoutError::UninitializedAnchor {
+            axis: "x",
+            position,
+            other: other.map(String::from)
+        };
+
+        assert_eq!(
+            uninitialized
+                .try_constraint_equal_to(&LayoutAnchorX::Uninitialized)
+                .unwrap_err(),
+            error(AnchorPosition::Both, None)
+        );
+
+        assert_eq!(
+            uninitialized
+                .try_constraint_greater_than_or_equal_to(&node.leading)
+                .unwrap_err(),
+            error(AnchorPosition::From, Some("LayoutNode 1's Leading anchor"))
+        );
+
+        assert_eq!(
+            node.trailing
+                .try_constraint_less_than_or_equal_to(&uninitialized)
+                .unwrap_err(),
+            error(AnchorPosition::To, Some("LayoutNode 1's Trailing anchor"))
+        );
+
+        assert!(node.leading.try_constraint_equal_to(&engine.root().leading).is_ok());
+    }
+}
@@ -2,14 +2,32 @@
 //! are supported via the `Layout` trait, which all widgets implement. If you opt in to the
 //! `AutoLayout` feature, each widget will default to using AutoLayout, which can be beneficial in
 //! more complicated views that need to deal with differing screen sizes.
+//!
+//! Constraints normally proxy to the system's autolayout engine. A `LayoutEngine` can solve the
+//! same constraints in pure Rust instead, for a tree of `LayoutNode`s - handy for unit tests, and
+//! for platforms without good autolayout support.
+//!
+//! Without the `autolayout` feature, `Layout::set_autoresizing_mask()` lets views follow their
+//! superview's size, and `ViewDelegate::layout_subviews()` is the place to reposition anything
+//! else when a view is resized.
+//!
+//! Views also have content hugging and compression resistance priorities (see `LayoutPriority`),
+//! which decide which of them grows or shrinks first when their intrinsic sizes don't fit.
+//!
+//! Anchors have `try_constraint_*` variants of their constraint methods, which return a
+//! `LayoutError` rather than panicking, and `check_layout()` collects broken and ambiguous
+//! layouts from the system in debug builds.
 
 mod traits;
 pub use traits::Layout;
 
-#[cfg(all(feature = "appkit", target_os = "macos"))]
+mod autoresizing;
+pub use autoresizing::AutoresizingMask;
+
+#[cfg(all(feature = "appkit", feature = "autolayout", target_os = "macos"))]
 mod animator;
 
-#[cfg(all(feature = "appkit", target_os = "macos"))]
+#[cfg(all(feature = "appkit", feature = "autolayout", target_os = "macos"))]
 pub use animator::LayoutConstraintAnimatorProxy;
 
 #[cfg(feature = "autolayout")]
@@ -24,6 +42,40 @@ mod constraint;
 #[cfg(feature = "autolayout")]
 pub use constraint::LayoutConstraint;
 
+#[cfg(feature = "autolayout")]
+mod priority;
+
+#[cfg(feature = "autolayout")]
+pub use priority::LayoutPriority;
+
+#[cfg(feature = "autolayout")]
+mod error;
+
+#[cfg(feature = "autolayout")]
+pub use error::{AnchorPosition, LayoutError};
+
+#[cfg(all(feature = "autolayout", any(feature = "appkit", feature = "uikit")))]
+mod debug;
+
+#[cfg(all(feature = "autolayout", any(feature = "appkit", feature = "uikit")))]
+pub use debug::check_layout;
+
+#[cfg(feature = "autolayout")]
+mod engine;
+
+#[cfg(feature = "autolayout")]
+pub use engine::{EngineAnchor, LayoutEngine, LayoutNode};
+
+#[cfg(feature = "autolayout")]
+mod dsl;
+
+#[cfg(feature = "autolayout")]
+pub use dsl::{ConstraintInsets, ConstraintTarget};
+
+#[cfg(feature = "autolayout")]
+#[doc(hidden)]
+pub use dsl::{relate, relate_scaled};
+
 #[cfg(feature = "autolayout")]
 mod dimension;
 
@@ -42,6 +94,12 @@ mod vertical;
 #[cfg(feature = "autolayout")]
 pub use vertical::LayoutAnchorY;
 
+#[cfg(feature = "autolayout")]
+mod guide;
+
+#[cfg(feature = "autolayout")]
+pub use guide::LayoutGuide;
+
 #[cfg(feature = "autolayout")]
 mod safe_guide;
 
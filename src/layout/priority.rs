@@ -0,0 +1,117 @@
+//! Priorities for constraints, content hugging and compression resistance.
+
+use std::cmp::Ordering;
+
+/// How strongly the layout system tries to satisfy a constraint, or keep a view at its intrinsic
+/// size, relative to everything else it's solving for. Priorities range from 1 to 1000; anything
+/// below `Required` can be broken if it conflicts with something more important.
+///
+/// The named levels match the system's. For something in between, use `Custom` - or `offset()`,
+/// to place a priority just above or below one of the named levels:
+///
+/// ```rust,no_run
+/// use cacao::layout::{Layout, LayoutConstraintOrientation, LayoutPriority};
+/// use cacao::text::Label;
+///
+/// // Keep this label from being squashed before anything else in the row.
+/// let label = Label::new();
+/// let priority = LayoutPriority::High.offset(1.);
+/// label.set_content_compression_resistance_priority(priority, LayoutConstraintOrientation::Horizontal);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub enum LayoutPriority {
+    /// The constraint must be satisfied (1000). This is the default for constraints.
+    #[default]
+    Required,
+
+    /// The priority controls and buttons resist compression with by default (750).
+    High,
+
+    /// The priority controls and buttons hug their content with by default (250).
+    Low,
+
+    /// The priority used when sizing a view to fit its content, e.g with `fittingSize` (50).
+    /// Don't use this for constraints directly.
+    Fitting,
+
+    /// Any other priority, from 1 to 1000.
+    Custom(f64)
+}
+
+impl LayoutPriority {
+    /// Returns the numeric value of this priority.
+    pub fn value(&self) -> f64 {
+        match self {
+            LayoutPriority::Required => 1000.,
+            LayoutPriority::High => 750.,
+            LayoutPriority::Low => 250.,
+            LayoutPriority::Fitting => 50.,
+            LayoutPriority::Custom(value) => *value
+        }
+    }
+
+    /// Returns a priority `amount` above (or, if negative, below) this one, clamped to 1...1000.
+    pub fn offset(&self, amount: f64) -> Self {
+        (self.value() + amount).clamp(1., 1000.).into()
+    }
+}
+
+impl From<f64> for LayoutPriority {
+    fn from(value: f64) -> Self {
+        match value {
+            1000. => LayoutPriority::Required,
+            750. => LayoutPriority::High,
+            250. => LayoutPriority::Low,
+            50. => LayoutPriority::Fitting,
+            value => LayoutPriority::Custom(value)
+        }
+    }
+}
+
+impl From<f32> for LayoutPriority {
+    fn from(value: f32) -> Self {
+        LayoutPriority::from(value as f64)
+    }
+}
+
+impl From<LayoutPriority> for f64 {
+    fn from(priority: LayoutPriority) -> Self {
+        priority.value()
+    }
+}
+
+impl From<LayoutPriority> for f32 {
+    fn from(priority: LayoutPriority) -> Self {
+        priority.value() as f32
+    }
+}
+
+impl PartialEq for LayoutPriority {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl PartialOrd for LayoutPriority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value().partial_cmp(&other.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutPriority;
+
+    #[test]
+    fn test_priority_levels() {
+        assert!(LayoutPriority::Required > LayoutPriority::High);
+        assert!(LayoutPriority::Low > LayoutPriority::Fitting);
+        assert_eq!(LayoutPriority::Custom(750.), LayoutPriority::High);
+
+        assert_eq!(LayoutPriority::from(250.), LayoutPriority::Low);
+        assert!(matches!(LayoutPriority::from(250f32), LayoutPriority::Low));
+        assert!(matches!(LayoutPriority::High.offset(1.), LayoutPriority::Custom(v) if v == 751.));
+        assert_eq!(LayoutPriority::Required.offset(1.), LayoutPriority::Required);
+        assert_eq!(f64::from(LayoutPriority::Fitting), 50.);
+    }
+}
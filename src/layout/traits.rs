@@ -8,8 +8,18 @@ use objc::runtime::Object;
 use objc::{msg_send, sel, sel_impl};
 use objc_id::ShareId;
 
-use crate::foundation::{id, nil, to_bool, NSArray, NSString, NO, YES};
+use crate::foundation::{id, nil, to_bool, NSArray, NSString, NSUInteger, NO, YES};
 use crate::geometry::Rect;
+use crate::layout::AutoresizingMask;
+
+#[cfg(feature = "autolayout")]
+use crate::foundation::NSInteger;
+
+#[cfg(feature = "autolayout")]
+use crate::geometry::Size;
+
+#[cfg(feature = "autolayout")]
+use crate::layout::{LayoutConstraintOrientation, LayoutGuide, LayoutPriority};
 use crate::objc_access::ObjcAccess;
 
 #[cfg(feature = "appkit")]
@@ -61,6 +71,29 @@ pub trait Layout: ObjcAccess {
         });
     }
 
+    /// Sets how this view's frame should change when its superview is resized - e.g,
+    /// `&[AutoresizingMask::FlexibleWidth]` to keep the same distance from both the left and right
+    /// edges. An empty slice keeps the frame fixed.
+    ///
+    /// Masks only apply to frame-based views; with the `autolayout` feature, this also opts the
+    /// view in to translating its mask into constraints (see
+    /// `set_translates_autoresizing_mask_into_constraints`).
+    fn set_autoresizing_mask(&self, masks: &[AutoresizingMask]) {
+        let mut mask: NSUInteger = 0;
+
+        for m in masks {
+            let i: NSUInteger = m.into();
+            mask |= i;
+        }
+
+        self.with_backing_obj_mut(|backing_node| unsafe {
+            #[cfg(feature = "autolayout")]
+            let _: () = msg_send![backing_node, setTranslatesAutoresizingMaskIntoConstraints: YES];
+
+            let _: () = msg_send![backing_node, setAutoresizingMask: mask];
+        });
+    }
+
     /// Sets whether the view for this trait should translate autoresizing masks into layout
     /// constraints.
     ///
@@ -76,6 +109,119 @@ pub trait Layout: ObjcAccess {
         });
     }
 
+    /// Adds a layout guide to this view, so that it can be constrained to this view and its
+    /// subviews.
+    #[cfg(feature = "autolayout")]
+    fn add_layout_guide(&self, guide: &LayoutGuide) {
+        self.with_backing_obj_mut(|backing_node| {
+            guide.objc.with_mut(|guide| unsafe {
+                let _: () = msg_send![backing_node, addLayoutGuide: guide];
+            });
+        });
+    }
+
+    /// Removes a layout guide from this view, deactivating any constraints that involve it.
+    #[cfg(feature = "autolayout")]
+    fn remove_layout_guide(&self, guide: &LayoutGuide) {
+        self.with_backing_obj_mut(|backing_node| {
+            guide.objc.with_mut(|guide| unsafe {
+                let _: () = msg_send![backing_node, removeLayoutGuide: guide];
+            });
+        });
+    }
+
+    /// Sets how strongly this view resists growing beyond its intrinsic size along `orientation`.
+    /// When there's more room than the views in a layout need, the one with the lowest hugging
+    /// priority grows first.
+    #[cfg(feature = "autolayout")]
+    fn set_content_hugging_priority<P: Into<LayoutPriority>>(&self, priority: P, orientation: LayoutConstraintOrientation) {
+        let priority: f32 = priority.into().into();
+        let orientation: NSInteger = orientation.into();
+
+        self.with_backing_obj_mut(|obj| unsafe {
+            #[cfg(feature = "appkit")]
+            let _: () = msg_send![obj, setContentHuggingPriority:priority forOrientation:orientation];
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            let _: () = msg_send![obj, setContentHuggingPriority:priority forAxis:orientation];
+        });
+    }
+
+    /// Returns how strongly this view resists growing beyond its intrinsic size along
+    /// `orientation`.
+    #[cfg(feature = "autolayout")]
+    fn content_hugging_priority(&self, orientation: LayoutConstraintOrientation) -> LayoutPriority {
+        let orientation: NSInteger = orientation.into();
+
+        self.get_from_backing_obj(|obj| unsafe {
+            #[cfg(feature = "appkit")]
+            let priority: f32 = msg_send![obj, contentHuggingPriorityForOrientation: orientation];
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            let priority: f32 = msg_send![obj, contentHuggingPriorityForAxis: orientation];
+
+            priority.into()
+        })
+    }
+
+    /// Sets how strongly this view resists shrinking below its intrinsic size along
+    /// `orientation`. When there's less room than the views in a layout need, the one with the
+    /// lowest compression resistance priority shrinks first.
+    #[cfg(feature = "autolayout")]
+    fn set_content_compression_resistance_priority<P: Into<LayoutPriority>>(
+        &self,
+        priority: P,
+        orientation: LayoutConstraintOrientation
+    ) {
+        let priority: f32 = priority.into().into();
+        let orientation: NSInteger = orientation.into();
+
+        self.with_backing_obj_mut(|obj| unsafe {
+            #[cfg(feature = "appkit")]
+            let _: () = msg_send![obj, setContentCompressionResistancePriority:priority forOrientation:orientation];
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            let _: () = msg_send![obj, setContentCompressionResistancePriority:priority forAxis:orientation];
+        });
+    }
+
+    /// Returns how strongly this view resists shrinking below its intrinsic size along
+    /// `orientation`.
+    #[cfg(feature = "autolayout")]
+    fn content_compression_resistance_priority(&self, orientation: LayoutConstraintOrientation) -> LayoutPriority {
+        let orientation: NSInteger = orientation.into();
+
+        self.get_from_backing_obj(|obj| unsafe {
+            #[cfg(feature = "appkit")]
+            let priority: f32 = msg_send![obj, contentCompressionResistancePriorityForOrientation: orientation];
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            let priority: f32 = msg_send![obj, contentCompressionResistancePriorityForAxis: orientation];
+
+            priority.into()
+        })
+    }
+
+    /// Returns the size this view would like to be, based on its content alone - e.g, the size of
+    /// a label's text. Dimensions the view has no preference for (as with a plain `View`) are
+    /// reported as `-1`.
+    #[cfg(feature = "autolayout")]
+    fn intrinsic_content_size(&self) -> Size {
+        self.get_from_backing_obj(|obj| {
+            let size: CGSize = unsafe { msg_send![obj, intrinsicContentSize] };
+            size.into()
+        })
+    }
+
+    /// Tells the layout system that this view's intrinsic content size has changed, e.g because
+    /// its content did, so that constraints depending on it are re-solved.
+    #[cfg(feature = "autolayout")]
+    fn invalidate_intrinsic_content_size(&self) {
+        self.with_backing_obj_mut(|obj| unsafe {
+            let _: () = msg_send![obj, invalidateIntrinsicContentSize];
+        });
+    }
+
     /// Sets whether the view for this is hidden or not.
     ///
     /// When hidden, widgets don't receive events and is not visible.
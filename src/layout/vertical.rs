@@ -4,6 +4,9 @@ use objc_id::ShareId;
 
 use crate::foundation::id;
 use crate::layout::constraint::LayoutConstraint;
+use crate::layout::engine::EngineAnchor;
+use crate::layout::error::{describe, uninitialized};
+use crate::layout::{LayoutError, LayoutRelation};
 
 /// A wrapper for `NSLayoutAnchorY`, used to handle values for how a given view should
 /// layout along the y-axis.
@@ -19,7 +22,10 @@ pub enum LayoutAnchorY {
     Bottom(ShareId<Object>),
 
     /// Represents a center anchor for the Y axis.
-    Center(ShareId<Object>)
+    Center(ShareId<Object>),
+
+    /// Represents an anchor on a `LayoutNode`, solved by a `LayoutEngine` rather than the system.
+    Engine(EngineAnchor)
 }
 
 impl Default for LayoutAnchorY {
@@ -44,14 +50,30 @@ impl LayoutAnchorY {
         Self::Center(unsafe { ShareId::from_ptr(msg_send![view, centerYAnchor]) })
     }
 
-    /// Boilerplate for handling constraint construction and panic'ing with some more helpful
-    /// messages. The goal here is to make AutoLayout slightly easier to debug when things go
-    /// wrong.
-    fn constraint_with<F>(&self, anchor_to: &LayoutAnchorY, handler: F) -> LayoutConstraint
+    /// Describes this anchor and the view it belongs to, for errors. Returns `None` if it's
+    /// uninitialized.
+    fn description(&self) -> Option<String> {
+        match self {
+            Self::Uninitialized => None,
+            Self::Top(obj) | Self::Bottom(obj) | Self::Center(obj) => Some(describe(obj)),
+            Self::Engine(anchor) => Some(anchor.description())
+        }
+    }
+
+    /// Boilerplate for handling constraint construction and returning some more helpful errors.
+    /// The goal here is to make AutoLayout slightly easier to debug when things go wrong.
+    fn constraint_with<F>(
+        &self,
+        anchor_to: &LayoutAnchorY,
+        relation: LayoutRelation,
+        handler: F
+    ) -> Result<LayoutConstraint, LayoutError>
     where
         F: Fn(&ShareId<Object>, &ShareId<Object>) -> id
     {
         match (self, anchor_to) {
+            (Self::Engine(from), Self::Engine(to)) => from.constraint(relation, Some(to), 1., 0.),
+
             (Self::Top(from), Self::Top(to))
             | (Self::Top(from), Self::Bottom(to))
             | (Self::Top(from), Self::Center(to))
@@ -60,40 +82,102 @@ impl LayoutAnchorY {
             | (Self::Bottom(from), Self::Center(to))
             | (Self::Center(from), Self::Center(to))
             | (Self::Center(from), Self::Top(to))
-            | (Self::Center(from), Self::Bottom(to)) => LayoutConstraint::new(handler(from, to)),
-
-            (Self::Uninitialized, Self::Uninitialized) => {
-                panic!("Attempted to create constraints with uninitialized \"from\" and \"to\" y anchors.");
-            },
+            | (Self::Center(from), Self::Bottom(to)) => Ok(LayoutConstraint::new(handler(from, to))),
 
-            (Self::Uninitialized, _) => {
-                panic!("Attempted to create constraints with an uninitialized \"from\" y anchor.");
+            (Self::Uninitialized, _) | (_, Self::Uninitialized) => {
+                Err(uninitialized("y", self.description(), anchor_to.description()))
             },
 
-            (_, Self::Uninitialized) => {
-                panic!("Attempted to create constraints with an uninitialized \"to\" y anchor.");
-            }
+            (Self::Engine(_), _) | (_, Self::Engine(_)) => Err(LayoutError::MismatchedEngines {
+                from: self.description().unwrap_or_default(),
+                to: anchor_to.description().unwrap_or_default()
+            })
         }
     }
 
     /// Return a constraint equal to another vertical anchor.
+    ///
+    /// Panics if either anchor is uninitialized; see `try_constraint_equal_to()`.
     pub fn constraint_equal_to(&self, anchor_to: &LayoutAnchorY) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
-            msg_send![*from, constraintEqualToAnchor:&**to]
-        })
+        self.try_constraint_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Return a constraint greater than or equal to another vertical anchor.
+    ///
+    /// Panics if either anchor is uninitialized; see `try_constraint_greater_than_or_equal_to()`.
     pub fn constraint_greater_than_or_equal_to(&self, anchor_to: &LayoutAnchorY) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
-            msg_send![*from, constraintGreaterThanOrEqualToAnchor:&**to]
-        })
+        self.try_constraint_greater_than_or_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
     /// Return a constraint less than or equal to another vertical anchor.
+    ///
+    /// Panics if either anchor is uninitialized; see `try_constraint_less_than_or_equal_to()`.
     pub fn constraint_less_than_or_equal_to(&self, anchor_to: &LayoutAnchorY) -> LayoutConstraint {
-        self.constraint_with(anchor_to, |from, to| unsafe {
+        self.try_constraint_less_than_or_equal_to(anchor_to)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Return a constraint equal to another vertical anchor, or an error if either anchor is
+    /// uninitialized.
+    pub fn try_constraint_equal_to(&self, anchor_to: &LayoutAnchorY) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::Equal, |from, to| unsafe {
+            msg_send![*from, constraintEqualToAnchor:&**to]
+        })
+    }
+
+    /// Return a constraint greater than or equal to another vertical anchor, or an error if
+    /// either anchor is uninitialized.
+    pub fn try_constraint_greater_than_or_equal_to(&self, anchor_to: &LayoutAnchorY) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::GreaterThanOrEqual, |from, to| unsafe {
+            msg_send![*from, constraintGreaterThanOrEqualToAnchor:&**to]
+        })
+    }
+
+    /// Return a constraint less than or equal to another vertical anchor, or an error if either
+    /// anchor is uninitialized.
+    pub fn try_constraint_less_than_or_equal_to(&self, anchor_to: &LayoutAnchorY) -> Result<LayoutConstraint, LayoutError> {
+        self.constraint_with(anchor_to, LayoutRelation::LessThanOrEqual, |from, to| unsafe {
             msg_send![*from, constraintLessThanOrEqualToAnchor:&**to]
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LayoutAnchorY;
+    use crate::layout::{AnchorPosition, LayoutEngine, LayoutError};
+
+    #[test]
+    fn test_uninitialized_anchors() {
+        let engine = LayoutEngine::new();
+        let node = engine.add_node(&engine.root());
+        let uninitialized = LayoutAnchorY::default();
+
+        let error = |position, other: Option<&str>| LayoutError::UninitializedAnchor {
+            axis: "y",
+            position,
+            other: other.map(String::from)
+        };
+
+        assert_eq!(
+            uninitialized
+                .try_constraint_equal_to(&LayoutAnchorY::Uninitialized)
+                .unwrap_err(),
+            error(AnchorPosition::Both, None)
+        );
+
+        assert_eq!(
+            uninitialized.try_constraint_greater_than_or_equal_to(&node.top).unwrap_err(),
+            error(AnchorPosition::From, Some("LayoutNode 1's Top anchor"))
+        );
+
+        assert_eq!(
+            node.bottom.try_constraint_less_than_or_equal_to(&uninitialized).unwrap_err(),
+            error(AnchorPosition::To, Some("LayoutNode 1's Bottom anchor"))
+        );
+
+        assert!(node.top.try_constraint_equal_to(&engine.root().top).is_ok());
+    }
+}
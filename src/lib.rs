@@ -71,22 +71,24 @@
 //! The following are a list of [Cargo features][cargo-features] that can be enabled or disabled.
 //!
 //! - `autolayout`: Enables the use of AutoLayout across all widget types. This is a default
-//! feature, but is gated to enable platforms that might shim AppKit without AutoLayout support.
+//!   feature, but is gated to enable platforms that might shim AppKit without AutoLayout support.
 //! - `cloudkit`: Links `CloudKit.framework` and provides some wrappers around CloudKit
-//! functionality. Currently not feature complete.
+//!   functionality. Currently not feature complete.
 //! - `color_fallbacks`: Provides fallback colors for older systems where `systemColor` types don't
-//! exist. This feature is very uncommon and you probably don't need it.
+//!   exist. This feature is very uncommon and you probably don't need it.
 //! - `quicklook`: Links `QuickLook.framework` and offers methods for generating preview images for
-//! files.
+//!   files.
+//! - `serde`: Enables typed `get_typed`/`set_typed` methods on `UserDefaults`, which can store any
+//!   type implementing `Serialize` and `Deserialize`.
 //! - `user-notifications`: Links `UserNotifications.framework` and provides functionality for
-//! emitting notifications on appkit and uikit. Note that this _requires_ your application be
-//! code-signed, and will not work without it.
+//!   emitting notifications on appkit and uikit. Note that this _requires_ your application be
+//!   code-signed, and will not work without it.
 //! - `webview`: Links `WebKit.framework` and provides a `WebView` control backed by `WKWebView`.
-//! This feature will not be supported on tvOS, as the platform has no webview control.
+//!   This feature will not be supported on tvOS, as the platform has no webview control.
 //! - `webview-downloading-macos`: Enables downloading files from the `WebView` via a private
-//! interface. This is not an App-Store-safe feature, so be aware of that before enabling. This
-//! feature is not supported on uikit (a user would handle downloads very differently) or tvOS
-//! (there's no web browser there at all).
+//!   interface. This is not an App-Store-safe feature, so be aware of that before enabling. This
+//!   feature is not supported on uikit (a user would handle downloads very differently) or tvOS
+//!   (there's no web browser there at all).
 //!
 //! [cargo-features]: https://doc.rust-lang.org/stable/cargo/reference/manifest.html#the-features-section
 
@@ -155,6 +157,9 @@ pub mod networking;
 pub mod notification_center;
 pub(crate) mod objc_access;
 
+#[cfg(feature = "appkit")]
+pub mod outlineview;
+
 #[cfg(feature = "appkit")]
 pub mod pasteboard;
 
@@ -170,6 +175,9 @@ pub mod switch;
 #[cfg(feature = "appkit")]
 pub mod select;
 
+#[cfg(feature = "appkit")]
+pub mod tableview;
+
 #[cfg(feature = "appkit")]
 pub mod text;
 
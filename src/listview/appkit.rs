@@ -7,6 +7,7 @@
 //! for in the modern era. It also implements a few helpers for things like setting a background
 //! color, and enforcing layer backing by default.
 
+use std::cell::RefCell;
 use std::sync::Once;
 
 use objc::declare::ClassDecl;
@@ -15,15 +16,76 @@ use objc::{class, msg_send, sel, sel_impl};
 use objc_id::Id;
 
 use crate::appkit::menu::{Menu, MenuItem};
+use crate::appkit::Event;
 use crate::dragdrop::DragInfo;
-use crate::foundation::{id, load_or_register_class, nil, NSArray, NSInteger, NSUInteger, NO, YES};
-use crate::listview::{ListViewDelegate, RowEdge, LISTVIEW_DELEGATE_PTR};
+use crate::foundation::{id, load_or_register_class, nil, NSArray, NSInteger, NSString, NSUInteger, NO, YES};
+use crate::listview::{
+    selected_rows, IndexPath, ListViewDelegate, RowEdge, RowKind, RowMap, LISTVIEW_DELEGATE_PTR, LISTVIEW_ROW_MAP_PTR
+};
 use crate::utils::load;
 
-/// Determines the number of items by way of the backing data source (the Rust struct).
+/// Returns the cached `RowMap` for a list view.
+fn row_map(this: &Object) -> &RefCell<Option<RowMap>> {
+    load::<RefCell<Option<RowMap>>>(this, LISTVIEW_ROW_MAP_PTR)
+}
+
+/// Returns what a row displays. This goes through the cached `RowMap`, so the delegate is only
+/// asked about its sections when the rows have changed since the last callback.
+fn kind_of_row<T: ListViewDelegate>(this: &Object, view: &T, row: NSInteger) -> Option<RowKind> {
+    row_map(this)
+        .borrow_mut()
+        .get_or_insert_with(|| RowMap::new(view))
+        .kind_of_row(row as usize)
+}
+
+/// Returns the index paths of the selected items. Header and footer rows can't be selected, but
+/// are skipped all the same.
+fn selected_index_paths<T: ListViewDelegate>(this: &Object, view: &T) -> Vec<IndexPath> {
+    let rows = selected_rows(this);
+
+    row_map(this)
+        .borrow_mut()
+        .get_or_insert_with(|| RowMap::new(view))
+        .index_paths_for_rows(&rows)
+}
+
+/// The key presses `ListView` hands to its delegate, rather than to `NSTableView`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ListViewKey {
+    /// Delete, or forward delete - see `ListViewDelegate::delete_pressed()`.
+    Delete,
+
+    /// Return, or Enter - see `ListViewDelegate::return_pressed()`.
+    Return
+}
+
+impl ListViewKey {
+    /// Matches the characters of a key press, as `NSEvent` reports them.
+    fn from_characters(characters: &str) -> Option<Self> {
+        match characters {
+            // NSDeleteCharacter, NSDeleteFunctionKey
+            "\u{7f}" | "\u{f728}" => Some(ListViewKey::Delete),
+
+            // NSCarriageReturnCharacter, NSEnterCharacter
+            "\r" | "\u{3}" => Some(ListViewKey::Return),
+
+            _ => None
+        }
+    }
+}
+
+/// Determines the number of rows by way of the backing data source (the Rust struct). Sections
+/// are flattened into rows, headers and footers included.
+///
+/// `NSTableView` asks for this whenever it reloads, so this is where the cached `RowMap` is
+/// rebuilt.
 extern "C" fn number_of_items<T: ListViewDelegate>(this: &Object, _: Sel, _: id) -> NSInteger {
     let view = load::<T>(this, LISTVIEW_DELEGATE_PTR);
-    view.number_of_items() as NSInteger
+    let map = RowMap::new(view);
+    let rows = map.len();
+
+    row_map(this).replace(Some(map));
+    rows as NSInteger
 }
 
 extern "C" fn view_for_column<T: ListViewDelegate>(
@@ -41,7 +103,18 @@ extern "C" fn view_for_column<T: ListViewDelegate>(
     }*/
 
     let view = load::<T>(this, LISTVIEW_DELEGATE_PTR);
-    let item = view.item_for(item as usize);
+
+    let item = match kind_of_row(this, view, item) {
+        Some(RowKind::Header(section)) => view.header_for_section(section),
+        Some(RowKind::Item(index_path)) => Some(view.item_for_index_path(index_path)),
+        Some(RowKind::Footer(section)) => view.footer_for_section(section),
+        None => None
+    };
+
+    let item = match item {
+        Some(item) => item,
+        None => return nil
+    };
 
     // A hacky method of returning the underlying pointer
     // without Rust annoying us.
@@ -67,6 +140,75 @@ extern "C" fn will_display_cell<T: ListViewDelegate>(
     view.will_display_item(item as usize);
 }
 
+/// Section headers are shown as group rows, which `NSTableView` styles (and floats) as such.
+extern "C" fn is_group_row<T: ListViewDelegate>(this: &Object, _: Sel, _table_view: id, row: NSInteger) -> BOOL {
+    let view = load::<T>(this, LISTVIEW_DELEGATE_PTR);
+
+    match kind_of_row(this, view, row) {
+        Some(RowKind::Header(_)) => YES,
+        _ => NO
+    }
+}
+
+/// Only items can be selected - not section headers or footers - and then only if the delegate
+/// agrees.
+extern "C" fn should_select_row<T: ListViewDelegate>(this: &Object, _: Sel, _table_view: id, row: NSInteger) -> BOOL {
+    let view = load::<T>(this, LISTVIEW_DELEGATE_PTR);
+
+    match kind_of_row(this, view, row) {
+        Some(RowKind::Item(index_path)) => match view.should_select(index_path) {
+            true => YES,
+            false => NO
+        },
+        _ => NO
+    }
+}
+
+/// Returns the string type-to-select matches against for a row. Headers and footers don't take
+/// part, as they can't be selected.
+extern "C" fn type_select_string<T: ListViewDelegate>(
+    this: &Object,
+    _: Sel,
+    _table_view: id,
+    _table_column: id,
+    row: NSInteger
+) -> id {
+    let view = load::<T>(this, LISTVIEW_DELEGATE_PTR);
+
+    match kind_of_row(this, view, row) {
+        Some(RowKind::Item(index_path)) => match view.type_select_string(index_path) {
+            // The table view doesn't take ownership of the string, so hand back an autoreleased one.
+            Some(string) => unsafe {
+                let string = NSString::new(&string);
+                let string: id = msg_send![&*string, retain];
+                msg_send![string, autorelease]
+            },
+
+            None => nil
+        },
+        _ => nil
+    }
+}
+
+/// Hands Delete and Return key presses to the delegate, and everything else (or anything the
+/// delegate didn't handle) to `NSTableView`.
+extern "C" fn key_down<T: ListViewDelegate>(this: &Object, _: Sel, event: id) {
+    let view = load::<T>(this, LISTVIEW_DELEGATE_PTR);
+    let characters = Event::new(event).characters();
+
+    let handled = match ListViewKey::from_characters(&characters) {
+        Some(ListViewKey::Delete) => view.delete_pressed(&selected_index_paths(this, view)),
+        Some(ListViewKey::Return) => view.return_pressed(&selected_index_paths(this, view)),
+        None => false
+    };
+
+    if !handled {
+        unsafe {
+            let _: () = msg_send![super(this, class!(NSTableView)), keyDown: event];
+        }
+    }
+}
+
 extern "C" fn menu_needs_update<T: ListViewDelegate>(this: &Object, _: Sel, menu: id) {
     let view = load::<T>(this, LISTVIEW_DELEGATE_PTR);
     let items = view.context_menu();
@@ -91,10 +233,8 @@ extern "C" fn select_row<T: ListViewDelegate>(
 }*/
 
 extern "C" fn selection_did_change<T: ListViewDelegate>(this: &Object, _: Sel, notification: id) {
-    let selected_row: NSInteger = unsafe {
-        let tableview: id = msg_send![notification, object];
-        msg_send![tableview, selectedRow]
-    };
+    let tableview: id = unsafe { msg_send![notification, object] };
+    let selected_row: NSInteger = unsafe { msg_send![tableview, selectedRow] };
 
     let view = load::<T>(this, LISTVIEW_DELEGATE_PTR);
     if selected_row == -1 {
@@ -102,6 +242,8 @@ extern "C" fn selection_did_change<T: ListViewDelegate>(this: &Object, _: Sel, n
     } else {
         view.item_selected(Some(selected_row as usize));
     }
+
+    view.selection_changed(&selected_index_paths(this, view));
 }
 
 extern "C" fn row_actions_for_row<T: ListViewDelegate>(
@@ -204,6 +346,7 @@ pub(crate) fn register_listview_class() -> *const Class {
 pub(crate) fn register_listview_class_with_delegate<T: ListViewDelegate>(instance: &T) -> *const Class {
     load_or_register_class("NSTableView", instance.subclass_name(), |decl| unsafe {
         decl.add_ivar::<usize>(LISTVIEW_DELEGATE_PTR);
+        decl.add_ivar::<usize>(LISTVIEW_ROW_MAP_PTR);
 
         decl.add_method(sel!(isFlipped), enforce_normalcy as extern "C" fn(&Object, _) -> BOOL);
 
@@ -220,6 +363,18 @@ pub(crate) fn register_listview_class_with_delegate<T: ListViewDelegate>(instanc
             sel!(tableView:viewForTableColumn:row:),
             view_for_column::<T> as extern "C" fn(&Object, _, id, id, NSInteger) -> id
         );
+        decl.add_method(
+            sel!(tableView:isGroupRow:),
+            is_group_row::<T> as extern "C" fn(&Object, _, id, NSInteger) -> BOOL
+        );
+        decl.add_method(
+            sel!(tableView:shouldSelectRow:),
+            should_select_row::<T> as extern "C" fn(&Object, _, id, NSInteger) -> BOOL
+        );
+        decl.add_method(
+            sel!(tableView:typeSelectStringForTableColumn:row:),
+            type_select_string::<T> as extern "C" fn(&Object, _, id, id, NSInteger) -> id
+        );
         decl.add_method(
             sel!(tableViewSelectionDidChange:),
             selection_did_change::<T> as extern "C" fn(&Object, _, id)
@@ -229,6 +384,9 @@ pub(crate) fn register_listview_class_with_delegate<T: ListViewDelegate>(instanc
             row_actions_for_row::<T> as extern "C" fn(&Object, _, id, NSInteger, NSInteger) -> id
         );
 
+        // Keyboard handling, for Delete and Return
+        decl.add_method(sel!(keyDown:), key_down::<T> as extern "C" fn(&Object, _, id));
+
         // A slot for some menu handling; we just let it be done here for now rather than do the
         // whole delegate run, since things are fast enough nowadays to just replace the entire
         // menu.
@@ -260,3 +418,20 @@ pub(crate) fn register_listview_class_with_delegate<T: ListViewDelegate>(instanc
         );
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ListViewKey;
+
+    #[test]
+    fn test_list_view_keys() {
+        assert_eq!(ListViewKey::from_characters("\u{7f}"), Some(ListViewKey::Delete));
+        assert_eq!(ListViewKey::from_characters("\u{f728}"), Some(ListViewKey::Delete));
+        assert_eq!(ListViewKey::from_characters("\r"), Some(ListViewKey::Return));
+        assert_eq!(ListViewKey::from_characters("\u{3}"), Some(ListViewKey::Return));
+
+        assert_eq!(ListViewKey::from_characters("a"), None);
+        assert_eq!(ListViewKey::from_characters(""), None);
+        assert_eq!(ListViewKey::from_characters("\r\r"), None);
+    }
+}
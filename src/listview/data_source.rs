@@ -0,0 +1,157 @@
+use std::cell::RefCell;
+use std::hash::Hash;
+
+use crate::listview::{ListDiff, ListView, RowAnimation};
+
+/// Holds the items backing a `ListView`, and animates the list view whenever they're replaced -
+/// so there's no index math to get wrong. Keep one in your `ListViewDelegate`, and answer
+/// `number_of_items()` and `item_for()` from it:
+///
+/// ```rust,no_run
+/// use cacao::layout::{Layout, LayoutConstraint};
+/// use cacao::listview::{ListDataSource, ListView, ListViewDelegate, ListViewRow, RowAnimation};
+/// use cacao::text::Label;
+/// use cacao::view::{View, ViewDelegate};
+///
+/// const MESSAGE_ROW: &str = "MessageRow";
+///
+/// #[derive(Default)]
+/// struct MessageRow {
+///     subject: Label
+/// }
+///
+/// impl ViewDelegate for MessageRow {
+///     const NAME: &'static str = "MessageRow";
+///
+///     fn did_load(&mut self, view: View) {
+///         view.add_subview(&self.subject);
+///
+///         LayoutConstraint::activate(&[
+///             self.subject.leading.constraint_equal_to(&view.leading).offset(16.),
+///             self.subject.trailing.constraint_equal_to(&view.trailing).offset(-16.),
+///             self.subject.center_y.constraint_equal_to(&view.center_y)
+///         ]);
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct Inbox {
+///     view: Option<ListView>,
+///     messages: ListDataSource<String>
+/// }
+///
+/// impl Inbox {
+///     fn refresh(&self, messages: Vec<String>) {
+///         if let Some(view) = &self.view {
+///             self.messages.set_items(view, messages, RowAnimation::Fade);
+///         }
+///     }
+/// }
+///
+/// impl ListViewDelegate for Inbox {
+///     const NAME: &'static str = "InboxListView";
+///
+///     fn did_load(&mut self, view: ListView) {
+///         view.register(MESSAGE_ROW, MessageRow::default);
+///         self.view = Some(view);
+///     }
+///
+///     fn number_of_items(&self) -> usize {
+///         self.messages.len()
+///     }
+///
+///     fn item_for(&self, row: usize) -> ListViewRow {
+///         let mut view = self.view.as_ref().unwrap().dequeue::<MessageRow>(MESSAGE_ROW);
+///
+///         if let Some(view) = &mut view.delegate {
+///             self.messages.with(row, |subject| view.subject.set_text(subject));
+///         }
+///
+///         view.into_row()
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct ListDataSource<T> {
+    items: RefCell<Vec<T>>
+}
+
+impl<T> ListDataSource<T> {
+    /// Creates a data source with an initial set of items. These aren't animated in; the list
+    /// view picks them up when it first loads (or on `reload()`).
+    pub fn new(items: Vec<T>) -> Self {
+        ListDataSource {
+            items: RefCell::new(items)
+        }
+    }
+
+    /// The number of items, for `ListViewDelegate::number_of_items()`.
+    pub fn len(&self) -> usize {
+        self.items.borrow().len()
+    }
+
+    /// Whether there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.borrow().is_empty()
+    }
+
+    /// Calls `handler` with the item at `row`, if there is one, and returns its result.
+    pub fn with<F, R>(&self, row: usize, handler: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R
+    {
+        self.items.borrow().get(row).map(handler)
+    }
+
+    /// Returns a copy of the item at `row`, if there is one.
+    pub fn get(&self, row: usize) -> Option<T>
+    where
+        T: Clone
+    {
+        self.items.borrow().get(row).cloned()
+    }
+
+    /// Returns a copy of all the items.
+    pub fn items(&self) -> Vec<T>
+    where
+        T: Clone
+    {
+        self.items.borrow().clone()
+    }
+
+    /// Stores the new items, then applies `diff` to the list view. The items are released
+    /// before the list view asks for rows, so `item_for()` can read them.
+    fn update<D>(&self, list_view: &ListView<D>, items: Vec<T>, diff: ListDiff, animation: RowAnimation) -> ListDiff {
+        *self.items.borrow_mut() = items;
+        list_view.apply_diff(&diff, animation);
+        diff
+    }
+}
+
+impl<T: Hash + Eq> ListDataSource<T> {
+    /// Replaces the items, animating rows in, out and around `list_view` to match. Items are
+    /// matched up by equality, so a changed item is removed and re-inserted; if your items have
+    /// a stable identity (e.g, an id), `set_items_by_key()` reloads them in place instead.
+    ///
+    /// Returns the changes that were applied.
+    pub fn set_items<D>(&self, list_view: &ListView<D>, items: Vec<T>, animation: RowAnimation) -> ListDiff {
+        let diff = ListDiff::new(&self.items.borrow(), &items);
+        self.update(list_view, items, diff, animation)
+    }
+}
+
+impl<T: PartialEq> ListDataSource<T> {
+    /// Replaces the items, animating rows in, out and around `list_view` to match. Items are
+    /// matched up by the key `key` returns for them, and changed items with the same key are
+    /// reloaded in place.
+    ///
+    /// Returns the changes that were applied.
+    pub fn set_items_by_key<D, K, F>(&self, list_view: &ListView<D>, items: Vec<T>, animation: RowAnimation, key: F) -> ListDiff
+    where
+        K: Hash + Eq,
+        F: Fn(&T) -> K
+    {
+        let diff = ListDiff::by_key(&self.items.borrow(), &items, key);
+        self.update(list_view, items, diff, animation)
+    }
+}
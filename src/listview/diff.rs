@@ -0,0 +1,244 @@
+//! Computes the row changes between two snapshots of a list, so that a `ListView` can animate
+//! from one to the other without callers having to work out indexes by hand.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// The changes that turn one snapshot of a list into another: which rows were deleted, inserted,
+/// moved, or changed in place.
+///
+/// Items are matched up between the two snapshots by identity - either the items themselves
+/// (`ListDiff::new`), or a key derived from them (`ListDiff::by_key`). Moves are kept to a
+/// minimum: items that are already in the right order relative to each other stay put.
+///
+/// ```rust
+/// use cacao::listview::ListDiff;
+///
+/// let diff = ListDiff::new(&["a", "b", "c", "d"], &["b", "c", "d", "a", "e"]);
+///
+/// assert_eq!(diff.deletes, vec![]);
+/// assert_eq!(diff.inserts, vec![4]);
+/// assert_eq!(diff.moves, vec![(0, 3)]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ListDiff {
+    /// Indexes (in the old snapshot) of rows that were removed, in ascending order.
+    pub deletes: Vec<usize>,
+
+    /// Indexes (in the new snapshot) of rows that were added, in ascending order.
+    pub inserts: Vec<usize>,
+
+    /// Rows that changed position, as `(old index, new index)` pairs, ordered by new index.
+    pub moves: Vec<(usize, usize)>,
+
+    /// Indexes (in the new snapshot) of rows whose item changed, but kept its identity. Always
+    /// empty for `ListDiff::new`, where an item's identity is the item itself.
+    pub reloads: Vec<usize>,
+
+    /// Every item present in both snapshots, as `(old index, new index)` pairs, ordered by new
+    /// index.
+    matches: Vec<(usize, usize)>
+}
+
+impl ListDiff {
+    /// Diffs two snapshots, matching items up by equality.
+    pub fn new<T: Hash + Eq>(old: &[T], new: &[T]) -> Self {
+        ListDiff::by_key(old, new, |item| item)
+    }
+
+    /// Diffs two snapshots, matching items up by the key `key` returns for them (e.g, a database
+    /// id). Items with the same key that aren't equal are reported as `reloads`.
+    ///
+    /// If several items share a key, they're matched up in order.
+    pub fn by_key<'a, T, K, F>(old: &'a [T], new: &'a [T], key: F) -> Self
+    where
+        T: PartialEq,
+        K: Hash + Eq,
+        F: Fn(&'a T) -> K
+    {
+        let mut old_indexes: HashMap<K, VecDeque<usize>> = HashMap::new();
+        for (index, item) in old.iter().enumerate() {
+            old_indexes.entry(key(item)).or_default().push_back(index);
+        }
+
+        let mut matched = vec![false; old.len()];
+        let mut diff = ListDiff::default();
+
+        for (index, item) in new.iter().enumerate() {
+            match old_indexes.get_mut(&key(item)).and_then(|indexes| indexes.pop_front()) {
+                Some(old_index) => {
+                    matched[old_index] = true;
+                    diff.matches.push((old_index, index));
+
+                    if old[old_index] != *item {
+                        diff.reloads.push(index);
+                    }
+                },
+
+                None => diff.inserts.push(index)
+            }
+        }
+
+        diff.deletes = (0..old.len()).filter(|index| !matched[*index]).collect();
+
+        // The longest run of matches that are already in order can stay where they are; every
+        // other match has to move.
+        let old_order: Vec<usize> = diff.matches.iter().map(|(old_index, _)| *old_index).collect();
+        let mut stays = vec![false; old_order.len()];
+        for position in longest_increasing_subsequence(&old_order) {
+            stays[position] = true;
+        }
+
+        diff.moves = diff
+            .matches
+            .iter()
+            .zip(stays)
+            .filter(|(_, stays)| !stays)
+            .map(|(pair, _)| *pair)
+            .collect();
+
+        diff
+    }
+
+    /// Whether the two snapshots were identical.
+    pub fn is_empty(&self) -> bool {
+        self.deletes.is_empty() && self.inserts.is_empty() && self.moves.is_empty() && self.reloads.is_empty()
+    }
+
+    /// Translates `moves` into calls that are applied one after another, as `NSTableView`
+    /// expects within an update block: each `(from, to)` pair is relative to the rows as they are
+    /// after the previous calls. These run after `deletes` have been removed, and before
+    /// `inserts` are added.
+    pub(crate) fn sequential_moves(&self) -> Vec<(usize, usize)> {
+        if self.moves.is_empty() {
+            return Vec::new();
+        }
+
+        // The rows left once deletes are gone, identified by their old index.
+        let mut rows: Vec<usize> = self.matches.iter().map(|(old_index, _)| *old_index).collect();
+        rows.sort_unstable();
+
+        let moving: HashMap<usize, usize> = self.moves.iter().cloned().collect();
+        let mut steps = Vec::with_capacity(self.moves.len());
+        let mut previous: Option<usize> = None;
+
+        // Each moving row goes directly after the row that precedes it in the new snapshot.
+        // Those are either rows that stay, or moving rows that were already placed, so by the
+        // end everything is in order.
+        for (old_index, _) in &self.matches {
+            if moving.contains_key(old_index) {
+                let from = rows.iter().position(|row| row == old_index).unwrap();
+                rows.remove(from);
+
+                let to = match previous {
+                    Some(previous) => rows.iter().position(|row| *row == previous).unwrap() + 1,
+                    None => 0
+                };
+
+                rows.insert(to, *old_index);
+                steps.push((from, to));
+            }
+
+            previous = Some(*old_index);
+        }
+
+        steps
+    }
+}
+
+/// Returns the positions (not values) of a longest strictly increasing subsequence of `values`,
+/// in ascending order.
+fn longest_increasing_subsequence(values: &[usize]) -> Vec<usize> {
+    // `tails[n]` is the position of the smallest value that ends an increasing run of length
+    // `n + 1`, and `links` points each position at the one before it in its run.
+    let mut tails: Vec<usize> = Vec::new();
+    let mut links: Vec<Option<usize>> = vec![None; values.len()];
+
+    for (position, value) in values.iter().enumerate() {
+        let length = tails.partition_point(|tail| values[*tail] < *value);
+
+        if length > 0 {
+            links[position] = Some(tails[length - 1]);
+        }
+
+        match length == tails.len() {
+            true => tails.push(position),
+            false => tails[length] = position
+        }
+    }
+
+    let mut subsequence = Vec::with_capacity(tails.len());
+    let mut position = tails.last().cloned();
+
+    while let Some(p) = position {
+        subsequence.push(p);
+        position = links[p];
+    }
+
+    subsequence.reverse();
+    subsequence
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListDiff;
+
+    /// Applies a diff the way `ListView::apply_diff` does, checking that it produces `new`.
+    fn apply<T: Clone + PartialEq + std::fmt::Debug>(old: &[T], new: &[T], diff: &ListDiff) {
+        let mut rows: Vec<T> = old.to_vec();
+
+        for index in diff.deletes.iter().rev() {
+            rows.remove(*index);
+        }
+
+        for (from, to) in diff.sequential_moves() {
+            let row = rows.remove(from);
+            rows.insert(to, row);
+        }
+
+        for index in &diff.inserts {
+            rows.insert(*index, new[*index].clone());
+        }
+
+        for index in &diff.reloads {
+            rows[*index] = new[*index].clone();
+        }
+
+        assert_eq!(rows, new);
+    }
+
+    #[test]
+    fn test_list_diff() {
+        let old = ["a", "b", "c", "d", "e"];
+        let new = ["e", "b", "x", "d", "c"];
+        let diff = ListDiff::new(&old, &new);
+
+        assert_eq!(diff.deletes, vec![0]);
+        assert_eq!(diff.inserts, vec![2]);
+        assert_eq!(diff.moves.len(), 2);
+        apply(&old, &new, &diff);
+
+        // A rotation only needs one move.
+        let diff = ListDiff::new(&[1, 2, 3, 4], &[2, 3, 4, 1]);
+        assert_eq!(diff.moves, vec![(0, 3)]);
+        assert_eq!(diff.sequential_moves(), vec![(0, 3)]);
+
+        assert!(ListDiff::new(&[1, 2, 3], &[1, 2, 3]).is_empty());
+
+        // Keys match items up, and changed items with the same key are reloaded.
+        let old = [(1, "inbox"), (2, "drafts"), (3, "sent")];
+        let new = [(3, "sent"), (1, "inbox (2)"), (2, "drafts")];
+        let diff = ListDiff::by_key(&old, &new, |(id, _)| *id);
+
+        assert!(diff.deletes.is_empty() && diff.inserts.is_empty());
+        assert_eq!(diff.reloads, vec![1]);
+        assert_eq!(diff.moves, vec![(2, 0)]);
+        apply(&old, &new, &diff);
+
+        // Reversals, duplicates and everything at once.
+        let old: Vec<u32> = (0..40).map(|i| (i * 7) % 23).collect();
+        let new: Vec<u32> = (0..35).map(|i| (i * 11 + 5) % 29).rev().collect();
+        apply(&old, &new, &ListDiff::new(&old, &new));
+        apply(&new, &old, &ListDiff::new(&new, &old));
+    }
+}
@@ -54,12 +54,12 @@ use crate::foundation::{id, nil, NSArray, NSInteger, NSString, NSUInteger, NO, Y
 use crate::layout::Layout;
 
 #[cfg(feature = "autolayout")]
-use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY};
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY, LayoutGuide};
 
 use crate::objc_access::ObjcAccess;
 use crate::scrollview::ScrollView;
 use crate::utils::properties::{ObjcProperty, PropertyNullable};
-use crate::utils::{os, CGSize, CellFactory};
+use crate::utils::{os, CellFactory};
 use crate::view::{ViewAnimatorProxy, ViewDelegate};
 
 #[cfg(feature = "appkit")]
@@ -89,7 +89,20 @@ pub use row::ListViewRow;
 mod actions;
 pub use actions::{RowAction, RowActionStyle};
 
+mod diff;
+pub use diff::ListDiff;
+
+#[cfg(feature = "appkit")]
+mod data_source;
+
+#[cfg(feature = "appkit")]
+pub use data_source::ListDataSource;
+
+mod sections;
+pub use sections::{IndexPath, RowKind, RowMap};
+
 pub(crate) static LISTVIEW_DELEGATE_PTR: &str = "rstListViewDelegatePtr";
+pub(crate) static LISTVIEW_ROW_MAP_PTR: &str = "rstListViewRowMapPtr";
 
 use std::any::Any;
 use std::sync::{Arc, RwLock};
@@ -141,6 +154,10 @@ pub struct ListView<T = ()> {
 
     menu: PropertyNullable<Vec<MenuItem>>,
 
+    /// The delegate's `RowMap`, cached for the per-row callbacks. It's cleared whenever rows are
+    /// reloaded, inserted, removed or moved, and rebuilt by the next callback that needs it.
+    row_map: Rc<RefCell<Option<RowMap>>>,
+
     /// A pointer to the Objective-C runtime view controller.
     pub objc: ObjcProperty,
 
@@ -231,6 +248,7 @@ impl ListView {
         ListView {
             cell_factory: CellFactory::new(),
             menu: PropertyNullable::default(),
+            row_map: Rc::new(RefCell::new(None)),
             delegate: None,
 
             #[cfg(feature = "autolayout")]
@@ -286,10 +304,13 @@ where
         let view = common_init(class);
         let mut delegate = Box::new(delegate);
         let cell = CellFactory::new();
+        let row_map: Rc<RefCell<Option<RowMap>>> = Rc::new(RefCell::new(None));
 
         unsafe {
             let delegate_ptr: *const T = &*delegate;
+            let row_map_ptr: *const RefCell<Option<RowMap>> = &*row_map;
             (&mut *view).set_ivar(LISTVIEW_DELEGATE_PTR, delegate_ptr as usize);
+            (&mut *view).set_ivar(LISTVIEW_ROW_MAP_PTR, row_map_ptr as usize);
             let _: () = msg_send![view, setDelegate: view];
             let _: () = msg_send![view, setDataSource: view];
         };
@@ -315,6 +336,7 @@ where
         let mut view = ListView {
             cell_factory: cell,
             menu: PropertyNullable::default(),
+            row_map,
             delegate: None,
             objc: ObjcProperty::retain(view),
             animator: ViewAnimatorProxy::new(anchor_view),
@@ -367,6 +389,7 @@ impl<T> ListView<T> {
         ListView {
             cell_factory: CellFactory::new(),
             menu: self.menu.clone(),
+            row_map: self.row_map.clone(),
             delegate: None,
             objc: self.objc.clone(),
             animator: self.animator.clone(),
@@ -445,6 +468,18 @@ impl<T> ListView<T> {
         });
     }
 
+    /// Returns a guide for this view's layout margins. See `LayoutGuide` for details.
+    #[cfg(feature = "autolayout")]
+    pub fn layout_margins_guide(&self) -> LayoutGuide {
+        self.get_from_backing_obj(|obj| LayoutGuide::layout_margins(obj as *const Object as id))
+    }
+
+    /// Returns a guide for this view's readable content area. See `LayoutGuide` for details.
+    #[cfg(feature = "autolayout")]
+    pub fn readable_content_guide(&self) -> LayoutGuide {
+        self.get_from_backing_obj(|obj| LayoutGuide::readable_content(obj as *const Object as id))
+    }
+
     /// Sets the style for the underlying NSTableView. This property is only supported on macOS
     /// 11.0+, and will always be `FullWidth` on anything older.
     ///
@@ -487,6 +522,7 @@ impl<T> ListView<T> {
             let index_set: id = msg_send![class!(NSMutableIndexSet), new];
 
             for index in indexes {
+                let index = *index as NSUInteger;
                 let _: () = msg_send![index_set, addIndex: index];
             }
 
@@ -499,6 +535,36 @@ impl<T> ListView<T> {
         }
     }
 
+    /// Returns every selected row, in ascending order.
+    #[cfg(feature = "appkit")]
+    pub fn selected_rows(&self) -> Vec<usize> {
+        self.objc.get(selected_rows)
+    }
+
+    /// Sets whether the user can select more than one row at a time (with Shift and Command).
+    /// Defaults to `false`.
+    #[cfg(feature = "appkit")]
+    pub fn set_allows_multiple_selection(&self, allows: bool) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setAllowsMultipleSelection:match allows {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
+    /// Sets whether typing selects the first row whose text (from
+    /// `ListViewDelegate::type_select_string()`) matches what was typed. Defaults to `true`.
+    #[cfg(feature = "appkit")]
+    pub fn set_allows_type_select(&self, allows: bool) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setAllowsTypeSelect:match allows {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
     /// This hack exists to avoid a bug with how Rust's model isn't really friendly with more
     /// old-school GUI models. The tl;dr is that we unfortunately have to cheat a bit to gracefully
     /// handle two conditions.
@@ -565,6 +631,8 @@ impl<T> ListView<T> {
     /// rows at once, you should also run this inside a `perform_batch_updates` call, as that will
     /// optimize things accordingly.
     pub fn insert_rows(&self, indexes: &[usize], animation: RowAnimation) {
+        self.row_map.replace(None);
+
         #[cfg(feature = "appkit")]
         unsafe {
             let index_set: id = msg_send![class!(NSMutableIndexSet), new];
@@ -617,6 +685,8 @@ impl<T> ListView<T> {
     /// rows at once, you should also run this inside a `perform_batch_updates` call, as that will
     /// optimize things accordingly.
     pub fn remove_rows(&self, indexes: &[usize], animations: RowAnimation) {
+        self.row_map.replace(None);
+
         #[cfg(feature = "appkit")]
         unsafe {
             let index_set: id = msg_send![class!(NSMutableIndexSet), new];
@@ -637,6 +707,110 @@ impl<T> ListView<T> {
         }
     }
 
+    /// Moves the row at `from` to `to`, animating it into place.
+    ///
+    /// Your underlying data store must be updated *before* calling this. Inside a
+    /// `perform_batch_updates` call, indexes are relative to the rows as they are after any
+    /// earlier inserts, removals and moves in the same batch.
+    #[cfg(feature = "appkit")]
+    pub fn move_row(&self, from: usize, to: usize) {
+        self.row_map.replace(None);
+
+        let from = from as NSInteger;
+        let to = to as NSInteger;
+
+        // This is done for a very explicit reason; see the comments on the method itself for
+        // an explanation.
+        self.hack_avoid_dequeue_loop(|obj| unsafe {
+            let _: () = msg_send![obj, moveRowAtIndex:from toIndex:to];
+        });
+    }
+
+    /// Applies a `ListDiff` in one batch: deleted rows are removed and inserted rows are added
+    /// with `animation`, moved rows slide into place, and changed rows are reloaded.
+    ///
+    /// As with `insert_rows` and `remove_rows`, your underlying data store must already match
+    /// the new snapshot. `ListDataSource` takes care of both for you.
+    ///
+    /// Like the row updates it's built on, this is only available with AppKit for now.
+    #[cfg(feature = "appkit")]
+    pub fn apply_diff(&self, diff: &ListDiff, animation: RowAnimation) {
+        if diff.is_empty() {
+            return;
+        }
+
+        let moves = diff.sequential_moves();
+
+        self.perform_batch_updates(|listview| {
+            if !diff.deletes.is_empty() {
+                listview.remove_rows(&diff.deletes, animation);
+            }
+
+            for (from, to) in &moves {
+                listview.move_row(*from, *to);
+            }
+
+            if !diff.inserts.is_empty() {
+                listview.insert_rows(&diff.inserts, animation);
+            }
+        });
+
+        if !diff.reloads.is_empty() {
+            self.reload_rows(&diff.reloads);
+        }
+    }
+
+    /// Inserts the rows for `sections` (headers and footers included), with the specified
+    /// animation. `map` should describe the list *after* the sections were added.
+    ///
+    /// As with `insert_rows`, your underlying data store must be updated *before* calling this.
+    pub fn insert_sections(&self, map: &RowMap, sections: &[usize], animation: RowAnimation) {
+        let rows: Vec<usize> = sections.iter().flat_map(|section| map.rows_in_section(*section)).collect();
+        self.insert_rows(&rows, animation);
+    }
+
+    /// Removes the rows for `sections` (headers and footers included), with the specified
+    /// animation. `map` should describe the list *before* the sections were removed.
+    ///
+    /// As with `remove_rows`, your underlying data store must be updated *before* calling this.
+    /// In a batch that also inserts, remove first.
+    pub fn remove_sections(&self, map: &RowMap, sections: &[usize], animation: RowAnimation) {
+        let rows: Vec<usize> = sections.iter().flat_map(|section| map.rows_in_section(*section)).collect();
+        self.remove_rows(&rows, animation);
+    }
+
+    /// Inserts rows for the items at `index_paths`, with the specified animation. `map` should
+    /// describe the list *after* the items were added.
+    pub fn insert_items(&self, map: &RowMap, index_paths: &[IndexPath], animation: RowAnimation) {
+        let rows: Vec<usize> = index_paths.iter().filter_map(|path| map.row_for_index_path(*path)).collect();
+        self.insert_rows(&rows, animation);
+    }
+
+    /// Removes the rows for the items at `index_paths`, with the specified animation. `map`
+    /// should describe the list *before* the items were removed.
+    pub fn remove_items(&self, map: &RowMap, index_paths: &[IndexPath], animation: RowAnimation) {
+        let rows: Vec<usize> = index_paths.iter().filter_map(|path| map.row_for_index_path(*path)).collect();
+        self.remove_rows(&rows, animation);
+    }
+
+    /// Reloads the rows for the items at `index_paths`.
+    pub fn reload_items(&self, map: &RowMap, index_paths: &[IndexPath]) {
+        let rows: Vec<usize> = index_paths.iter().filter_map(|path| map.row_for_index_path(*path)).collect();
+        self.reload_rows(&rows);
+    }
+
+    /// Sets whether section headers (group rows) float at the top of the list while their
+    /// section is scrolled past. This defaults to `true`.
+    #[cfg(feature = "appkit")]
+    pub fn set_floats_group_rows(&self, floats: bool) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setFloatsGroupRows:match floats {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
     /// Sets an enforced row-height; if you need dynamic rows, you'll want to
     /// look at ListViewDelegate methods, or use AutoLayout.
     pub fn set_row_height(&self, height: CGFloat) {
@@ -699,6 +873,8 @@ impl<T> ListView<T> {
     /// Calling this will reload (and redraw) your listview based on whatever the data source
     /// reports back.
     pub fn reload(&self) {
+        self.row_map.replace(None);
+
         self.objc.with_mut(|obj| unsafe {
             let _: () = msg_send![obj, reloadData];
         });
@@ -744,6 +920,26 @@ impl<T> ListView<T> {
     }
 }
 
+/// Returns the selected rows of an `NSTableView`, in ascending order.
+#[cfg(feature = "appkit")]
+pub(crate) fn selected_rows(table_view: &Object) -> Vec<usize> {
+    // NSNotFound
+    let not_found = NSInteger::MAX as NSUInteger;
+    let mut rows = Vec::new();
+
+    unsafe {
+        let indexes: id = msg_send![table_view, selectedRowIndexes];
+        let mut index: NSUInteger = msg_send![indexes, firstIndex];
+
+        while index != not_found {
+            rows.push(index as usize);
+            index = msg_send![indexes, indexGreaterThanIndex: index];
+        }
+    }
+
+    rows
+}
+
 impl<T> ObjcAccess for ListView<T> {
     fn with_backing_obj_mut<F: Fn(id)>(&self, handler: F) {
         // In AppKit, we need to provide the scrollview for layout purposes - iOS and tvOS will know
@@ -0,0 +1,261 @@
+//! Sections for `ListView`. `NSTableView` only knows about a flat list of rows, so sections are
+//! laid out as a run of rows each: an optional header (shown as a group row, which floats at the
+//! top of the list while its section is scrolled past), the section's items, and an optional
+//! footer.
+//!
+//! `RowMap` translates between those rows and the `IndexPath`s your delegate works with.
+
+use std::ops::Range;
+
+use crate::listview::ListViewDelegate;
+
+/// Addresses an item in a sectioned `ListView`, by its section and its index in that section.
+/// Index paths sort by section, then item.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IndexPath {
+    /// The section the item is in.
+    pub section: usize,
+
+    /// The index of the item in its section.
+    pub item: usize
+}
+
+impl IndexPath {
+    /// Creates an index path for `item` in `section`.
+    pub fn new(section: usize, item: usize) -> Self {
+        IndexPath { section, item }
+    }
+}
+
+/// What a row in a sectioned `ListView` displays.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RowKind {
+    /// The header for a section.
+    Header(usize),
+
+    /// An item.
+    Item(IndexPath),
+
+    /// The footer for a section.
+    Footer(usize)
+}
+
+/// The shape of one section: whether it has a header and footer, and how many items it holds.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct Section {
+    header: bool,
+    items: usize,
+    footer: bool
+}
+
+impl Section {
+    fn len(&self) -> usize {
+        self.header as usize + self.items + self.footer as usize
+    }
+}
+
+/// A snapshot of how the sections of a `ListView` map to its rows, as reported by its delegate.
+///
+/// Build one with `RowMap::new(&delegate)` whenever you need to translate between rows and index
+/// paths - e.g, to find the item for a row passed to `ListViewDelegate::item_selected()`. When
+/// inserting or removing sections or items, take one before updating your data (for removals)
+/// and one after (for insertions):
+///
+/// ```rust,no_run
+/// use cacao::listview::{IndexPath, ListView, ListViewDelegate, RowAnimation, RowMap};
+///
+/// fn move_to_archive<T: ListViewDelegate>(delegate: &T, list_view: &ListView, item: IndexPath) {
+///     let before = RowMap::new(delegate);
+///
+///     // ...remove the item from its section, and add it to the (new) archive section...
+///
+///     let after = RowMap::new(delegate);
+///
+///     list_view.perform_batch_updates(|list_view| {
+///         list_view.remove_items(&before, &[item], RowAnimation::SlideLeft);
+///         list_view.insert_sections(&after, &[after.number_of_sections() - 1], RowAnimation::Fade);
+///     });
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RowMap {
+    sections: Vec<Section>,
+
+    /// The row each section starts at.
+    starts: Vec<usize>,
+
+    len: usize
+}
+
+impl RowMap {
+    /// Asks `delegate` for the current shape of its sections.
+    pub fn new<T: ListViewDelegate>(delegate: &T) -> Self {
+        RowMap::from_sections(
+            (0..delegate.number_of_sections())
+                .map(|section| Section {
+                    header: delegate.section_has_header(section),
+                    items: delegate.items_in_section(section),
+                    footer: delegate.section_has_footer(section)
+                })
+                .collect()
+        )
+    }
+
+    fn from_sections(sections: Vec<Section>) -> Self {
+        let mut starts = Vec::with_capacity(sections.len());
+        let mut len = 0;
+
+        for section in &sections {
+            starts.push(len);
+            len += section.len();
+        }
+
+        RowMap { sections, starts, len }
+    }
+
+    /// The total number of rows, including headers and footers.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no rows at all.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of sections.
+    pub fn number_of_sections(&self) -> usize {
+        self.sections.len()
+    }
+
+    /// Returns what `row` displays, or `None` if it's out of bounds.
+    pub fn kind_of_row(&self, row: usize) -> Option<RowKind> {
+        if row >= self.len {
+            return None;
+        }
+
+        // Empty sections share their start with the next one, so this finds the last section
+        // starting at or before `row` - which is the one that actually holds it.
+        let index = self.starts.partition_point(|start| *start <= row) - 1;
+        let section = &self.sections[index];
+        let offset = row - self.starts[index];
+
+        Some(match offset {
+            0 if section.header => RowKind::Header(index),
+            offset if offset - section.header as usize == section.items => RowKind::Footer(index),
+            offset => RowKind::Item(IndexPath::new(index, offset - section.header as usize))
+        })
+    }
+
+    /// Returns the index path of the item at `row`, or `None` if it's a header, a footer, or out
+    /// of bounds.
+    pub fn index_path_for_row(&self, row: usize) -> Option<IndexPath> {
+        match self.kind_of_row(row) {
+            Some(RowKind::Item(index_path)) => Some(index_path),
+            _ => None
+        }
+    }
+
+    /// Returns the index paths of the items at `rows` - e.g, the selected rows - in the same
+    /// order. Headers, footers and out of bounds rows are skipped.
+    pub fn index_paths_for_rows(&self, rows: &[usize]) -> Vec<IndexPath> {
+        rows.iter().filter_map(|row| self.index_path_for_row(*row)).collect()
+    }
+
+    /// Returns the row that displays `kind`, or `None` if there isn't one.
+    pub fn row_for(&self, kind: RowKind) -> Option<usize> {
+        let (section, offset) = match kind {
+            RowKind::Header(section) => (section, 0),
+            RowKind::Item(index_path) => (index_path.section, index_path.item + 1),
+            RowKind::Footer(section) => (section, self.sections.get(section)?.items + 1)
+        };
+
+        let shape = self.sections.get(section)?;
+        let exists = match kind {
+            RowKind::Header(_) => shape.header,
+            RowKind::Item(index_path) => index_path.item < shape.items,
+            RowKind::Footer(_) => shape.footer
+        };
+
+        // Offsets above count a header row whether or not there is one.
+        match exists {
+            true => Some(self.starts[section] + offset - (!shape.header) as usize),
+            false => None
+        }
+    }
+
+    /// Returns the row that displays the item at `index_path`, or `None` if there isn't one.
+    pub fn row_for_index_path(&self, index_path: IndexPath) -> Option<usize> {
+        self.row_for(RowKind::Item(index_path))
+    }
+
+    /// Returns the rows that make up `section`, including its header and footer. Out of bounds
+    /// sections are empty.
+    pub fn rows_in_section(&self, section: usize) -> Range<usize> {
+        match self.sections.get(section) {
+            Some(shape) => self.starts[section]..self.starts[section] + shape.len(),
+            None => self.len..self.len
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IndexPath, RowKind, RowMap, Section};
+
+    #[test]
+    fn test_row_map() {
+        let map = RowMap::from_sections(vec![
+            Section {
+                header: true,
+                items: 2,
+                footer: false
+            },
+            Section {
+                header: true,
+                items: 0,
+                footer: true
+            },
+            Section {
+                header: false,
+                items: 0,
+                footer: false
+            },
+            Section {
+                header: false,
+                items: 1,
+                footer: true
+            },
+        ]);
+
+        let kinds: Vec<RowKind> = (0..map.len()).filter_map(|row| map.kind_of_row(row)).collect();
+        assert_eq!(kinds, vec![
+            RowKind::Header(0),
+            RowKind::Item(IndexPath::new(0, 0)),
+            RowKind::Item(IndexPath::new(0, 1)),
+            RowKind::Header(1),
+            RowKind::Footer(1),
+            RowKind::Item(IndexPath::new(3, 0)),
+            RowKind::Footer(3),
+        ]);
+
+        for (row, kind) in kinds.iter().enumerate() {
+            assert_eq!(map.row_for(*kind), Some(row));
+        }
+
+        assert_eq!(map.kind_of_row(7), None);
+        assert_eq!(map.index_path_for_row(0), None);
+        assert_eq!(map.row_for_index_path(IndexPath::new(0, 2)), None);
+        assert_eq!(map.row_for(RowKind::Footer(0)), None);
+        assert_eq!(map.rows_in_section(1), 3..5);
+        assert_eq!(map.rows_in_section(2), 5..5);
+        assert_eq!(map.rows_in_section(9), 7..7);
+
+        // Selections only ever report items.
+        assert_eq!(map.index_paths_for_rows(&[0, 2, 3, 4, 5, 6, 7]), vec![
+            IndexPath::new(0, 1),
+            IndexPath::new(3, 0)
+        ]);
+        assert_eq!(map.index_paths_for_rows(&[]), vec![]);
+    }
+}
@@ -3,7 +3,7 @@
 use crate::appkit::menu::MenuItem;
 use crate::dragdrop::{DragInfo, DragOperation};
 use crate::layout::Layout;
-use crate::listview::{ListView, ListViewRow, RowAction, RowEdge};
+use crate::listview::{IndexPath, ListView, ListViewRow, RowAction, RowEdge};
 use crate::view::View;
 
 #[allow(unused_variables)]
@@ -25,21 +25,109 @@ pub trait ListViewDelegate {
     fn did_load(&mut self, view: ListView);
 
     /// Returns the number of items in the list view.
+    ///
+    /// If your list has sections, implement `items_in_section()` instead; this is then only used
+    /// by its default implementation.
     fn number_of_items(&self) -> usize;
 
+    /// Returns the number of sections in the list view. Lists have a single section by default.
+    fn number_of_sections(&self) -> usize {
+        1
+    }
+
+    /// Returns the number of items in `section`. Defaults to `number_of_items()`.
+    fn items_in_section(&self, section: usize) -> usize {
+        self.number_of_items()
+    }
+
+    /// Whether `section` has a header. Headers are shown as group rows, which float at the top of
+    /// the list while their section is scrolled past (see `ListView::set_floats_group_rows()`).
+    ///
+    /// This is asked often, so should be cheap - the view itself comes from
+    /// `header_for_section()`.
+    fn section_has_header(&self, section: usize) -> bool {
+        false
+    }
+
+    /// Returns the header view for `section`. Only called if `section_has_header()` returned
+    /// `true`.
+    fn header_for_section(&self, section: usize) -> Option<ListViewRow> {
+        None
+    }
+
+    /// Whether `section` has a footer, which is shown as a regular (unselectable) row after its
+    /// items.
+    fn section_has_footer(&self, section: usize) -> bool {
+        false
+    }
+
+    /// Returns the footer view for `section`. Only called if `section_has_footer()` returned
+    /// `true`.
+    fn footer_for_section(&self, section: usize) -> Option<ListViewRow> {
+        None
+    }
+
     /// Called when an item will be displayed.
+    ///
+    /// Like the other callbacks that take a `row`, this is the row in the list view - headers
+    /// and footers included. `RowMap` translates it to an `IndexPath` for sectioned lists.
     fn will_display_item(&self, row: usize) {}
 
     /// This is temporary and you should not rely on this signature if you
     /// choose to try and work with this. NSTableView & such associated delegate patterns
     /// are tricky to support in Rust, and while I have a few ideas about them, I haven't
     /// had time to sit down and figure them out properly yet.
+    ///
+    /// If your list has sections, implement `item_for_index_path()` instead.
     fn item_for(&self, row: usize) -> ListViewRow;
 
+    /// Returns the view for the item at `index_path`. Defaults to `item_for()`, with the item's
+    /// index in its section.
+    fn item_for_index_path(&self, index_path: IndexPath) -> ListViewRow {
+        self.item_for(index_path.item)
+    }
+
     /// Called when an item has been selected (clicked/tapped on). If the selection was cleared,
     /// then this will be called with `None`.
+    ///
+    /// If multiple selection is enabled, this is the most recently selected row - implement
+    /// `selection_changed()` to get all of them.
     fn item_selected(&self, row: Option<usize>) {}
 
+    /// Called whenever the selection changes, with every selected item, in ascending order. The
+    /// slice is empty if the selection was cleared.
+    ///
+    /// Unlike the callbacks that take a `row`, this and the callbacks below are passed index
+    /// paths; in a list without sections, the section is always `0`.
+    fn selection_changed(&self, index_paths: &[IndexPath]) {}
+
+    /// Whether the user can select the item at `index_path`. Return `false` to veto the
+    /// selection. Section headers and footers are never selectable.
+    fn should_select(&self, index_path: IndexPath) -> bool {
+        true
+    }
+
+    /// Returns the text that type-to-select matches against for the item at `index_path` -
+    /// typically its title. Items that return `None` are skipped, and type-to-select does
+    /// nothing if no item returns text. See also `ListView::set_allows_type_select()`.
+    fn type_select_string(&self, index_path: IndexPath) -> Option<String> {
+        None
+    }
+
+    /// Called when the user presses Delete (or Forward Delete) in the list view, with the
+    /// selected items. Return `true` if you handled it; otherwise the key press is passed along
+    /// as usual.
+    fn delete_pressed(&self, index_paths: &[IndexPath]) -> bool {
+        false
+    }
+
+    /// Called when the user presses Return (or Enter) in the list view, with the selected items -
+    /// e.g, to open them. Return `true` if you handled it; otherwise the key press is passed
+    /// along as usual.
+    fn return_pressed(&self, index_paths: &[IndexPath]) -> bool {
+        false
+    }
+
     /// Called when the menu for the tableview is about to be shown. You can update the menu here
     /// depending on, say, what the user has context-clicked on. You should avoid any expensive
     /// work in here and return the menu as fast as possible.
@@ -0,0 +1,346 @@
+use std::any::{Any, TypeId};
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+use super::executor::{Executor, Job};
+
+/// Where an `EventBus` subscriber is called. These mirror the `Dispatcher` trait's
+/// `on_ui_message` and `on_background_message`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Delivery {
+    /// Called synchronously on the posting thread, before `post()` returns.
+    Immediate,
+
+    /// Called on the main thread. This is where all UI work should be happening.
+    Main,
+
+    /// Called off the main thread.
+    Background
+}
+
+type Handler = Arc<dyn Fn(&(dyn Any + Send + Sync)) + Send + Sync>;
+type Liveness = Box<dyn Fn() -> bool + Send + Sync>;
+
+struct Subscriber {
+    id: usize,
+    name: String,
+    payload: TypeId,
+    delivery: Delivery,
+    handler: Handler,
+
+    /// For weak subscriptions, reports whether the target is still around.
+    alive: Option<Liveness>
+}
+
+impl Subscriber {
+    fn is_alive(&self) -> bool {
+        match &self.alive {
+            Some(alive) => alive(),
+            None => true
+        }
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    next_id: usize,
+    subscribers: Vec<Subscriber>
+}
+
+struct Inner {
+    registry: Mutex<Registry>,
+    executor: Arc<dyn Executor>
+}
+
+/// An in-process publish/subscribe bus, with the same shape as `NotificationCenter` but
+/// implemented entirely in Rust - it doesn't need Foundation, so code built on it can run in unit
+/// tests and on other platforms.
+///
+/// Events are keyed by name (either a `NotificationName` or any string) and carry a typed
+/// payload. A subscriber only receives posts whose payload is of the type it subscribed with.
+///
+/// Ordering is deterministic: for a given post, subscribers are called in the order they
+/// subscribed, and each `Delivery` queue sees posts in the order they were made.
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use cacao::notification_center::{Delivery, EventBus, ManualExecutor};
+///
+/// #[derive(Debug)]
+/// struct TodoAdded {
+///     title: String
+/// }
+///
+/// let executor = Arc::new(ManualExecutor::new());
+/// let bus = EventBus::with_executor(executor.clone());
+///
+/// let _subscription = bus.subscribe("todo-added", Delivery::Main, |event: &TodoAdded| {
+///     println!("Added {}", event.title);
+/// });
+///
+/// bus.post("todo-added", TodoAdded { title: "Write docs".into() });
+///
+/// // Main thread deliveries run whenever the main queue is drained.
+/// assert_eq!(executor.run_main(), 1);
+/// ```
+#[derive(Clone)]
+pub struct EventBus {
+    inner: Arc<Inner>
+}
+
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+impl Default for EventBus {
+    /// Returns a bus that delivers via Grand Central Dispatch.
+    fn default() -> Self {
+        EventBus::new()
+    }
+}
+
+impl EventBus {
+    /// Returns a new bus that delivers via Grand Central Dispatch.
+    #[cfg(any(feature = "appkit", feature = "uikit"))]
+    pub fn new() -> Self {
+        EventBus::with_executor(Arc::new(super::DispatchExecutor::new()))
+    }
+
+    /// Returns a new bus that hands `Main` and `Background` deliveries to the given executor.
+    pub fn with_executor(executor: Arc<dyn Executor>) -> Self {
+        EventBus {
+            inner: Arc::new(Inner {
+                registry: Mutex::new(Registry::default()),
+                executor
+            })
+        }
+    }
+
+    fn add(&self, name: &str, payload: TypeId, delivery: Delivery, handler: Handler, alive: Option<Liveness>) -> Subscription {
+        let mut registry = self.inner.registry.lock().unwrap();
+        let id = registry.next_id;
+        registry.next_id += 1;
+
+        registry.subscribers.push(Subscriber {
+            id,
+            name: name.to_string(),
+            payload,
+            delivery,
+            handler,
+            alive
+        });
+
+        Subscription {
+            id,
+            bus: Some(Arc::downgrade(&self.inner))
+        }
+    }
+
+    /// Calls `handler` for every post to `name` with a payload of type `T`, on the given
+    /// `Delivery` queue. The subscription lasts until the returned `Subscription` is dropped.
+    pub fn subscribe<N, T, F>(&self, name: N, delivery: Delivery, handler: F) -> Subscription
+    where
+        N: AsRef<str>,
+        T: Any + Send + Sync,
+        F: Fn(&T) + Send + Sync + 'static
+    {
+        let handler: Handler = Arc::new(move |payload| {
+            if let Some(payload) = payload.downcast_ref::<T>() {
+                handler(payload);
+            }
+        });
+
+        self.add(name.as_ref(), TypeId::of::<T>(), delivery, handler, None)
+    }
+
+    /// Like `subscribe()`, but only holds a weak reference to `target`: once it's dropped, the
+    /// handler stops being called and the subscription is cleaned up, even if the returned
+    /// `Subscription` is still alive (or was detached).
+    pub fn subscribe_weak<N, S, T, F>(&self, name: N, delivery: Delivery, target: &Arc<S>, handler: F) -> Subscription
+    where
+        N: AsRef<str>,
+        S: Send + Sync + 'static,
+        T: Any + Send + Sync,
+        F: Fn(&S, &T) + Send + Sync + 'static
+    {
+        let weak = Arc::downgrade(target);
+        let handler: Handler = Arc::new(move |payload| {
+            if let (Some(target), Some(payload)) = (weak.upgrade(), payload.downcast_ref::<T>()) {
+                handler(&target, payload);
+            }
+        });
+
+        let weak = Arc::downgrade(target);
+        let alive: Liveness = Box::new(move || weak.strong_count() > 0);
+
+        self.add(name.as_ref(), TypeId::of::<T>(), delivery, handler, Some(alive))
+    }
+
+    /// Posts `payload` to every subscriber of `name` that subscribed with type `T`. `Immediate`
+    /// subscribers are called before this returns; `Main` and `Background` subscribers are each
+    /// handed to the executor as a single job, so they run back to back, in subscription order.
+    pub fn post<N: AsRef<str>, T: Any + Send + Sync>(&self, name: N, payload: T) {
+        let name = name.as_ref();
+        let payload: Arc<dyn Any + Send + Sync> = Arc::new(payload);
+        let mut queues: [Vec<Handler>; 3] = [Vec::new(), Vec::new(), Vec::new()];
+
+        {
+            let mut registry = self.inner.registry.lock().unwrap();
+            registry.subscribers.retain(Subscriber::is_alive);
+
+            for subscriber in &registry.subscribers {
+                if subscriber.name == name && subscriber.payload == TypeId::of::<T>() {
+                    queues[subscriber.delivery as usize].push(subscriber.handler.clone());
+                }
+            }
+        }
+
+        let [immediate, main, background] = queues;
+
+        for handler in immediate {
+            handler(&*payload);
+        }
+
+        if !main.is_empty() {
+            self.inner.executor.run_on_main(batch(main, payload.clone()));
+        }
+
+        if !background.is_empty() {
+            self.inner.executor.run_in_background(batch(background, payload));
+        }
+    }
+
+    /// The executor this bus hands `Main` and `Background` deliveries to.
+    pub(crate) fn executor(&self) -> Arc<dyn Executor> {
+        self.inner.executor.clone()
+    }
+
+    /// Returns how many live subscribers `name` has, across all payload types.
+    pub fn subscriber_count<N: AsRef<str>>(&self, name: N) -> usize {
+        let registry = self.inner.registry.lock().unwrap();
+
+        registry
+            .subscribers
+            .iter()
+            .filter(|subscriber| subscriber.name == name.as_ref() && subscriber.is_alive())
+            .count()
+    }
+}
+
+/// Wraps a set of handlers into a single job that calls each of them, in order.
+fn batch(handlers: Vec<Handler>, payload: Arc<dyn Any + Send + Sync>) -> Job {
+    Box::new(move || {
+        for handler in handlers {
+            handler(&*payload);
+        }
+    })
+}
+
+impl fmt::Debug for EventBus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let registry = self.inner.registry.lock().unwrap();
+        let names: Vec<&str> = registry.subscribers.iter().map(|s| s.name.as_str()).collect();
+
+        f.debug_struct("EventBus").field("subscribers", &names).finish()
+    }
+}
+
+/// Returned when subscribing to an `EventBus`. The subscriber is removed when this is dropped,
+/// unless it's been `detach()`ed.
+#[derive(Debug)]
+pub struct Subscription {
+    id: usize,
+    bus: Option<Weak<Inner>>
+}
+
+impl Subscription {
+    /// Keeps the subscriber registered for as long as the bus is alive, rather than until this is
+    /// dropped. This is mostly useful with `subscribe_weak()`, where the target's lifetime already
+    /// bounds the subscription.
+    pub fn detach(mut self) {
+        self.bus = None;
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(inner) = self.bus.take().and_then(|bus| bus.upgrade()) {
+            let mut registry = inner.registry.lock().unwrap();
+            registry.subscribers.retain(|subscriber| subscriber.id != self.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::{Delivery, EventBus};
+    use crate::notification_center::{ManualExecutor, NotificationName};
+
+    #[test]
+    fn test_delivery_and_ordering() {
+        let executor = Arc::new(ManualExecutor::new());
+        let bus = EventBus::with_executor(executor.clone());
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let subscriptions: Vec<_> = [
+            ("main-1", Delivery::Main),
+            ("immediate", Delivery::Immediate),
+            ("background", Delivery::Background),
+            ("main-2", Delivery::Main)
+        ]
+        .iter()
+        .map(|(label, delivery)| {
+            let log = log.clone();
+            bus.subscribe("event", *delivery, move |value: &u32| {
+                log.lock().unwrap().push(format!("{} {}", label, value));
+            })
+        })
+        .collect();
+
+        // Mismatched names and payload types are ignored.
+        bus.post("event", "not a u32");
+        bus.post("other", 0u32);
+
+        bus.post("event", 1u32);
+        bus.post("event", 2u32);
+        assert_eq!(*log.lock().unwrap(), vec!["immediate 1", "immediate 2"]);
+
+        assert_eq!(executor.run_main(), 2);
+        assert_eq!(executor.run_background(), 2);
+        assert_eq!(log.lock().unwrap()[2..], [
+            "main-1 1",
+            "main-2 1",
+            "main-1 2",
+            "main-2 2",
+            "background 1",
+            "background 2"
+        ]);
+
+        drop(subscriptions);
+        assert_eq!(bus.subscriber_count("event"), 0);
+    }
+
+    #[test]
+    fn test_weak_subscriptions() {
+        let executor = Arc::new(ManualExecutor::new());
+        let bus = EventBus::with_executor(executor.clone());
+        let target = Arc::new(Mutex::new(0));
+
+        bus.subscribe_weak(
+            NotificationName::NSSystemColorsDidChange,
+            Delivery::Main,
+            &target,
+            |count, _: &()| *count.lock().unwrap() += 1
+        )
+        .detach();
+
+        bus.post(NotificationName::NSSystemColorsDidChange, ());
+        executor.run_main();
+        assert_eq!(*target.lock().unwrap(), 1);
+
+        // A delivery that was already queued is skipped once the target goes away.
+        bus.post(NotificationName::NSSystemColorsDidChange, ());
+        drop(target);
+        executor.run_main();
+        assert_eq!(bus.subscriber_count(NotificationName::NSSystemColorsDidChange), 0);
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Mutex;
+
+/// A unit of work handed to an `Executor`.
+pub type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Decides where `EventBus` deliveries run. Implementations must run jobs for each queue in the
+/// order they were submitted, which is what gives the bus its ordering guarantees.
+pub trait Executor: Send + Sync {
+    /// Runs `job` on the main (UI) thread.
+    fn run_on_main(&self, job: Job);
+
+    /// Runs `job` off the main thread.
+    fn run_in_background(&self, job: Job);
+}
+
+/// An `Executor` backed by Grand Central Dispatch: main deliveries go to the main queue, and
+/// background deliveries to a private serial queue.
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+pub struct DispatchExecutor {
+    background: dispatch::Queue
+}
+
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+impl DispatchExecutor {
+    /// Returns a new `DispatchExecutor`, with its own background queue.
+    pub fn new() -> Self {
+        DispatchExecutor {
+            background: dispatch::Queue::create("com.cacao.event-bus", dispatch::QueueAttribute::Serial)
+        }
+    }
+}
+
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+impl Default for DispatchExecutor {
+    fn default() -> Self {
+        DispatchExecutor::new()
+    }
+}
+
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+impl fmt::Debug for DispatchExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DispatchExecutor").finish()
+    }
+}
+
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+impl Executor for DispatchExecutor {
+    fn run_on_main(&self, job: Job) {
+        dispatch::Queue::main().exec_async(job);
+    }
+
+    fn run_in_background(&self, job: Job) {
+        self.background.exec_async(job);
+    }
+}
+
+/// An `Executor` that only queues jobs, leaving it up to you to run them - by calling
+/// `run_main()` from whatever you consider the main thread, and `run_background()` from
+/// wherever background work should happen.
+///
+/// This doesn't touch Foundation at all, so it's what you want for unit tests (where you can
+/// drain the queues at exactly the point you want to make assertions) and for platforms without
+/// Grand Central Dispatch.
+#[derive(Default)]
+pub struct ManualExecutor {
+    main: Mutex<VecDeque<Job>>,
+    background: Mutex<VecDeque<Job>>
+}
+
+impl ManualExecutor {
+    /// Returns a new `ManualExecutor` with empty queues.
+    pub fn new() -> Self {
+        ManualExecutor::default()
+    }
+
+    /// Runs every queued main thread job (including any queued while running), returning how
+    /// many were run.
+    pub fn run_main(&self) -> usize {
+        drain(&self.main)
+    }
+
+    /// Runs every queued background job (including any queued while running), returning how
+    /// many were run.
+    pub fn run_background(&self) -> usize {
+        drain(&self.background)
+    }
+}
+
+/// Pops and runs jobs one at a time, so that the lock isn't held while a job runs (jobs are free
+/// to queue more work).
+fn drain(queue: &Mutex<VecDeque<Job>>) -> usize {
+    let mut count = 0;
+
+    loop {
+        let job = queue.lock().unwrap().pop_front();
+
+        match job {
+            Some(job) => {
+                job();
+                count += 1;
+            },
+
+            None => return count
+        }
+    }
+}
+
+impl fmt::Debug for ManualExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ManualExecutor")
+            .field("main", &self.main.lock().unwrap().len())
+            .field("background", &self.background.lock().unwrap().len())
+            .finish()
+    }
+}
+
+impl Executor for ManualExecutor {
+    fn run_on_main(&self, job: Job) {
+        self.main.lock().unwrap().push_back(job);
+    }
+
+    fn run_in_background(&self, job: Job) {
+        self.background.lock().unwrap().push_back(job);
+    }
+}
@@ -10,64 +10,204 @@
 //! can call through to your desired path directly. This control is provided due to the need for
 //! integrating with certain aspects of the underlying Cocoa/Foundation/Kit frameworks.
 //!
+//! For events that never leave your own code, there's also `EventBus`: a pure-Rust bus with the
+//! same observe/post shape, typed payloads, and main/background delivery. It doesn't depend on
+//! Foundation, so logic built on it can be unit tested (or run) anywhere.
+//!
+//! `MessageRouter` builds on `EventBus` to route typed messages to any number of `Dispatcher`
+//! implementations - windows, views, controllers - rather than just the app delegate, with
+//! `Reply` channels for request/response.
+//!
 //! ## Example
+//!
+//! ```rust,no_run
+//! use cacao::notification_center::{NotificationCenter, NotificationName, OperationQueue};
+//!
+//! let center = NotificationCenter::default();
+//!
+//! // Handlers are removed when the returned token is dropped, so keep it around for as long as
+//! // you want to hear about changes.
+//! let _token = center.observe_on(
+//!     NotificationName::NSApplicationDidChangeScreenParameters,
+//!     &OperationQueue::main(),
+//!     |notification| {
+//!         println!("{} - screens changed!", notification.name());
+//!     }
+//! );
+//!
+//! // Custom notifications are just strings.
+//! let _custom = center.observe("com.myapp.todos-changed", |_| {});
+//! center.post("com.myapp.todos-changed", None);
+//! ```
+
+use std::collections::HashMap;
+
+use block::ConcreteBlock;
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
+use objc_id::ShareId;
+
+use crate::defaults::Value;
+use crate::foundation::{id, nil, NSMutableDictionary, NSString};
+
+mod bus;
+pub use bus::{Delivery, EventBus, Subscription};
 
-//use std::sync::Mutex;
-//use std::collections::HashMap;
+mod executor;
+pub use executor::{Executor, Job, ManualExecutor};
 
-//use lazy_static::lazy_static;
-//use objc::{class, msg_send, sel, sel_impl};
-//use objc::runtime::Object;
-//use objc_id::ShareId;
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+pub use executor::DispatchExecutor;
 
 mod name;
 pub use name::NotificationName;
 
+mod notification;
+pub use notification::Notification;
+
+mod queue;
+pub use queue::OperationQueue;
+
+mod reply;
+pub use reply::{Reply, ReplyReceiver};
+
+mod router;
+pub use router::{MessageRouter, Registration};
+
 mod traits;
 pub use traits::Dispatcher;
 
-/*lazy_static! {
-    pub static ref DefaultNotificationCenter: NotificationCenter = {
-        NotificationCenter {
-            objc: unsafe {
-                ShareId::from_ptr(msg_send![class!(NSNotificationCenter), defaultCenter])
-            },
+/// Wraps a reference to an `NSNotificationCenter` instance - either one of the shared, system
+/// provided centers, or one you've created for your own use.
+#[derive(Clone, Debug)]
+pub struct NotificationCenter {
+    /// The underlying `NSNotificationCenter`.
+    pub objc: ShareId<Object>
+}
 
-            subscribers: Mutex::new(HashMap::new())
-        }
-    };
-}*/
-
-// Wraps a reference to an `NSNotificationCenter` instance. Currently this only supports the
-// default center; in the future it should aim to support custom variants.
-//#[derive(Debug)]
-//pub struct NotificationCenter {
-//    pub objc: ShareId<Object>,
-//pub subscribers: Mutex<HashMap<String, Vec<Dispatcher>>>
-//}
-
-/*impl Default for NotificationCenter {
+impl Default for NotificationCenter {
     /// Returns a wrapper over `[NSNotificationCenter defaultCenter]`. From here you can handle
     /// observing, removing, and posting notifications.
     fn default() -> Self {
+        NotificationCenter {
+            objc: unsafe { ShareId::from_ptr(msg_send![class!(NSNotificationCenter), defaultCenter]) }
+        }
+    }
+}
+
+impl NotificationCenter {
+    /// Returns a new, private notification center. Notifications posted to it are only seen by
+    /// observers registered on it, which can be handy for scoping chatter to one part of your app.
+    pub fn new() -> Self {
+        NotificationCenter {
+            objc: unsafe { ShareId::from_retained_ptr(msg_send![class!(NSNotificationCenter), new]) }
+        }
+    }
+
+    /// Returns the `NSWorkspace` notification center. Workspace notifications (e.g,
+    /// `NSWorkspaceDidWake`) are only ever posted here, not to the default center.
+    #[cfg(feature = "appkit")]
+    pub fn workspace() -> Self {
         NotificationCenter {
             objc: unsafe {
-                ShareId::from_ptr(msg_send![class!(NSNotificationCenter), defaultCenter])
+                let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+                ShareId::from_ptr(msg_send![workspace, notificationCenter])
             }
         }
     }
-}*/
 
-/*impl NotificationCenter {
-    pub fn observe<T: Dispatcher>(&self, name: &str, handler: &T) {
+    /// Returns the distributed notification center, which delivers notifications between
+    /// processes. Note that `user_info` values must be property list types to cross processes.
+    #[cfg(feature = "appkit")]
+    pub fn distributed() -> Self {
+        NotificationCenter {
+            objc: unsafe { ShareId::from_ptr(msg_send![class!(NSDistributedNotificationCenter), defaultCenter]) }
+        }
+    }
+
+    /// Registers `handler` to be called whenever a notification named `name` is posted. The
+    /// handler runs synchronously on whichever thread posted the notification - if you need it on
+    /// a specific queue (e.g, the main queue, for UI work), use `observe_on()` instead.
+    ///
+    /// The handler is removed when the returned `NotificationToken` is dropped.
+    pub fn observe<N, F>(&self, name: N, handler: F) -> NotificationToken
+    where
+        N: AsRef<str>,
+        F: Fn(&Notification) + Send + Sync + 'static
+    {
+        self.add_observer(name.as_ref(), None, handler)
+    }
+
+    /// Registers `handler` to be called whenever a notification named `name` is posted, with
+    /// delivery happening on the given `OperationQueue`.
+    ///
+    /// The handler is removed when the returned `NotificationToken` is dropped.
+    pub fn observe_on<N, F>(&self, name: N, queue: &OperationQueue, handler: F) -> NotificationToken
+    where
+        N: AsRef<str>,
+        F: Fn(&Notification) + Send + Sync + 'static
+    {
+        self.add_observer(name.as_ref(), Some(queue), handler)
+    }
+
+    fn add_observer<F>(&self, name: &str, queue: Option<&OperationQueue>, handler: F) -> NotificationToken
+    where
+        F: Fn(&Notification) + Send + Sync + 'static
+    {
+        let name = NSString::new(name);
+
+        let block = ConcreteBlock::new(move |notification: id| {
+            handler(&Notification::new(notification));
+        });
+        let block = block.copy();
+
+        let queue: id = match queue {
+            Some(queue) => &*queue.0 as *const Object as id,
+            None => nil
+        };
 
+        let observer = unsafe {
+            ShareId::from_ptr(msg_send![&*self.objc, addObserverForName:&*name
+                object:nil
+                queue:queue
+                usingBlock:block])
+        };
+
+        NotificationToken {
+            center: self.objc.clone(),
+            observer
+        }
     }
 
-    pub fn remove<T: Dispatcher>(&self, name: &str, handler: &T) {
+    /// Posts a notification named `name`, with an optional `user_info` dictionary. Observers
+    /// without a queue are called synchronously, before this returns.
+    pub fn post<N: AsRef<str>>(&self, name: N, user_info: Option<HashMap<String, Value>>) {
+        let name = NSString::new(name.as_ref());
+        let user_info = user_info.map(NSMutableDictionary::from);
+
+        let user_info: id = match &user_info {
+            Some(user_info) => &**user_info as *const Object as id,
+            None => nil
+        };
 
+        unsafe {
+            let _: () = msg_send![&*self.objc, postNotificationName:&*name object:nil userInfo:user_info];
+        }
     }
+}
 
-    pub fn post(&self, name: &str) {
+/// Returned when observing a `NotificationCenter`. The observer stays registered for as long as
+/// this is alive, and is removed from the center when it's dropped.
+#[derive(Debug)]
+pub struct NotificationToken {
+    center: ShareId<Object>,
+    observer: ShareId<Object>
+}
 
+impl Drop for NotificationToken {
+    fn drop(&mut self) {
+        unsafe {
+            let _: () = msg_send![&*self.center, removeObserver:&*self.observer];
+        }
     }
-}*/
+}
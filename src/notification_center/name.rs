@@ -5,7 +5,7 @@ use crate::foundation::NSString;
 ///
 /// Since this framework utilizes Objective-C, these are ultimately backed by `NSString`... but we
 /// want them to be a bit more type-friendly and autocomplete-able.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum NotificationName {
     /// Posted when the audio engine config changes.
     ///
@@ -1313,10 +1313,582 @@ pub enum NotificationName {
     WKAccessibilityReduceMotionStatusDidChange
 }
 
+impl NotificationName {
+    /// Returns the underlying notification name - i.e, the value of the corresponding
+    /// `NSNotificationName` constant. For the vast majority of notifications this is the name of
+    /// the constant itself (e.g, `NSWindowDidResizeNotification`); AppKit's accessibility
+    /// notifications (`AXMoved`, etc) and a few others are the exception.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationName::AudioEngineConfigurationChange => "AVAudioEngineConfigurationChangeNotification",
+            NotificationName::AudioSessionInterruption => "AVAudioSessionInterruptionNotification",
+            NotificationName::AudioSessionMediaServicesWereLost => "AVAudioSessionMediaServicesWereLostNotification",
+            NotificationName::AudioSessionMediaServicesWereReset => "AVAudioSessionMediaServicesWereResetNotification",
+            NotificationName::AudioSessionRouteChange => "AVAudioSessionRouteChangeNotification",
+            NotificationName::AudioSessionSilenceSecondaryAudioHint => "AVAudioSessionSilenceSecondaryAudioHintNotification",
+            NotificationName::AudioUnitComponentTagsDidChange => "AVAudioUnitComponentTagsDidChangeNotification",
+            NotificationName::CloudKitAccountChanged => "CKAccountChangedNotification",
+            NotificationName::CLKComplicationServerActiveComplicationsDidChange => {
+                "CLKComplicationServerActiveComplicationsDidChangeNotification"
+            },
+            NotificationName::CNContactStoreDidChange => "CNContactStoreDidChangeNotification",
+            NotificationName::EKEventStoreChanged => "EKEventStoreChangedNotification",
+            NotificationName::HKUserPreferencesDidChange => "HKUserPreferencesDidChangeNotification",
+            NotificationName::HMCharacteristicPropertySupportsEvent => "HMCharacteristicPropertySupportsEventNotification",
+            NotificationName::NSBundleResourceRequestLowDiskSpace => "NSBundleResourceRequestLowDiskSpaceNotification",
+            NotificationName::NSCalendarDayChanged => "NSCalendarDayChangedNotification",
+            NotificationName::NSExtensionHostDidBecomeActive => "NSExtensionHostDidBecomeActiveNotification",
+            NotificationName::NSExtensionHostDidEnterBackground => "NSExtensionHostDidEnterBackgroundNotification",
+            NotificationName::NSExtensionHostWillEnterForeground => "NSExtensionHostWillEnterForegroundNotification",
+            NotificationName::NSExtensionHostWillResignActive => "NSExtensionHostWillResignActiveNotification",
+            NotificationName::NSFileHandleConnectionAccepted => "NSFileHandleConnectionAcceptedNotification",
+            NotificationName::NSFileHandleDataAvailable => "NSFileHandleDataAvailableNotification",
+            NotificationName::NSFileHandleReadToEndOfFileCompletion => "NSFileHandleReadToEndOfFileCompletionNotification",
+            NotificationName::NSHTTPCookieManagerAcceptPolicyChanged => "NSHTTPCookieManagerAcceptPolicyChangedNotification",
+            NotificationName::NSHTTPCookieManagerCookiesChanged => "NSHTTPCookieManagerCookiesChangedNotification",
+            NotificationName::NSManagedObjectContextDidSave => "NSManagingContextDidSaveChangesNotification",
+            NotificationName::NSManagedObjectContextObjectsDidChange => "NSObjectsChangedInManagingContextNotification",
+            NotificationName::NSManagedObjectContextWillSave => "NSManagedObjectContextWillSaveNotification",
+            NotificationName::NSMetadataQueryDidFinishGathering => "NSMetadataQueryDidFinishGatheringNotification",
+            NotificationName::NSMetadataQueryDidStartGathering => "NSMetadataQueryDidStartGatheringNotification",
+            NotificationName::NSMetadataQueryDidUpdate => "NSMetadataQueryDidUpdateNotification",
+            NotificationName::NSMetadataQueryGatheringProgress => "NSMetadataQueryGatheringProgressNotification",
+            NotificationName::NSPersistentStoreCoordinatorStoresDidChange => {
+                "NSPersistentStoreCoordinatorStoresDidChangeNotification"
+            },
+            NotificationName::NSPersistentStoreCoordinatorStoresWillChange => {
+                "NSPersistentStoreCoordinatorStoresWillChangeNotification"
+            },
+            NotificationName::NSPersistentStoreCoordinatorWillRemoveStore => {
+                "NSPersistentStoreCoordinatorWillRemoveStoreNotification"
+            },
+            NotificationName::NSProcessInfoPowerStateDidChange => "NSProcessInfoPowerStateDidChangeNotification",
+            NotificationName::NSSystemClockDidChange => "NSSystemClockDidChangeNotification",
+            NotificationName::NSSystemTimeZoneDidChange => "NSSystemTimeZoneDidChangeNotification",
+            NotificationName::NSURLCredentialStorageChanged => "NSURLCredentialStorageChangedNotification",
+            NotificationName::NSUbiquityIdentityDidChange => "NSUbiquityIdentityDidChangeNotification",
+            NotificationName::NSUndoManagerCheckpoint => "NSUndoManagerCheckpointNotification",
+            NotificationName::NSUndoManagerDidCloseUndoGroup => "NSUndoManagerDidCloseUndoGroupNotification",
+            NotificationName::NSUndoManagerDidOpenUndoGroup => "NSUndoManagerDidOpenUndoGroupNotification",
+            NotificationName::NSUndoManagerDidRedoChange => "NSUndoManagerDidRedoChangeNotification",
+            NotificationName::NSUndoManagerDidUndoChange => "NSUndoManagerDidUndoChangeNotification",
+            NotificationName::NSUndoManagerWillCloseUndoGroup => "NSUndoManagerWillCloseUndoGroupNotification",
+            NotificationName::NSUndoManagerWillRedoChange => "NSUndoManagerWillRedoChangeNotification",
+            NotificationName::NSUndoManagerWillUndoChange => "NSUndoManagerWillUndoChangeNotification",
+            NotificationName::PKPassLibraryDidChange => "PKPassLibraryDidChangeNotification",
+            NotificationName::PKPassLibraryRemotePaymentPassesDidChange => {
+                "PKPassLibraryRemotePaymentPassesDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityAnnouncementDidFinish => "UIAccessibilityAnnouncementDidFinishNotification",
+            NotificationName::UIAccessibilityElementFocused => "UIAccessibilityElementFocusedNotification",
+            NotificationName::WKAudioFilePlayerItemDidPlayToEndTime => "WKAudioFilePlayerItemDidPlayToEndTimeNotification",
+            NotificationName::WKAudioFilePlayerItemFailedToPlayToEndTime => {
+                "WKAudioFilePlayerItemFailedToPlayToEndTimeNotification"
+            },
+            NotificationName::WKAudioFilePlayerItemTimeJumped => "WKAudioFilePlayerItemTimeJumpedNotification",
+            NotificationName::ABPeoplePickerDisplayedPropertyDidChange => "ABPeoplePickerDisplayedPropertyDidChangeNotification",
+            NotificationName::ABPeoplePickerGroupSelectionDidChange => "ABPeoplePickerGroupSelectionDidChangeNotification",
+            NotificationName::ABPeoplePickerNameSelectionDidChange => "ABPeoplePickerNameSelectionDidChangeNotification",
+            NotificationName::ABPeoplePickerValueSelectionDidChange => "ABPeoplePickerValueSelectionDidChangeNotification",
+            NotificationName::ACAccountStoreDidChange => "ACAccountStoreDidChangeNotification",
+            NotificationName::AVAssetChapterMetadataGroupsDidChange => "AVAssetChapterMetadataGroupsDidChangeNotification",
+            NotificationName::AVAssetContainsFragmentsDidChange => "AVAssetContainsFragmentsDidChangeNotification",
+            NotificationName::AVAssetDurationDidChange => "AVAssetDurationDidChangeNotification",
+            NotificationName::AVAssetMediaSelectionGroupsDidChange => "AVAssetMediaSelectionGroupsDidChangeNotification",
+            NotificationName::AVAssetTrackSegmentsDidChange => "AVAssetTrackSegmentsDidChangeNotification",
+            NotificationName::AVAssetTrackTimeRangeDidChange => "AVAssetTrackTimeRangeDidChangeNotification",
+            NotificationName::AVAssetTrackTrackAssociationsDidChange => "AVAssetTrackTrackAssociationsDidChangeNotification",
+            NotificationName::AVAssetWasDefragmented => "AVAssetWasDefragmentedNotification",
+            NotificationName::AVCaptureDeviceWasConnected => "AVCaptureDeviceWasConnectedNotification",
+            NotificationName::AVCaptureDeviceWasDisconnected => "AVCaptureDeviceWasDisconnectedNotification",
+            NotificationName::AVCaptureInputPortFormatDescriptionDidChange => {
+                "AVCaptureInputPortFormatDescriptionDidChangeNotification"
+            },
+            NotificationName::AVCaptureSessionDidStartRunning => "AVCaptureSessionDidStartRunningNotification",
+            NotificationName::AVCaptureSessionDidStopRunning => "AVCaptureSessionDidStopRunningNotification",
+            NotificationName::AVCaptureSessionRuntimeError => "AVCaptureSessionRuntimeErrorNotification",
+            NotificationName::AVFragmentedMovieContainsMovieFragmentsDidChange => {
+                "AVFragmentedMovieContainsMovieFragmentsDidChangeNotification"
+            },
+            NotificationName::AVFragmentedMovieDurationDidChange => "AVFragmentedMovieDurationDidChangeNotification",
+            NotificationName::AVFragmentedMovieTrackSegmentsDidChange => "AVFragmentedMovieTrackSegmentsDidChangeNotification",
+            NotificationName::AVFragmentedMovieTrackTimeRangeDidChange => "AVFragmentedMovieTrackTimeRangeDidChangeNotification",
+            NotificationName::AVFragmentedMovieTrackTotalSampleDataLengthDidChange => {
+                "AVFragmentedMovieTrackTotalSampleDataLengthDidChangeNotification"
+            },
+            NotificationName::AVFragmentedMovieWasDefragmented => "AVFragmentedMovieWasDefragmentedNotification",
+            NotificationName::AVPlayerItemDidPlayToEndTime => "AVPlayerItemDidPlayToEndTimeNotification",
+            NotificationName::AVPlayerItemFailedToPlayToEndTime => "AVPlayerItemFailedToPlayToEndTimeNotification",
+            NotificationName::AVPlayerItemNewAccessLogEntry => "AVPlayerItemNewAccessLogEntryNotification",
+            NotificationName::AVPlayerItemNewErrorLogEntry => "AVPlayerItemNewErrorLogEntryNotification",
+            NotificationName::AVPlayerItemPlaybackStalled => "AVPlayerItemPlaybackStalledNotification",
+            NotificationName::AVPlayerItemTimeJumped => "AVPlayerItemTimeJumpedNotification",
+            NotificationName::AVSampleBufferDisplayLayerFailedToDecode => "AVSampleBufferDisplayLayerFailedToDecodeNotification",
+            NotificationName::CWBSSIDDidChange => "com.apple.coreWLAN.notification.bssid",
+            NotificationName::CWCountryCodeDidChange => "com.apple.coreWLAN.notification.countryCode",
+            NotificationName::CWLinkDidChange => "com.apple.coreWLAN.notification.link",
+            NotificationName::CWLinkQualityDidChange => "com.apple.coreWLAN.notification.linkQuality",
+            NotificationName::CWModeDidChange => "com.apple.coreWLAN.notification.mode",
+            NotificationName::CWPowerDidChange => "com.apple.coreWLAN.notification.power",
+            NotificationName::CWSSIDDidChange => "com.apple.coreWLAN.notification.ssid",
+            NotificationName::CWScanCacheDidUpdate => "com.apple.coreWLAN.notification.scanCacheUpdated",
+            NotificationName::GCControllerDidConnect => "GCControllerDidConnectNotification",
+            NotificationName::GCControllerDidDisconnect => "GCControllerDidDisconnectNotification",
+            NotificationName::IKFilterBrowserFilterDoubleClick => "IKFilterBrowserFilterDoubleClickNotification",
+            NotificationName::IKFilterBrowserFilterSelected => "IKFilterBrowserFilterSelectedNotification",
+            NotificationName::IKFilterBrowserWillPreviewFilter => "IKFilterBrowserWillPreviewFilterNotification",
+            NotificationName::IOBluetoothHostControllerPoweredOff => "IOBluetoothHostControllerPoweredOffNotification",
+            NotificationName::IOBluetoothHostControllerPoweredOn => "IOBluetoothHostControllerPoweredOnNotification",
+            NotificationName::IOBluetoothL2CAPChannelPublished => "IOBluetoothL2CAPChannelPublishedNotification",
+            NotificationName::IOBluetoothL2CAPChannelTerminated => "IOBluetoothL2CAPChannelTerminatedNotification",
+            NotificationName::MKAnnotationCalloutInfoDidChange => "MKAnnotationCalloutInfoDidChangeNotification",
+            NotificationName::NEFilterConfigurationDidChange => "NEFilterConfigurationDidChangeNotification",
+            NotificationName::NEVPNConfigurationChange => "NEVPNConfigurationChangeNotification",
+            NotificationName::NEVPNStatusDidChange => "NEVPNStatusDidChangeNotification",
+            NotificationName::NSAccessibilityAnnouncementRequested => "AXAnnouncementRequested",
+            NotificationName::NSAccessibilityAnnouncementKey => "AXAnnouncementKey",
+            NotificationName::NSAccessibilityPriorityKey => "AXPriorityKey",
+            NotificationName::NSAccessibilityApplicationActivated => "AXApplicationActivated",
+            NotificationName::NSAccessibilityApplicationDeactivated => "AXApplicationDeactivated",
+            NotificationName::NSAccessibilityApplicationHidden => "AXApplicationHidden",
+            NotificationName::NSAccessibilityApplicationShown => "AXApplicationShown",
+            NotificationName::NSAccessibilityCreated => "AXCreated",
+            NotificationName::NSAccessibilityDrawerCreated => "AXDrawerCreated",
+            NotificationName::NSAccessibilityFocusedUIElementChanged => "AXFocusedUIElementChanged",
+            NotificationName::NSAccessibilityFocusedWindowChanged => "AXFocusedWindowChanged",
+            NotificationName::NSAccessibilityHelpTagCreated => "AXHelpTagCreated",
+            NotificationName::NSAccessibilityLayoutChanged => "AXLayoutChanged",
+            NotificationName::NSAccessibilityUIElementsKey => "AXUIElementsKey",
+            NotificationName::NSAccessibilityMainWindowChanged => "AXMainWindowChanged",
+            NotificationName::NSAccessibilityMoved => "AXMoved",
+            NotificationName::NSAccessibilityResized => "AXResized",
+            NotificationName::NSAccessibilityRowCollapsed => "AXRowCollapsed",
+            NotificationName::NSAccessibilityRowCountChanged => "AXRowCountChanged",
+            NotificationName::NSAccessibilityRowExpanded => "AXRowExpanded",
+            NotificationName::NSAccessibilitySelectedCellsChanged => "AXSelectedCellsChanged",
+            NotificationName::NSAccessibilitySelectedChildrenChanged => "AXSelectedChildrenChanged",
+            NotificationName::NSAccessibilitySelectedChildrenMoved => "AXSelectedChildrenMoved",
+            NotificationName::NSAccessibilitySelectedColumnsChanged => "AXSelectedColumnsChanged",
+            NotificationName::NSAccessibilitySelectedRowsChanged => "AXSelectedRowsChanged",
+            NotificationName::NSAccessibilitySelectedTextChanged => "AXSelectedTextChanged",
+            NotificationName::NSAccessibilitySheetCreated => "AXSheetCreated",
+            NotificationName::NSAccessibilityTitleChanged => "AXTitleChanged",
+            NotificationName::NSAccessibilityUIElementDestroyed => "AXUIElementDestroyed",
+            NotificationName::NSAccessibilityUnitsChanged => "AXUnitsChanged",
+            NotificationName::NSAccessibilityValueChanged => "AXValueChanged",
+            NotificationName::NSAccessibilityWindowCreated => "AXWindowCreated",
+            NotificationName::NSAccessibilityWindowDeminiaturized => "AXWindowDeminiaturized",
+            NotificationName::NSAccessibilityWindowMiniaturized => "AXWindowMiniaturized",
+            NotificationName::NSAccessibilityWindowMoved => "AXWindowMoved",
+            NotificationName::NSAccessibilityWindowResized => "AXWindowResized",
+            NotificationName::NSAnimationProgressMark => "NSAnimationProgressMarkNotification",
+            NotificationName::NSAntialiasThresholdChanged => "NSAntialiasThresholdChangedNotification",
+            NotificationName::NSAppleEventManagerWillProcessFirstEvent => "NSAppleEventManagerWillProcessFirstEventNotification",
+            NotificationName::NSApplicationDidBecomeActive => "NSApplicationDidBecomeActiveNotification",
+            NotificationName::NSApplicationDidChangeOcclusionState => "NSApplicationDidChangeOcclusionStateNotification",
+            NotificationName::NSApplicationDidChangeScreenParameters => "NSApplicationDidChangeScreenParametersNotification",
+            NotificationName::NSApplicationDidFinishLaunching => "NSApplicationDidFinishLaunchingNotification",
+            NotificationName::NSApplicationDidFinishRestoringWindows => "NSApplicationDidFinishRestoringWindowsNotification",
+            NotificationName::NSApplicationDidHide => "NSApplicationDidHideNotification",
+            NotificationName::NSApplicationDidResignActive => "NSApplicationDidResignActiveNotification",
+            NotificationName::NSApplicationDidUnhide => "NSApplicationDidUnhideNotification",
+            NotificationName::NSApplicationDidUpdate => "NSApplicationDidUpdateNotification",
+            NotificationName::NSApplicationWillBecomeActive => "NSApplicationWillBecomeActiveNotification",
+            NotificationName::NSApplicationWillFinishLaunching => "NSApplicationWillFinishLaunchingNotification",
+            NotificationName::NSApplicationWillHide => "NSApplicationWillHideNotification",
+            NotificationName::NSApplicationWillResignActive => "NSApplicationWillResignActiveNotification",
+            NotificationName::NSApplicationWillTerminate => "NSApplicationWillTerminateNotification",
+            NotificationName::NSApplicationWillUnhide => "NSApplicationWillUnhideNotification",
+            NotificationName::NSApplicationWillUpdate => "NSApplicationWillUpdateNotification",
+            NotificationName::NSBrowserColumnConfigurationDidChange => "NSBrowserColumnConfigurationDidChangeNotification",
+            NotificationName::NSClassDescriptionNeededForClass => "NSClassDescriptionNeededForClassNotification",
+            NotificationName::NSColorListDidChange => "NSColorListDidChangeNotification",
+            NotificationName::NSColorPanelColorDidChange => "NSColorPanelColorDidChangeNotification",
+            NotificationName::NSColorPanel => "NSColorPanelNotification",
+            NotificationName::NSComboBoxSelectionDidChange => "NSComboBoxSelectionDidChangeNotification",
+            NotificationName::NSComboBoxSelectionIsChanging => "NSComboBoxSelectionIsChangingNotification",
+            NotificationName::NSComboBoxWillDismiss => "NSComboBoxWillDismissNotification",
+            NotificationName::NSComboBoxWillPopUp => "NSComboBoxWillPopUpNotification",
+            NotificationName::NSContextHelpModeDidActivate => "NSContextHelpModeDidActivateNotification",
+            NotificationName::NSContextHelpModeDidDeactivate => "NSContextHelpModeDidDeactivateNotification",
+            NotificationName::NSControlTextDidBeginEditing => "NSControlTextDidBeginEditingNotification",
+            NotificationName::NSControlTextDidChange => "NSControlTextDidChangeNotification",
+            NotificationName::NSControlTextDidEndEditing => "NSControlTextDidEndEditingNotification",
+            NotificationName::NSControlTintDidChange => "NSControlTintDidChangeNotification",
+            NotificationName::NSDrawerDidClose => "NSDrawerDidCloseNotification",
+            NotificationName::NSDrawerDidOpen => "NSDrawerDidOpenNotification",
+            NotificationName::NSDrawerWillClose => "NSDrawerWillCloseNotification",
+            NotificationName::NSDrawerWillOpen => "NSDrawerWillOpenNotification",
+            NotificationName::NSFontCollectionDidChange => "NSFontCollectionDidChangeNotification",
+            NotificationName::NSFontSetChanged => "NSFontSetChangedNotification",
+            NotificationName::NSImageRepRegistryDidChange => "NSImageRepRegistryDidChangeNotification",
+            NotificationName::NSMenuDidAddItem => "NSMenuDidAddItemNotification",
+            NotificationName::NSMenuDidBeginTracking => "NSMenuDidBeginTrackingNotification",
+            NotificationName::NSMenuDidChangeItem => "NSMenuDidChangeItemNotification",
+            NotificationName::NSMenuDidEndTracking => "NSMenuDidEndTrackingNotification",
+            NotificationName::NSMenuDidRemoveItem => "NSMenuDidRemoveItemNotification",
+            NotificationName::NSMenuDidSendAction => "NSMenuDidSendActionNotification",
+            NotificationName::NSMenuWillSendAction => "NSMenuWillSendActionNotification",
+            NotificationName::NSOutlineViewColumnDidMove => "NSOutlineViewColumnDidMoveNotification",
+            NotificationName::NSOutlineViewColumnDidResize => "NSOutlineViewColumnDidResizeNotification",
+            NotificationName::NSOutlineViewItemDidCollapse => "NSOutlineViewItemDidCollapseNotification",
+            NotificationName::NSOutlineViewItemDidExpand => "NSOutlineViewItemDidExpandNotification",
+            NotificationName::NSOutlineViewItemWillCollapse => "NSOutlineViewItemWillCollapseNotification",
+            NotificationName::NSOutlineViewItemWillExpand => "NSOutlineViewItemWillExpandNotification",
+            NotificationName::NSOutlineViewSelectionDidChange => "NSOutlineViewSelectionDidChangeNotification",
+            NotificationName::NSOutlineViewSelectionIsChanging => "NSOutlineViewSelectionIsChangingNotification",
+            NotificationName::NSPersistentStoreDidImportUbiquitousContentChanges => {
+                "NSPersistentStoreDidImportUbiquitousContentChangesNotification"
+            },
+            NotificationName::NSPopUpButtonCellWillPopUp => "NSPopUpButtonCellWillPopUpNotification",
+            NotificationName::NSPopUpButtonWillPopUp => "NSPopUpButtonWillPopUpNotification",
+            NotificationName::NSPopoverDidClose => "NSPopoverDidCloseNotification",
+            NotificationName::NSPopoverDidShow => "NSPopoverDidShowNotification",
+            NotificationName::NSPopoverWillClose => "NSPopoverWillCloseNotification",
+            NotificationName::NSPopoverWillShow => "NSPopoverWillShowNotification",
+            NotificationName::NSPreferencePaneCancelUnselect => "NSPreferencePaneCancelUnselectNotification",
+            NotificationName::NSPreferencePaneDoUnselect => "NSPreferencePaneDoUnselectNotification",
+            NotificationName::NSPreferencePaneSwitchToPane => "NSPreferencePaneSwitchToPaneNotification",
+            NotificationName::NSPreferencePaneUpdateHelpMenu => "NSPreferencePaneUpdateHelpMenuNotification",
+            NotificationName::NSPreferencePrefPaneIsAvailable => "NSPreferencePrefPaneIsAvailableNotification",
+            NotificationName::NSPreferredScrollerStyleDidChange => "NSPreferredScrollerStyleDidChangeNotification",
+            NotificationName::NSRuleEditorRowsDidChange => "NSRuleEditorRowsDidChangeNotification",
+            NotificationName::NSScreenColorSpaceDidChange => "NSScreenColorSpaceDidChangeNotification",
+            NotificationName::NSScrollViewDidEndLiveMagnify => "NSScrollViewDidEndLiveMagnifyNotification",
+            NotificationName::NSScrollViewDidEndLiveScroll => "NSScrollViewDidEndLiveScrollNotification",
+            NotificationName::NSScrollViewDidLiveScroll => "NSScrollViewDidLiveScrollNotification",
+            NotificationName::NSScrollViewWillStartLiveMagnify => "NSScrollViewWillStartLiveMagnifyNotification",
+            NotificationName::NSScrollViewWillStartLiveScroll => "NSScrollViewWillStartLiveScrollNotification",
+            NotificationName::NSSpellCheckerDidChangeAutomaticCapitalization => {
+                "NSSpellCheckerDidChangeAutomaticCapitalizationNotification"
+            },
+            NotificationName::NSSpellCheckerDidChangeAutomaticDashSubstitution => {
+                "NSSpellCheckerDidChangeAutomaticDashSubstitutionNotification"
+            },
+            NotificationName::NSSpellCheckerDidChangeAutomaticPeriodSubstitution => {
+                "NSSpellCheckerDidChangeAutomaticPeriodSubstitutionNotification"
+            },
+            NotificationName::NSSpellCheckerDidChangeAutomaticQuoteSubstitution => {
+                "NSSpellCheckerDidChangeAutomaticQuoteSubstitutionNotification"
+            },
+            NotificationName::NSSpellCheckerDidChangeAutomaticSpellingCorrection => {
+                "NSSpellCheckerDidChangeAutomaticSpellingCorrectionNotification"
+            },
+            NotificationName::NSSpellCheckerDidChangeAutomaticTextReplacement => {
+                "NSSpellCheckerDidChangeAutomaticTextReplacementNotification"
+            },
+            NotificationName::NSSplitViewDidResizeSubviews => "NSSplitViewDidResizeSubviewsNotification",
+            NotificationName::NSSplitViewWillResizeSubviews => "NSSplitViewWillResizeSubviewsNotification",
+            NotificationName::NSSystemColorsDidChange => "NSSystemColorsDidChangeNotification",
+            NotificationName::NSTableViewColumnDidMove => "NSTableViewColumnDidMoveNotification",
+            NotificationName::NSTableViewColumnDidResize => "NSTableViewColumnDidResizeNotification",
+            NotificationName::NSTableViewSelectionDidChange => "NSTableViewSelectionDidChangeNotification",
+            NotificationName::NSTableViewSelectionIsChanging => "NSTableViewSelectionIsChangingNotification",
+            NotificationName::NSTextAlternativesSelectedAlternativeString => {
+                "NSTextAlternativesSelectedAlternativeStringNotification"
+            },
+            NotificationName::NSTextDidBeginEditing => "NSTextDidBeginEditingNotification",
+            NotificationName::NSTextDidChange => "NSTextDidChangeNotification",
+            NotificationName::NSTextDidEndEditing => "NSTextDidEndEditingNotification",
+            NotificationName::NSTextInputContextKeyboardSelectionDidChange => {
+                "NSTextInputContextKeyboardSelectionDidChangeNotification"
+            },
+            NotificationName::NSTextStorageDidProcessEditing => "NSTextStorageDidProcessEditingNotification",
+            NotificationName::NSTextStorageWillProcessEditing => "NSTextStorageWillProcessEditingNotification",
+            NotificationName::NSTextViewDidChangeSelection => "NSTextViewDidChangeSelectionNotification",
+            NotificationName::NSTextViewDidChangeTypingAttributes => "NSTextViewDidChangeTypingAttributesNotification",
+            NotificationName::NSTextViewWillChangeNotifyingTextView => "NSTextViewWillChangeNotifyingTextViewNotification",
+            NotificationName::NSToolbarDidRemoveItem => "NSToolbarDidRemoveItemNotification",
+            NotificationName::NSToolbarWillAddItem => "NSToolbarWillAddItemNotification",
+            NotificationName::NSViewBoundsDidChange => "NSViewBoundsDidChangeNotification",
+            NotificationName::NSViewDidUpdateTrackingAreas => "NSViewDidUpdateTrackingAreasNotification",
+            NotificationName::NSViewFocusDidChange => "NSViewFocusDidChangeNotification",
+            NotificationName::NSViewFrameDidChange => "NSViewFrameDidChangeNotification",
+            NotificationName::NSViewGlobalFrameDidChange => "NSViewGlobalFrameDidChangeNotification",
+            NotificationName::NSWindowDidBecomeKey => "NSWindowDidBecomeKeyNotification",
+            NotificationName::NSWindowDidBecomeMain => "NSWindowDidBecomeMainNotification",
+            NotificationName::NSWindowDidChangeBackingProperties => "NSWindowDidChangeBackingPropertiesNotification",
+            NotificationName::NSWindowDidChangeOcclusionState => "NSWindowDidChangeOcclusionStateNotification",
+            NotificationName::NSWindowDidChangeScreen => "NSWindowDidChangeScreenNotification",
+            NotificationName::NSWindowDidChangeScreenProfile => "NSWindowDidChangeScreenProfileNotification",
+            NotificationName::NSWindowDidDeminiaturize => "NSWindowDidDeminiaturizeNotification",
+            NotificationName::NSWindowDidEndLiveResize => "NSWindowDidEndLiveResizeNotification",
+            NotificationName::NSWindowDidEndSheet => "NSWindowDidEndSheetNotification",
+            NotificationName::NSWindowDidEnterFullScreen => "NSWindowDidEnterFullScreenNotification",
+            NotificationName::NSWindowDidEnterVersionBrowser => "NSWindowDidEnterVersionBrowserNotification",
+            NotificationName::NSWindowDidExitFullScreen => "NSWindowDidExitFullScreenNotification",
+            NotificationName::NSWindowDidExitVersionBrowser => "NSWindowDidExitVersionBrowserNotification",
+            NotificationName::NSWindowDidExpose => "NSWindowDidExposeNotification",
+            NotificationName::NSWindowDidMiniaturize => "NSWindowDidMiniaturizeNotification",
+            NotificationName::NSWindowDidMove => "NSWindowDidMoveNotification",
+            NotificationName::NSWindowDidResignKey => "NSWindowDidResignKeyNotification",
+            NotificationName::NSWindowDidResignMain => "NSWindowDidResignMainNotification",
+            NotificationName::NSWindowDidResize => "NSWindowDidResizeNotification",
+            NotificationName::NSWindowDidUpdate => "NSWindowDidUpdateNotification",
+            NotificationName::NSWindowWillBeginSheet => "NSWindowWillBeginSheetNotification",
+            NotificationName::NSWindowWillClose => "NSWindowWillCloseNotification",
+            NotificationName::NSWindowWillEnterFullScreen => "NSWindowWillEnterFullScreenNotification",
+            NotificationName::NSWindowWillEnterVersionBrowser => "NSWindowWillEnterVersionBrowserNotification",
+            NotificationName::NSWindowWillExitFullScreen => "NSWindowWillExitFullScreenNotification",
+            NotificationName::NSWindowWillExitVersionBrowser => "NSWindowWillExitVersionBrowserNotification",
+            NotificationName::NSWindowWillMiniaturize => "NSWindowWillMiniaturizeNotification",
+            NotificationName::NSWindowWillMove => "NSWindowWillMoveNotification",
+            NotificationName::NSWindowWillStartLiveResize => "NSWindowWillStartLiveResizeNotification",
+            NotificationName::NSWorkspaceAccessibilityDisplayOptionsDidChange => {
+                "NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification"
+            },
+            NotificationName::NSWorkspaceActiveSpaceDidChange => "NSWorkspaceActiveSpaceDidChangeNotification",
+            NotificationName::NSWorkspaceDidActivateApplication => "NSWorkspaceDidActivateApplicationNotification",
+            NotificationName::NSWorkspaceDidChangeFileLabels => "NSWorkspaceDidChangeFileLabelsNotification",
+            NotificationName::NSWorkspaceDidDeactivateApplication => "NSWorkspaceDidDeactivateApplicationNotification",
+            NotificationName::NSWorkspaceDidHideApplication => "NSWorkspaceDidHideApplicationNotification",
+            NotificationName::NSWorkspaceDidLaunchApplication => "NSWorkspaceDidLaunchApplicationNotification",
+            NotificationName::NSWorkspaceDidMount => "NSWorkspaceDidMountNotification",
+            NotificationName::NSWorkspaceDidPerformFileOperation => "NSWorkspaceDidPerformFileOperationNotification",
+            NotificationName::NSWorkspaceDidRenameVolume => "NSWorkspaceDidRenameVolumeNotification",
+            NotificationName::NSWorkspaceDidTerminateApplication => "NSWorkspaceDidTerminateApplicationNotification",
+            NotificationName::NSWorkspaceDidUnhideApplication => "NSWorkspaceDidUnhideApplicationNotification",
+            NotificationName::NSWorkspaceDidUnmount => "NSWorkspaceDidUnmountNotification",
+            NotificationName::NSWorkspaceDidWake => "NSWorkspaceDidWakeNotification",
+            NotificationName::NSWorkspaceScreensDidSleep => "NSWorkspaceScreensDidSleepNotification",
+            NotificationName::NSWorkspaceScreensDidWake => "NSWorkspaceScreensDidWakeNotification",
+            NotificationName::NSWorkspaceSessionDidBecomeActive => "NSWorkspaceSessionDidBecomeActiveNotification",
+            NotificationName::NSWorkspaceSessionDidResignActive => "NSWorkspaceSessionDidResignActiveNotification",
+            NotificationName::NSWorkspaceWillLaunchApplication => "NSWorkspaceWillLaunchApplicationNotification",
+            NotificationName::NSWorkspaceWillPowerOff => "NSWorkspaceWillPowerOffNotification",
+            NotificationName::NSWorkspaceWillSleep => "NSWorkspaceWillSleepNotification",
+            NotificationName::NSWorkspaceWillUnmount => "NSWorkspaceWillUnmountNotification",
+            NotificationName::PDFDocumentDidBeginFind => "PDFDocumentDidBeginFindNotification",
+            NotificationName::PDFDocumentDidBeginPageFind => "PDFDocumentDidBeginPageFindNotification",
+            NotificationName::PDFDocumentDidBeginPageWrite => "PDFDocumentDidBeginPageWriteNotification",
+            NotificationName::PDFDocumentDidBeginWrite => "PDFDocumentDidBeginWriteNotification",
+            NotificationName::PDFDocumentDidEndFind => "PDFDocumentDidEndFindNotification",
+            NotificationName::PDFDocumentDidEndPageFind => "PDFDocumentDidEndPageFindNotification",
+            NotificationName::PDFDocumentDidEndPageWrite => "PDFDocumentDidEndPageWriteNotification",
+            NotificationName::PDFDocumentDidEndWrite => "PDFDocumentDidEndWriteNotification",
+            NotificationName::PDFDocumentDidFindMatch => "PDFDocumentDidFindMatchNotification",
+            NotificationName::PDFDocumentDidUnlock => "PDFDocumentDidUnlockNotification",
+            NotificationName::PDFThumbnailViewDocumentEdited => "PDFThumbnailViewDocumentEditedNotification",
+            NotificationName::PDFViewAnnotationHit => "PDFViewAnnotationHitNotification",
+            NotificationName::PDFViewAnnotationWillHit => "PDFViewAnnotationWillHitNotification",
+            NotificationName::PDFViewChangedHistory => "PDFViewChangedHistoryNotification",
+            NotificationName::PDFViewCopyPermission => "PDFViewCopyPermissionNotification",
+            NotificationName::PDFViewDisplayBoxChanged => "PDFViewDisplayBoxChangedNotification",
+            NotificationName::PDFViewDisplayModeChanged => "PDFViewDisplayModeChangedNotification",
+            NotificationName::PDFViewDocumentChanged => "PDFViewDocumentChangedNotification",
+            NotificationName::PDFViewPageChanged => "PDFViewPageChangedNotification",
+            NotificationName::PDFViewPrintPermission => "PDFViewPrintPermissionNotification",
+            NotificationName::PDFViewScaleChanged => "PDFViewScaleChangedNotification",
+            NotificationName::PDFViewSelectionChanged => "PDFViewSelectionChangedNotification",
+            NotificationName::PDFViewVisiblePagesChanged => "PDFViewVisiblePagesChangedNotification",
+            NotificationName::KABDatabaseChanged => "kABDatabaseChangedNotification",
+            NotificationName::KABDatabaseChangedExternally => "kABDatabaseChangedExternallyNotification",
+            NotificationName::KQuartzFilterManagerDidAddFilter => "kQuartzFilterManagerDidAddFilterNotification",
+            NotificationName::KQuartzFilterManagerDidModifyFilter => "kQuartzFilterManagerDidModifyFilterNotification",
+            NotificationName::KQuartzFilterManagerDidRemoveFilter => "kQuartzFilterManagerDidRemoveFilterNotification",
+            NotificationName::KQuartzFilterManagerDidSelectFilter => "kQuartzFilterManagerDidSelectFilterNotification",
+            NotificationName::EAAccessoryDidConnect => "EAAccessoryDidConnectNotification",
+            NotificationName::EAAccessoryDidDisconnect => "EAAccessoryDidDisconnectNotification",
+            NotificationName::SKCloudServiceCapabilitiesDidChange => "SKCloudServiceCapabilitiesDidChangeNotification",
+            NotificationName::SKStorefrontIdentifierDidChange => "SKStorefrontIdentifierDidChangeNotification",
+            NotificationName::UIAccessibilityAssistiveTouchStatusDidChange => {
+                "UIAccessibilityAssistiveTouchStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityBoldTextStatusDidChange => "UIAccessibilityBoldTextStatusDidChangeNotification",
+            NotificationName::UIAccessibilityClosedCaptioningStatusDidChange => {
+                "UIAccessibilityClosedCaptioningStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityDarkerSystemColorsStatusDidChange => {
+                "UIAccessibilityDarkerSystemColorsStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityGrayscaleStatusDidChange => "UIAccessibilityGrayscaleStatusDidChangeNotification",
+            NotificationName::UIAccessibilityGuidedAccessStatusDidChange => {
+                "UIAccessibilityGuidedAccessStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityHearingDevicePairedEarDidChange => {
+                "UIAccessibilityHearingDevicePairedEarDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityInvertColorsStatusDidChange => {
+                "UIAccessibilityInvertColorsStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityMonoAudioStatusDidChange => "UIAccessibilityMonoAudioStatusDidChangeNotification",
+            NotificationName::UIAccessibilityReduceMotionStatusDidChange => {
+                "UIAccessibilityReduceMotionStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityReduceTransparencyStatusDidChange => {
+                "UIAccessibilityReduceTransparencyStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilityShakeToUndoDidChange => "UIAccessibilityShakeToUndoDidChangeNotification",
+            NotificationName::UIAccessibilitySpeakScreenStatusDidChange => {
+                "UIAccessibilitySpeakScreenStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilitySpeakSelectionStatusDidChange => {
+                "UIAccessibilitySpeakSelectionStatusDidChangeNotification"
+            },
+            NotificationName::UIAccessibilitySwitchControlStatusDidChange => {
+                "UIAccessibilitySwitchControlStatusDidChangeNotification"
+            },
+            NotificationName::UIApplicationDidBecomeActive => "UIApplicationDidBecomeActiveNotification",
+            NotificationName::UIApplicationDidEnterBackground => "UIApplicationDidEnterBackgroundNotification",
+            NotificationName::UIApplicationDidFinishLaunching => "UIApplicationDidFinishLaunchingNotification",
+            NotificationName::UIApplicationDidReceiveMemoryWarning => "UIApplicationDidReceiveMemoryWarningNotification",
+            NotificationName::UIApplicationSignificantTimeChange => "UIApplicationSignificantTimeChangeNotification",
+            NotificationName::UIApplicationUserDidTakeScreenshot => "UIApplicationUserDidTakeScreenshotNotification",
+            NotificationName::UIApplicationWillEnterForeground => "UIApplicationWillEnterForegroundNotification",
+            NotificationName::UIApplicationWillResignActive => "UIApplicationWillResignActiveNotification",
+            NotificationName::UIApplicationWillTerminate => "UIApplicationWillTerminateNotification",
+            NotificationName::UIContentSizeCategoryDidChange => "UIContentSizeCategoryDidChangeNotification",
+            NotificationName::UIDeviceProximityStateDidChange => "UIDeviceProximityStateDidChangeNotification",
+            NotificationName::UIScreenBrightnessDidChange => "UIScreenBrightnessDidChangeNotification",
+            NotificationName::UIScreenDidConnect => "UIScreenDidConnectNotification",
+            NotificationName::UIScreenDidDisconnect => "UIScreenDidDisconnectNotification",
+            NotificationName::UIScreenModeDidChange => "UIScreenModeDidChangeNotification",
+            NotificationName::UITableViewSelectionDidChange => "UITableViewSelectionDidChangeNotification",
+            NotificationName::UITextFieldTextDidBeginEditing => "UITextFieldTextDidBeginEditingNotification",
+            NotificationName::UITextFieldTextDidChange => "UITextFieldTextDidChangeNotification",
+            NotificationName::UITextFieldTextDidEndEditing => "UITextFieldTextDidEndEditingNotification",
+            NotificationName::UITextInputCurrentInputModeDidChange => "UITextInputCurrentInputModeDidChangeNotification",
+            NotificationName::UITextViewTextDidBeginEditing => "UITextViewTextDidBeginEditingNotification",
+            NotificationName::UITextViewTextDidChange => "UITextViewTextDidChangeNotification",
+            NotificationName::UITextViewTextDidEndEditing => "UITextViewTextDidEndEditingNotification",
+            NotificationName::UIViewControllerShowDetailTargetDidChange => {
+                "UIViewControllerShowDetailTargetDidChangeNotification"
+            },
+            NotificationName::UIWindowDidBecomeHidden => "UIWindowDidBecomeHiddenNotification",
+            NotificationName::UIWindowDidBecomeKey => "UIWindowDidBecomeKeyNotification",
+            NotificationName::UIWindowDidBecomeVisible => "UIWindowDidBecomeVisibleNotification",
+            NotificationName::UIWindowDidResignKey => "UIWindowDidResignKeyNotification",
+            NotificationName::AVCaptureDeviceSubjectAreaDidChange => "AVCaptureDeviceSubjectAreaDidChangeNotification",
+            NotificationName::AVCaptureSessionInterruptionEnded => "AVCaptureSessionInterruptionEndedNotification",
+            NotificationName::AVCaptureSessionWasInterrupted => "AVCaptureSessionWasInterruptedNotification",
+            NotificationName::MFMessageComposeViewControllerTextMessageAvailabilityDidChange => {
+                "MFMessageComposeViewControllerTextMessageAvailabilityDidChangeNotification"
+            },
+            NotificationName::MPMediaLibraryDidChange => "MPMediaLibraryDidChangeNotification",
+            NotificationName::MPMusicPlayerControllerNowPlayingItemDidChange => {
+                "MPMusicPlayerControllerNowPlayingItemDidChangeNotification"
+            },
+            NotificationName::MPMusicPlayerControllerPlaybackStateDidChange => {
+                "MPMusicPlayerControllerPlaybackStateDidChangeNotification"
+            },
+            NotificationName::MPMusicPlayerControllerVolumeDidChange => "MPMusicPlayerControllerVolumeDidChangeNotification",
+            NotificationName::UIApplicationBackgroundRefreshStatusDidChange => {
+                "UIApplicationBackgroundRefreshStatusDidChangeNotification"
+            },
+            NotificationName::UIDeviceBatteryLevelDidChange => "UIDeviceBatteryLevelDidChangeNotification",
+            NotificationName::UIDeviceBatteryStateDidChange => "UIDeviceBatteryStateDidChangeNotification",
+            NotificationName::UIDeviceOrientationDidChange => "UIDeviceOrientationDidChangeNotification",
+            NotificationName::UIDocumentStateChanged => "UIDocumentStateChangedNotification",
+            NotificationName::UIKeyboardDidChangeFrame => "UIKeyboardDidChangeFrameNotification",
+            NotificationName::UIKeyboardDidHide => "UIKeyboardDidHideNotification",
+            NotificationName::UIKeyboardDidShow => "UIKeyboardDidShowNotification",
+            NotificationName::UIKeyboardWillChangeFrame => "UIKeyboardWillChangeFrameNotification",
+            NotificationName::UIKeyboardWillHide => "UIKeyboardWillHideNotification",
+            NotificationName::UIKeyboardWillShow => "UIKeyboardWillShowNotification",
+            NotificationName::UIMenuControllerDidHideMenu => "UIMenuControllerDidHideMenuNotification",
+            NotificationName::UIMenuControllerDidShowMenu => "UIMenuControllerDidShowMenuNotification",
+            NotificationName::UIMenuControllerMenuFrameDidChange => "UIMenuControllerMenuFrameDidChangeNotification",
+            NotificationName::UIMenuControllerWillHideMenu => "UIMenuControllerWillHideMenuNotification",
+            NotificationName::UIMenuControllerWillShowMenu => "UIMenuControllerWillShowMenuNotification",
+            NotificationName::UIPasteboardChanged => "UIPasteboardChangedNotification",
+            NotificationName::UIPasteboardRemoved => "UIPasteboardRemovedNotification",
+            NotificationName::UIApplicationProtectedDataDidBecomeAvailable => {
+                "UIApplicationProtectedDataDidBecomeAvailableNotification"
+            },
+            NotificationName::UIApplicationProtectedDataWillBecomeUnavailable => {
+                "UIApplicationProtectedDataWillBecomeUnavailableNotification"
+            },
+            NotificationName::NSSpellCheckerDidChangeAutomaticTextCompletion => {
+                "NSSpellCheckerDidChangeAutomaticTextCompletionNotification"
+            },
+            NotificationName::MPMusicPlayerControllerQueueDidChange => "MPMusicPlayerControllerQueueDidChangeNotification",
+            NotificationName::AVDisplayManagerModeSwitchEnd => "AVDisplayManagerModeSwitchEndNotification",
+            NotificationName::AVDisplayManagerModeSwitchSettingsChanged => {
+                "AVDisplayManagerModeSwitchSettingsChangedNotification"
+            },
+            NotificationName::AVDisplayManagerModeSwitchStart => "AVDisplayManagerModeSwitchStartNotification",
+            NotificationName::AVPlayerAvailableHDRModesDidChange => "AVPlayerAvailableHDRModesDidChangeNotification",
+            NotificationName::AVRouteDetectorMultipleRoutesDetectedDidChange => {
+                "AVRouteDetectorMultipleRoutesDetectedDidChangeNotification"
+            },
+            NotificationName::AVSampleBufferAudioRendererWasFlushedAutomatically => {
+                "AVSampleBufferAudioRendererWasFlushedAutomaticallyNotification"
+            },
+            NotificationName::CTServiceRadioAccessTechnologyDidChange => "CTServiceRadioAccessTechnologyDidChangeNotification",
+            NotificationName::GKPlayerAuthenticationDidChangeNotificationName => {
+                "GKPlayerAuthenticationDidChangeNotificationName"
+            },
+            NotificationName::GKPlayerDidChangeNotificationName => "GKPlayerDidChangeNotificationName",
+            NotificationName::NEDNSProxyConfigurationDidChange => "NEDNSProxyConfigurationDidChangeNotification",
+            NotificationName::NSPersistentStoreRemoteChange => "NSPersistentStoreRemoteChangeNotification",
+            NotificationName::SKStorefrontCountryCodeDidChange => "SKStorefrontCountryCodeDidChangeNotification",
+            NotificationName::WKAccessibilityReduceMotionStatusDidChange => {
+                "WKAccessibilityReduceMotionStatusDidChangeNotification"
+            },
+        }
+    }
+}
+
+impl AsRef<str> for NotificationName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
 impl From<NotificationName> for NSString<'_> {
     fn from(name: NotificationName) -> Self {
-        match name {
-            _ => NSString::no_copy("")
+        NSString::no_copy(name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::NotificationName;
+
+    #[test]
+    fn test_notification_names() {
+        // Most names are the constant's own name...
+        let names = [
+            NotificationName::NSApplicationDidChangeScreenParameters,
+            NotificationName::NSWindowDidResize,
+            NotificationName::NSWorkspaceDidWake,
+            NotificationName::UIApplicationDidBecomeActive
+        ];
+
+        for name in &names {
+            assert_eq!(name.as_str(), format!("{:?}Notification", name));
+            assert_eq!(name.as_ref(), name.as_str());
         }
+
+        // ...but not all of them.
+        assert_eq!(
+            NotificationName::AudioSessionInterruption.as_str(),
+            "AVAudioSessionInterruptionNotification"
+        );
+        assert_eq!(
+            NotificationName::CloudKitAccountChanged.as_str(),
+            "CKAccountChangedNotification"
+        );
+        assert_eq!(NotificationName::NSAccessibilityMoved.as_str(), "AXMoved");
+        assert_eq!(
+            NotificationName::CWSSIDDidChange.as_str(),
+            "com.apple.coreWLAN.notification.ssid"
+        );
+        assert_eq!(
+            NotificationName::NSManagedObjectContextDidSave.as_str(),
+            "NSManagingContextDidSaveChangesNotification"
+        );
+
+        // Every name maps to its own string, so a `Notification` can always be matched back to
+        // the name it was posted under.
+        let source = include_str!("name.rs");
+        let start = source.find("pub fn as_str").unwrap();
+        let end = source.find("impl AsRef<str> for NotificationName").unwrap();
+        let strings: Vec<&str> = source[start..end].split('"').skip(1).step_by(2).collect();
+        let unique: HashSet<&str> = strings.iter().copied().collect();
+        assert!(strings.len() > 400);
+        assert_eq!(unique.len(), strings.len());
     }
 }
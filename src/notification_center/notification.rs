@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use objc::runtime::Object;
+use objc::{msg_send, sel, sel_impl};
+use objc_id::ShareId;
+
+use crate::defaults::Value;
+use crate::foundation::{id, NSString};
+
+/// Wraps an `NSNotification`, as handed to `NotificationCenter` observers.
+#[derive(Clone, Debug)]
+pub struct Notification {
+    /// The underlying `NSNotification`.
+    pub objc: ShareId<Object>
+}
+
+impl Notification {
+    pub(crate) fn new(objc: id) -> Self {
+        Notification {
+            objc: unsafe { ShareId::from_ptr(objc) }
+        }
+    }
+
+    /// Returns the name of this notification.
+    pub fn name(&self) -> String {
+        NSString::retain(unsafe { msg_send![&*self.objc, name] }).to_string()
+    }
+
+    /// Returns the object that posted this notification, if there is one. For system
+    /// notifications this is typically the sender - e.g, the `NSWindow` for window notifications.
+    pub fn object(&self) -> Option<ShareId<Object>> {
+        let object: id = unsafe { msg_send![&*self.objc, object] };
+
+        match object.is_null() {
+            true => None,
+            false => Some(unsafe { ShareId::from_ptr(object) })
+        }
+    }
+
+    /// Returns the `userInfo` dictionary for this notification. Only entries whose values can be
+    /// represented as a `Value` are included; anything else (e.g, an `NSValue` wrapping a rect)
+    /// can be read from the raw dictionary via `user_info_object()`.
+    pub fn user_info(&self) -> HashMap<String, Value> {
+        let user_info: id = unsafe { msg_send![&*self.objc, userInfo] };
+
+        match Value::from_objc(user_info) {
+            Some(Value::Dictionary(values)) => values,
+            _ => HashMap::new()
+        }
+    }
+
+    /// Returns the raw `userInfo` value for `key`, for entries that `user_info()` can't represent.
+    pub fn user_info_object(&self, key: &str) -> Option<ShareId<Object>> {
+        let key = NSString::new(key);
+
+        unsafe {
+            let user_info: id = msg_send![&*self.objc, userInfo];
+
+            if user_info.is_null() {
+                return None;
+            }
+
+            let value: id = msg_send![user_info, objectForKey:&*key];
+
+            match value.is_null() {
+                true => None,
+                false => Some(ShareId::from_ptr(value))
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
+use objc_id::ShareId;
+
+use crate::foundation::NSInteger;
+
+/// Wraps an `NSOperationQueue`, which can be used to control where `NotificationCenter`
+/// observers are called.
+#[derive(Clone, Debug)]
+pub struct OperationQueue(pub ShareId<Object>);
+
+impl Default for OperationQueue {
+    /// Equivalent to calling `OperationQueue::new()`.
+    fn default() -> Self {
+        OperationQueue::new()
+    }
+}
+
+impl OperationQueue {
+    /// Returns a new queue, which runs operations on background threads.
+    pub fn new() -> Self {
+        OperationQueue(unsafe { ShareId::from_retained_ptr(msg_send![class!(NSOperationQueue), new]) })
+    }
+
+    /// Returns the queue associated with the main thread. Use this for observers that touch UI.
+    pub fn main() -> Self {
+        OperationQueue(unsafe { ShareId::from_ptr(msg_send![class!(NSOperationQueue), mainQueue]) })
+    }
+
+    /// Sets the maximum number of operations this queue runs at once. Setting this to `1` makes
+    /// it a serial queue, so observers are called one at a time in the order notifications arrive.
+    pub fn set_max_concurrent_operation_count(&self, count: usize) {
+        let count = count as NSInteger;
+
+        unsafe {
+            let _: () = msg_send![&*self.0, setMaxConcurrentOperationCount: count];
+        }
+    }
+}
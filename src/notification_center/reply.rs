@@ -0,0 +1,155 @@
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use super::executor::Executor;
+
+type Callback<T> = Box<dyn FnOnce(T) + Send + 'static>;
+
+struct State<T> {
+    value: Option<T>,
+    callback: Option<Callback<T>>,
+    replied: bool,
+    receiver_dropped: bool
+}
+
+struct Slot<T> {
+    state: Mutex<State<T>>,
+    ready: Condvar,
+    executor: Arc<dyn Executor>
+}
+
+impl<T: Send + 'static> Slot<T> {
+    /// Hands `value` to `callback` on the main thread.
+    fn deliver(&self, value: T, callback: Callback<T>) {
+        self.executor.run_on_main(Box::new(move || callback(value)));
+    }
+}
+
+/// The sending half of a request/response exchange, passed to receivers inside a message. Call
+/// `send()` with the response; only the first reply is kept, so if a request is routed to several
+/// receivers, the first to answer wins.
+pub struct Reply<T> {
+    slot: Arc<Slot<T>>
+}
+
+impl<T> Clone for Reply<T> {
+    fn clone(&self) -> Self {
+        Reply { slot: self.slot.clone() }
+    }
+}
+
+impl<T> fmt::Debug for Reply<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reply").finish()
+    }
+}
+
+impl<T: Send + 'static> Reply<T> {
+    /// Sends the response. Returns `false` if a reply was already sent or nobody is waiting for
+    /// one anymore.
+    pub fn send(&self, value: T) -> bool {
+        let mut state = self.slot.state.lock().unwrap();
+
+        if state.replied || state.receiver_dropped {
+            return false;
+        }
+
+        state.replied = true;
+
+        match state.callback.take() {
+            Some(callback) => {
+                drop(state);
+                self.slot.deliver(value, callback);
+            },
+
+            None => {
+                state.value = Some(value);
+                self.slot.ready.notify_all();
+            }
+        }
+
+        true
+    }
+}
+
+/// The receiving half of a request/response exchange, returned from `MessageRouter::request_main`
+/// and `MessageRouter::request_background`.
+pub struct ReplyReceiver<T> {
+    slot: Arc<Slot<T>>
+}
+
+impl<T> fmt::Debug for ReplyReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplyReceiver").finish()
+    }
+}
+
+impl<T: Send + 'static> ReplyReceiver<T> {
+    /// Returns the response, if one has arrived. Never blocks.
+    pub fn try_recv(&self) -> Option<T> {
+        self.slot.state.lock().unwrap().value.take()
+    }
+
+    /// Blocks until the response arrives or `timeout` elapses.
+    ///
+    /// Never call this on the main thread for a request that's answered on the main thread -
+    /// the receiver can't run while you're blocking it, so this would always time out.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.slot.state.lock().unwrap();
+
+        loop {
+            if let Some(value) = state.value.take() {
+                return Some(value);
+            }
+
+            let now = Instant::now();
+
+            if now >= deadline {
+                return None;
+            }
+
+            state = self.slot.ready.wait_timeout(state, deadline - now).unwrap().0;
+        }
+    }
+
+    /// Calls `handler` with the response on the main thread, once it arrives. This is the usual
+    /// way to consume a reply from UI code, since it never blocks.
+    pub fn on_main<F: FnOnce(T) + Send + 'static>(self, handler: F) {
+        let mut state = self.slot.state.lock().unwrap();
+
+        match state.value.take() {
+            Some(value) => {
+                drop(state);
+                self.slot.deliver(value, Box::new(handler));
+            },
+
+            None => state.callback = Some(Box::new(handler))
+        }
+    }
+}
+
+impl<T> Drop for ReplyReceiver<T> {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.slot.state.lock() {
+            state.receiver_dropped = state.callback.is_none();
+        }
+    }
+}
+
+/// Creates a connected `Reply` and `ReplyReceiver`, delivering `on_main` callbacks via `executor`.
+pub(crate) fn channel<T>(executor: Arc<dyn Executor>) -> (Reply<T>, ReplyReceiver<T>) {
+    let slot = Arc::new(Slot {
+        state: Mutex::new(State {
+            value: None,
+            callback: None,
+            replied: false,
+            receiver_dropped: false
+        }),
+        ready: Condvar::new(),
+        executor
+    });
+
+    (Reply { slot: slot.clone() }, ReplyReceiver { slot })
+}
@@ -0,0 +1,290 @@
+use std::any::type_name;
+use std::fmt;
+use std::sync::Arc;
+
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+use lazy_static::lazy_static;
+
+use super::bus::{Delivery, EventBus, Subscription};
+use super::executor::Executor;
+use super::reply::{channel, ReplyReceiver};
+use super::{Dispatcher, Reply};
+
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+lazy_static! {
+    static ref SHARED_ROUTER: MessageRouter = MessageRouter::new();
+}
+
+/// Routes messages to any number of receivers, based on the message type. This is the
+/// multi-receiver counterpart to `App::dispatch_main` and `App::dispatch_background`, which can
+/// only reach the app delegate: here, any window, view or controller that implements `Dispatcher`
+/// can register itself, and every receiver registered for a message type gets a copy.
+///
+/// Main messages are delivered on the main thread (`Dispatcher::on_ui_message`), and background
+/// messages on a background queue (`Dispatcher::on_background_message`) - unlike
+/// `App::dispatch_background`, which stays on the main queue. Receivers are called in
+/// the order they registered.
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use cacao::notification_center::{Dispatcher, MessageRouter, Reply};
+///
+/// #[derive(Clone, Debug)]
+/// enum Message {
+///     Refresh,
+///     CountTodos(Reply<usize>)
+/// }
+///
+/// struct TodosWindow;
+///
+/// impl Dispatcher for TodosWindow {
+///     type Message = Message;
+///
+///     fn on_ui_message(&self, message: Message) {
+///         if let Message::Refresh = message {
+///             // Reload the list...
+///         }
+///     }
+///
+///     fn on_background_message(&self, message: Message) {
+///         if let Message::CountTodos(reply) = message {
+///             reply.send(42);
+///         }
+///     }
+/// }
+///
+/// let window = Arc::new(TodosWindow);
+/// let _registration = MessageRouter::shared().register(&window);
+///
+/// MessageRouter::shared().dispatch_main(Message::Refresh);
+/// MessageRouter::shared()
+///     .request_background(Message::CountTodos)
+///     .on_main(|count| println!("{} todos", count));
+/// ```
+#[derive(Clone)]
+pub struct MessageRouter {
+    bus: EventBus
+}
+
+#[cfg(any(feature = "appkit", feature = "uikit"))]
+impl Default for MessageRouter {
+    /// Returns a router that delivers via Grand Central Dispatch.
+    fn default() -> Self {
+        MessageRouter::new()
+    }
+}
+
+impl MessageRouter {
+    /// Returns a new router that delivers via Grand Central Dispatch.
+    #[cfg(any(feature = "appkit", feature = "uikit"))]
+    pub fn new() -> Self {
+        MessageRouter { bus: EventBus::new() }
+    }
+
+    /// Returns the process-wide router, which delivers via Grand Central Dispatch.
+    #[cfg(any(feature = "appkit", feature = "uikit"))]
+    pub fn shared() -> &'static MessageRouter {
+        &SHARED_ROUTER
+    }
+
+    /// Returns a new router that hands deliveries to the given executor - e.g, a `ManualExecutor`
+    /// in unit tests.
+    pub fn with_executor(executor: Arc<dyn Executor>) -> Self {
+        MessageRouter {
+            bus: EventBus::with_executor(executor)
+        }
+    }
+
+    /// The bus name messages of type `M` are posted under for `delivery`. The bus also matches on
+    /// the payload's `TypeId`, so the type name only needs to be readable, not unique.
+    fn name<M: 'static>(delivery: Delivery) -> String {
+        format!("cacao.router.{:?}.{}", delivery, type_name::<M>())
+    }
+
+    /// Registers `receiver` for its `Dispatcher::Message` type. The router only holds a weak
+    /// reference, so registering doesn't keep the receiver alive; it's unregistered when either
+    /// it or the returned `Registration` is dropped.
+    pub fn register<R>(&self, receiver: &Arc<R>) -> Registration
+    where
+        R: Dispatcher + Send + Sync + 'static,
+        R::Message: Clone + 'static
+    {
+        Registration(vec![
+            self.bus.subscribe_weak(
+                Self::name::<R::Message>(Delivery::Main),
+                Delivery::Main,
+                receiver,
+                |receiver: &R, message: &R::Message| receiver.on_ui_message(message.clone())
+            ),
+            self.bus.subscribe_weak(
+                Self::name::<R::Message>(Delivery::Background),
+                Delivery::Background,
+                receiver,
+                |receiver: &R, message: &R::Message| receiver.on_background_message(message.clone())
+            ),
+        ])
+    }
+
+    /// Registers a closure to receive messages of type `M` on the main thread.
+    pub fn receive_main<M, F>(&self, handler: F) -> Registration
+    where
+        M: Clone + Send + Sync + 'static,
+        F: Fn(M) + Send + Sync + 'static
+    {
+        let subscription = self
+            .bus
+            .subscribe(Self::name::<M>(Delivery::Main), Delivery::Main, move |message: &M| {
+                handler(message.clone())
+            });
+
+        Registration(vec![subscription])
+    }
+
+    /// Registers a closure to receive messages of type `M` on a background queue.
+    pub fn receive_background<M, F>(&self, handler: F) -> Registration
+    where
+        M: Clone + Send + Sync + 'static,
+        F: Fn(M) + Send + Sync + 'static
+    {
+        let subscription = self.bus.subscribe(
+            Self::name::<M>(Delivery::Background),
+            Delivery::Background,
+            move |message: &M| handler(message.clone())
+        );
+
+        Registration(vec![subscription])
+    }
+
+    /// Sends `message` to every receiver registered for its type, on the main thread.
+    pub fn dispatch_main<M: Clone + Send + Sync + 'static>(&self, message: M) {
+        self.bus.post(Self::name::<M>(Delivery::Main), message);
+    }
+
+    /// Sends `message` to every receiver registered for its type, on a background queue.
+    pub fn dispatch_background<M: Clone + Send + Sync + 'static>(&self, message: M) {
+        self.bus.post(Self::name::<M>(Delivery::Background), message);
+    }
+
+    /// Sends a request on the main thread, returning a receiver for the response. `build` is
+    /// handed the `Reply` to embed in the message - for an enum variant holding one, the variant
+    /// itself works (e.g, `router.request_main(Message::CountTodos)`).
+    pub fn request_main<M, T, F>(&self, build: F) -> ReplyReceiver<T>
+    where
+        M: Clone + Send + Sync + 'static,
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> M
+    {
+        let (reply, receiver) = channel(self.bus.executor());
+        self.dispatch_main(build(reply));
+        receiver
+    }
+
+    /// Sends a request on a background queue, returning a receiver for the response. See
+    /// `request_main()` for details.
+    pub fn request_background<M, T, F>(&self, build: F) -> ReplyReceiver<T>
+    where
+        M: Clone + Send + Sync + 'static,
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> M
+    {
+        let (reply, receiver) = channel(self.bus.executor());
+        self.dispatch_background(build(reply));
+        receiver
+    }
+}
+
+impl fmt::Debug for MessageRouter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MessageRouter").field("bus", &self.bus).finish()
+    }
+}
+
+/// Returned when registering with a `MessageRouter`. The receiver is unregistered when this is
+/// dropped.
+#[derive(Debug)]
+pub struct Registration(Vec<Subscription>);
+
+impl Registration {
+    /// Keeps the receiver registered for as long as it (and the router) are alive, rather than
+    /// until this is dropped.
+    pub fn detach(self) {
+        for subscription in self.0 {
+            subscription.detach();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use super::MessageRouter;
+    use crate::notification_center::{Dispatcher, ManualExecutor, Reply};
+
+    #[derive(Clone, Debug)]
+    enum Message {
+        Ping(&'static str),
+        Count(Reply<usize>)
+    }
+
+    #[derive(Default)]
+    struct Receiver {
+        log: Mutex<Vec<String>>
+    }
+
+    impl Dispatcher for Receiver {
+        type Message = Message;
+
+        fn on_ui_message(&self, message: Message) {
+            if let Message::Ping(text) = message {
+                self.log.lock().unwrap().push(format!("main {}", text));
+            }
+        }
+
+        fn on_background_message(&self, message: Message) {
+            match message {
+                Message::Ping(text) => self.log.lock().unwrap().push(format!("background {}", text)),
+                Message::Count(reply) => {
+                    reply.send(self.log.lock().unwrap().len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_routing_and_replies() {
+        let executor = Arc::new(ManualExecutor::new());
+        let router = MessageRouter::with_executor(executor.clone());
+
+        let first = Arc::new(Receiver::default());
+        let second = Arc::new(Receiver::default());
+        let _first = router.register(&first);
+        let _second = router.register(&second);
+
+        router.dispatch_main(Message::Ping("a"));
+        router.dispatch_background(Message::Ping("b"));
+        executor.run_main();
+        executor.run_background();
+        assert_eq!(*first.log.lock().unwrap(), vec!["main a", "background b"]);
+        assert_eq!(*second.log.lock().unwrap(), vec!["main a", "background b"]);
+
+        // Both receivers answer; the first reply wins.
+        second.log.lock().unwrap().clear();
+        let receiver = router.request_background(Message::Count);
+        assert_eq!(receiver.try_recv(), None);
+        executor.run_background();
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(1)), Some(2));
+
+        // Dropped receivers stop getting messages.
+        drop(first);
+        let answer = Arc::new(Mutex::new(None));
+        let sink = answer.clone();
+        router
+            .request_background(Message::Count)
+            .on_main(move |count| *sink.lock().unwrap() = Some(count));
+        executor.run_background();
+        executor.run_main();
+        assert_eq!(*answer.lock().unwrap(), Some(0));
+    }
+}
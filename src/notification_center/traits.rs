@@ -10,6 +10,9 @@
 ///
 /// This will asynchronously loop a message back to the "top" of your app, via your app delegate.
 /// You can process it from there.
+///
+/// Windows, views and controllers can implement this too, and register with a `MessageRouter` to
+/// receive messages alongside (or instead of) the app delegate.
 pub trait Dispatcher {
     /// The type of Message you're sending. This should be lightweight and thread safe.
     type Message: Send + Sync;
@@ -0,0 +1,394 @@
+//! Registers the `NSOutlineView` subclasses that back `OutlineView`, and forwards their data
+//! source and delegate calls to the Rust side, translating between items and the objects that
+//! stand in for them.
+
+use std::cell::RefCell;
+
+use objc::runtime::{Class, Object, Sel, BOOL};
+use objc::{class, msg_send, sel, sel_impl};
+
+use crate::appkit::menu::Menu;
+use crate::foundation::{id, load_or_register_class, nil, NSInteger, NSString, NSUInteger, NO, YES};
+use crate::outlineview::items::{destination_index, ItemTable};
+use crate::outlineview::{OutlineViewDelegate, ITEM_PASTEBOARD_TYPE, OUTLINEVIEW_DELEGATE_PTR, OUTLINEVIEW_ITEMS_PTR};
+use crate::utils::load;
+
+/// `NSDragOperationMove`.
+const DRAG_OPERATION_MOVE: NSUInteger = 16;
+
+/// Returns the table of items for an outline view.
+fn items<T: OutlineViewDelegate>(this: &Object) -> &RefCell<ItemTable<T::Item>> {
+    load::<RefCell<ItemTable<T::Item>>>(this, OUTLINEVIEW_ITEMS_PTR)
+}
+
+/// Returns the item an object stands in for; `nil` is the root of the tree.
+fn item_for<T: OutlineViewDelegate>(this: &Object, object: id) -> Option<T::Item> {
+    items::<T>(this).borrow().item_for(object)
+}
+
+/// Returns the object that stands in for an item.
+fn object_for<T: OutlineViewDelegate>(this: &Object, item: &T::Item) -> id {
+    items::<T>(this).borrow_mut().object_for(item)
+}
+
+/// Returns the item from an expand/collapse notification.
+fn notification_item<T: OutlineViewDelegate>(this: &Object, notification: id) -> Option<T::Item> {
+    let object: id = unsafe {
+        let user_info: id = msg_send![notification, userInfo];
+        let key = NSString::new("NSObject");
+        msg_send![user_info, objectForKey:&*key]
+    };
+
+    item_for::<T>(this, object)
+}
+
+extern "C" fn number_of_children<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, item: id) -> NSInteger {
+    let parent = item_for::<T>(this, item);
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+    view.number_of_children(parent.as_ref()) as NSInteger
+}
+
+extern "C" fn child_of_item<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, index: NSInteger, item: id) -> id {
+    let parent = item_for::<T>(this, item);
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+    let child = view.child(index as usize, parent.as_ref());
+    object_for::<T>(this, &child)
+}
+
+extern "C" fn is_item_expandable<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, item: id) -> BOOL {
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+
+    match item_for::<T>(this, item) {
+        Some(item) if view.is_expandable(&item) => YES,
+        _ => NO
+    }
+}
+
+extern "C" fn is_group_item<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, item: id) -> BOOL {
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+
+    match item_for::<T>(this, item) {
+        Some(item) if view.is_group_item(&item) => YES,
+        _ => NO
+    }
+}
+
+extern "C" fn view_for_item<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, _column: id, item: id) -> id {
+    let item = match item_for::<T>(this, item) {
+        Some(item) => item,
+        None => return nil
+    };
+
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+    let row = view.item_for(&item);
+
+    // As with `ListView`, the outline view retains the row, so handing back the pointer is fine.
+    row.objc.get(|obj| unsafe { msg_send![obj, self] })
+}
+
+extern "C" fn should_expand_item<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, item: id) -> BOOL {
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+
+    match item_for::<T>(this, item) {
+        Some(item) if !view.should_expand(&item) => NO,
+        _ => YES
+    }
+}
+
+extern "C" fn should_collapse_item<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, item: id) -> BOOL {
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+
+    match item_for::<T>(this, item) {
+        Some(item) if !view.should_collapse(&item) => NO,
+        _ => YES
+    }
+}
+
+extern "C" fn item_did_expand<T: OutlineViewDelegate>(this: &Object, _: Sel, notification: id) {
+    if let Some(item) = notification_item::<T>(this, notification) {
+        let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+        view.item_expanded(&item);
+    }
+}
+
+extern "C" fn item_did_collapse<T: OutlineViewDelegate>(this: &Object, _: Sel, notification: id) {
+    if let Some(item) = notification_item::<T>(this, notification) {
+        let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+        view.item_collapsed(&item);
+    }
+}
+
+extern "C" fn selection_did_change<T: OutlineViewDelegate>(this: &Object, _: Sel, notification: id) {
+    let object: id = unsafe {
+        let outline: id = msg_send![notification, object];
+        let row: NSInteger = msg_send![outline, selectedRow];
+
+        match row {
+            -1 => nil,
+            row => msg_send![outline, itemAtRow: row]
+        }
+    };
+
+    let item = item_for::<T>(this, object);
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+    view.item_selected(item);
+}
+
+extern "C" fn persistent_object_for_item<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, item: id) -> id {
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+
+    match item_for::<T>(this, item).and_then(|item| view.persistent_key(&item)) {
+        // The outline view doesn't take ownership of the key, so hand back an autoreleased one.
+        Some(key) => unsafe {
+            let key = NSString::new(&key);
+            let key: id = msg_send![&*key, retain];
+            msg_send![key, autorelease]
+        },
+
+        None => nil
+    }
+}
+
+extern "C" fn item_for_persistent_object<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, object: id) -> id {
+    if object == nil {
+        return nil;
+    }
+
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+    let key = NSString::retain(object);
+
+    match view.item_for_persistent_key(key.to_str()) {
+        Some(item) => object_for::<T>(this, &item),
+        None => nil
+    }
+}
+
+/// Puts the token for a dragged item on the pasteboard. The item itself stays on the Rust side.
+extern "C" fn pasteboard_writer_for_item<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, item: id) -> id {
+    if item_for::<T>(this, item).is_none() {
+        return nil;
+    }
+
+    unsafe {
+        let token: NSUInteger = msg_send![item, unsignedIntegerValue];
+        let token = NSString::new(&token.to_string());
+        let kind = NSString::new(ITEM_PASTEBOARD_TYPE);
+
+        let writer: id = msg_send![class!(NSPasteboardItem), new];
+        let _: BOOL = msg_send![writer, setString:&*token forType:&*kind];
+        msg_send![writer, autorelease]
+    }
+}
+
+/// A validated drop: the item being moved, where to, and where from.
+struct ItemDrop<I> {
+    item: I,
+    parent: Option<I>,
+    index: usize,
+    from_parent: id,
+    from_index: NSInteger
+}
+
+/// Works out where a drop would move an item, if anywhere - ruling out drags from elsewhere, and
+/// drops onto the dragged item or its descendants.
+fn drop_for<T: OutlineViewDelegate>(this: &Object, info: id, parent: id, index: NSInteger) -> Option<ItemDrop<T::Item>> {
+    let (source, token) = unsafe {
+        let source: id = msg_send![info, draggingSource];
+        let pasteboard: id = msg_send![info, draggingPasteboard];
+        let kind = NSString::new(ITEM_PASTEBOARD_TYPE);
+        let token: id = msg_send![pasteboard, stringForType:&*kind];
+        (source, token)
+    };
+
+    if !std::ptr::eq(source, this) || token == nil {
+        return None;
+    }
+
+    let token: usize = NSString::retain(token).to_str().parse().ok()?;
+    let item = items::<T>(this).borrow().item(token).cloned()?;
+    let object = object_for::<T>(this, &item);
+
+    let mut ancestor = parent;
+    while ancestor != nil {
+        if ancestor == object {
+            return None;
+        }
+
+        ancestor = unsafe { msg_send![this, parentForItem: ancestor] };
+    }
+
+    let (from_parent, from_index): (id, NSInteger) = unsafe {
+        let from_parent: id = msg_send![this, parentForItem: object];
+        let from_index: NSInteger = msg_send![this, childIndexForItem: object];
+        (from_parent, from_index)
+    };
+
+    let parent_item = item_for::<T>(this, parent);
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+
+    // Drops onto an item (rather than between its children) append to it.
+    let proposed = match index {
+        -1 => view.number_of_children(parent_item.as_ref()),
+        index => index as usize
+    };
+
+    let same_parent = match from_parent == parent {
+        true => Some(from_index as usize),
+        false => None
+    };
+
+    Some(ItemDrop {
+        item,
+        parent: parent_item,
+        index: destination_index(same_parent, proposed),
+        from_parent,
+        from_index
+    })
+}
+
+extern "C" fn validate_drop<T: OutlineViewDelegate>(
+    this: &Object,
+    _: Sel,
+    _: id,
+    info: id,
+    parent: id,
+    index: NSInteger
+) -> NSUInteger {
+    let target = match drop_for::<T>(this, info, parent, index) {
+        Some(target) => target,
+        None => return 0
+    };
+
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+
+    match view.can_move(&target.item, target.parent.as_ref(), target.index) {
+        true => DRAG_OPERATION_MOVE,
+        false => 0
+    }
+}
+
+extern "C" fn accept_drop<T: OutlineViewDelegate>(this: &Object, _: Sel, _: id, info: id, parent: id, index: NSInteger) -> BOOL {
+    let target = match drop_for::<T>(this, info, parent, index) {
+        Some(target) => target,
+        None => return NO
+    };
+
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+
+    if !view.move_item(&target.item, target.parent.as_ref(), target.index) {
+        return NO;
+    }
+
+    unsafe {
+        let to_index = target.index as NSInteger;
+        let _: () = msg_send![this, moveItemAtIndex:target.from_index
+            inParent:target.from_parent
+            toIndex:to_index
+            inParent:parent
+        ];
+    }
+
+    YES
+}
+
+extern "C" fn menu_needs_update<T: OutlineViewDelegate>(this: &Object, _: Sel, menu: id) {
+    let view = load::<T>(this, OUTLINEVIEW_DELEGATE_PTR);
+    let items = view.context_menu();
+    let _ = Menu::append(menu, items);
+}
+
+/// Enforces normalcy, or: a needlessly cruel method in terms of the name. You get the idea though.
+extern "C" fn enforce_normalcy(_: &Object, _: Sel) -> BOOL {
+    YES
+}
+
+/// Injects an `NSOutlineView` subclass with flipped coordinates, for outlines without a
+/// delegate.
+pub(crate) fn register_outlineview_class() -> *const Class {
+    load_or_register_class("NSOutlineView", "RSTOutlineView", |decl| unsafe {
+        decl.add_method(sel!(isFlipped), enforce_normalcy as extern "C" fn(&Object, _) -> BOOL);
+    })
+}
+
+/// Injects an `NSOutlineView` subclass that acts as its own data source and delegate,
+/// forwarding to the `OutlineViewDelegate` stored in its ivar.
+pub(crate) fn register_outlineview_class_with_delegate<T: OutlineViewDelegate>(instance: &T) -> *const Class {
+    load_or_register_class("NSOutlineView", instance.subclass_name(), |decl| unsafe {
+        decl.add_ivar::<usize>(OUTLINEVIEW_DELEGATE_PTR);
+        decl.add_ivar::<usize>(OUTLINEVIEW_ITEMS_PTR);
+
+        decl.add_method(sel!(isFlipped), enforce_normalcy as extern "C" fn(&Object, _) -> BOOL);
+
+        // Data source
+        decl.add_method(
+            sel!(outlineView:numberOfChildrenOfItem:),
+            number_of_children::<T> as extern "C" fn(&Object, _, id, id) -> NSInteger
+        );
+        decl.add_method(
+            sel!(outlineView:child:ofItem:),
+            child_of_item::<T> as extern "C" fn(&Object, _, id, NSInteger, id) -> id
+        );
+        decl.add_method(
+            sel!(outlineView:isItemExpandable:),
+            is_item_expandable::<T> as extern "C" fn(&Object, _, id, id) -> BOOL
+        );
+        decl.add_method(
+            sel!(outlineView:persistentObjectForItem:),
+            persistent_object_for_item::<T> as extern "C" fn(&Object, _, id, id) -> id
+        );
+        decl.add_method(
+            sel!(outlineView:itemForPersistentObject:),
+            item_for_persistent_object::<T> as extern "C" fn(&Object, _, id, id) -> id
+        );
+
+        // Delegate
+        decl.add_method(
+            sel!(outlineView:viewForTableColumn:item:),
+            view_for_item::<T> as extern "C" fn(&Object, _, id, id, id) -> id
+        );
+        decl.add_method(
+            sel!(outlineView:isGroupItem:),
+            is_group_item::<T> as extern "C" fn(&Object, _, id, id) -> BOOL
+        );
+        decl.add_method(
+            sel!(outlineView:shouldExpandItem:),
+            should_expand_item::<T> as extern "C" fn(&Object, _, id, id) -> BOOL
+        );
+        decl.add_method(
+            sel!(outlineView:shouldCollapseItem:),
+            should_collapse_item::<T> as extern "C" fn(&Object, _, id, id) -> BOOL
+        );
+        decl.add_method(
+            sel!(outlineViewItemDidExpand:),
+            item_did_expand::<T> as extern "C" fn(&Object, _, id)
+        );
+        decl.add_method(
+            sel!(outlineViewItemDidCollapse:),
+            item_did_collapse::<T> as extern "C" fn(&Object, _, id)
+        );
+        decl.add_method(
+            sel!(outlineViewSelectionDidChange:),
+            selection_did_change::<T> as extern "C" fn(&Object, _, id)
+        );
+
+        // Drag and drop reordering
+        decl.add_method(
+            sel!(outlineView:pasteboardWriterForItem:),
+            pasteboard_writer_for_item::<T> as extern "C" fn(&Object, _, id, id) -> id
+        );
+        decl.add_method(
+            sel!(outlineView:validateDrop:proposedItem:proposedChildIndex:),
+            validate_drop::<T> as extern "C" fn(&Object, _, id, id, id, NSInteger) -> NSUInteger
+        );
+        decl.add_method(
+            sel!(outlineView:acceptDrop:item:childIndex:),
+            accept_drop::<T> as extern "C" fn(&Object, _, id, id, id, NSInteger) -> BOOL
+        );
+
+        // See `ListView` - the whole menu is rebuilt each time it's shown.
+        decl.add_method(
+            sel!(menuNeedsUpdate:),
+            menu_needs_update::<T> as extern "C" fn(&Object, _, id)
+        );
+    })
+}
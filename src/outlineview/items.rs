@@ -0,0 +1,176 @@
+//! `NSOutlineView` identifies items by pointer, and doesn't retain them. This maps each Rust item
+//! to a stable token, backed by a retained `NSNumber` that stands in for it on the Objective-C
+//! side - so the same item is always the same object, for as long as the outline view shows it.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
+use objc_id::ShareId;
+
+use crate::foundation::{id, nil, NSUInteger};
+
+/// An item, the object that stands in for it (created on first use), and the pass it was last
+/// handed to the outline view in.
+#[derive(Debug)]
+struct Entry<I> {
+    item: I,
+    object: Option<ShareId<Object>>,
+    pass: usize
+}
+
+/// Maps items to tokens, and tokens to the objects that stand in for them. Tokens are never
+/// reused, so a stale one (say, on a drag from before a reload) can't turn into another item.
+#[derive(Debug, Default)]
+pub(crate) struct ItemTable<I> {
+    tokens: HashMap<I, usize>,
+    entries: HashMap<usize, Entry<I>>,
+    next_token: usize,
+    pass: usize
+}
+
+impl<I: Clone + Eq + Hash> ItemTable<I> {
+    /// Creates an empty table.
+    pub(crate) fn new() -> Self {
+        ItemTable {
+            tokens: HashMap::new(),
+            entries: HashMap::new(),
+            next_token: 0,
+            pass: 0
+        }
+    }
+
+    /// Returns the token for `item`, assigning the next one if it hasn't been seen before. This
+    /// also marks `item` as in use for the current pass.
+    pub(crate) fn token(&mut self, item: &I) -> usize {
+        let pass = self.pass;
+
+        if let Some(token) = self.tokens.get(item) {
+            if let Some(entry) = self.entries.get_mut(token) {
+                entry.pass = pass;
+            }
+
+            return *token;
+        }
+
+        let token = self.next_token;
+        self.next_token += 1;
+        self.tokens.insert(item.clone(), token);
+        self.entries.insert(token, Entry {
+            item: item.clone(),
+            object: None,
+            pass
+        });
+
+        token
+    }
+
+    /// Returns the item for `token`, if there is one.
+    pub(crate) fn item(&self, token: usize) -> Option<&I> {
+        self.entries.get(&token).map(|entry| &entry.item)
+    }
+
+    /// Returns the object that stands in for `item`, creating it if need be.
+    pub(crate) fn object_for(&mut self, item: &I) -> id {
+        let token = self.token(item);
+        let entry = self.entries.get_mut(&token).expect("a token always has an entry");
+
+        let object = entry.object.get_or_insert_with(|| unsafe {
+            let token = token as NSUInteger;
+            let number: id = msg_send![class!(NSNumber), alloc];
+            ShareId::from_retained_ptr(msg_send![number, initWithUnsignedInteger: token])
+        });
+
+        &**object as *const Object as id
+    }
+
+    /// Returns the item that `object` stands in for. Objects from elsewhere (and `nil`, which
+    /// stands for the root of the outline) return `None`.
+    pub(crate) fn item_for(&self, object: id) -> Option<I> {
+        if object == nil {
+            return None;
+        }
+
+        let token: NSUInteger = unsafe { msg_send![object, unsignedIntegerValue] };
+
+        match self.entries.get(&(token as usize)) {
+            Some(Entry {
+                item,
+                object: Some(ours),
+                ..
+            }) if std::ptr::eq(&**ours, object) => Some(item.clone()),
+            _ => None
+        }
+    }
+
+    /// Starts a new pass. Call this before the outline view asks for every item it shows (i.e,
+    /// before `reloadData`), then `prune()` once it has.
+    pub(crate) fn start_pass(&mut self) {
+        self.pass += 1;
+    }
+
+    /// Drops every item that hasn't been handed out since `start_pass()`, along with the object
+    /// that stood in for it.
+    pub(crate) fn prune(&mut self) {
+        let pass = self.pass;
+        let tokens = &mut self.tokens;
+
+        self.entries.retain(|_, entry| match entry.pass == pass {
+            true => true,
+            false => {
+                tokens.remove(&entry.item);
+                false
+            }
+        });
+    }
+
+    /// Drops `item`, along with the object that stood in for it.
+    pub(crate) fn remove(&mut self, item: &I) {
+        if let Some(token) = self.tokens.remove(item) {
+            self.entries.remove(&token);
+        }
+    }
+}
+
+/// Where an item dropped at `proposed` (an index among its new parent's *current* children)
+/// ends up once it's been removed from its old spot. `from` is its current index, if it's being
+/// moved within the same parent.
+pub(crate) fn destination_index(from: Option<usize>, proposed: usize) -> usize {
+    match from {
+        Some(from) if from < proposed => proposed - 1,
+        _ => proposed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{destination_index, ItemTable};
+
+    #[test]
+    fn test_item_table() {
+        let mut table = ItemTable::new();
+
+        assert_eq!(table.token(&"inbox"), 0);
+        assert_eq!(table.token(&"archive"), 1);
+        assert_eq!(table.token(&"inbox"), 0);
+        assert_eq!(table.item(1), Some(&"archive"));
+        assert_eq!(table.item(2), None);
+
+        // Only items handed out since the pass started survive pruning, and tokens aren't reused.
+        table.start_pass();
+        table.token(&"inbox");
+        table.prune();
+        assert_eq!(table.item(0), Some(&"inbox"));
+        assert_eq!(table.item(1), None);
+        assert_eq!(table.token(&"archive"), 2);
+
+        table.remove(&"inbox");
+        assert_eq!(table.item(0), None);
+
+        // Moving down within the same parent skips over the item's own (vacated) spot.
+        assert_eq!(destination_index(Some(1), 4), 3);
+        assert_eq!(destination_index(Some(3), 1), 1);
+        assert_eq!(destination_index(None, 4), 4);
+    }
+}
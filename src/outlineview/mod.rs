@@ -0,0 +1,696 @@
+//! Wraps `NSOutlineView`, for hierarchical lists - file browsers, sidebar source lists, and the
+//! like.
+//!
+//! An `OutlineView` works like a `ListView`, but asks its delegate for the children of each
+//! item rather than a flat list of rows. Items are whatever type you'd like (see
+//! `OutlineViewDelegate::Item`), and rows are `ListViewRow`s, registered and dequeued the same
+//! way `ListView` rows are.
+//!
+//! ```rust,no_run
+//! use cacao::layout::{Layout, LayoutConstraint};
+//! use cacao::listview::ListViewRow;
+//! use cacao::outlineview::{OutlineView, OutlineViewDelegate};
+//! use cacao::text::Label;
+//! use cacao::view::{View, ViewDelegate};
+//!
+//! #[derive(Default)]
+//! struct FolderRow {
+//!     name: Label
+//! }
+//!
+//! impl ViewDelegate for FolderRow {
+//!     const NAME: &'static str = "FolderRow";
+//!
+//!     fn did_load(&mut self, view: View) {
+//!         view.add_subview(&self.name);
+//!
+//!         LayoutConstraint::activate(&[
+//!             self.name.leading.constraint_equal_to(&view.leading),
+//!             self.name.trailing.constraint_equal_to(&view.trailing),
+//!             self.name.center_y.constraint_equal_to(&view.center_y)
+//!         ]);
+//!     }
+//! }
+//!
+//! #[derive(Default)]
+//! struct Folders {
+//!     view: Option<OutlineView<Folders>>
+//! }
+//!
+//! impl OutlineViewDelegate for Folders {
+//!     const NAME: &'static str = "FoldersOutlineView";
+//!
+//!     // Folders are identified by their path.
+//!     type Item = String;
+//!
+//!     fn did_load(&mut self, view: OutlineView<Folders>) {
+//!         view.register("FolderRow", FolderRow::default);
+//!         view.set_autosave_name("Folders");
+//!         self.view = Some(view);
+//!     }
+//!
+//!     fn number_of_children(&self, parent: Option<&String>) -> usize {
+//!         match parent {
+//!             None => 1,
+//!             Some(_) => 0
+//!         }
+//!     }
+//!
+//!     fn child(&self, index: usize, parent: Option<&String>) -> String {
+//!         "/Users".to_string()
+//!     }
+//!
+//!     fn is_expandable(&self, path: &String) -> bool {
+//!         false
+//!     }
+//!
+//!     fn item_for(&self, path: &String) -> ListViewRow {
+//!         let mut row = self.view.as_ref().unwrap().dequeue::<FolderRow>("FolderRow");
+//!
+//!         if let Some(row) = &mut row.delegate {
+//!             row.name.set_text(path);
+//!         }
+//!
+//!         row.into_row()
+//!     }
+//!
+//!     fn persistent_key(&self, path: &String) -> Option<String> {
+//!         Some(path.clone())
+//!     }
+//!
+//!     fn item_for_persistent_key(&self, key: &str) -> Option<String> {
+//!         Some(key.to_string())
+//!     }
+//! }
+//!
+//! let outline = OutlineView::with(Folders::default());
+//! ```
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use core_graphics::base::CGFloat;
+use objc::runtime::{Class, Object, BOOL};
+use objc::{class, msg_send, sel, sel_impl};
+use objc_id::ShareId;
+
+use crate::foundation::{id, nil, to_bool, NSArray, NSInteger, NSString, NSUInteger, NO, YES};
+use crate::layout::Layout;
+use crate::listview::{ListViewRow, RowAnimation};
+use crate::objc_access::ObjcAccess;
+use crate::scrollview::ScrollView;
+use crate::utils::properties::ObjcProperty;
+use crate::utils::{os, CellFactory};
+use crate::view::{ViewAnimatorProxy, ViewDelegate};
+
+#[cfg(feature = "autolayout")]
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY};
+
+mod appkit;
+use appkit::{register_outlineview_class, register_outlineview_class_with_delegate};
+
+mod items;
+use items::ItemTable;
+
+mod traits;
+pub use traits::OutlineViewDelegate;
+
+pub(crate) static OUTLINEVIEW_DELEGATE_PTR: &str = "rstOutlineViewDelegatePtr";
+pub(crate) static OUTLINEVIEW_ITEMS_PTR: &str = "rstOutlineViewItemsPtr";
+
+/// The pasteboard type used to drag items around within an outline view.
+pub(crate) static ITEM_PASTEBOARD_TYPE: &str = "com.cacao.outlineview.item";
+
+/// A helper method for instantiating outline classes and applying default settings to them.
+fn common_init(class: *const Class) -> id {
+    unsafe {
+        // As with `ListView`, AutoLayout applies to the enclosing scroll view, not the outline.
+        let outline: id = msg_send![class, new];
+
+        // @TODO: Clean this up in a dealloc method.
+        let menu: id = msg_send![class!(NSMenu), new];
+        let _: () = msg_send![menu, setDelegate: outline];
+        let _: () = msg_send![outline, setMenu: menu];
+
+        let _: () = msg_send![outline, setWantsLayer: YES];
+        let _: () = msg_send![outline, setUsesAutomaticRowHeights: YES];
+        let _: () = msg_send![outline, setFloatsGroupRows: YES];
+        let _: () = msg_send![outline, setColumnAutoresizingStyle:1];
+        let _: () = msg_send![outline, setHeaderView: nil];
+
+        // The disclosure triangles live in the outline column, which has to be added by hand.
+        let identifier = NSString::no_copy("CacaoOutlineViewColumn");
+        let column_alloc: id = msg_send![class!(NSTableColumn), alloc];
+        let column: id = msg_send![column_alloc, initWithIdentifier:&*identifier];
+        let _: () = msg_send![column, setResizingMask:(1<<0)];
+        let _: () = msg_send![outline, addTableColumn: column];
+        let _: () = msg_send![outline, setOutlineTableColumn: column];
+
+        outline
+    }
+}
+
+/// Wraps `NSOutlineView`, inside of an `NSScrollView`. See the module documentation for an
+/// example.
+///
+/// Methods that take or return items are only available on outline views made with
+/// `OutlineView::with()`, and use the delegate's `Item` type. The handle passed to
+/// `OutlineViewDelegate::did_load()` has the same type, so store that.
+#[derive(Debug)]
+pub struct OutlineView<T = ()> {
+    /// Internal map of cell identifers/vendors. These are used for handling dynamic cell
+    /// allocation and reuse.
+    cell_factory: CellFactory,
+
+    /// The objects that stand in for items on the Objective-C side. For an outline view made with
+    /// `OutlineView::with()`, this is a `RefCell<ItemTable<T::Item>>`; it's type-erased so that
+    /// `OutlineView::new()` needn't have an item type.
+    items: Rc<dyn Any>,
+
+    /// A pointer to the Objective-C runtime outline view.
+    pub objc: ObjcProperty,
+
+    /// An object that supports limited animations. Can be cloned into animation closures.
+    pub animator: ViewAnimatorProxy,
+
+    /// The `NSScrollView` the outline is in. Layout applies to this.
+    pub scrollview: ScrollView,
+
+    /// A pointer to the delegate for this view.
+    pub delegate: Option<Box<T>>,
+
+    /// A pointer to the Objective-C runtime top layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub top: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime leading layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub leading: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime left layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub left: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime trailing layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub trailing: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime right layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub right: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime bottom layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub bottom: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime width layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub width: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime height layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub height: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime center X layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub center_x: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime center Y layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub center_y: LayoutAnchorY
+}
+
+impl Default for OutlineView {
+    fn default() -> Self {
+        OutlineView::new()
+    }
+}
+
+impl OutlineView {
+    /// Returns an outline view with no delegate (and so, no items). You'll likely want
+    /// `OutlineView::with()` instead.
+    pub fn new() -> Self {
+        let class = register_outlineview_class();
+        OutlineView::init(common_init(class), Rc::new(()))
+    }
+}
+
+impl<T> OutlineView<T>
+where
+    T: OutlineViewDelegate + 'static
+{
+    /// Initializes a new OutlineView with a given `OutlineViewDelegate`, which provides its items
+    /// and rows and responds to events.
+    pub fn with(delegate: T) -> OutlineView<T> {
+        let class = register_outlineview_class_with_delegate::<T>(&delegate);
+        let view = common_init(class);
+        let mut delegate = Box::new(delegate);
+        let items: Rc<RefCell<ItemTable<T::Item>>> = Rc::new(RefCell::new(ItemTable::new()));
+
+        unsafe {
+            let delegate_ptr: *const T = &*delegate;
+            let items_ptr: *const RefCell<ItemTable<T::Item>> = &*items;
+            (&mut *view).set_ivar(OUTLINEVIEW_DELEGATE_PTR, delegate_ptr as usize);
+            (&mut *view).set_ivar(OUTLINEVIEW_ITEMS_PTR, items_ptr as usize);
+            let _: () = msg_send![view, setDelegate: view];
+            let _: () = msg_send![view, setDataSource: view];
+        };
+
+        let mut view = OutlineView::init(view, items);
+        delegate.did_load(view.clone_as_handle());
+        view.delegate = Some(delegate);
+        view
+    }
+
+    /// Returns the item table, which is shared with the Objective-C side.
+    fn items(&self) -> &RefCell<ItemTable<T::Item>> {
+        // Only `OutlineView::with()` (and handles cloned from it) can make an `OutlineView<T>`
+        // for a delegate, and it always sets up a table of `T::Item`s.
+        self.items
+            .downcast_ref::<RefCell<ItemTable<T::Item>>>()
+            .expect("OutlineView::with() always creates a table of the delegate's items")
+    }
+
+    /// Returns the object that stands in for `item`, or `nil` (the root) for `None`.
+    fn object_for(&self, item: Option<&T::Item>) -> id {
+        match item {
+            Some(item) => self.items().borrow_mut().object_for(item),
+            None => nil
+        }
+    }
+
+    /// Returns the item that `object` stands in for.
+    fn item_for(&self, object: id) -> Option<T::Item> {
+        self.items().borrow().item_for(object)
+    }
+
+    /// Reloads the entire outline. Expanded items stay expanded, and items that are no longer
+    /// in the outline are forgotten.
+    pub fn reload(&self) {
+        self.items().borrow_mut().start_pass();
+
+        // Reloading asks the delegate for rows, which dequeues them; see
+        // `ListView::hack_avoid_dequeue_loop()` for why this doesn't borrow mutably.
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, reloadData];
+        });
+
+        // The outline asks for the children of the root and of every expanded item while
+        // reloading, so anything it didn't ask for is gone.
+        self.items().borrow_mut().prune();
+    }
+
+    /// Reloads `item`, and optionally its children.
+    pub fn reload_item(&self, item: &T::Item, reload_children: bool) {
+        let object = self.object_for(Some(item));
+
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, reloadItem:object reloadChildren:match reload_children {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
+    /// Expands `item`, and optionally all of its descendants.
+    pub fn expand_item(&self, item: &T::Item, expand_children: bool) {
+        let object = self.object_for(Some(item));
+
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, expandItem:object expandChildren:match expand_children {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
+    /// Expands every item in the outline.
+    pub fn expand_all(&self) {
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, expandItem:nil expandChildren:YES];
+        });
+    }
+
+    /// Collapses `item`, and optionally all of its descendants.
+    pub fn collapse_item(&self, item: &T::Item, collapse_children: bool) {
+        let object = self.object_for(Some(item));
+
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, collapseItem:object collapseChildren:match collapse_children {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
+    /// Returns whether `item` is expanded.
+    pub fn is_item_expanded(&self, item: &T::Item) -> bool {
+        let object = self.object_for(Some(item));
+        self.objc
+            .get(|obj| to_bool(unsafe { msg_send![obj, isItemExpanded: object] }))
+    }
+
+    /// Returns the parent of `item`, or `None` if it's at the root of the outline.
+    pub fn parent_of(&self, item: &T::Item) -> Option<T::Item> {
+        let object = self.object_for(Some(item));
+        let parent: id = self.objc.get(|obj| unsafe { msg_send![obj, parentForItem: object] });
+        self.item_for(parent)
+    }
+
+    /// Returns the row `item` is displayed in, or `None` if it isn't visible (e.g, because its
+    /// parent is collapsed).
+    pub fn row_for_item(&self, item: &T::Item) -> Option<usize> {
+        let object = self.object_for(Some(item));
+        let row: NSInteger = self.objc.get(|obj| unsafe { msg_send![obj, rowForItem: object] });
+
+        match row {
+            -1 => None,
+            row => Some(row as usize)
+        }
+    }
+
+    /// Returns the item displayed in `row`, if any.
+    pub fn item_at_row(&self, row: usize) -> Option<T::Item> {
+        let row = row as NSInteger;
+        let object: id = self.objc.get(|obj| unsafe { msg_send![obj, itemAtRow: row] });
+        self.item_for(object)
+    }
+
+    /// Returns the selected item, if any.
+    pub fn selected_item(&self) -> Option<T::Item> {
+        let row: NSInteger = self.objc.get(|obj| unsafe { msg_send![obj, selectedRow] });
+
+        match row {
+            -1 => None,
+            row => self.item_at_row(row as usize)
+        }
+    }
+
+    /// Returns the item that was last clicked (or context-clicked), if any. Useful for working
+    /// out what a context menu is for.
+    pub fn clicked_item(&self) -> Option<T::Item> {
+        let row: NSInteger = self.objc.get(|obj| unsafe { msg_send![obj, clickedRow] });
+
+        match row {
+            -1 => None,
+            row => self.item_at_row(row as usize)
+        }
+    }
+
+    /// Selects `item`, if it's visible.
+    pub fn select_item(&self, item: &T::Item) {
+        if let Some(row) = self.row_for_item(item) {
+            let indexes = index_set(&[row]);
+
+            self.objc.with_mut(|obj| unsafe {
+                let _: () = msg_send![obj, selectRowIndexes:&*indexes byExtendingSelection:NO];
+            });
+        }
+    }
+
+    /// Inserts children of `parent` (or of the root, if `None`) at `indexes`, with the specified
+    /// animation. Your underlying data store must be updated *before* calling this.
+    pub fn insert_items(&self, parent: Option<&T::Item>, indexes: &[usize], animation: RowAnimation) {
+        let parent = self.object_for(parent);
+        let indexes = index_set(indexes);
+        let animation: NSUInteger = animation.into();
+
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, insertItemsAtIndexes:&*indexes inParent:parent withAnimation:animation];
+        });
+    }
+
+    /// Removes the children of `parent` (or of the root, if `None`) at `indexes`, with the
+    /// specified animation. Your underlying data store must be updated *before* calling this.
+    pub fn remove_items(&self, parent: Option<&T::Item>, indexes: &[usize], animation: RowAnimation) {
+        let parent = self.object_for(parent);
+        let removed = self.loaded_items(parent, indexes);
+        let indexes = index_set(indexes);
+        let animation: NSUInteger = animation.into();
+
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, removeItemsAtIndexes:&*indexes inParent:parent withAnimation:animation];
+        });
+
+        let mut items = self.items().borrow_mut();
+        for item in &removed {
+            items.remove(item);
+        }
+    }
+
+    /// Returns the items the outline has loaded at `indexes` among the children of `parent`,
+    /// along with their expanded descendants. Anything else it may have loaded is forgotten on
+    /// the next `reload()`.
+    fn loaded_items(&self, parent: id, indexes: &[usize]) -> Vec<T::Item> {
+        let objects = self.objc.get(|obj| unsafe {
+            let mut objects = Vec::new();
+            let expanded: BOOL = msg_send![obj, isItemExpanded: parent];
+
+            if parent == nil || to_bool(expanded) {
+                for index in indexes {
+                    let index = *index as NSInteger;
+                    let child: id = msg_send![obj, child:index ofItem:parent];
+                    objects.push(child);
+                }
+            }
+
+            let mut i = 0;
+            while i < objects.len() {
+                let object = objects[i];
+                let expanded: BOOL = msg_send![obj, isItemExpanded: object];
+
+                if to_bool(expanded) {
+                    let count: NSInteger = msg_send![obj, numberOfChildrenOfItem: object];
+
+                    for index in 0..count {
+                        let child: id = msg_send![obj, child:index ofItem:object];
+                        objects.push(child);
+                    }
+                }
+
+                i += 1;
+            }
+
+            objects
+        });
+
+        let items = self.items().borrow();
+        objects.into_iter().filter_map(|object| items.item_for(object)).collect()
+    }
+}
+
+impl<T> OutlineView<T> {
+    /// Wraps an outline view in a scroll view, and sets up anchors for the scroll view.
+    fn init(view: id, items: Rc<dyn Any>) -> Self {
+        let scrollview = ScrollView::new();
+
+        scrollview.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setDocumentView: view];
+        });
+
+        // We need to use the NSScrollView anchor points, not the NSOutlineView.
+        let anchor_view: id = scrollview.objc.get(|obj| unsafe { msg_send![obj, self] });
+
+        OutlineView {
+            cell_factory: CellFactory::new(),
+            items,
+            delegate: None,
+            objc: ObjcProperty::retain(view),
+            animator: ViewAnimatorProxy::new(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            top: LayoutAnchorY::top(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            left: LayoutAnchorX::left(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            leading: LayoutAnchorX::leading(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            right: LayoutAnchorX::right(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            trailing: LayoutAnchorX::trailing(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            bottom: LayoutAnchorY::bottom(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            width: LayoutAnchorDimension::width(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            height: LayoutAnchorDimension::height(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            center_x: LayoutAnchorX::center(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            center_y: LayoutAnchorY::center(anchor_view),
+
+            scrollview
+        }
+    }
+
+    /// An internal method that returns a clone of this object, sans references to the delegate or
+    /// callback pointer. We use this in calling `did_load()`.
+    pub fn clone_as_handle(&self) -> OutlineView<T> {
+        OutlineView {
+            cell_factory: CellFactory::new(),
+            items: self.items.clone(),
+            delegate: None,
+            objc: self.objc.clone(),
+            animator: self.animator.clone(),
+
+            #[cfg(feature = "autolayout")]
+            top: self.top.clone(),
+
+            #[cfg(feature = "autolayout")]
+            leading: self.leading.clone(),
+
+            #[cfg(feature = "autolayout")]
+            left: self.left.clone(),
+
+            #[cfg(feature = "autolayout")]
+            trailing: self.trailing.clone(),
+
+            #[cfg(feature = "autolayout")]
+            right: self.right.clone(),
+
+            #[cfg(feature = "autolayout")]
+            bottom: self.bottom.clone(),
+
+            #[cfg(feature = "autolayout")]
+            width: self.width.clone(),
+
+            #[cfg(feature = "autolayout")]
+            height: self.height.clone(),
+
+            #[cfg(feature = "autolayout")]
+            center_x: self.center_x.clone(),
+
+            #[cfg(feature = "autolayout")]
+            center_y: self.center_y.clone(),
+
+            scrollview: self.scrollview.clone_as_handle()
+        }
+    }
+
+    /// Register a row vendor function with an identifier. This is stored internally and used
+    /// for row reuse.
+    pub fn register<F, R>(&self, identifier: &'static str, vendor: F)
+    where
+        F: Fn() -> R + 'static,
+        R: ViewDelegate + 'static
+    {
+        self.cell_factory.insert(identifier, vendor);
+    }
+
+    /// Dequeue a reusable row. If one is not in the queue, will create and cache one for reuse.
+    pub fn dequeue<R: ViewDelegate + 'static>(&self, identifier: &'static str) -> ListViewRow<R> {
+        let key = NSString::new(identifier);
+        let cell: id = self
+            .objc
+            .get(|obj| unsafe { msg_send![obj, makeViewWithIdentifier:&*key owner:nil] });
+
+        if cell != nil {
+            ListViewRow::from_cached(cell)
+        } else {
+            let delegate: Box<R> = self.cell_factory.get(identifier);
+            let view = ListViewRow::with_boxed(delegate);
+            view.set_identifier(identifier);
+            view
+        }
+    }
+
+    /// Saves which items are expanded in `UserDefaults` under `name`, and restores them from
+    /// there. Items are saved by the keys from `OutlineViewDelegate::persistent_key()`.
+    pub fn set_autosave_name(&self, name: &str) {
+        let name = NSString::new(name);
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setAutosaveName:&*name];
+            let _: () = msg_send![obj, setAutosaveExpandedItems: YES];
+        });
+    }
+
+    /// Sets whether the user can drag items around the outline, to reorder them or move them to
+    /// another parent. Moves are checked with `OutlineViewDelegate::can_move()` and carried out
+    /// with `OutlineViewDelegate::move_item()`. Defaults to `false`.
+    pub fn set_allows_reordering(&self, allows: bool) {
+        // The array retains the type, but only once it's made - so keep it alive until then.
+        let kind = NSString::new(ITEM_PASTEBOARD_TYPE);
+
+        self.objc.with_mut(|obj| unsafe {
+            match allows {
+                true => {
+                    let types = NSArray::new(&[&*kind as *const Object as id]);
+                    let _: () = msg_send![obj, registerForDraggedTypes:&*types];
+
+                    // NSDragOperationMove
+                    let mask: NSUInteger = 16;
+                    let _: () = msg_send![obj, setDraggingSourceOperationMask:mask forLocal:YES];
+                },
+
+                false => {
+                    let _: () = msg_send![obj, unregisterDraggedTypes];
+                }
+            }
+        });
+    }
+
+    /// Sets how far (in points) each level of the outline is indented.
+    pub fn set_indentation_per_level(&self, indentation: f64) {
+        let indentation = indentation as CGFloat;
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setIndentationPerLevel: indentation];
+        });
+    }
+
+    /// Sets the style for the underlying NSOutlineView - e.g, `NSTableViewStyleSourceList` (3)
+    /// for a sidebar. This property is only supported on macOS 11.0+.
+    pub fn set_style(&self, style: NSInteger) {
+        if os::is_minimum_version(11) {
+            self.objc.with_mut(|obj| unsafe {
+                let _: () = msg_send![obj, setStyle: style];
+            });
+        }
+    }
+
+    /// Makes this outline view the first responder.
+    pub fn make_first_responder(&self) {
+        self.objc.with_mut(|obj| unsafe {
+            let window: id = msg_send![&*obj, window];
+            let _: () = msg_send![window, makeFirstResponder:&*obj];
+        });
+    }
+}
+
+/// Builds an `NSIndexSet` holding `indexes`.
+fn index_set(indexes: &[usize]) -> ShareId<Object> {
+    unsafe {
+        let index_set: id = msg_send![class!(NSMutableIndexSet), new];
+
+        for index in indexes {
+            let index = *index as NSUInteger;
+            let _: () = msg_send![index_set, addIndex: index];
+        }
+
+        ShareId::from_retained_ptr(index_set)
+    }
+}
+
+impl<T> ObjcAccess for OutlineView<T> {
+    fn with_backing_obj_mut<F: Fn(id)>(&self, handler: F) {
+        // As with `ListView`, layout applies to the scroll view.
+        self.scrollview.objc.with_mut(handler);
+    }
+
+    fn get_from_backing_obj<F: Fn(&Object) -> R, R>(&self, handler: F) -> R {
+        self.scrollview.objc.get(handler)
+    }
+}
+
+impl<T> Layout for OutlineView<T> {}
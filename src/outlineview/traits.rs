@@ -0,0 +1,106 @@
+//! Various traits used for OutlineViews.
+
+use std::hash::Hash;
+
+use crate::appkit::menu::MenuItem;
+use crate::listview::ListViewRow;
+use crate::outlineview::OutlineView;
+
+#[allow(unused_variables)]
+pub trait OutlineViewDelegate {
+    /// Used to cache subclass creations on the Objective-C side.
+    /// You can just set this to be the name of your view type. This
+    /// value *must* be unique per-type.
+    const NAME: &'static str;
+
+    /// The items in the tree. These identify rows, so should be cheap to clone and compare - an
+    /// id or a path, say, rather than the data itself. The same item must not appear twice.
+    type Item: Clone + Eq + Hash + 'static;
+
+    /// You should rarely (read: probably never) need to implement this yourself.
+    /// It simply acts as a getter for the associated `NAME` const on this trait.
+    fn subclass_name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    /// Called when the View is ready to work with. You're passed an `OutlineView` - this is
+    /// safe to store and use repeatedly, but it's not thread safe - any UI calls must be made
+    /// from the main thread! It's typed by this delegate, so its item methods take `Self::Item`.
+    fn did_load(&mut self, view: OutlineView<Self>)
+    where
+        Self: Sized;
+
+    /// Returns the number of children of `parent`, or of the root of the tree if `parent` is
+    /// `None`.
+    fn number_of_children(&self, parent: Option<&Self::Item>) -> usize;
+
+    /// Returns the child at `index` of `parent`, or of the root of the tree if `parent` is
+    /// `None`.
+    fn child(&self, index: usize, parent: Option<&Self::Item>) -> Self::Item;
+
+    /// Whether `item` can be expanded to show children.
+    fn is_expandable(&self, item: &Self::Item) -> bool;
+
+    /// Returns the view for `item`. Rows are dequeued the same way as `ListView` rows - see
+    /// `OutlineView::register()` and `OutlineView::dequeue()`.
+    fn item_for(&self, item: &Self::Item) -> ListViewRow;
+
+    /// Whether `item` is a group item - e.g, a section heading in a sidebar. Group items are
+    /// styled differently, and float at the top of the outline while scrolled past.
+    fn is_group_item(&self, item: &Self::Item) -> bool {
+        false
+    }
+
+    /// Called when an item has been selected (clicked/tapped on). If the selection was cleared,
+    /// then this will be called with `None`.
+    fn item_selected(&self, item: Option<Self::Item>) {}
+
+    /// Whether `item` should be expanded. Return `false` to keep it collapsed.
+    fn should_expand(&self, item: &Self::Item) -> bool {
+        true
+    }
+
+    /// Whether `item` should be collapsed. Return `false` to keep it expanded.
+    fn should_collapse(&self, item: &Self::Item) -> bool {
+        true
+    }
+
+    /// Called after `item` has been expanded.
+    fn item_expanded(&self, item: &Self::Item) {}
+
+    /// Called after `item` has been collapsed.
+    fn item_collapsed(&self, item: &Self::Item) {}
+
+    /// Returns a key that identifies `item` across launches, for saving which items are
+    /// expanded (see `OutlineView::set_autosave_name()`). Items without one aren't saved.
+    fn persistent_key(&self, item: &Self::Item) -> Option<String> {
+        None
+    }
+
+    /// Returns the item for a key returned by `persistent_key()`, if it still exists.
+    fn item_for_persistent_key(&self, key: &str) -> Option<Self::Item> {
+        None
+    }
+
+    /// Whether `item` can be dragged to `index` among the children of `parent` (or of the root,
+    /// if `parent` is `None`). `index` is where the item would end up, once moved. Only called
+    /// if reordering is enabled with `OutlineView::set_allows_reordering()`, and never for drops
+    /// onto the item itself or its descendants.
+    fn can_move(&self, item: &Self::Item, parent: Option<&Self::Item>, index: usize) -> bool {
+        false
+    }
+
+    /// Moves `item` to `index` among the children of `parent` (or of the root, if `parent` is
+    /// `None`), after the user dropped it there. Update your data, and return `true` - the
+    /// outline view then animates the row into place.
+    fn move_item(&self, item: &Self::Item, parent: Option<&Self::Item>, index: usize) -> bool {
+        false
+    }
+
+    /// Called when the menu for the outline view is about to be shown. You can update the menu
+    /// here depending on, say, what the user has context-clicked on (see
+    /// `OutlineView::clicked_item()`).
+    fn context_menu(&self) -> Vec<MenuItem> {
+        vec![]
+    }
+}
@@ -54,7 +54,7 @@ use crate::pasteboard::PasteboardType;
 use crate::utils::properties::ObjcProperty;
 
 #[cfg(feature = "autolayout")]
-use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY};
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY, LayoutGuide};
 
 #[cfg(feature = "appkit")]
 mod appkit;
@@ -302,6 +302,18 @@ impl<T> ScrollView<T> {
             let _: () = msg_send![layer, setBackgroundColor: color];
         });
     }
+
+    /// Returns a guide for this view's layout margins. See `LayoutGuide` for details.
+    #[cfg(feature = "autolayout")]
+    pub fn layout_margins_guide(&self) -> LayoutGuide {
+        self.get_from_backing_obj(|obj| LayoutGuide::layout_margins(obj as *const Object as id))
+    }
+
+    /// Returns a guide for this view's readable content area. See `LayoutGuide` for details.
+    #[cfg(feature = "autolayout")]
+    pub fn readable_content_guide(&self) -> LayoutGuide {
+        self.get_from_backing_obj(|obj| LayoutGuide::readable_content(obj as *const Object as id))
+    }
 }
 
 impl<T> ObjcAccess for ScrollView<T> {
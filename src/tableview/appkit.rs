@@ -0,0 +1,163 @@
+//! Registers the `NSTableView` subclasses that back `TableView`, and forwards their data source
+//! and delegate calls to the Rust side.
+
+use core_graphics::base::CGFloat;
+
+use objc::runtime::{Class, Object, Sel, BOOL};
+use objc::{msg_send, sel, sel_impl};
+
+use crate::appkit::menu::Menu;
+use crate::foundation::{id, load_or_register_class, NSArray, NSInteger, NSString, YES};
+use crate::tableview::{SortDescriptor, TableViewDelegate, TABLEVIEW_DELEGATE_PTR};
+use crate::utils::load;
+
+/// Returns the identifier of an `NSTableColumn`.
+fn identifier_for(column: id) -> String {
+    NSString::retain(unsafe { msg_send![column, identifier] }).to_string()
+}
+
+/// Returns the column in a column notification's `userInfo`.
+fn column_from(notification: id) -> id {
+    unsafe {
+        let user_info: id = msg_send![notification, userInfo];
+        let key = NSString::new("NSTableColumn");
+        msg_send![user_info, objectForKey:&*key]
+    }
+}
+
+/// Returns an integer from a column notification's `userInfo`.
+fn integer_from(notification: id, key: &str) -> NSInteger {
+    unsafe {
+        let user_info: id = msg_send![notification, userInfo];
+        let key = NSString::new(key);
+        let number: id = msg_send![user_info, objectForKey:&*key];
+        msg_send![number, integerValue]
+    }
+}
+
+/// Determines the number of rows by way of the backing data source (the Rust struct).
+extern "C" fn number_of_rows<T: TableViewDelegate>(this: &Object, _: Sel, _: id) -> NSInteger {
+    let view = load::<T>(this, TABLEVIEW_DELEGATE_PTR);
+    view.number_of_rows() as NSInteger
+}
+
+extern "C" fn view_for_column<T: TableViewDelegate>(this: &Object, _: Sel, _table_view: id, column: id, row: NSInteger) -> id {
+    let view = load::<T>(this, TABLEVIEW_DELEGATE_PTR);
+    let cell = view.cell_for(&identifier_for(column), row as usize);
+
+    // As with `ListView`, the table retains the cell view, so handing back the pointer is fine.
+    cell.objc.get(|obj| unsafe { msg_send![obj, self] })
+}
+
+extern "C" fn selection_did_change<T: TableViewDelegate>(this: &Object, _: Sel, notification: id) {
+    let selected_row: NSInteger = unsafe {
+        let tableview: id = msg_send![notification, object];
+        msg_send![tableview, selectedRow]
+    };
+
+    let view = load::<T>(this, TABLEVIEW_DELEGATE_PTR);
+
+    match selected_row {
+        -1 => view.item_selected(None),
+        row => view.item_selected(Some(row as usize))
+    }
+}
+
+extern "C" fn sort_descriptors_did_change<T: TableViewDelegate>(this: &Object, _: Sel, table_view: id, _old: id) {
+    let descriptors = NSArray::retain(unsafe { msg_send![table_view, sortDescriptors] }).map(SortDescriptor::from_objc);
+
+    let view = load::<T>(this, TABLEVIEW_DELEGATE_PTR);
+    view.sort_descriptors_changed(&descriptors);
+}
+
+extern "C" fn did_click_column<T: TableViewDelegate>(this: &Object, _: Sel, _table_view: id, column: id) {
+    let view = load::<T>(this, TABLEVIEW_DELEGATE_PTR);
+    view.column_header_clicked(&identifier_for(column));
+}
+
+extern "C" fn column_did_move<T: TableViewDelegate>(this: &Object, _: Sel, notification: id) {
+    let from = integer_from(notification, "NSOldColumn") as usize;
+    let to = integer_from(notification, "NSNewColumn") as usize;
+
+    // The notification has no column in it, but by now, the moved column is at `to`.
+    let identifier = unsafe {
+        let tableview: id = msg_send![notification, object];
+        let columns = NSArray::retain(msg_send![tableview, tableColumns]);
+        identifier_for(msg_send![&*columns, objectAtIndex: to])
+    };
+
+    let view = load::<T>(this, TABLEVIEW_DELEGATE_PTR);
+    view.column_moved(&identifier, from, to);
+}
+
+extern "C" fn column_did_resize<T: TableViewDelegate>(this: &Object, _: Sel, notification: id) {
+    let column = column_from(notification);
+    let width: CGFloat = unsafe { msg_send![column, width] };
+
+    let view = load::<T>(this, TABLEVIEW_DELEGATE_PTR);
+    view.column_resized(&identifier_for(column), width as f64);
+}
+
+extern "C" fn menu_needs_update<T: TableViewDelegate>(this: &Object, _: Sel, menu: id) {
+    let view = load::<T>(this, TABLEVIEW_DELEGATE_PTR);
+    let items = view.context_menu();
+    let _ = Menu::append(menu, items);
+}
+
+/// Enforces normalcy, or: a needlessly cruel method in terms of the name. You get the idea though.
+extern "C" fn enforce_normalcy(_: &Object, _: Sel) -> BOOL {
+    YES
+}
+
+/// Injects an `NSTableView` subclass with flipped coordinates, for tables without a delegate.
+pub(crate) fn register_tableview_class() -> *const Class {
+    load_or_register_class("NSTableView", "RSTTableView", |decl| unsafe {
+        decl.add_method(sel!(isFlipped), enforce_normalcy as extern "C" fn(&Object, _) -> BOOL);
+    })
+}
+
+/// Injects an `NSTableView` subclass that acts as its own data source and delegate, forwarding
+/// to the `TableViewDelegate` stored in its ivar.
+pub(crate) fn register_tableview_class_with_delegate<T: TableViewDelegate>(instance: &T) -> *const Class {
+    load_or_register_class("NSTableView", instance.subclass_name(), |decl| unsafe {
+        decl.add_ivar::<usize>(TABLEVIEW_DELEGATE_PTR);
+
+        decl.add_method(sel!(isFlipped), enforce_normalcy as extern "C" fn(&Object, _) -> BOOL);
+
+        // Tableview-specific
+        decl.add_method(
+            sel!(numberOfRowsInTableView:),
+            number_of_rows::<T> as extern "C" fn(&Object, _, id) -> NSInteger
+        );
+        decl.add_method(
+            sel!(tableView:viewForTableColumn:row:),
+            view_for_column::<T> as extern "C" fn(&Object, _, id, id, NSInteger) -> id
+        );
+        decl.add_method(
+            sel!(tableViewSelectionDidChange:),
+            selection_did_change::<T> as extern "C" fn(&Object, _, id)
+        );
+        decl.add_method(
+            sel!(tableView:sortDescriptorsDidChange:),
+            sort_descriptors_did_change::<T> as extern "C" fn(&Object, _, id, id)
+        );
+        decl.add_method(
+            sel!(tableView:didClickTableColumn:),
+            did_click_column::<T> as extern "C" fn(&Object, _, id, id)
+        );
+        decl.add_method(
+            sel!(tableViewColumnDidMove:),
+            column_did_move::<T> as extern "C" fn(&Object, _, id)
+        );
+        decl.add_method(
+            sel!(tableViewColumnDidResize:),
+            column_did_resize::<T> as extern "C" fn(&Object, _, id)
+        );
+
+        // See `ListView` - the whole menu is rebuilt each time it's shown.
+        decl.add_method(
+            sel!(menuNeedsUpdate:),
+            menu_needs_update::<T> as extern "C" fn(&Object, _, id)
+        );
+    })
+}
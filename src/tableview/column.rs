@@ -0,0 +1,159 @@
+use core_graphics::base::CGFloat;
+
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
+use objc_id::ShareId;
+
+use crate::foundation::{id, to_bool, NSString, NSUInteger, NO, YES};
+use crate::tableview::SortDescriptor;
+
+/// `NSTableColumnAutoresizingMask`: the column resizes along with the table.
+const AUTORESIZING_MASK: NSUInteger = 1 << 0;
+
+/// `NSTableColumnUserResizingMask`: the user can resize the column by dragging its header.
+const USER_RESIZING_MASK: NSUInteger = 1 << 1;
+
+/// Wraps `NSTableColumn`. Columns are identified by a string, which is what `TableViewDelegate`
+/// methods are passed to tell you which column they're asking about.
+///
+/// ```rust,no_run
+/// use cacao::tableview::{SortDescriptor, TableColumn, TableView};
+///
+/// let name = TableColumn::new("name");
+/// name.set_title("Name");
+/// name.set_min_width(120.);
+/// name.set_sort_descriptor(SortDescriptor::new("name", true));
+///
+/// let table = TableView::new();
+/// table.add_column(&name);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TableColumn {
+    /// A pointer to the underlying `NSTableColumn`.
+    pub objc: ShareId<Object>
+}
+
+impl TableColumn {
+    /// Creates a new column with the given identifier. Columns resize along with the table,
+    /// and can be resized by the user, by default.
+    pub fn new(identifier: &str) -> Self {
+        let identifier = NSString::new(identifier);
+
+        let column = unsafe {
+            let alloc: id = msg_send![class!(NSTableColumn), alloc];
+            let column: id = msg_send![alloc, initWithIdentifier:&*identifier];
+            let _: () = msg_send![column, setResizingMask:AUTORESIZING_MASK | USER_RESIZING_MASK];
+            ShareId::from_retained_ptr(column)
+        };
+
+        TableColumn { objc: column }
+    }
+
+    /// Wraps an existing `NSTableColumn`.
+    pub(crate) fn wrap(column: id) -> Self {
+        TableColumn {
+            objc: unsafe { ShareId::from_ptr(column) }
+        }
+    }
+
+    /// Returns the identifier for this column.
+    pub fn identifier(&self) -> String {
+        NSString::retain(unsafe { msg_send![&*self.objc, identifier] }).to_string()
+    }
+
+    /// Sets the title shown in this column's header.
+    pub fn set_title(&self, title: &str) {
+        let title = NSString::new(title);
+
+        unsafe {
+            let _: () = msg_send![&*self.objc, setTitle:&*title];
+        }
+    }
+
+    /// Returns the title shown in this column's header.
+    pub fn title(&self) -> String {
+        NSString::retain(unsafe { msg_send![&*self.objc, title] }).to_string()
+    }
+
+    /// Sets the width of this column. This is clamped to the minimum and maximum widths.
+    pub fn set_width(&self, width: f64) {
+        let width = width as CGFloat;
+
+        unsafe {
+            let _: () = msg_send![&*self.objc, setWidth: width];
+        }
+    }
+
+    /// Returns the current width of this column.
+    pub fn width(&self) -> f64 {
+        let width: CGFloat = unsafe { msg_send![&*self.objc, width] };
+        width as f64
+    }
+
+    /// Sets the narrowest this column can be resized to.
+    pub fn set_min_width(&self, width: f64) {
+        let width = width as CGFloat;
+
+        unsafe {
+            let _: () = msg_send![&*self.objc, setMinWidth: width];
+        }
+    }
+
+    /// Sets the widest this column can be resized to.
+    pub fn set_max_width(&self, width: f64) {
+        let width = width as CGFloat;
+
+        unsafe {
+            let _: () = msg_send![&*self.objc, setMaxWidth: width];
+        }
+    }
+
+    /// Sets whether the user can resize this column by dragging the edge of its header. Columns
+    /// still resize along with the table either way.
+    pub fn set_resizable(&self, resizable: bool) {
+        unsafe {
+            let mask: NSUInteger = msg_send![&*self.objc, resizingMask];
+
+            let mask = match resizable {
+                true => mask | USER_RESIZING_MASK,
+                false => mask & !USER_RESIZING_MASK
+            };
+
+            let _: () = msg_send![&*self.objc, setResizingMask: mask];
+        }
+    }
+
+    /// Sets whether this column is hidden.
+    pub fn set_hidden(&self, hidden: bool) {
+        unsafe {
+            let _: () = msg_send![&*self.objc, setHidden:match hidden {
+                true => YES,
+                false => NO
+            }];
+        }
+    }
+
+    /// Returns whether this column is hidden.
+    pub fn is_hidden(&self) -> bool {
+        to_bool(unsafe { msg_send![&*self.objc, isHidden] })
+    }
+
+    /// Makes this column sortable: clicking its header sorts the table by `descriptor`, and
+    /// clicking it again reverses the order. The table's sort descriptors are updated, and
+    /// `TableViewDelegate::sort_descriptors_changed()` is called, so you can re-sort your data.
+    pub fn set_sort_descriptor(&self, descriptor: SortDescriptor) {
+        unsafe {
+            let descriptor = descriptor.to_objc();
+            let _: () = msg_send![&*self.objc, setSortDescriptorPrototype: descriptor];
+        }
+    }
+
+    /// Sets the tooltip shown when hovering over this column's header.
+    pub fn set_header_tooltip(&self, tooltip: &str) {
+        let tooltip = NSString::new(tooltip);
+
+        unsafe {
+            let _: () = msg_send![&*self.objc, setHeaderToolTip:&*tooltip];
+        }
+    }
+}
@@ -0,0 +1,606 @@
+//! Wraps a multi-column `NSTableView`, for spreadsheet-like grids of data.
+//!
+//! Where `ListView` is a single column of rows, a `TableView` has any number of `TableColumn`s,
+//! with a header that the user can click to sort, drag to reorder, and resize. Cells are
+//! `ListViewRow`s, registered and dequeued the same way `ListView` rows are.
+//!
+//! ```rust,no_run
+//! use cacao::layout::{Layout, LayoutConstraint};
+//! use cacao::listview::ListViewRow;
+//! use cacao::tableview::{SortDescriptor, TableColumn, TableView, TableViewDelegate};
+//! use cacao::text::Label;
+//! use cacao::view::{View, ViewDelegate};
+//!
+//! #[derive(Default)]
+//! struct FileCell {
+//!     text: Label
+//! }
+//!
+//! impl ViewDelegate for FileCell {
+//!     const NAME: &'static str = "FileCell";
+//!
+//!     fn did_load(&mut self, view: View) {
+//!         view.add_subview(&self.text);
+//!
+//!         LayoutConstraint::activate(&[
+//!             self.text.leading.constraint_equal_to(&view.leading).offset(4.),
+//!             self.text.trailing.constraint_equal_to(&view.trailing).offset(-4.),
+//!             self.text.center_y.constraint_equal_to(&view.center_y)
+//!         ]);
+//!     }
+//! }
+//!
+//! #[derive(Default)]
+//! struct Files {
+//!     table: Option<TableView>,
+//!     files: Vec<(String, u64)>
+//! }
+//!
+//! impl TableViewDelegate for Files {
+//!     const NAME: &'static str = "FilesTableView";
+//!
+//!     fn did_load(&mut self, table: TableView) {
+//!         for (identifier, title) in &[("name", "Name"), ("size", "Size")] {
+//!             let column = TableColumn::new(identifier);
+//!             column.set_title(title);
+//!             column.set_sort_descriptor(SortDescriptor::new(*identifier, true));
+//!             table.add_column(&column);
+//!         }
+//!
+//!         table.register("FileCell", FileCell::default);
+//!         table.set_autosave_name("Files");
+//!         self.table = Some(table);
+//!     }
+//!
+//!     fn number_of_rows(&self) -> usize {
+//!         self.files.len()
+//!     }
+//!
+//!     fn cell_for(&self, column: &str, row: usize) -> ListViewRow {
+//!         let mut cell = self.table.as_ref().unwrap().dequeue::<FileCell>("FileCell");
+//!
+//!         if let Some(cell) = &mut cell.delegate {
+//!             let (name, size) = &self.files[row];
+//!
+//!             match column {
+//!                 "size" => cell.text.set_text(&format!("{} bytes", size)),
+//!                 _ => cell.text.set_text(name)
+//!             }
+//!         }
+//!
+//!         cell.into_row()
+//!     }
+//!
+//!     fn sort_descriptors_changed(&self, descriptors: &[SortDescriptor]) {
+//!         // Re-sort the data, then reload.
+//!         if let Some(table) = &self.table {
+//!             table.reload();
+//!         }
+//!     }
+//! }
+//!
+//! let table = TableView::with(Files::default());
+//! ```
+
+use core_graphics::base::CGFloat;
+use objc::runtime::{Class, Object};
+use objc::{class, msg_send, sel, sel_impl};
+use objc_id::ShareId;
+
+use crate::color::Color;
+use crate::foundation::{id, nil, NSArray, NSInteger, NSString, NSUInteger, NO, YES};
+use crate::layout::Layout;
+use crate::listview::{ListViewRow, RowAnimation};
+use crate::objc_access::ObjcAccess;
+use crate::scrollview::ScrollView;
+use crate::utils::properties::ObjcProperty;
+use crate::utils::CellFactory;
+use crate::view::{ViewAnimatorProxy, ViewDelegate};
+
+#[cfg(feature = "autolayout")]
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY};
+
+mod appkit;
+use appkit::{register_tableview_class, register_tableview_class_with_delegate};
+
+mod column;
+pub use column::TableColumn;
+
+mod sort;
+pub use sort::SortDescriptor;
+
+mod traits;
+pub use traits::TableViewDelegate;
+
+pub(crate) static TABLEVIEW_DELEGATE_PTR: &str = "rstTableViewDelegatePtr";
+
+/// A helper method for instantiating table classes and applying default settings to them.
+fn common_init(class: *const Class) -> id {
+    unsafe {
+        // As with `ListView`, AutoLayout applies to the enclosing scroll view, not the table.
+        let tableview: id = msg_send![class, new];
+
+        // @TODO: Clean this up in a dealloc method.
+        let menu: id = msg_send![class!(NSMenu), new];
+        let _: () = msg_send![menu, setDelegate: tableview];
+        let _: () = msg_send![tableview, setMenu: menu];
+
+        let _: () = msg_send![tableview, setWantsLayer: YES];
+        let _: () = msg_send![tableview, setUsesAutomaticRowHeights: YES];
+        let _: () = msg_send![tableview, setAllowsColumnReordering: YES];
+        let _: () = msg_send![tableview, setAllowsColumnResizing: YES];
+
+        // NSTableViewUniformColumnAutoresizingStyle
+        let _: () = msg_send![tableview, setColumnAutoresizingStyle:1];
+
+        tableview
+    }
+}
+
+/// Wraps a multi-column `NSTableView`, inside of an `NSScrollView`. See the module documentation
+/// for an example.
+#[derive(Debug)]
+pub struct TableView<T = ()> {
+    /// Internal map of cell identifers/vendors. These are used for handling dynamic cell
+    /// allocation and reuse.
+    cell_factory: CellFactory,
+
+    /// A pointer to the Objective-C runtime table view.
+    pub objc: ObjcProperty,
+
+    /// An object that supports limited animations. Can be cloned into animation closures.
+    pub animator: ViewAnimatorProxy,
+
+    /// The `NSScrollView` the table is in. Layout applies to this.
+    pub scrollview: ScrollView,
+
+    /// A pointer to the delegate for this view.
+    pub delegate: Option<Box<T>>,
+
+    /// A pointer to the Objective-C runtime top layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub top: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime leading layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub leading: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime left layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub left: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime trailing layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub trailing: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime right layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub right: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime bottom layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub bottom: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime width layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub width: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime height layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub height: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime center X layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub center_x: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime center Y layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub center_y: LayoutAnchorY
+}
+
+impl Default for TableView {
+    fn default() -> Self {
+        TableView::new()
+    }
+}
+
+impl TableView {
+    /// Returns a table view with no delegate. You'll likely want `TableView::with()` instead.
+    pub fn new() -> Self {
+        let class = register_tableview_class();
+        TableView::init(common_init(class))
+    }
+}
+
+impl<T> TableView<T>
+where
+    T: TableViewDelegate + 'static
+{
+    /// Initializes a new TableView with a given `TableViewDelegate`, which provides its rows and
+    /// cells and responds to events.
+    pub fn with(delegate: T) -> TableView<T> {
+        let class = register_tableview_class_with_delegate::<T>(&delegate);
+        let view = common_init(class);
+        let mut delegate = Box::new(delegate);
+
+        unsafe {
+            let delegate_ptr: *const T = &*delegate;
+            (&mut *view).set_ivar(TABLEVIEW_DELEGATE_PTR, delegate_ptr as usize);
+            let _: () = msg_send![view, setDelegate: view];
+            let _: () = msg_send![view, setDataSource: view];
+        };
+
+        let mut view = TableView::init(view);
+        delegate.did_load(view.clone_as_handle());
+        view.delegate = Some(delegate);
+        view
+    }
+}
+
+impl<T> TableView<T> {
+    /// Wraps a table view in a scroll view, and sets up anchors for the scroll view.
+    fn init(view: id) -> Self {
+        let scrollview = ScrollView::new();
+
+        scrollview.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setDocumentView: view];
+        });
+
+        // We need to use the NSScrollView anchor points, not the NSTableView.
+        let anchor_view: id = scrollview.objc.get(|obj| unsafe { msg_send![obj, self] });
+
+        TableView {
+            cell_factory: CellFactory::new(),
+            delegate: None,
+            objc: ObjcProperty::retain(view),
+            animator: ViewAnimatorProxy::new(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            top: LayoutAnchorY::top(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            left: LayoutAnchorX::left(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            leading: LayoutAnchorX::leading(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            right: LayoutAnchorX::right(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            trailing: LayoutAnchorX::trailing(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            bottom: LayoutAnchorY::bottom(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            width: LayoutAnchorDimension::width(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            height: LayoutAnchorDimension::height(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            center_x: LayoutAnchorX::center(anchor_view),
+
+            #[cfg(feature = "autolayout")]
+            center_y: LayoutAnchorY::center(anchor_view),
+
+            scrollview
+        }
+    }
+
+    /// An internal method that returns a clone of this object, sans references to the delegate or
+    /// callback pointer. We use this in calling `did_load()`.
+    pub fn clone_as_handle(&self) -> TableView {
+        TableView {
+            cell_factory: CellFactory::new(),
+            delegate: None,
+            objc: self.objc.clone(),
+            animator: self.animator.clone(),
+
+            #[cfg(feature = "autolayout")]
+            top: self.top.clone(),
+
+            #[cfg(feature = "autolayout")]
+            leading: self.leading.clone(),
+
+            #[cfg(feature = "autolayout")]
+            left: self.left.clone(),
+
+            #[cfg(feature = "autolayout")]
+            trailing: self.trailing.clone(),
+
+            #[cfg(feature = "autolayout")]
+            right: self.right.clone(),
+
+            #[cfg(feature = "autolayout")]
+            bottom: self.bottom.clone(),
+
+            #[cfg(feature = "autolayout")]
+            width: self.width.clone(),
+
+            #[cfg(feature = "autolayout")]
+            height: self.height.clone(),
+
+            #[cfg(feature = "autolayout")]
+            center_x: self.center_x.clone(),
+
+            #[cfg(feature = "autolayout")]
+            center_y: self.center_y.clone(),
+
+            scrollview: self.scrollview.clone_as_handle()
+        }
+    }
+
+    /// Register a cell vendor function with an identifier. This is stored internally and used
+    /// for cell reuse.
+    pub fn register<F, R>(&self, identifier: &'static str, vendor: F)
+    where
+        F: Fn() -> R + 'static,
+        R: ViewDelegate + 'static
+    {
+        self.cell_factory.insert(identifier, vendor);
+    }
+
+    /// Dequeue a reusable cell. If one is not in the queue, will create and cache one for reuse.
+    pub fn dequeue<R: ViewDelegate + 'static>(&self, identifier: &'static str) -> ListViewRow<R> {
+        let key = NSString::new(identifier);
+        let cell: id = self
+            .objc
+            .get(|obj| unsafe { msg_send![obj, makeViewWithIdentifier:&*key owner:nil] });
+
+        if cell != nil {
+            ListViewRow::from_cached(cell)
+        } else {
+            let delegate: Box<R> = self.cell_factory.get(identifier);
+            let view = ListViewRow::with_boxed(delegate);
+            view.set_identifier(identifier);
+            view
+        }
+    }
+
+    /// Adds a column to the end of the table.
+    pub fn add_column(&self, column: &TableColumn) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, addTableColumn:&*column.objc];
+        });
+    }
+
+    /// Removes a column from the table.
+    pub fn remove_column(&self, column: &TableColumn) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, removeTableColumn:&*column.objc];
+        });
+    }
+
+    /// Returns the table's columns, in the order they're displayed.
+    pub fn columns(&self) -> Vec<TableColumn> {
+        self.objc
+            .get(|obj| NSArray::retain(unsafe { msg_send![obj, tableColumns] }).map(TableColumn::wrap))
+    }
+
+    /// Returns the column with the given identifier, if there is one.
+    pub fn column(&self, identifier: &str) -> Option<TableColumn> {
+        let identifier = NSString::new(identifier);
+        let column: id = self
+            .objc
+            .get(|obj| unsafe { msg_send![obj, tableColumnWithIdentifier:&*identifier] });
+
+        match column == nil {
+            true => None,
+            false => Some(TableColumn::wrap(column))
+        }
+    }
+
+    /// Moves the column at index `from` to index `to`.
+    pub fn move_column(&self, from: usize, to: usize) {
+        let from = from as NSInteger;
+        let to = to as NSInteger;
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, moveColumn:from toColumn:to];
+        });
+    }
+
+    /// Sets whether the user can reorder columns by dragging their headers. Defaults to `true`.
+    pub fn set_allows_column_reordering(&self, allows: bool) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setAllowsColumnReordering:match allows {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
+    /// Sets whether the user can resize columns. Defaults to `true`; individual columns can opt
+    /// out with `TableColumn::set_resizable()`.
+    pub fn set_allows_column_resizing(&self, allows: bool) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setAllowsColumnResizing:match allows {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
+    /// Saves the order, widths and visibility of columns in `UserDefaults` under `name`, and
+    /// restores them from there. Call this *after* adding your columns, so there's something
+    /// to restore.
+    pub fn set_autosave_name(&self, name: &str) {
+        let name = NSString::new(name);
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setAutosaveName:&*name];
+            let _: () = msg_send![obj, setAutosaveTableColumns: YES];
+        });
+    }
+
+    /// Returns the table's sort descriptors. The first is the primary sort.
+    pub fn sort_descriptors(&self) -> Vec<SortDescriptor> {
+        self.objc
+            .get(|obj| NSArray::retain(unsafe { msg_send![obj, sortDescriptors] }).map(SortDescriptor::from_objc))
+    }
+
+    /// Sets the table's sort descriptors - e.g, to restore a sort order. This updates the sort
+    /// indicators in column headers, and calls `TableViewDelegate::sort_descriptors_changed()`.
+    pub fn set_sort_descriptors(&self, descriptors: &[SortDescriptor]) {
+        let descriptors: NSArray = descriptors
+            .iter()
+            .map(|descriptor| descriptor.to_objc())
+            .collect::<Vec<id>>()
+            .into();
+
+        // The delegate callback fires from in here, and may well want to reload.
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, setSortDescriptors:&*descriptors];
+        });
+    }
+
+    /// Sets whether the column header is shown. Defaults to `true`.
+    pub fn set_header_visible(&self, visible: bool) {
+        self.objc.with_mut(|obj| unsafe {
+            let header: id = match visible {
+                true => msg_send![class!(NSTableHeaderView), new],
+                false => nil
+            };
+
+            let _: () = msg_send![obj, setHeaderView: header];
+        });
+    }
+
+    /// Call this to set the background color for the table.
+    pub fn set_background_color<C: AsRef<Color>>(&self, color: C) {
+        let color: id = color.as_ref().into();
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setBackgroundColor: color];
+        });
+    }
+
+    /// Instructs the table to alternate row background colors.
+    pub fn set_uses_alternating_backgrounds(&self, uses: bool) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setUsesAlternatingRowBackgroundColors:match uses {
+                true => YES,
+                false => NO
+            }];
+        });
+    }
+
+    /// Sets an enforced row height. Rows size to fit their cells by default.
+    pub fn set_row_height(&self, height: CGFloat) {
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setUsesAutomaticRowHeights: NO];
+            let _: () = msg_send![obj, setRowHeight: height];
+        });
+    }
+
+    /// Reloads the table. Calling this will reload (and redraw) all rows, based on whatever
+    /// the delegate reports back.
+    pub fn reload(&self) {
+        // Reloading asks the delegate for cells, which dequeues them; see
+        // `ListView::hack_avoid_dequeue_loop()` for why this doesn't borrow mutably.
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, reloadData];
+        });
+    }
+
+    /// Reloads every cell in the rows at the specified indexes.
+    pub fn reload_rows(&self, rows: &[usize]) {
+        let columns: NSInteger = self.objc.get(|obj| unsafe { msg_send![obj, numberOfColumns] });
+        let columns = index_set(&(0..columns as usize).collect::<Vec<usize>>());
+        let rows = index_set(rows);
+
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, reloadDataForRowIndexes:&*rows columnIndexes:&*columns];
+        });
+    }
+
+    /// Insert new rows at the specified indexes, with the specified animation. Your underlying
+    /// data store must be updated *before* calling this.
+    pub fn insert_rows(&self, rows: &[usize], animation: RowAnimation) {
+        let animation: NSUInteger = animation.into();
+        let rows = index_set(rows);
+
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, insertRowsAtIndexes:&*rows withAnimation:animation];
+        });
+    }
+
+    /// Remove rows at the specified indexes, with the specified animation. Your underlying data
+    /// store must be updated *before* calling this.
+    pub fn remove_rows(&self, rows: &[usize], animation: RowAnimation) {
+        let animation: NSUInteger = animation.into();
+        let rows = index_set(rows);
+
+        self.objc.get(|obj| unsafe {
+            let _: () = msg_send![obj, removeRowsAtIndexes:&*rows withAnimation:animation];
+        });
+    }
+
+    /// Returns the selected row, if any.
+    pub fn selected_row(&self) -> Option<usize> {
+        let row: NSInteger = self.objc.get(|obj| unsafe { msg_send![obj, selectedRow] });
+
+        match row {
+            -1 => None,
+            row => Some(row as usize)
+        }
+    }
+
+    /// Returns the row that was last clicked (or context-clicked), if any. Useful for working
+    /// out what a context menu is for.
+    pub fn clicked_row(&self) -> Option<usize> {
+        let row: NSInteger = self.objc.get(|obj| unsafe { msg_send![obj, clickedRow] });
+
+        match row {
+            -1 => None,
+            row => Some(row as usize)
+        }
+    }
+
+    /// Returns the identifier of the column that was last clicked (or context-clicked), if any.
+    pub fn clicked_column(&self) -> Option<String> {
+        let index: NSInteger = self.objc.get(|obj| unsafe { msg_send![obj, clickedColumn] });
+
+        match index {
+            -1 => None,
+            index => self.columns().get(index as usize).map(|column| column.identifier())
+        }
+    }
+
+    /// Makes this table view the first responder.
+    pub fn make_first_responder(&self) {
+        self.objc.with_mut(|obj| unsafe {
+            let window: id = msg_send![&*obj, window];
+            let _: () = msg_send![window, makeFirstResponder:&*obj];
+        });
+    }
+}
+
+/// Builds an `NSIndexSet` holding `indexes`.
+fn index_set(indexes: &[usize]) -> ShareId<Object> {
+    unsafe {
+        let index_set: id = msg_send![class!(NSMutableIndexSet), new];
+
+        for index in indexes {
+            let index = *index as NSUInteger;
+            let _: () = msg_send![index_set, addIndex: index];
+        }
+
+        ShareId::from_retained_ptr(index_set)
+    }
+}
+
+impl<T> ObjcAccess for TableView<T> {
+    fn with_backing_obj_mut<F: Fn(id)>(&self, handler: F) {
+        // As with `ListView`, layout applies to the scroll view.
+        self.scrollview.objc.with_mut(handler);
+    }
+
+    fn get_from_backing_obj<F: Fn(&Object) -> R, R>(&self, handler: F) -> R {
+        self.scrollview.objc.get(handler)
+    }
+}
+
+impl<T> Layout for TableView<T> {}
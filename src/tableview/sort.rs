@@ -0,0 +1,81 @@
+use std::cmp::Ordering;
+
+use objc::{class, msg_send, sel, sel_impl};
+
+use crate::foundation::{id, nil, to_bool, NSString, NO, YES};
+
+/// Describes how a `TableView` is sorted: by which key, and in which direction. Keys are
+/// whatever you'd like them to be - they're handed back to you in
+/// `TableViewDelegate::sort_descriptors_changed()`, for you to sort your data by.
+///
+/// ```rust
+/// use cacao::tableview::SortDescriptor;
+///
+/// let mut names = vec!["b", "c", "a"];
+/// let descriptor = SortDescriptor::new("name", false);
+///
+/// names.sort_by(|a, b| descriptor.ordering(a.cmp(b)));
+/// assert_eq!(names, vec!["c", "b", "a"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SortDescriptor {
+    /// The key to sort by.
+    pub key: String,
+
+    /// Whether to sort in ascending order.
+    pub ascending: bool
+}
+
+impl SortDescriptor {
+    /// Creates a sort descriptor for `key`.
+    pub fn new<S: Into<String>>(key: S, ascending: bool) -> Self {
+        SortDescriptor {
+            key: key.into(),
+            ascending
+        }
+    }
+
+    /// Returns a copy of this descriptor, sorting in the opposite direction.
+    pub fn reversed(&self) -> Self {
+        SortDescriptor {
+            key: self.key.clone(),
+            ascending: !self.ascending
+        }
+    }
+
+    /// Takes the ascending `ordering` of two items, and returns it in this descriptor's
+    /// direction.
+    pub fn ordering(&self, ordering: Ordering) -> Ordering {
+        match self.ascending {
+            true => ordering,
+            false => ordering.reverse()
+        }
+    }
+
+    /// Returns an (autoreleased) `NSSortDescriptor` for this descriptor.
+    pub(crate) fn to_objc(&self) -> id {
+        let key = NSString::new(&self.key);
+
+        unsafe {
+            msg_send![class!(NSSortDescriptor), sortDescriptorWithKey:&*key ascending:match self.ascending {
+                true => YES,
+                false => NO
+            }]
+        }
+    }
+
+    /// Reads an `NSSortDescriptor`.
+    pub(crate) fn from_objc(descriptor: id) -> Self {
+        unsafe {
+            let key: id = msg_send![descriptor, key];
+            let ascending = to_bool(msg_send![descriptor, ascending]);
+
+            let key = match key == nil {
+                true => String::new(),
+                false => NSString::retain(key).to_string()
+            };
+
+            SortDescriptor { key, ascending }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Various traits used for TableViews.
+
+use crate::appkit::menu::MenuItem;
+use crate::listview::ListViewRow;
+use crate::tableview::{SortDescriptor, TableView};
+
+#[allow(unused_variables)]
+pub trait TableViewDelegate {
+    /// Used to cache subclass creations on the Objective-C side.
+    /// You can just set this to be the name of your view type. This
+    /// value *must* be unique per-type.
+    const NAME: &'static str;
+
+    /// You should rarely (read: probably never) need to implement this yourself.
+    /// It simply acts as a getter for the associated `NAME` const on this trait.
+    fn subclass_name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    /// Called when the View is ready to work with. You're passed a `TableView` - this is safe to
+    /// store and use repeatedly, but it's not thread safe - any UI calls must be made from the
+    /// main thread!
+    ///
+    /// This is a good place to add columns, and to register cell vendors.
+    fn did_load(&mut self, view: TableView);
+
+    /// Returns the number of rows in the table.
+    fn number_of_rows(&self) -> usize;
+
+    /// Returns the view for the cell at `row`, in the column identified by `column`. Cells are
+    /// dequeued the same way as `ListView` rows - see `TableView::register()` and
+    /// `TableView::dequeue()`.
+    fn cell_for(&self, column: &str, row: usize) -> ListViewRow;
+
+    /// Called when a row has been selected (clicked/tapped on). If the selection was cleared,
+    /// then this will be called with `None`.
+    fn item_selected(&self, row: Option<usize>) {}
+
+    /// Called when the table's sort descriptors change - usually because the user clicked a
+    /// sortable column's header. The first descriptor is the primary sort. Re-sort your data,
+    /// then call `TableView::reload()`.
+    fn sort_descriptors_changed(&self, descriptors: &[SortDescriptor]) {}
+
+    /// Called when the user clicks the header of the column identified by `column`, whether or
+    /// not it's sortable.
+    fn column_header_clicked(&self, column: &str) {}
+
+    /// Called when the user drags the column identified by `column` from index `from` to index
+    /// `to`.
+    fn column_moved(&self, column: &str, from: usize, to: usize) {}
+
+    /// Called when the column identified by `column` is resized, by the user or along with the
+    /// table.
+    fn column_resized(&self, column: &str, width: f64) {}
+
+    /// Called when the menu for the table is about to be shown. You can update the menu here
+    /// depending on, say, what the user has context-clicked on (see
+    /// `TableView::clicked_row()`). You should avoid any expensive work in here and return the
+    /// menu as fast as possible.
+    fn context_menu(&self) -> Vec<MenuItem> {
+        vec![]
+    }
+}
@@ -72,3 +72,55 @@ impl std::fmt::Debug for CellFactory {
         f.debug_struct("CellFactory").finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::CellFactory;
+    use crate::view::ViewDelegate;
+
+    #[derive(Debug, Default)]
+    struct FileCell {
+        name: String
+    }
+
+    impl ViewDelegate for FileCell {
+        const NAME: &'static str = "FileCell";
+    }
+
+    #[derive(Debug, Default)]
+    struct FolderCell;
+
+    impl ViewDelegate for FolderCell {
+        const NAME: &'static str = "FolderCell";
+    }
+
+    #[test]
+    fn test_vends_registered_cells() {
+        let factory = CellFactory::new();
+        factory.insert("FileCell", || FileCell {
+            name: "notes.txt".into()
+        });
+        factory.insert("FolderCell", FolderCell::default);
+
+        // Each dequeue that misses the system's reuse queue gets a fresh cell.
+        let mut cell = factory.get::<FileCell>("FileCell");
+        cell.name.clear();
+        assert_eq!(factory.get::<FileCell>("FileCell").name, "notes.txt");
+
+        factory.get::<FolderCell>("FolderCell");
+    }
+
+    #[test]
+    #[should_panic(expected = "did you forget to register it?")]
+    fn test_unregistered_cell_panics() {
+        CellFactory::new().get::<FileCell>("FileCell");
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to match the type")]
+    fn test_mismatched_cell_type_panics() {
+        let factory = CellFactory::new();
+        factory.insert("FileCell", FolderCell::default);
+        factory.get::<FileCell>("FileCell");
+    }
+}
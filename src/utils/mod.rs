@@ -2,12 +2,9 @@
 //! belong to. These are typically internal, and if you rely on them... well, don't be surprised if
 //! they go away one day.
 
-use core_graphics::base::CGFloat;
-
 use objc::{class, msg_send, sel, sel_impl};
 
 use objc::runtime::Object;
-use objc::{Encode, Encoding};
 use objc_id::ShareId;
 
 use crate::foundation::{id, BOOL, NO, YES};
@@ -67,39 +64,6 @@ where
     queue.exec_sync(method);
 }
 
-/// Upstream core graphics does not implement Encode for certain things, so we wrap them here -
-/// these are only used in reading certain types passed to us from some delegate methods.
-#[repr(C)]
-#[derive(Clone, Copy, Debug, Default, PartialEq)]
-pub struct CGSize {
-    /// The width of this size.
-    pub width: CGFloat,
-
-    /// The height of this size.
-    pub height: CGFloat
-}
-
-impl CGSize {
-    /// Create and return a new `CGSize`.
-    pub fn new(width: CGFloat, height: CGFloat) -> Self {
-        CGSize { width, height }
-    }
-
-    /// Create and return a `CGSizeZero` equivalent.
-    pub fn zero() -> Self {
-        CGSize { width: 0., height: 0. }
-    }
-}
-
-unsafe impl Encode for CGSize {
-    /// Adds support for CGSize Objective-C encoding.
-    fn encode() -> Encoding {
-        let encoding = format!("{{CGSize={}{}}}", CGFloat::encode().as_str(), CGFloat::encode().as_str());
-
-        unsafe { Encoding::from_str(&encoding) }
-    }
-}
-
 /// A helper method for ensuring that Cocoa is running in multi-threaded mode.
 ///
 /// Why do we need this? According to Apple, if you're going to make use of standard POSIX threads,
@@ -1,18 +1,30 @@
 use core_graphics::base::CGFloat;
+use core_graphics::geometry::{CGAffineTransform, CGRect};
 
-use objc::runtime::{Class, Object};
-use objc::{msg_send, sel, sel_impl};
+use objc::runtime::{Object, BOOL};
+use objc::{class, msg_send, sel, sel_impl};
 use objc_id::ShareId;
 
-use crate::foundation::id;
+use crate::color::Color;
+use crate::foundation::{id, YES};
+use crate::geometry::Transform;
+use crate::utils::properties::ObjcProperty;
+use crate::view::BACKGROUND_COLOR;
 
 /// A wrapper for an animation proxy object in Cocoa that supports basic animations.
+///
+/// Changes made through this inside `AnimationContext::run()` animate with that context's
+/// duration and timing function; outside of one, they apply immediately.
+///
+/// The first field is the view's `animator` proxy. Layer changes need the view itself, which is
+/// held alongside it - so create these with `new()`, rather than from an `animator` alone.
 #[derive(Clone, Debug)]
-pub struct ViewAnimatorProxy(pub ShareId<Object>);
+pub struct ViewAnimatorProxy(pub ShareId<Object>, ObjcProperty);
 
 impl ViewAnimatorProxy {
     pub fn new(proxy: id) -> Self {
-        Self(unsafe { ShareId::from_ptr(msg_send![proxy, animator]) })
+        let animator = unsafe { ShareId::from_ptr(msg_send![proxy, animator]) };
+        Self(animator, ObjcProperty::retain(proxy))
     }
 
     /// Sets the alpha value for the view being animated.
@@ -21,4 +33,82 @@ impl ViewAnimatorProxy {
             let _: () = msg_send![&*self.0, setAlphaValue: value];
         }
     }
+
+    /// Sets the frame for the view being animated.
+    pub fn set_frame<R: Into<CGRect>>(&self, rect: R) {
+        let frame: CGRect = rect.into();
+
+        unsafe {
+            let _: () = msg_send![&*self.0, setFrame: frame];
+        }
+    }
+
+    /// Sets the bounds for the view being animated.
+    pub fn set_bounds<R: Into<CGRect>>(&self, rect: R) {
+        let bounds: CGRect = rect.into();
+
+        unsafe {
+            let _: () = msg_send![&*self.0, setBounds: bounds];
+        }
+    }
+
+    /// Sets the background color for the view being animated.
+    pub fn set_background_color<C: AsRef<Color>>(&self, color: C) {
+        let color: id = color.as_ref().into();
+
+        self.1.with_mut(|view| unsafe {
+            // Views that draw their own background color hold on to it, to re-apply it when the
+            // layer is updated.
+            if (*view).class().instance_variable(BACKGROUND_COLOR).is_some() {
+                (&mut *view).set_ivar(BACKGROUND_COLOR, color);
+            }
+
+            let layer: id = msg_send![view, layer];
+            let cg: id = msg_send![color, CGColor];
+
+            with_implicit_animation(|| {
+                let _: () = msg_send![layer, setBackgroundColor: cg];
+            });
+        });
+    }
+
+    /// Sets the transform for the layer of the view being animated. Note that AppKit anchors view
+    /// layers at their top-left corner, so scaling and rotation happen around that point.
+    pub fn set_transform(&self, transform: Transform) {
+        let transform: CGAffineTransform = transform.into();
+
+        self.1.get(|view| unsafe {
+            let layer: id = msg_send![view, layer];
+
+            with_implicit_animation(|| {
+                let _: () = msg_send![layer, setAffineTransform: transform];
+            });
+        });
+    }
+
+    /// Lays out the view being animated, and its subviews, if their constraints have changed -
+    /// animating them into their new frames. Call this after changing constraint priorities or
+    /// activating and deactivating constraints; changes to offsets animate on their own.
+    pub fn layout_if_needed(&self) {
+        self.1.get(|view| {
+            with_implicit_animation(|| unsafe {
+                let _: () = msg_send![view, layoutSubtreeIfNeeded];
+            });
+        });
+    }
+}
+
+/// Runs `handler` with implicit animation allowed in the current animation context, so changes to
+/// layer properties and frames animate along with everything else, and then restores the previous
+/// setting.
+fn with_implicit_animation<F: Fn()>(handler: F) {
+    unsafe {
+        let context: id = msg_send![class!(NSAnimationContext), currentContext];
+        let allows: BOOL = msg_send![context, allowsImplicitAnimation];
+        let _: () = msg_send![context, setAllowsImplicitAnimation: YES];
+
+        handler();
+
+        let _: () = msg_send![context, setAllowsImplicitAnimation: allows];
+    }
 }
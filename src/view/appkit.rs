@@ -9,6 +9,8 @@
 
 use std::sync::Once;
 
+use core_graphics::geometry::CGRect;
+
 use objc::declare::ClassDecl;
 use objc::runtime::{Class, Object, Sel, BOOL};
 use objc::{class, msg_send, sel, sel_impl};
@@ -16,6 +18,7 @@ use objc_id::Id;
 
 use crate::dragdrop::DragInfo;
 use crate::foundation::{id, load_or_register_class, nil, NSUInteger, NO, YES};
+use crate::geometry::Size;
 use crate::utils::load;
 use crate::view::{ViewDelegate, BACKGROUND_COLOR, VIEW_DELEGATE_PTR};
 
@@ -75,6 +78,18 @@ extern "C" fn dragging_exited<T: ViewDelegate>(this: &mut Object, _: Sel, info:
     });
 }
 
+/// Called when the view has been resized, to resize its subviews. Autoresizing masks are applied
+/// by the superclass first, and then the delegate gets a chance to lay out the rest.
+extern "C" fn resize_subviews<T: ViewDelegate>(this: &mut Object, _: Sel, old_size: Size) {
+    let bounds: CGRect = unsafe {
+        let _: () = msg_send![super(this, class!(NSView)), resizeSubviewsWithOldSize: old_size];
+        msg_send![this, bounds]
+    };
+
+    let view = load::<T>(this, VIEW_DELEGATE_PTR);
+    view.layout_subviews(bounds.into());
+}
+
 /// Called for layer updates.
 extern "C" fn update_layer(this: &Object, _: Sel) {
     unsafe {
@@ -126,6 +141,11 @@ pub(crate) fn register_view_class_with_delegate<T: ViewDelegate>(instance: &T) -
 
         decl.add_method(sel!(isFlipped), enforce_normalcy as extern "C" fn(&Object, _) -> BOOL);
 
+        decl.add_method(
+            sel!(resizeSubviewsWithOldSize:),
+            resize_subviews::<T> as extern "C" fn(&mut Object, _, Size)
+        );
+
         // Drag and drop operations (e.g, accepting files)
         decl.add_method(
             sel!(draggingEntered:),
@@ -0,0 +1,409 @@
+//! The flex layout algorithm itself, following the CSS flexbox spec closely enough for layouts to
+//! be shared with the web: children are broken into lines, flexible lengths are resolved per line
+//! (growing or shrinking within each child's min and max size), and then everything is
+//! positioned with `justify_content`, `align_items` and `align_content`.
+//!
+//! This works entirely in terms of `Rect`s and `Size`s, so it doesn't need (or touch) any views.
+
+use crate::geometry::{Rect, Size};
+
+use super::{FlexAlign, FlexAlignContent, FlexBasis, FlexDirection, FlexItem, FlexJustify, FlexStyle, FlexWrap};
+
+/// Splits a size into its main and cross axis components.
+fn main_cross(horizontal: bool, size: Size) -> (f64, f64) {
+    match horizontal {
+        true => (size.width, size.height),
+        false => (size.height, size.width)
+    }
+}
+
+impl FlexItem {
+    /// The size this item starts from on the main axis, before clamping (the "flex base size").
+    fn base_size(&self, horizontal: bool, container: f64) -> f64 {
+        match self.basis {
+            FlexBasis::Auto => main_cross(horizontal, self.size).0,
+            FlexBasis::Points(points) => points,
+            FlexBasis::Percent(percent) => container * percent / 100.
+        }
+    }
+
+    /// Clamps a main axis size to this item's min and max. As in CSS, the min wins if they
+    /// conflict.
+    fn clamp_main(&self, horizontal: bool, value: f64) -> f64 {
+        let min = main_cross(horizontal, self.min_size).0;
+        let max = main_cross(horizontal, self.max_size).0;
+        value.min(max).max(min)
+    }
+
+    /// Clamps a cross axis size to this item's min and max.
+    fn clamp_cross(&self, horizontal: bool, value: f64) -> f64 {
+        let min = main_cross(horizontal, self.min_size).1;
+        let max = main_cross(horizontal, self.max_size).1;
+        value.min(max).max(min)
+    }
+}
+
+/// Returns where to start, and how much extra space to put between each of `count` things, to
+/// distribute `free` space the way `justify` describes. Negative free space (i.e, overflow) falls
+/// back to packing at the start, or centering for the `SpaceAround`/`SpaceEvenly` cases.
+fn distribute(justify: FlexJustify, free: f64, count: usize) -> (f64, f64) {
+    let count = count as f64;
+
+    match justify {
+        FlexJustify::FlexStart => (0., 0.),
+        FlexJustify::FlexEnd => (free, 0.),
+        FlexJustify::Center => (free / 2., 0.),
+        FlexJustify::SpaceBetween if free > 0. && count > 1. => (0., free / (count - 1.)),
+        FlexJustify::SpaceBetween => (0., 0.),
+        FlexJustify::SpaceAround if free > 0. => (free / count / 2., free / count),
+        FlexJustify::SpaceEvenly if free > 0. => (free / (count + 1.), free / (count + 1.)),
+        FlexJustify::SpaceAround | FlexJustify::SpaceEvenly => (free / 2., 0.)
+    }
+}
+
+impl FlexStyle {
+    /// Computes the frames for `items`, laid out in `bounds` with this style. Frames are in the
+    /// same coordinate space as `bounds`, and returned in the same order as `items`.
+    ///
+    /// ```rust
+    /// use cacao::geometry::{Rect, Size};
+    /// use cacao::view::{FlexDirection, FlexItem, FlexStyle};
+    ///
+    /// let style = FlexStyle::new(FlexDirection::Row);
+    /// let fixed = FlexItem::new(Size::new(100., 40.));
+    /// let fill = FlexItem { grow: 1., ..FlexItem::default() };
+    ///
+    /// let frames = style.layout(Rect::new(0., 0., 300., 40.), &[fixed, fill]);
+    /// assert_eq!(frames[1], Rect::new(0., 100., 200., 40.));
+    /// ```
+    pub fn layout(&self, bounds: Rect, items: &[FlexItem]) -> Vec<Rect> {
+        let content = bounds.inset(self.padding);
+
+        let horizontal = match self.direction {
+            FlexDirection::Row | FlexDirection::RowReverse => true,
+            FlexDirection::Column | FlexDirection::ColumnReverse => false
+        };
+
+        let reversed = match self.direction {
+            FlexDirection::RowReverse | FlexDirection::ColumnReverse => true,
+            FlexDirection::Row | FlexDirection::Column => false
+        };
+
+        let (container_main, container_cross) = main_cross(horizontal, content.size());
+        let (container_main, container_cross) = (container_main.max(0.), container_cross.max(0.));
+
+        let (main_gap, cross_gap) = match horizontal {
+            true => (self.column_gap, self.row_gap),
+            false => (self.row_gap, self.column_gap)
+        };
+
+        let bases: Vec<f64> = items.iter().map(|item| item.base_size(horizontal, container_main)).collect();
+
+        let hypothetical: Vec<f64> = items
+            .iter()
+            .zip(&bases)
+            .map(|(item, base)| item.clamp_main(horizontal, *base))
+            .collect();
+
+        let lines = self.collect_lines(&hypothetical, container_main, main_gap);
+
+        let mut mains = vec![0.; items.len()];
+        for line in &lines {
+            resolve_flexible_lengths(
+                line,
+                items,
+                &bases,
+                &hypothetical,
+                container_main,
+                main_gap,
+                horizontal,
+                &mut mains
+            );
+        }
+
+        // Each line is as tall (or, in columns, as wide) as its tallest item, except that a
+        // single-line container's line always fills it.
+        let crosses: Vec<f64> = items
+            .iter()
+            .map(|item| item.clamp_cross(horizontal, main_cross(horizontal, item.size).1))
+            .collect();
+
+        let mut line_crosses: Vec<f64> = lines
+            .iter()
+            .map(|line| line.iter().map(|&i| crosses[i]).fold(0., f64::max))
+            .collect();
+
+        if self.wrap == FlexWrap::NoWrap {
+            for line_cross in line_crosses.iter_mut() {
+                *line_cross = container_cross;
+            }
+        }
+
+        let gaps = cross_gap * lines.len().saturating_sub(1) as f64;
+        let free = container_cross - line_crosses.iter().sum::<f64>() - gaps;
+
+        let align_content = match self.align_content {
+            FlexAlignContent::FlexStart => FlexJustify::FlexStart,
+            FlexAlignContent::FlexEnd => FlexJustify::FlexEnd,
+            FlexAlignContent::Center => FlexJustify::Center,
+            FlexAlignContent::SpaceBetween => FlexJustify::SpaceBetween,
+            FlexAlignContent::SpaceAround => FlexJustify::SpaceAround,
+            FlexAlignContent::SpaceEvenly => FlexJustify::SpaceEvenly,
+
+            FlexAlignContent::Stretch => {
+                if free > 0. {
+                    let extra = free / line_crosses.len() as f64;
+
+                    for line_cross in line_crosses.iter_mut() {
+                        *line_cross += extra;
+                    }
+                }
+
+                FlexJustify::FlexStart
+            }
+        };
+
+        let (mut cross_position, line_spacing) = distribute(align_content, free, lines.len());
+
+        let mut frames = vec![Rect::zero(); items.len()];
+
+        for (line, line_cross) in lines.iter().zip(&line_crosses) {
+            let gaps = main_gap * line.len().saturating_sub(1) as f64;
+            let free = container_main - line.iter().map(|&i| mains[i]).sum::<f64>() - gaps;
+            let (mut main_position, spacing) = distribute(self.justify_content, free, line.len());
+
+            for &i in line {
+                let item = &items[i];
+
+                let (cross, offset) = match item.align_self.unwrap_or(self.align_items) {
+                    FlexAlign::FlexStart => (crosses[i], 0.),
+                    FlexAlign::FlexEnd => (crosses[i], line_cross - crosses[i]),
+                    FlexAlign::Center => (crosses[i], (line_cross - crosses[i]) / 2.),
+                    FlexAlign::Stretch => (item.clamp_cross(horizontal, *line_cross), 0.)
+                };
+
+                let main = match reversed {
+                    true => container_main - main_position - mains[i],
+                    false => main_position
+                };
+
+                let cross_start = match self.wrap {
+                    FlexWrap::WrapReverse => container_cross - cross_position - offset - cross,
+                    FlexWrap::NoWrap | FlexWrap::Wrap => cross_position + offset
+                };
+
+                frames[i] = match horizontal {
+                    true => Rect::new(content.top + cross_start, content.left + main, mains[i], cross),
+                    false => Rect::new(content.top + main, content.left + cross_start, cross, mains[i])
+                };
+
+                main_position += mains[i] + main_gap + spacing;
+            }
+
+            cross_position += line_cross + cross_gap + line_spacing;
+        }
+
+        frames
+    }
+
+    /// Breaks items into lines, by their hypothetical main sizes. A line always holds at least
+    /// one item, even if that item overflows it.
+    fn collect_lines(&self, sizes: &[f64], container: f64, gap: f64) -> Vec<Vec<usize>> {
+        let mut lines = Vec::new();
+        let mut line: Vec<usize> = Vec::new();
+        let mut used = 0.;
+
+        for (i, size) in sizes.iter().enumerate() {
+            let gap = match line.is_empty() {
+                true => 0.,
+                false => gap
+            };
+
+            if self.wrap != FlexWrap::NoWrap && !line.is_empty() && used + gap + size > container {
+                lines.push(std::mem::take(&mut line));
+                used = 0.;
+                line.push(i);
+                used += size;
+                continue;
+            }
+
+            line.push(i);
+            used += gap + size;
+        }
+
+        if !line.is_empty() {
+            lines.push(line);
+        }
+
+        lines
+    }
+}
+
+/// Resolves the main sizes of the items on one line, growing or shrinking them to fit the line
+/// and freezing any that hit their min or max size along the way - the "resolve flexible lengths"
+/// steps of the CSS spec.
+#[allow(clippy::too_many_arguments)]
+fn resolve_flexible_lengths(
+    line: &[usize],
+    items: &[FlexItem],
+    bases: &[f64],
+    hypothetical: &[f64],
+    container: f64,
+    gap: f64,
+    horizontal: bool,
+    mains: &mut [f64]
+) {
+    let gaps = gap * line.len().saturating_sub(1) as f64;
+    let growing = line.iter().map(|&i| hypothetical[i]).sum::<f64>() + gaps < container;
+
+    let factor = |i: usize| match growing {
+        true => items[i].grow,
+        false => items[i].shrink
+    };
+
+    // Items that can't flex (or that min/max already push the wrong way) are sized up front.
+    let mut frozen: Vec<bool> = line
+        .iter()
+        .map(|&i| {
+            mains[i] = hypothetical[i];
+
+            factor(i) <= 0. || (growing && bases[i] > hypothetical[i]) || (!growing && bases[i] < hypothetical[i])
+        })
+        .collect();
+
+    let free_space = |frozen: &[bool], mains: &[f64]| {
+        let used: f64 = line
+            .iter()
+            .zip(frozen)
+            .map(|(&i, frozen)| match frozen {
+                true => mains[i],
+                false => bases[i]
+            })
+            .sum();
+
+        container - gaps - used
+    };
+
+    let initial_free = free_space(&frozen, mains);
+
+    while frozen.iter().any(|frozen| !frozen) {
+        let unfrozen: Vec<(usize, usize)> = line
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| !frozen[*j])
+            .map(|(j, &i)| (j, i))
+            .collect();
+
+        let factors: f64 = unfrozen.iter().map(|&(_, i)| factor(i)).sum();
+        let mut free = free_space(&frozen, mains);
+
+        // Factors that add up to less than 1 only take that fraction of the free space.
+        if factors < 1. && (initial_free * factors).abs() < free.abs() {
+            free = initial_free * factors;
+        }
+
+        let scaled_shrink: f64 = unfrozen.iter().map(|&(_, i)| items[i].shrink * bases[i]).sum();
+        let mut violation = 0.;
+        let mut violations = Vec::with_capacity(unfrozen.len());
+
+        for &(j, i) in &unfrozen {
+            let target = match growing {
+                true => bases[i] + free * items[i].grow / factors,
+                false if scaled_shrink > 0. => bases[i] + free * items[i].shrink * bases[i] / scaled_shrink,
+                false => bases[i]
+            };
+
+            let clamped = items[i].clamp_main(horizontal, target);
+            mains[i] = clamped;
+            violation += clamped - target;
+            violations.push((j, clamped - target));
+        }
+
+        // Freeze whichever items were clamped in the direction of the total violation, and try
+        // again with the rest - or, if nothing was clamped, we're done.
+        for (j, item_violation) in violations {
+            frozen[j] = match violation {
+                v if v > 0. => item_violation > 0.,
+                v if v < 0. => item_violation < 0.,
+                _ => true
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::EdgeInsets;
+
+    #[test]
+    fn test_flex_layout() {
+        let bounds = Rect::new(0., 0., 300., 100.);
+
+        // Growing and shrinking, within min and max sizes.
+        let mut style = FlexStyle::new(FlexDirection::Row);
+        style.column_gap = 10.;
+        style.padding = EdgeInsets::uniform(10.);
+        style.align_items = FlexAlign::Center;
+
+        let fixed = FlexItem::new(Size::new(50., 20.));
+        let grow = FlexItem {
+            grow: 1.,
+            ..FlexItem::new(Size::new(0., 40.))
+        };
+        let capped = FlexItem {
+            grow: 3.,
+            max_size: Size::new(60., f64::INFINITY),
+            ..FlexItem::new(Size::new(0., 40.))
+        };
+
+        let frames = style.layout(bounds, &[fixed, grow, capped]);
+        assert_eq!(frames, vec![
+            Rect::new(40., 10., 50., 20.),
+            Rect::new(30., 70., 150., 40.),
+            Rect::new(30., 230., 60., 40.),
+        ]);
+
+        let frames = style.layout(Rect::new(0., 0., 100., 100.), &[FlexItem::new(Size::new(60., 20.)); 2]);
+        assert_eq!(frames[0].width, 35.);
+        assert_eq!(frames[1].left, 55.);
+
+        // Reversed direction, with space between.
+        let mut style = FlexStyle::new(FlexDirection::RowReverse);
+        style.justify_content = FlexJustify::SpaceBetween;
+        style.align_items = FlexAlign::FlexStart;
+
+        let frames = style.layout(bounds, &[FlexItem::new(Size::new(50., 20.)); 3]);
+        let lefts: Vec<f64> = frames.iter().map(|frame| frame.left).collect();
+        assert_eq!(lefts, vec![250., 125., 0.]);
+
+        // Wrapping columns, with lines packed in the middle.
+        let mut style = FlexStyle::new(FlexDirection::Column);
+        style.wrap = FlexWrap::Wrap;
+        style.set_gap(10.);
+        style.align_content = FlexAlignContent::Center;
+
+        let frames = style.layout(bounds, &[FlexItem::new(Size::new(40., 40.)); 3]);
+        assert_eq!(frames, vec![
+            Rect::new(0., 105., 40., 40.),
+            Rect::new(50., 105., 40., 40.),
+            Rect::new(0., 155., 40., 40.),
+        ]);
+
+        // Percent bases, and stretched lines.
+        let mut style = FlexStyle::new(FlexDirection::Row);
+        style.wrap = FlexWrap::WrapReverse;
+
+        let half = FlexItem {
+            basis: FlexBasis::Percent(50.),
+            ..FlexItem::new(Size::new(0., 10.))
+        };
+
+        let frames = style.layout(bounds, &[half; 3]);
+        assert_eq!(frames, vec![
+            Rect::new(50., 0., 150., 50.),
+            Rect::new(50., 150., 150., 50.),
+            Rect::new(0., 0., 150., 50.),
+        ]);
+    }
+}
@@ -0,0 +1,308 @@
+//! A container that lays out its children with flexbox semantics, computed in pure Rust.
+//!
+//! Each child carries a `FlexItem` (grow, shrink, basis, size and alignment), and the container a
+//! `FlexStyle` (direction, wrap, justify, align and gap). Whenever the container is resized, it
+//! computes frames for its children and applies them with `Layout::set_frame()` - so it works with
+//! or without the `autolayout` feature, and a `FlexView` can itself be constrained or nested like
+//! any other view.
+//!
+//! ```rust,no_run
+//! use cacao::geometry::Size;
+//! use cacao::view::{FlexDirection, FlexItem, FlexJustify, FlexStyle, FlexView, View};
+//!
+//! let toolbar = FlexView::new();
+//!
+//! let mut style = FlexStyle::new(FlexDirection::Row);
+//! style.justify_content = FlexJustify::SpaceBetween;
+//! style.set_gap(8.);
+//! toolbar.set_style(style);
+//!
+//! let title = View::new();
+//! toolbar.add_child(&title, FlexItem {
+//!     grow: 1.,
+//!     ..FlexItem::new(Size::new(0., 32.))
+//! });
+//!
+//! let button = View::new();
+//! toolbar.add_child(&button, FlexItem::new(Size::new(80., 32.)));
+//! ```
+//!
+//! The layout computation is available on its own, as `FlexStyle::layout()`, for testing or for
+//! laying out things other than views.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use core_graphics::geometry::CGRect;
+
+use objc::runtime::Object;
+use objc::{msg_send, sel, sel_impl};
+
+use crate::color::Color;
+use crate::foundation::id;
+use crate::geometry::Rect;
+use crate::layout::Layout;
+use crate::objc_access::ObjcAccess;
+use crate::utils::properties::ObjcProperty;
+use crate::view::{View, ViewDelegate};
+
+#[cfg(feature = "autolayout")]
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY};
+
+mod compute;
+
+mod style;
+pub use style::{FlexAlign, FlexAlignContent, FlexBasis, FlexDirection, FlexItem, FlexJustify, FlexStyle, FlexWrap};
+
+/// A child of a `FlexView`, along with its flex properties.
+#[derive(Debug)]
+struct FlexChild {
+    objc: ObjcProperty,
+    item: FlexItem
+}
+
+impl ObjcAccess for FlexChild {
+    fn with_backing_obj_mut<F: Fn(id)>(&self, handler: F) {
+        self.objc.with_mut(handler);
+    }
+
+    fn get_from_backing_obj<F: Fn(&Object) -> R, R>(&self, handler: F) -> R {
+        self.objc.get(handler)
+    }
+}
+
+impl Layout for FlexChild {}
+
+/// The style and children of a `FlexView`, shared between it and its view delegate.
+#[derive(Debug, Default)]
+struct FlexState {
+    style: FlexStyle,
+    children: Vec<FlexChild>
+}
+
+impl FlexState {
+    /// Computes frames for the children within `bounds`, and applies them.
+    fn apply(&self, bounds: Rect) {
+        let items: Vec<FlexItem> = self.children.iter().map(|child| child.item).collect();
+
+        for (child, frame) in self.children.iter().zip(self.style.layout(bounds, &items)) {
+            child.set_frame(frame);
+        }
+    }
+
+    /// Returns the index of the child backed by `view`, if any.
+    fn position<V: Layout>(&self, view: &V) -> Option<usize> {
+        let obj = backing_pointer(view);
+        self.children.iter().position(|child| backing_pointer(child) == obj)
+    }
+}
+
+/// Returns the pointer to the Objective-C object backing `view`, for identifying children.
+fn backing_pointer<V: Layout>(view: &V) -> id {
+    view.get_from_backing_obj(|obj| obj as *const Object as id)
+}
+
+/// Re-applies the layout whenever the container is resized.
+#[derive(Debug)]
+struct FlexViewDelegate {
+    state: Rc<RefCell<FlexState>>
+}
+
+impl ViewDelegate for FlexViewDelegate {
+    const NAME: &'static str = "CacaoFlexView";
+
+    fn layout_subviews(&self, bounds: Rect) {
+        self.state.borrow().apply(bounds);
+    }
+}
+
+/// A view that lays out its children with flexbox semantics. See the module documentation for
+/// more information.
+#[derive(Debug)]
+pub struct FlexView {
+    /// A pointer to the Objective-C runtime view.
+    pub objc: ObjcProperty,
+
+    /// The style and children, shared with the delegate on `view`.
+    state: Rc<RefCell<FlexState>>,
+
+    /// The backing view, which calls back into us when it's resized.
+    view: View<FlexViewDelegate>,
+
+    /// A pointer to the Objective-C runtime top layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub top: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime leading layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub leading: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime left layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub left: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime trailing layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub trailing: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime right layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub right: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime bottom layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub bottom: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime width layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub width: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime height layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub height: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime center X layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub center_x: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime center Y layout constraint.
+    #[cfg(feature = "autolayout")]
+    pub center_y: LayoutAnchorY
+}
+
+impl Default for FlexView {
+    fn default() -> Self {
+        FlexView::new()
+    }
+}
+
+impl FlexView {
+    /// Returns an empty `FlexView`, with the default style: a single row, with children packed
+    /// at the start and stretched to its height.
+    pub fn new() -> Self {
+        let state = Rc::new(RefCell::new(FlexState::default()));
+        let view = View::with(FlexViewDelegate { state: state.clone() });
+
+        FlexView {
+            objc: view.objc.clone(),
+            state,
+
+            #[cfg(feature = "autolayout")]
+            top: view.top.clone(),
+
+            #[cfg(feature = "autolayout")]
+            leading: view.leading.clone(),
+
+            #[cfg(feature = "autolayout")]
+            left: view.left.clone(),
+
+            #[cfg(feature = "autolayout")]
+            trailing: view.trailing.clone(),
+
+            #[cfg(feature = "autolayout")]
+            right: view.right.clone(),
+
+            #[cfg(feature = "autolayout")]
+            bottom: view.bottom.clone(),
+
+            #[cfg(feature = "autolayout")]
+            width: view.width.clone(),
+
+            #[cfg(feature = "autolayout")]
+            height: view.height.clone(),
+
+            #[cfg(feature = "autolayout")]
+            center_x: view.center_x.clone(),
+
+            #[cfg(feature = "autolayout")]
+            center_y: view.center_y.clone(),
+
+            view
+        }
+    }
+
+    /// Returns the current style.
+    pub fn style(&self) -> FlexStyle {
+        self.state.borrow().style
+    }
+
+    /// Sets the style, and lays out the children again.
+    pub fn set_style(&self, style: FlexStyle) {
+        self.state.borrow_mut().style = style;
+        self.layout();
+    }
+
+    /// Adds `view` as the last child, laid out with `item`.
+    ///
+    /// The child's frame is managed by this container from here on. Under the `autolayout`
+    /// feature, the child is switched over to frame-based layout - it shouldn't be given
+    /// constraints relative to the container, although its own subviews can use them.
+    pub fn add_child<V: Layout>(&self, view: &V, item: FlexItem) {
+        #[cfg(feature = "autolayout")]
+        view.set_translates_autoresizing_mask_into_constraints(true);
+
+        self.view.add_subview(view);
+
+        self.state.borrow_mut().children.push(FlexChild {
+            objc: ObjcProperty::retain(backing_pointer(view)),
+            item
+        });
+
+        self.layout();
+    }
+
+    /// Updates the flex properties for a child, and lays out the children again. Does nothing if
+    /// `view` isn't a child of this container.
+    pub fn set_item<V: Layout>(&self, view: &V, item: FlexItem) {
+        {
+            let mut state = self.state.borrow_mut();
+
+            match state.position(view) {
+                Some(index) => state.children[index].item = item,
+                None => return
+            }
+        }
+
+        self.layout();
+    }
+
+    /// Removes `view` from this container, and lays out the remaining children again. Does
+    /// nothing if `view` isn't a child of this container.
+    pub fn remove_child<V: Layout>(&self, view: &V) {
+        let child = {
+            let mut state = self.state.borrow_mut();
+
+            match state.position(view) {
+                Some(index) => state.children.remove(index),
+                None => return
+            }
+        };
+
+        child.remove_from_superview();
+        self.layout();
+    }
+
+    /// Lays out the children in the container's current bounds. This happens automatically
+    /// whenever the container is resized, or its style or children change.
+    pub fn layout(&self) {
+        let bounds: CGRect = self.objc.get(|obj| unsafe { msg_send![obj, bounds] });
+        self.state.borrow().apply(bounds.into());
+    }
+
+    /// Call this to set the background color for the backing layer.
+    pub fn set_background_color<C: AsRef<Color>>(&self, color: C) {
+        self.view.set_background_color(color);
+    }
+}
+
+impl ObjcAccess for FlexView {
+    fn with_backing_obj_mut<F: Fn(id)>(&self, handler: F) {
+        self.objc.with_mut(handler);
+    }
+
+    fn get_from_backing_obj<F: Fn(&Object) -> R, R>(&self, handler: F) -> R {
+        self.objc.get(handler)
+    }
+}
+
+impl Layout for FlexView {}
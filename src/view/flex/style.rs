@@ -0,0 +1,226 @@
+//! The properties that control a flex layout - for the container (`FlexStyle`) and for each of
+//! its children (`FlexItem`). These mirror their CSS counterparts, and default to the same values.
+
+use crate::geometry::{EdgeInsets, Size};
+
+/// The direction that children are laid out in - the "main axis".
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FlexDirection {
+    /// Left to right. This is the default.
+    #[default]
+    Row,
+
+    /// Right to left.
+    RowReverse,
+
+    /// Top to bottom.
+    Column,
+
+    /// Bottom to top.
+    ColumnReverse
+}
+
+/// Whether children that don't fit on one line wrap onto more lines.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FlexWrap {
+    /// All children are kept on one line, shrinking them if need be. This is the default.
+    #[default]
+    NoWrap,
+
+    /// Children wrap onto more lines, stacked below (or, in columns, to the right of) the first.
+    Wrap,
+
+    /// Children wrap onto more lines, stacked above (or, in columns, to the left of) the first.
+    WrapReverse
+}
+
+/// How the space left over on a line is distributed along the main axis (`justify-content`).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FlexJustify {
+    /// Children are packed at the start of the line. This is the default.
+    #[default]
+    FlexStart,
+
+    /// Children are packed at the end of the line.
+    FlexEnd,
+
+    /// Children are packed in the middle of the line.
+    Center,
+
+    /// The first and last children touch the ends of the line, with equal space between the rest.
+    SpaceBetween,
+
+    /// Every child has equal space on either side, so the ends get half as much as the gaps.
+    SpaceAround,
+
+    /// The ends and the gaps between children all get the same space.
+    SpaceEvenly
+}
+
+/// How children are positioned across a line, on the cross axis (`align-items`, and
+/// `align-self` for a single child).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FlexAlign {
+    /// Children are aligned to the start of the line.
+    FlexStart,
+
+    /// Children are aligned to the end of the line.
+    FlexEnd,
+
+    /// Children are centered in the line.
+    Center,
+
+    /// Children are stretched to fill the line (within their min and max sizes), ignoring their
+    /// own cross size. This is the default.
+    #[default]
+    Stretch
+}
+
+/// How lines are distributed along the cross axis, when children wrap onto more than one
+/// (`align-content`). Single-line containers always fill the cross axis with their line.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FlexAlignContent {
+    /// Lines are packed at the start of the container.
+    FlexStart,
+
+    /// Lines are packed at the end of the container.
+    FlexEnd,
+
+    /// Lines are packed in the middle of the container.
+    Center,
+
+    /// The first and last lines touch the edges of the container, with equal space between.
+    SpaceBetween,
+
+    /// Every line has equal space on either side.
+    SpaceAround,
+
+    /// The edges and the gaps between lines all get the same space.
+    SpaceEvenly,
+
+    /// Lines are grown equally to fill the container. This is the default.
+    #[default]
+    Stretch
+}
+
+/// The starting size of a child along the main axis, before growing or shrinking (`flex-basis`).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum FlexBasis {
+    /// Use the child's `size`. This is the default.
+    #[default]
+    Auto,
+
+    /// A fixed size, in points.
+    Points(f64),
+
+    /// A percentage (`0.` - `100.`) of the container's size, less its padding.
+    Percent(f64)
+}
+
+/// Properties for a flex container: how it lays out its children as a whole.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct FlexStyle {
+    /// The direction children are laid out in.
+    pub direction: FlexDirection,
+
+    /// Whether children wrap onto more lines.
+    pub wrap: FlexWrap,
+
+    /// How leftover space on each line is distributed.
+    pub justify_content: FlexJustify,
+
+    /// How children are aligned across each line. Children can override this with
+    /// `FlexItem::align_self`.
+    pub align_items: FlexAlign,
+
+    /// How lines are distributed, when children wrap.
+    pub align_content: FlexAlignContent,
+
+    /// Space between rows, in points - between lines in a `Row`, or children in a `Column`.
+    pub row_gap: f64,
+
+    /// Space between columns, in points - between children in a `Row`, or lines in a `Column`.
+    pub column_gap: f64,
+
+    /// Space between the container's edges and its children.
+    pub padding: EdgeInsets
+}
+
+impl FlexStyle {
+    /// Returns a style with the given direction, and defaults for everything else.
+    pub fn new(direction: FlexDirection) -> Self {
+        FlexStyle {
+            direction,
+            ..FlexStyle::default()
+        }
+    }
+
+    /// Sets both `row_gap` and `column_gap` to `gap`.
+    pub fn set_gap(&mut self, gap: f64) {
+        self.row_gap = gap;
+        self.column_gap = gap;
+    }
+}
+
+/// Properties for a child of a flex container: how it's sized and aligned within its line.
+///
+/// ```rust
+/// use cacao::geometry::Size;
+/// use cacao::view::FlexItem;
+///
+/// // 44 points tall, and taking up whatever width is left over in a row.
+/// let item = FlexItem {
+///     grow: 1.,
+///     ..FlexItem::new(Size::new(0., 44.))
+/// };
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FlexItem {
+    /// How much of the leftover space on a line this child takes, relative to its siblings
+    /// (`flex-grow`). `0.` (the default) doesn't grow at all.
+    pub grow: f64,
+
+    /// How much this child shrinks when its line overflows, relative to its siblings and
+    /// weighted by its basis (`flex-shrink`). `0.` doesn't shrink; the default is `1.`.
+    pub shrink: f64,
+
+    /// The starting size along the main axis.
+    pub basis: FlexBasis,
+
+    /// The child's preferred size. Its main axis is used for an `Auto` basis, and its cross axis
+    /// unless the child is stretched.
+    pub size: Size,
+
+    /// The smallest this child can be.
+    pub min_size: Size,
+
+    /// The largest this child can be. Unbounded by default.
+    pub max_size: Size,
+
+    /// Overrides the container's `align_items` for this child.
+    pub align_self: Option<FlexAlign>
+}
+
+impl FlexItem {
+    /// Returns an item with the given preferred size, and defaults for everything else.
+    pub fn new(size: Size) -> Self {
+        FlexItem {
+            size,
+            ..FlexItem::default()
+        }
+    }
+}
+
+impl Default for FlexItem {
+    fn default() -> Self {
+        FlexItem {
+            grow: 0.,
+            shrink: 1.,
+            basis: FlexBasis::Auto,
+            size: Size::zero(),
+            min_size: Size::zero(),
+            max_size: Size::new(f64::INFINITY, f64::INFINITY),
+            align_self: None
+        }
+    }
+}
@@ -53,7 +53,7 @@ use crate::objc_access::ObjcAccess;
 use crate::utils::properties::ObjcProperty;
 
 #[cfg(feature = "autolayout")]
-use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY, SafeAreaLayoutGuide};
+use crate::layout::{LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY, LayoutGuide, SafeAreaLayoutGuide};
 
 #[cfg(feature = "appkit")]
 use crate::pasteboard::PasteboardType;
@@ -77,6 +77,15 @@ mod splitviewcontroller;
 #[cfg(feature = "appkit")]
 pub use splitviewcontroller::SplitViewController;
 
+mod flex;
+pub use flex::{FlexAlign, FlexAlignContent, FlexBasis, FlexDirection, FlexItem, FlexJustify, FlexStyle, FlexView, FlexWrap};
+
+#[cfg(feature = "autolayout")]
+mod stack;
+
+#[cfg(feature = "autolayout")]
+pub use stack::{StackView, StackViewAlignment, StackViewDistribution};
+
 mod traits;
 pub use traits::ViewDelegate;
 
@@ -316,6 +325,18 @@ impl<T> View<T> {
             let _: () = msg_send![&*obj, setBackgroundColor: color];
         });
     }
+
+    /// Returns a guide for this view's layout margins. See `LayoutGuide` for details.
+    #[cfg(feature = "autolayout")]
+    pub fn layout_margins_guide(&self) -> LayoutGuide {
+        self.get_from_backing_obj(|obj| LayoutGuide::layout_margins(obj as *const Object as id))
+    }
+
+    /// Returns a guide for this view's readable content area. See `LayoutGuide` for details.
+    #[cfg(feature = "autolayout")]
+    pub fn readable_content_guide(&self) -> LayoutGuide {
+        self.get_from_backing_obj(|obj| LayoutGuide::readable_content(obj as *const Object as id))
+    }
 }
 
 impl<T> ObjcAccess for View<T> {
@@ -0,0 +1,143 @@
+use crate::foundation::NSInteger;
+
+#[cfg(feature = "appkit")]
+use crate::layout::LayoutConstraintOrientation;
+
+/// How a `StackView` positions its arranged views perpendicular to its orientation - i.e,
+/// vertically in a horizontal stack, and horizontally in a vertical one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StackViewAlignment {
+    /// Arranged views are stretched to fill the stack. This is the default.
+    Fill,
+
+    /// Arranged views are aligned to the leading edge of a vertical stack, or the top edge of a
+    /// horizontal one.
+    Leading,
+
+    /// Arranged views are centered.
+    Center,
+
+    /// Arranged views are aligned to the trailing edge of a vertical stack, or the bottom edge of
+    /// a horizontal one.
+    Trailing,
+
+    /// Arranged views are aligned by their first baseline. Horizontal stacks only.
+    FirstBaseline,
+
+    /// Arranged views are aligned by their last baseline. Horizontal stacks only.
+    LastBaseline
+}
+
+impl StackViewAlignment {
+    /// Returns the `NSLayoutAttribute` that `NSStackView` uses for this alignment, in the given
+    /// orientation. `NSStackView` has no fill alignment, so `Fill` maps to no attribute at all -
+    /// `StackView` constrains the arranged views itself in that case.
+    #[cfg(feature = "appkit")]
+    pub(crate) fn to_attribute(self, orientation: LayoutConstraintOrientation) -> NSInteger {
+        let horizontal = orientation == LayoutConstraintOrientation::Horizontal;
+
+        match (self, horizontal) {
+            (Self::Fill, _) => 0,
+            (Self::Leading, true) => 3,
+            (Self::Leading, false) => 5,
+            (Self::Center, true) => 10,
+            (Self::Center, false) => 9,
+            (Self::Trailing, true) => 4,
+            (Self::Trailing, false) => 6,
+            (Self::FirstBaseline, _) => 12,
+            (Self::LastBaseline, _) => 11
+        }
+    }
+}
+
+#[cfg(all(feature = "uikit", not(feature = "appkit")))]
+impl From<StackViewAlignment> for NSInteger {
+    fn from(alignment: StackViewAlignment) -> Self {
+        match alignment {
+            StackViewAlignment::Fill => 0,
+            StackViewAlignment::Leading => 1,
+            StackViewAlignment::FirstBaseline => 2,
+            StackViewAlignment::Center => 3,
+            StackViewAlignment::Trailing => 4,
+            StackViewAlignment::LastBaseline => 5
+        }
+    }
+}
+
+/// How a `StackView` sizes and spaces its arranged views along its orientation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StackViewDistribution {
+    /// Arranged views fill the stack, and are resized according to their hugging and compression
+    /// resistance priorities. This is the default.
+    Fill,
+
+    /// Arranged views fill the stack, and are all the same size.
+    FillEqually,
+
+    /// Arranged views fill the stack, and are resized in proportion to their intrinsic sizes.
+    FillProportionally,
+
+    /// Arranged views keep their sizes, with equal spacing between them.
+    EqualSpacing,
+
+    /// Arranged views keep their sizes, with equal distances between their centers.
+    EqualCentering
+}
+
+impl From<StackViewDistribution> for NSInteger {
+    fn from(distribution: StackViewDistribution) -> Self {
+        match distribution {
+            StackViewDistribution::Fill => 0,
+            StackViewDistribution::FillEqually => 1,
+            StackViewDistribution::FillProportionally => 2,
+            StackViewDistribution::EqualSpacing => 3,
+            StackViewDistribution::EqualCentering => 4
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StackViewAlignment, StackViewDistribution};
+    use crate::foundation::NSInteger;
+
+    #[cfg(feature = "appkit")]
+    #[test]
+    fn test_alignment_attributes() {
+        use crate::layout::LayoutConstraintOrientation;
+
+        let attributes = |orientation| {
+            [
+                StackViewAlignment::Fill,
+                StackViewAlignment::Leading,
+                StackViewAlignment::Center,
+                StackViewAlignment::Trailing,
+                StackViewAlignment::FirstBaseline,
+                StackViewAlignment::LastBaseline
+            ]
+            .iter()
+            .map(|alignment| alignment.to_attribute(orientation))
+            .collect::<Vec<NSInteger>>()
+        };
+
+        // Top, center Y and bottom for rows; leading, center X and trailing for columns.
+        assert_eq!(attributes(LayoutConstraintOrientation::Horizontal), vec![0, 3, 10, 4, 12, 11]);
+        assert_eq!(attributes(LayoutConstraintOrientation::Vertical), vec![0, 5, 9, 6, 12, 11]);
+    }
+
+    #[test]
+    fn test_distribution_values() {
+        let values: Vec<NSInteger> = [
+            StackViewDistribution::Fill,
+            StackViewDistribution::FillEqually,
+            StackViewDistribution::FillProportionally,
+            StackViewDistribution::EqualSpacing,
+            StackViewDistribution::EqualCentering
+        ]
+        .iter()
+        .map(|distribution| NSInteger::from(*distribution))
+        .collect();
+
+        assert_eq!(values, vec![0, 1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,349 @@
+//! Wraps `NSStackView` and `UIStackView` across platforms.
+//!
+//! A `StackView` lays out a row or column of views for you: add views with
+//! `add_arranged_subview()`, and the stack creates and maintains the constraints between them.
+//! Stacks are views themselves, so they can be nested (e.g, a column of rows) and constrained like
+//! any other view.
+//!
+//! ```rust,no_run
+//! use cacao::layout::LayoutConstraintOrientation;
+//! use cacao::view::{StackView, StackViewDistribution, View};
+//!
+//! let row = StackView::new();
+//! row.set_spacing(8.);
+//! row.set_distribution(StackViewDistribution::FillEqually);
+//!
+//! let column = StackView::new();
+//! column.set_orientation(LayoutConstraintOrientation::Vertical);
+//! column.add_arranged_subview(&row);
+//!
+//! let first = View::new();
+//! let second = View::new();
+//! row.add_arranged_subview(&first);
+//! row.add_arranged_subview(&second);
+//! ```
+//!
+//! Which arranged views grow or shrink to fit the stack comes down to their hugging and
+//! compression resistance priorities - set those on the views themselves, with
+//! `Layout::set_content_hugging_priority()` and
+//! `Layout::set_content_compression_resistance_priority()`.
+
+use std::cell::{Cell, RefCell};
+
+use core_graphics::base::CGFloat;
+
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
+
+use crate::foundation::{id, NSInteger, NSUInteger, NO, YES};
+use crate::geometry::EdgeInsets;
+use crate::layout::{Layout, LayoutAnchorDimension, LayoutAnchorX, LayoutAnchorY, LayoutConstraint, LayoutConstraintOrientation};
+use crate::objc_access::ObjcAccess;
+use crate::utils::properties::ObjcProperty;
+
+#[cfg(feature = "appkit")]
+use crate::foundation::NSArray;
+
+mod enums;
+pub use enums::{StackViewAlignment, StackViewDistribution};
+
+/// A view that arranges its subviews in a row or column. See the module documentation for more
+/// information.
+#[derive(Debug)]
+pub struct StackView {
+    /// A pointer to the Objective-C runtime stack view.
+    pub objc: ObjcProperty,
+
+    /// The current alignment. We need to hold on to this to re-apply it when the orientation
+    /// changes under AppKit.
+    alignment: Cell<StackViewAlignment>,
+
+    /// Constraints used to emulate `StackViewAlignment::Fill` under AppKit, which `NSStackView`
+    /// doesn't support natively.
+    fill_constraints: RefCell<Vec<LayoutConstraint>>,
+
+    /// A pointer to the Objective-C runtime top layout constraint.
+    pub top: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime leading layout constraint.
+    pub leading: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime left layout constraint.
+    pub left: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime trailing layout constraint.
+    pub trailing: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime right layout constraint.
+    pub right: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime bottom layout constraint.
+    pub bottom: LayoutAnchorY,
+
+    /// A pointer to the Objective-C runtime width layout constraint.
+    pub width: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime height layout constraint.
+    pub height: LayoutAnchorDimension,
+
+    /// A pointer to the Objective-C runtime center X layout constraint.
+    pub center_x: LayoutAnchorX,
+
+    /// A pointer to the Objective-C runtime center Y layout constraint.
+    pub center_y: LayoutAnchorY
+}
+
+impl Default for StackView {
+    fn default() -> Self {
+        StackView::new()
+    }
+}
+
+impl StackView {
+    /// Returns a new, empty, horizontal `StackView`, with `Fill` alignment and distribution.
+    pub fn new() -> Self {
+        let view: id = unsafe {
+            #[cfg(feature = "appkit")]
+            let view: id = msg_send![class!(NSStackView), new];
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            let view: id = msg_send![class!(UIStackView), new];
+
+            let _: () = msg_send![view, setTranslatesAutoresizingMaskIntoConstraints: NO];
+
+            #[cfg(feature = "appkit")]
+            let _: () = msg_send![view, setWantsLayer: YES];
+
+            view
+        };
+
+        let stack = StackView {
+            alignment: Cell::new(StackViewAlignment::Fill),
+            fill_constraints: RefCell::new(Vec::new()),
+            top: LayoutAnchorY::top(view),
+            left: LayoutAnchorX::left(view),
+            leading: LayoutAnchorX::leading(view),
+            right: LayoutAnchorX::right(view),
+            trailing: LayoutAnchorX::trailing(view),
+            bottom: LayoutAnchorY::bottom(view),
+            width: LayoutAnchorDimension::width(view),
+            height: LayoutAnchorDimension::height(view),
+            center_x: LayoutAnchorX::center(view),
+            center_y: LayoutAnchorY::center(view),
+            objc: ObjcProperty::from_retained(view)
+        };
+
+        // The platforms disagree on defaults, so we settle on the `UIStackView` ones.
+        stack.set_alignment(StackViewAlignment::Fill);
+        stack.set_distribution(StackViewDistribution::Fill);
+        stack
+    }
+
+    /// Returns whether this stack is laid out horizontally or vertically.
+    pub fn orientation(&self) -> LayoutConstraintOrientation {
+        self.objc.get(|obj| unsafe {
+            #[cfg(feature = "appkit")]
+            let orientation: NSInteger = msg_send![obj, orientation];
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            let orientation: NSInteger = msg_send![obj, axis];
+
+            orientation.into()
+        })
+    }
+
+    /// Sets whether this stack lays its views out horizontally (a row) or vertically (a column).
+    pub fn set_orientation(&self, orientation: LayoutConstraintOrientation) {
+        let value: NSInteger = orientation.into();
+
+        self.objc.with_mut(|obj| unsafe {
+            #[cfg(feature = "appkit")]
+            let _: () = msg_send![obj, setOrientation: value];
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            let _: () = msg_send![obj, setAxis: value];
+        });
+
+        // AppKit alignments are tied to the orientation, so they need re-applying.
+        #[cfg(feature = "appkit")]
+        self.set_alignment(self.alignment.get());
+    }
+
+    /// Sets the spacing between arranged views, in points.
+    pub fn set_spacing(&self, spacing: f64) {
+        let spacing = spacing as CGFloat;
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setSpacing: spacing];
+        });
+    }
+
+    /// Overrides the spacing after a specific arranged view, in points.
+    pub fn set_custom_spacing<V: Layout>(&self, spacing: f64, after: &V) {
+        let spacing = spacing as CGFloat;
+
+        self.objc.with_mut(|obj| {
+            after.with_backing_obj_mut(|view| unsafe {
+                let _: () = msg_send![obj, setCustomSpacing:spacing afterView:view];
+            });
+        });
+    }
+
+    /// Sets how arranged views are positioned perpendicular to the stack's orientation.
+    pub fn set_alignment(&self, alignment: StackViewAlignment) {
+        self.alignment.set(alignment);
+
+        #[cfg(feature = "appkit")]
+        {
+            let attribute = alignment.to_attribute(self.orientation());
+
+            self.objc.with_mut(|obj| unsafe {
+                let _: () = msg_send![obj, setAlignment: attribute];
+            });
+
+            self.update_fill_constraints();
+        }
+
+        #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+        {
+            let alignment: NSInteger = alignment.into();
+
+            self.objc.with_mut(|obj| unsafe {
+                let _: () = msg_send![obj, setAlignment: alignment];
+            });
+        }
+    }
+
+    /// Sets how arranged views are sized and spaced along the stack's orientation.
+    pub fn set_distribution(&self, distribution: StackViewDistribution) {
+        let distribution: NSInteger = distribution.into();
+
+        self.objc.with_mut(|obj| unsafe {
+            let _: () = msg_send![obj, setDistribution: distribution];
+        });
+    }
+
+    /// Sets the padding between the edges of the stack and its arranged views.
+    pub fn set_edge_insets(&self, insets: EdgeInsets) {
+        self.objc.with_mut(|obj| unsafe {
+            #[cfg(feature = "appkit")]
+            let _: () = msg_send![obj, setEdgeInsets: insets];
+
+            #[cfg(all(feature = "uikit", not(feature = "appkit")))]
+            {
+                let _: () = msg_send![obj, setLayoutMargins: insets];
+                let _: () = msg_send![obj, setLayoutMarginsRelativeArrangement: YES];
+            }
+        });
+
+        #[cfg(feature = "appkit")]
+        self.update_fill_constraints();
+    }
+
+    /// Adds a view to the end of the stack. This also adds it as a subview, so there's no need to
+    /// call `add_subview()` yourself.
+    pub fn add_arranged_subview<V: Layout>(&self, view: &V) {
+        self.objc.with_mut(|obj| {
+            view.with_backing_obj_mut(|subview| unsafe {
+                let _: () = msg_send![obj, addArrangedSubview: subview];
+            });
+        });
+
+        #[cfg(feature = "appkit")]
+        self.update_fill_constraints();
+    }
+
+    /// Inserts a view into the stack at `index`, which can be at most the number of views already
+    /// in the stack. This also adds it as a subview.
+    pub fn insert_arranged_subview<V: Layout>(&self, view: &V, index: usize) {
+        let index = index as NSUInteger;
+
+        self.objc.with_mut(|obj| {
+            view.with_backing_obj_mut(|subview| unsafe {
+                let _: () = msg_send![obj, insertArrangedSubview:subview atIndex:index];
+            });
+        });
+
+        #[cfg(feature = "appkit")]
+        self.update_fill_constraints();
+    }
+
+    /// Removes a view from the stack, and from the view hierarchy.
+    pub fn remove_arranged_subview<V: Layout>(&self, view: &V) {
+        self.objc.with_mut(|obj| {
+            view.with_backing_obj_mut(|subview| unsafe {
+                let _: () = msg_send![obj, removeArrangedSubview: subview];
+                let _: () = msg_send![subview, removeFromSuperview];
+            });
+        });
+
+        #[cfg(feature = "appkit")]
+        self.update_fill_constraints();
+    }
+
+    /// `NSStackView` has no fill alignment, so we emulate it by pinning each arranged view to the
+    /// (inset) edges of the stack. This rebuilds those constraints whenever anything they depend
+    /// on changes.
+    #[cfg(feature = "appkit")]
+    fn update_fill_constraints(&self) {
+        let mut constraints = self.fill_constraints.borrow_mut();
+        LayoutConstraint::deactivate(&constraints);
+        constraints.clear();
+
+        if self.alignment.get() != StackViewAlignment::Fill {
+            return;
+        }
+
+        let orientation = self.orientation();
+        let (stack, insets, views) = self.objc.get(|obj| unsafe {
+            let insets: EdgeInsets = msg_send![obj, edgeInsets];
+            let views = NSArray::retain(msg_send![obj, arrangedSubviews]);
+
+            (obj as *const Object as id, insets, views.map(|view| view))
+        });
+
+        for view in views {
+            match orientation {
+                LayoutConstraintOrientation::Vertical => {
+                    constraints.push(
+                        LayoutAnchorX::left(view)
+                            .constraint_equal_to(&LayoutAnchorX::left(stack))
+                            .offset(insets.left)
+                    );
+                    constraints.push(
+                        LayoutAnchorX::right(view)
+                            .constraint_equal_to(&LayoutAnchorX::right(stack))
+                            .offset(-insets.right)
+                    );
+                },
+
+                _ => {
+                    constraints.push(
+                        LayoutAnchorY::top(view)
+                            .constraint_equal_to(&LayoutAnchorY::top(stack))
+                            .offset(insets.top)
+                    );
+                    constraints.push(
+                        LayoutAnchorY::bottom(view)
+                            .constraint_equal_to(&LayoutAnchorY::bottom(stack))
+                            .offset(-insets.bottom)
+                    );
+                }
+            }
+        }
+
+        LayoutConstraint::activate(&constraints);
+    }
+}
+
+impl ObjcAccess for StackView {
+    fn with_backing_obj_mut<F: Fn(id)>(&self, handler: F) {
+        self.objc.with_mut(handler);
+    }
+
+    fn get_from_backing_obj<F: Fn(&Object) -> R, R>(&self, handler: F) -> R {
+        self.objc.get(handler)
+    }
+}
+
+impl Layout for StackView {}
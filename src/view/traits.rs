@@ -3,6 +3,7 @@
 #[cfg(feature = "appkit")]
 use crate::dragdrop::{DragInfo, DragOperation};
 
+use crate::geometry::Rect;
 use crate::view::View;
 
 /// This trait can be used for implementing custom View behavior. You implement this trait on your
@@ -41,6 +42,14 @@ pub trait ViewDelegate {
     /// Called when this has been removed from the view heirarchy.
     fn did_disappear(&self, animated: bool) {}
 
+    /// Called when this view lays out its subviews, with its current bounds - after it's been
+    /// resized, and (on iOS) whenever it's marked as needing layout.
+    ///
+    /// Subviews with an autoresizing mask have already been resized by the time this is called,
+    /// so this is the place to reposition any subviews that need more than a mask to follow the
+    /// new size (e.g, in frame-based layouts without the `autolayout` feature).
+    fn layout_subviews(&self, bounds: Rect) {}
+
     /// Invoked when the dragged image enters destination bounds or frame; returns dragging
     /// operation to perform.
     #[cfg(feature = "appkit")]
@@ -1,8 +1,10 @@
 use std::sync::Once;
 
+use core_graphics::geometry::CGRect;
+
 use objc::declare::ClassDecl;
 use objc::runtime::{Class, Object, Sel, BOOL};
-use objc::{class, sel, sel_impl};
+use objc::{class, msg_send, sel, sel_impl};
 use objc_id::Id;
 
 use crate::foundation::load_or_register_class;
@@ -10,6 +12,18 @@ use crate::foundation::{id, NSUInteger, NO, YES};
 use crate::utils::load;
 use crate::view::{ViewDelegate, VIEW_DELEGATE_PTR};
 
+/// Called when the view lays out its subviews. Autoresizing masks are applied by the superclass
+/// first, and then the delegate gets a chance to lay out the rest.
+extern "C" fn layout_subviews<T: ViewDelegate>(this: &mut Object, _: Sel) {
+    let bounds: CGRect = unsafe {
+        let _: () = msg_send![super(this, class!(UIView)), layoutSubviews];
+        msg_send![this, bounds]
+    };
+
+    let view = load::<T>(this, VIEW_DELEGATE_PTR);
+    view.layout_subviews(bounds.into());
+}
+
 /// Injects an `NSView` subclass. This is used for the default views that don't use delegates - we
 /// have separate classes here since we don't want to waste cycles on methods that will never be
 /// used if there's no delegates.
@@ -31,5 +45,7 @@ pub(crate) fn register_view_class() -> *const Class {
 pub(crate) fn register_view_class_with_delegate<T: ViewDelegate>(instance: &T) -> *const Class {
     load_or_register_class("UIView", instance.subclass_name(), |decl| unsafe {
         decl.add_ivar::<usize>(VIEW_DELEGATE_PTR);
+
+        decl.add_method(sel!(layoutSubviews), layout_subviews::<T> as extern "C" fn(&mut Object, _));
     })
 }